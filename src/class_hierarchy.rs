@@ -0,0 +1,219 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::class_reader::{ClassReader, SKIP_CODE, SKIP_DEBUG, SKIP_FRAMES};
+use crate::constants::ACC_INTERFACE;
+use crate::error::ClassWriteError;
+use crate::nodes::ClassNode;
+
+#[cfg(feature = "jdk-hierarchy")]
+mod jdk_classes;
+
+/// What a [`ClassHierarchy`] knows about a class or interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassInfo {
+    /// The internal name of the super class, or `None` for `java/lang/Object`.
+    ///
+    /// Interfaces have `java/lang/Object` as their super class.
+    pub super_name: Option<String>,
+    /// The internal names of the directly implemented (or, for an interface, extended)
+    /// interfaces.
+    pub interfaces: Vec<String>,
+    pub is_interface: bool,
+}
+
+impl ClassInfo {
+    /// Returns the information held by the header of `class_node`.
+    pub fn from_class_node(class_node: &ClassNode) -> Self {
+        Self {
+            super_name: class_node.super_name.clone(),
+            interfaces: class_node.interfaces.clone(),
+            is_interface: class_node.access_flags & ACC_INTERFACE != 0,
+        }
+    }
+}
+
+/// The class hierarchy that `COMPUTE_FRAMES` uses to merge the types of the values that reach
+/// an instruction from several paths, like `ClassWriter.getCommonSuperClass` in ASM.
+///
+/// Implementations only provide [`class_info`](Self::class_info); the other methods are
+/// derived from it. Pass one to a writer with
+/// [`ClassWriter::with_class_hierarchy`](crate::class_writer::ClassWriter::with_class_hierarchy).
+pub trait ClassHierarchy: Send + Sync {
+    /// Returns what is known about the class `name`, or `None` if this hierarchy does not know
+    /// it.
+    fn class_info(&self, name: &str) -> Option<ClassInfo>;
+
+    /// Returns the internal name of the super class of `name`, or `None` for
+    /// `java/lang/Object`.
+    ///
+    /// Returns [`ClassWriteError::UnknownClass`] if `name` is not in this hierarchy.
+    fn super_name(&self, name: &str) -> Result<Option<String>, ClassWriteError> {
+        self.class_info(name)
+            .map(|info| info.super_name)
+            .ok_or_else(|| ClassWriteError::UnknownClass(name.to_string()))
+    }
+
+    /// Returns whether `name` is an interface.
+    ///
+    /// Returns [`ClassWriteError::UnknownClass`] if `name` is not in this hierarchy.
+    fn is_interface(&self, name: &str) -> Result<bool, ClassWriteError> {
+        self.class_info(name)
+            .map(|info| info.is_interface)
+            .ok_or_else(|| ClassWriteError::UnknownClass(name.to_string()))
+    }
+
+    /// Returns whether a value of class `from` can be assigned to a variable of class `to`, that
+    /// is, whether `to` is `from`, one of its super classes or one of the interfaces they
+    /// implement.
+    ///
+    /// Returns [`ClassWriteError::UnknownClass`] if a class that must be looked up to answer is
+    /// not in this hierarchy.
+    fn is_assignable(&self, to: &str, from: &str) -> Result<bool, ClassWriteError> {
+        if to == from || to == "java/lang/Object" {
+            return Ok(true);
+        }
+        let mut pending = vec![from.to_string()];
+        let mut visited = HashSet::new();
+        while let Some(name) = pending.pop() {
+            if name == to {
+                return Ok(true);
+            }
+            if name == "java/lang/Object" || !visited.insert(name.clone()) {
+                continue;
+            }
+            let info = self
+                .class_info(&name)
+                .ok_or(ClassWriteError::UnknownClass(name))?;
+            pending.extend(info.super_name);
+            pending.extend(info.interfaces);
+        }
+        Ok(false)
+    }
+
+    /// Returns the most specific class that both `left` and `right` can be assigned to, or
+    /// `java/lang/Object` if one of them is an interface.
+    ///
+    /// Returns [`ClassWriteError::UnknownClass`] if a class that must be looked up to answer is
+    /// not in this hierarchy.
+    fn common_super_class(&self, left: &str, right: &str) -> Result<String, ClassWriteError> {
+        if self.is_assignable(left, right)? {
+            return Ok(left.to_string());
+        }
+        if self.is_assignable(right, left)? {
+            return Ok(right.to_string());
+        }
+        if self.is_interface(left)? || self.is_interface(right)? {
+            return Ok("java/lang/Object".to_string());
+        }
+        let mut current = left.to_string();
+        loop {
+            match self.super_name(&current)? {
+                Some(parent) if self.is_assignable(&parent, right)? => return Ok(parent),
+                Some(parent) => current = parent,
+                None => return Ok("java/lang/Object".to_string()),
+            }
+        }
+    }
+}
+
+/// A [`ClassHierarchy`] of the classes given to it, typically the classes of an application
+/// being transformed together.
+#[derive(Debug, Clone, Default)]
+pub struct ClassNodeHierarchy {
+    classes: HashMap<String, ClassInfo>,
+}
+
+impl ClassNodeHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a hierarchy of `class_nodes`.
+    pub fn from_class_nodes<'a>(class_nodes: impl IntoIterator<Item = &'a ClassNode>) -> Self {
+        let mut hierarchy = Self::new();
+        for class_node in class_nodes {
+            hierarchy.add_class_node(class_node);
+        }
+        hierarchy
+    }
+
+    /// Adds the class declared by `class_node`, replacing any class with the same name.
+    pub fn add_class_node(&mut self, class_node: &ClassNode) -> &mut Self {
+        self.add_class(&class_node.name, ClassInfo::from_class_node(class_node))
+    }
+
+    /// Adds the class `name`, replacing any class with the same name.
+    pub fn add_class(&mut self, name: &str, info: ClassInfo) -> &mut Self {
+        self.classes.insert(name.to_string(), info);
+        self
+    }
+}
+
+impl ClassHierarchy for ClassNodeHierarchy {
+    fn class_info(&self, name: &str) -> Option<ClassInfo> {
+        self.classes.get(name).cloned()
+    }
+}
+
+/// A [`ClassHierarchy`] that reads the class `a/b/C` from the file `a/b/C.class` under a root
+/// directory, such as a compiler output directory or an extracted jar.
+///
+/// Each class is read at most once. Classes whose file is missing or malformed are unknown.
+#[derive(Debug)]
+pub struct DirectoryClassHierarchy {
+    root: PathBuf,
+    cache: Mutex<HashMap<String, Option<ClassInfo>>>,
+}
+
+impl DirectoryClassHierarchy {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn read_class_info(&self, name: &str) -> Option<ClassInfo> {
+        let bytes = std::fs::read(self.root.join(format!("{name}.class"))).ok()?;
+        let class_node = ClassReader::new(&bytes)
+            .to_class_node_with_options(SKIP_CODE | SKIP_DEBUG | SKIP_FRAMES)
+            .ok()?;
+        (class_node.name == name).then(|| ClassInfo::from_class_node(&class_node))
+    }
+}
+
+impl ClassHierarchy for DirectoryClassHierarchy {
+    fn class_info(&self, name: &str) -> Option<ClassInfo> {
+        let mut cache = self.cache.lock().unwrap_or_else(|error| error.into_inner());
+        cache
+            .entry(name.to_string())
+            .or_insert_with(|| self.read_class_info(name))
+            .clone()
+    }
+}
+
+/// A [`ClassHierarchy`] of the public classes of the `java.base` module, from a snapshot bundled
+/// with the crate, so that frames can be computed without a JDK.
+///
+/// The snapshot records the super class, the implemented interfaces and the interface flag of
+/// each class. `COMPUTE_FRAMES` falls back to it for the classes that the writer's hierarchy does not know.
+#[cfg(feature = "jdk-hierarchy")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JdkClassHierarchy;
+
+#[cfg(feature = "jdk-hierarchy")]
+impl ClassHierarchy for JdkClassHierarchy {
+    fn class_info(&self, name: &str) -> Option<ClassInfo> {
+        let index = jdk_classes::JDK_CLASSES
+            .binary_search_by(|(class_name, _, _, _)| (*class_name).cmp(name))
+            .ok()?;
+        let (_, super_name, interfaces, is_interface) = jdk_classes::JDK_CLASSES[index];
+        Some(ClassInfo {
+            super_name: super_name.map(str::to_string),
+            interfaces: interfaces.iter().map(|name| name.to_string()).collect(),
+            is_interface,
+        })
+    }
+}
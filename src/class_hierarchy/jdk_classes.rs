@@ -0,0 +1,1376 @@
+// @generated by `java tools/JdkHierarchy.java` from java.base 17. Do not edit.
+
+/// `(name, super_name, interfaces, is_interface)` of the public java.base classes, their
+/// super classes and their interfaces, sorted by name.
+#[rustfmt::skip]
+pub(super) static JDK_CLASSES: &[(&str, Option<&str>, &[&str], bool)] = &[
+    ("java/io/BufferedInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("java/io/BufferedOutputStream", Some("java/io/FilterOutputStream"), &[], false),
+    ("java/io/BufferedReader", Some("java/io/Reader"), &[], false),
+    ("java/io/BufferedWriter", Some("java/io/Writer"), &[], false),
+    ("java/io/ByteArrayInputStream", Some("java/io/InputStream"), &[], false),
+    ("java/io/ByteArrayOutputStream", Some("java/io/OutputStream"), &[], false),
+    ("java/io/CharArrayReader", Some("java/io/Reader"), &[], false),
+    ("java/io/CharArrayWriter", Some("java/io/Writer"), &[], false),
+    ("java/io/CharConversionException", Some("java/io/IOException"), &[], false),
+    ("java/io/Closeable", Some("java/lang/Object"), &["java/lang/AutoCloseable"], true),
+    ("java/io/Console", Some("java/lang/Object"), &["java/io/Flushable"], false),
+    ("java/io/DataInput", Some("java/lang/Object"), &[], true),
+    ("java/io/DataInputStream", Some("java/io/FilterInputStream"), &["java/io/DataInput"], false),
+    ("java/io/DataOutput", Some("java/lang/Object"), &[], true),
+    ("java/io/DataOutputStream", Some("java/io/FilterOutputStream"), &["java/io/DataOutput"], false),
+    ("java/io/EOFException", Some("java/io/IOException"), &[], false),
+    ("java/io/Externalizable", Some("java/lang/Object"), &["java/io/Serializable"], true),
+    ("java/io/File", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Comparable"], false),
+    ("java/io/FileDescriptor", Some("java/lang/Object"), &[], false),
+    ("java/io/FileFilter", Some("java/lang/Object"), &[], true),
+    ("java/io/FileInputStream", Some("java/io/InputStream"), &[], false),
+    ("java/io/FileNotFoundException", Some("java/io/IOException"), &[], false),
+    ("java/io/FileOutputStream", Some("java/io/OutputStream"), &[], false),
+    ("java/io/FilePermission", Some("java/security/Permission"), &["java/io/Serializable"], false),
+    ("java/io/FileReader", Some("java/io/InputStreamReader"), &[], false),
+    ("java/io/FileWriter", Some("java/io/OutputStreamWriter"), &[], false),
+    ("java/io/FilenameFilter", Some("java/lang/Object"), &[], true),
+    ("java/io/FilterInputStream", Some("java/io/InputStream"), &[], false),
+    ("java/io/FilterOutputStream", Some("java/io/OutputStream"), &[], false),
+    ("java/io/FilterReader", Some("java/io/Reader"), &[], false),
+    ("java/io/FilterWriter", Some("java/io/Writer"), &[], false),
+    ("java/io/Flushable", Some("java/lang/Object"), &[], true),
+    ("java/io/IOError", Some("java/lang/Error"), &[], false),
+    ("java/io/IOException", Some("java/lang/Exception"), &[], false),
+    ("java/io/InputStream", Some("java/lang/Object"), &["java/io/Closeable"], false),
+    ("java/io/InputStreamReader", Some("java/io/Reader"), &[], false),
+    ("java/io/InterruptedIOException", Some("java/io/IOException"), &[], false),
+    ("java/io/InvalidClassException", Some("java/io/ObjectStreamException"), &[], false),
+    ("java/io/InvalidObjectException", Some("java/io/ObjectStreamException"), &[], false),
+    ("java/io/LineNumberInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("java/io/LineNumberReader", Some("java/io/BufferedReader"), &[], false),
+    ("java/io/NotActiveException", Some("java/io/ObjectStreamException"), &[], false),
+    ("java/io/NotSerializableException", Some("java/io/ObjectStreamException"), &[], false),
+    ("java/io/ObjectInput", Some("java/lang/Object"), &["java/io/DataInput", "java/lang/AutoCloseable"], true),
+    ("java/io/ObjectInputFilter", Some("java/lang/Object"), &[], true),
+    ("java/io/ObjectInputFilter$Config", Some("java/lang/Object"), &[], false),
+    ("java/io/ObjectInputFilter$FilterInfo", Some("java/lang/Object"), &[], true),
+    ("java/io/ObjectInputFilter$Status", Some("java/lang/Enum"), &[], false),
+    ("java/io/ObjectInputStream", Some("java/io/InputStream"), &["java/io/ObjectInput", "java/io/ObjectStreamConstants"], false),
+    ("java/io/ObjectInputStream$GetField", Some("java/lang/Object"), &[], false),
+    ("java/io/ObjectInputValidation", Some("java/lang/Object"), &[], true),
+    ("java/io/ObjectOutput", Some("java/lang/Object"), &["java/io/DataOutput", "java/lang/AutoCloseable"], true),
+    ("java/io/ObjectOutputStream", Some("java/io/OutputStream"), &["java/io/ObjectOutput", "java/io/ObjectStreamConstants"], false),
+    ("java/io/ObjectOutputStream$PutField", Some("java/lang/Object"), &[], false),
+    ("java/io/ObjectStreamClass", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/io/ObjectStreamConstants", Some("java/lang/Object"), &[], true),
+    ("java/io/ObjectStreamException", Some("java/io/IOException"), &[], false),
+    ("java/io/ObjectStreamField", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/io/OptionalDataException", Some("java/io/ObjectStreamException"), &[], false),
+    ("java/io/OutputStream", Some("java/lang/Object"), &["java/io/Closeable", "java/io/Flushable"], false),
+    ("java/io/OutputStreamWriter", Some("java/io/Writer"), &[], false),
+    ("java/io/PipedInputStream", Some("java/io/InputStream"), &[], false),
+    ("java/io/PipedOutputStream", Some("java/io/OutputStream"), &[], false),
+    ("java/io/PipedReader", Some("java/io/Reader"), &[], false),
+    ("java/io/PipedWriter", Some("java/io/Writer"), &[], false),
+    ("java/io/PrintStream", Some("java/io/FilterOutputStream"), &["java/lang/Appendable", "java/io/Closeable"], false),
+    ("java/io/PrintWriter", Some("java/io/Writer"), &[], false),
+    ("java/io/PushbackInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("java/io/PushbackReader", Some("java/io/FilterReader"), &[], false),
+    ("java/io/RandomAccessFile", Some("java/lang/Object"), &["java/io/DataOutput", "java/io/DataInput", "java/io/Closeable"], false),
+    ("java/io/Reader", Some("java/lang/Object"), &["java/lang/Readable", "java/io/Closeable"], false),
+    ("java/io/SequenceInputStream", Some("java/io/InputStream"), &[], false),
+    ("java/io/Serial", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/io/Serializable", Some("java/lang/Object"), &[], true),
+    ("java/io/SerializablePermission", Some("java/security/BasicPermission"), &[], false),
+    ("java/io/StreamCorruptedException", Some("java/io/ObjectStreamException"), &[], false),
+    ("java/io/StreamTokenizer", Some("java/lang/Object"), &[], false),
+    ("java/io/StringBufferInputStream", Some("java/io/InputStream"), &[], false),
+    ("java/io/StringReader", Some("java/io/Reader"), &[], false),
+    ("java/io/StringWriter", Some("java/io/Writer"), &[], false),
+    ("java/io/SyncFailedException", Some("java/io/IOException"), &[], false),
+    ("java/io/UTFDataFormatException", Some("java/io/IOException"), &[], false),
+    ("java/io/UncheckedIOException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/io/UnsupportedEncodingException", Some("java/io/IOException"), &[], false),
+    ("java/io/WriteAbortedException", Some("java/io/ObjectStreamException"), &[], false),
+    ("java/io/Writer", Some("java/lang/Object"), &["java/lang/Appendable", "java/io/Closeable", "java/io/Flushable"], false),
+    ("java/lang/AbstractMethodError", Some("java/lang/IncompatibleClassChangeError"), &[], false),
+    ("java/lang/AbstractStringBuilder", Some("java/lang/Object"), &["java/lang/Appendable", "java/lang/CharSequence"], false),
+    ("java/lang/Appendable", Some("java/lang/Object"), &[], true),
+    ("java/lang/ArithmeticException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/ArrayIndexOutOfBoundsException", Some("java/lang/IndexOutOfBoundsException"), &[], false),
+    ("java/lang/ArrayStoreException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/AssertionError", Some("java/lang/Error"), &[], false),
+    ("java/lang/AutoCloseable", Some("java/lang/Object"), &[], true),
+    ("java/lang/Boolean", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Comparable", "java/lang/constant/Constable"], false),
+    ("java/lang/BootstrapMethodError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/Byte", Some("java/lang/Number"), &["java/lang/Comparable", "java/lang/constant/Constable"], false),
+    ("java/lang/CharSequence", Some("java/lang/Object"), &[], true),
+    ("java/lang/Character", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Comparable", "java/lang/constant/Constable"], false),
+    ("java/lang/Character$Subset", Some("java/lang/Object"), &[], false),
+    ("java/lang/Character$UnicodeBlock", Some("java/lang/Character$Subset"), &[], false),
+    ("java/lang/Character$UnicodeScript", Some("java/lang/Enum"), &[], false),
+    ("java/lang/Class", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/reflect/GenericDeclaration", "java/lang/reflect/Type", "java/lang/reflect/AnnotatedElement", "java/lang/invoke/TypeDescriptor$OfField", "java/lang/constant/Constable"], false),
+    ("java/lang/ClassCastException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/ClassCircularityError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/ClassFormatError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/ClassLoader", Some("java/lang/Object"), &[], false),
+    ("java/lang/ClassNotFoundException", Some("java/lang/ReflectiveOperationException"), &[], false),
+    ("java/lang/ClassValue", Some("java/lang/Object"), &[], false),
+    ("java/lang/CloneNotSupportedException", Some("java/lang/Exception"), &[], false),
+    ("java/lang/Cloneable", Some("java/lang/Object"), &[], true),
+    ("java/lang/Comparable", Some("java/lang/Object"), &[], true),
+    ("java/lang/Compiler", Some("java/lang/Object"), &[], false),
+    ("java/lang/Deprecated", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/Double", Some("java/lang/Number"), &["java/lang/Comparable", "java/lang/constant/Constable", "java/lang/constant/ConstantDesc"], false),
+    ("java/lang/Enum", Some("java/lang/Object"), &["java/lang/constant/Constable", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/lang/Enum$EnumDesc", Some("java/lang/constant/DynamicConstantDesc"), &[], false),
+    ("java/lang/EnumConstantNotPresentException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/Error", Some("java/lang/Throwable"), &[], false),
+    ("java/lang/Exception", Some("java/lang/Throwable"), &[], false),
+    ("java/lang/ExceptionInInitializerError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/FdLibm$Cbrt", Some("java/lang/Object"), &[], false),
+    ("java/lang/FdLibm$Hypot", Some("java/lang/Object"), &[], false),
+    ("java/lang/FdLibm$Pow", Some("java/lang/Object"), &[], false),
+    ("java/lang/Float", Some("java/lang/Number"), &["java/lang/Comparable", "java/lang/constant/Constable", "java/lang/constant/ConstantDesc"], false),
+    ("java/lang/FunctionalInterface", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/IllegalAccessError", Some("java/lang/IncompatibleClassChangeError"), &[], false),
+    ("java/lang/IllegalAccessException", Some("java/lang/ReflectiveOperationException"), &[], false),
+    ("java/lang/IllegalArgumentException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/IllegalCallerException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/IllegalMonitorStateException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/IllegalStateException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/IllegalThreadStateException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/lang/IncompatibleClassChangeError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/IndexOutOfBoundsException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/InheritableThreadLocal", Some("java/lang/ThreadLocal"), &[], false),
+    ("java/lang/InstantiationError", Some("java/lang/IncompatibleClassChangeError"), &[], false),
+    ("java/lang/InstantiationException", Some("java/lang/ReflectiveOperationException"), &[], false),
+    ("java/lang/Integer", Some("java/lang/Number"), &["java/lang/Comparable", "java/lang/constant/Constable", "java/lang/constant/ConstantDesc"], false),
+    ("java/lang/InternalError", Some("java/lang/VirtualMachineError"), &[], false),
+    ("java/lang/InterruptedException", Some("java/lang/Exception"), &[], false),
+    ("java/lang/Iterable", Some("java/lang/Object"), &[], true),
+    ("java/lang/LayerInstantiationException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/LinkageError", Some("java/lang/Error"), &[], false),
+    ("java/lang/LiveStackFrame$PrimitiveSlot", Some("java/lang/Object"), &[], false),
+    ("java/lang/Long", Some("java/lang/Number"), &["java/lang/Comparable", "java/lang/constant/Constable", "java/lang/constant/ConstantDesc"], false),
+    ("java/lang/Math", Some("java/lang/Object"), &[], false),
+    ("java/lang/Module", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedElement"], false),
+    ("java/lang/ModuleLayer", Some("java/lang/Object"), &[], false),
+    ("java/lang/ModuleLayer$Controller", Some("java/lang/Object"), &[], false),
+    ("java/lang/NamedPackage", Some("java/lang/Object"), &[], false),
+    ("java/lang/NegativeArraySizeException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/NoClassDefFoundError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/NoSuchFieldError", Some("java/lang/IncompatibleClassChangeError"), &[], false),
+    ("java/lang/NoSuchFieldException", Some("java/lang/ReflectiveOperationException"), &[], false),
+    ("java/lang/NoSuchMethodError", Some("java/lang/IncompatibleClassChangeError"), &[], false),
+    ("java/lang/NoSuchMethodException", Some("java/lang/ReflectiveOperationException"), &[], false),
+    ("java/lang/NullPointerException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/Number", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/lang/NumberFormatException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/lang/Object", None, &[], false),
+    ("java/lang/OutOfMemoryError", Some("java/lang/VirtualMachineError"), &[], false),
+    ("java/lang/Override", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/Package", Some("java/lang/NamedPackage"), &["java/lang/reflect/AnnotatedElement"], false),
+    ("java/lang/Process", Some("java/lang/Object"), &[], false),
+    ("java/lang/ProcessBuilder", Some("java/lang/Object"), &[], false),
+    ("java/lang/ProcessBuilder$Redirect", Some("java/lang/Object"), &[], false),
+    ("java/lang/ProcessBuilder$Redirect$Type", Some("java/lang/Enum"), &[], false),
+    ("java/lang/ProcessHandle", Some("java/lang/Object"), &["java/lang/Comparable"], true),
+    ("java/lang/ProcessHandle$Info", Some("java/lang/Object"), &[], true),
+    ("java/lang/Readable", Some("java/lang/Object"), &[], true),
+    ("java/lang/Record", Some("java/lang/Object"), &[], false),
+    ("java/lang/ReflectiveOperationException", Some("java/lang/Exception"), &[], false),
+    ("java/lang/Runnable", Some("java/lang/Object"), &[], true),
+    ("java/lang/Runtime", Some("java/lang/Object"), &[], false),
+    ("java/lang/Runtime$Version", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/lang/RuntimeException", Some("java/lang/Exception"), &[], false),
+    ("java/lang/RuntimePermission", Some("java/security/BasicPermission"), &[], false),
+    ("java/lang/SafeVarargs", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/SecurityException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/SecurityManager", Some("java/lang/Object"), &[], false),
+    ("java/lang/Short", Some("java/lang/Number"), &["java/lang/Comparable", "java/lang/constant/Constable"], false),
+    ("java/lang/StackOverflowError", Some("java/lang/VirtualMachineError"), &[], false),
+    ("java/lang/StackTraceElement", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/lang/StackWalker", Some("java/lang/Object"), &[], false),
+    ("java/lang/StackWalker$Option", Some("java/lang/Enum"), &[], false),
+    ("java/lang/StackWalker$StackFrame", Some("java/lang/Object"), &[], true),
+    ("java/lang/StrictMath", Some("java/lang/Object"), &[], false),
+    ("java/lang/String", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Comparable", "java/lang/CharSequence", "java/lang/constant/Constable", "java/lang/constant/ConstantDesc"], false),
+    ("java/lang/StringBuffer", Some("java/lang/AbstractStringBuilder"), &["java/io/Serializable", "java/lang/Comparable", "java/lang/CharSequence"], false),
+    ("java/lang/StringBuilder", Some("java/lang/AbstractStringBuilder"), &["java/io/Serializable", "java/lang/Comparable", "java/lang/CharSequence"], false),
+    ("java/lang/StringIndexOutOfBoundsException", Some("java/lang/IndexOutOfBoundsException"), &[], false),
+    ("java/lang/SuppressWarnings", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/System", Some("java/lang/Object"), &[], false),
+    ("java/lang/System$Logger", Some("java/lang/Object"), &[], true),
+    ("java/lang/System$Logger$Level", Some("java/lang/Enum"), &[], false),
+    ("java/lang/System$LoggerFinder", Some("java/lang/Object"), &[], false),
+    ("java/lang/Thread", Some("java/lang/Object"), &["java/lang/Runnable"], false),
+    ("java/lang/Thread$State", Some("java/lang/Enum"), &[], false),
+    ("java/lang/Thread$UncaughtExceptionHandler", Some("java/lang/Object"), &[], true),
+    ("java/lang/ThreadDeath", Some("java/lang/Error"), &[], false),
+    ("java/lang/ThreadGroup", Some("java/lang/Object"), &["java/lang/Thread$UncaughtExceptionHandler"], false),
+    ("java/lang/ThreadLocal", Some("java/lang/Object"), &[], false),
+    ("java/lang/Throwable", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/lang/TypeNotPresentException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/UnknownError", Some("java/lang/VirtualMachineError"), &[], false),
+    ("java/lang/UnsatisfiedLinkError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/UnsupportedClassVersionError", Some("java/lang/ClassFormatError"), &[], false),
+    ("java/lang/UnsupportedOperationException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/VerifyError", Some("java/lang/LinkageError"), &[], false),
+    ("java/lang/VirtualMachineError", Some("java/lang/Error"), &[], false),
+    ("java/lang/Void", Some("java/lang/Object"), &[], false),
+    ("java/lang/WeakPairMap$Pair", Some("java/lang/Object"), &[], true),
+    ("java/lang/WeakPairMap$Pair$Lookup", Some("java/lang/Object"), &["java/lang/WeakPairMap$Pair"], false),
+    ("java/lang/WeakPairMap$Pair$Weak", Some("java/lang/WeakPairMap$WeakRefPeer"), &["java/lang/WeakPairMap$Pair"], false),
+    ("java/lang/WeakPairMap$WeakRefPeer", Some("java/lang/ref/WeakReference"), &[], false),
+    ("java/lang/annotation/Annotation", Some("java/lang/Object"), &[], true),
+    ("java/lang/annotation/AnnotationFormatError", Some("java/lang/Error"), &[], false),
+    ("java/lang/annotation/AnnotationTypeMismatchException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/annotation/Documented", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/annotation/ElementType", Some("java/lang/Enum"), &[], false),
+    ("java/lang/annotation/IncompleteAnnotationException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/annotation/Inherited", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/annotation/Native", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/annotation/Repeatable", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/annotation/Retention", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/annotation/RetentionPolicy", Some("java/lang/Enum"), &[], false),
+    ("java/lang/annotation/Target", Some("java/lang/Object"), &["java/lang/annotation/Annotation"], true),
+    ("java/lang/constant/ClassDesc", Some("java/lang/Object"), &["java/lang/constant/ConstantDesc", "java/lang/invoke/TypeDescriptor$OfField"], true),
+    ("java/lang/constant/Constable", Some("java/lang/Object"), &[], true),
+    ("java/lang/constant/ConstantDesc", Some("java/lang/Object"), &[], true),
+    ("java/lang/constant/ConstantDescs", Some("java/lang/Object"), &[], false),
+    ("java/lang/constant/DirectMethodHandleDesc", Some("java/lang/Object"), &["java/lang/constant/MethodHandleDesc"], true),
+    ("java/lang/constant/DirectMethodHandleDesc$Kind", Some("java/lang/Enum"), &[], false),
+    ("java/lang/constant/DynamicCallSiteDesc", Some("java/lang/Object"), &[], false),
+    ("java/lang/constant/DynamicConstantDesc", Some("java/lang/Object"), &["java/lang/constant/ConstantDesc"], false),
+    ("java/lang/constant/MethodHandleDesc", Some("java/lang/Object"), &["java/lang/constant/ConstantDesc"], true),
+    ("java/lang/constant/MethodTypeDesc", Some("java/lang/Object"), &["java/lang/constant/ConstantDesc", "java/lang/invoke/TypeDescriptor$OfMethod"], true),
+    ("java/lang/invoke/CallSite", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/ClassSpecializer$Factory", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/ClassSpecializer$SpeciesData", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/ConstantBootstraps", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/ConstantCallSite", Some("java/lang/invoke/CallSite"), &[], false),
+    ("java/lang/invoke/LambdaConversionException", Some("java/lang/Exception"), &[], false),
+    ("java/lang/invoke/LambdaMetafactory", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/MethodHandle", Some("java/lang/Object"), &["java/lang/constant/Constable"], false),
+    ("java/lang/invoke/MethodHandleInfo", Some("java/lang/Object"), &[], true),
+    ("java/lang/invoke/MethodHandleProxies", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/MethodHandles", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/MethodHandles$Lookup", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/MethodHandles$Lookup$ClassOption", Some("java/lang/Enum"), &[], false),
+    ("java/lang/invoke/MethodType", Some("java/lang/Object"), &["java/lang/constant/Constable", "java/lang/invoke/TypeDescriptor$OfMethod", "java/io/Serializable"], false),
+    ("java/lang/invoke/MutableCallSite", Some("java/lang/invoke/CallSite"), &[], false),
+    ("java/lang/invoke/SerializedLambda", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/lang/invoke/StringConcatException", Some("java/lang/Exception"), &[], false),
+    ("java/lang/invoke/StringConcatFactory", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/SwitchPoint", Some("java/lang/Object"), &[], false),
+    ("java/lang/invoke/TypeDescriptor", Some("java/lang/Object"), &[], true),
+    ("java/lang/invoke/TypeDescriptor$OfField", Some("java/lang/Object"), &["java/lang/invoke/TypeDescriptor"], true),
+    ("java/lang/invoke/TypeDescriptor$OfMethod", Some("java/lang/Object"), &["java/lang/invoke/TypeDescriptor"], true),
+    ("java/lang/invoke/VarHandle", Some("java/lang/Object"), &["java/lang/constant/Constable"], false),
+    ("java/lang/invoke/VarHandle$AccessMode", Some("java/lang/Enum"), &[], false),
+    ("java/lang/invoke/VarHandle$VarHandleDesc", Some("java/lang/constant/DynamicConstantDesc"), &[], false),
+    ("java/lang/invoke/VolatileCallSite", Some("java/lang/invoke/CallSite"), &[], false),
+    ("java/lang/invoke/WrongMethodTypeException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/module/Configuration", Some("java/lang/Object"), &[], false),
+    ("java/lang/module/FindException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/module/InvalidModuleDescriptorException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/module/ModuleDescriptor", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/lang/module/ModuleDescriptor$Builder", Some("java/lang/Object"), &[], false),
+    ("java/lang/module/ModuleDescriptor$Exports", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/lang/module/ModuleDescriptor$Exports$Modifier", Some("java/lang/Enum"), &[], false),
+    ("java/lang/module/ModuleDescriptor$Modifier", Some("java/lang/Enum"), &[], false),
+    ("java/lang/module/ModuleDescriptor$Opens", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/lang/module/ModuleDescriptor$Opens$Modifier", Some("java/lang/Enum"), &[], false),
+    ("java/lang/module/ModuleDescriptor$Provides", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/lang/module/ModuleDescriptor$Requires", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/lang/module/ModuleDescriptor$Requires$Modifier", Some("java/lang/Enum"), &[], false),
+    ("java/lang/module/ModuleDescriptor$Version", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/lang/module/ModuleFinder", Some("java/lang/Object"), &[], true),
+    ("java/lang/module/ModuleReader", Some("java/lang/Object"), &["java/io/Closeable"], true),
+    ("java/lang/module/ModuleReference", Some("java/lang/Object"), &[], false),
+    ("java/lang/module/ResolutionException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/module/ResolvedModule", Some("java/lang/Object"), &[], false),
+    ("java/lang/ref/Cleaner", Some("java/lang/Object"), &[], false),
+    ("java/lang/ref/Cleaner$Cleanable", Some("java/lang/Object"), &[], true),
+    ("java/lang/ref/PhantomReference", Some("java/lang/ref/Reference"), &[], false),
+    ("java/lang/ref/Reference", Some("java/lang/Object"), &[], false),
+    ("java/lang/ref/ReferenceQueue", Some("java/lang/Object"), &[], false),
+    ("java/lang/ref/SoftReference", Some("java/lang/ref/Reference"), &[], false),
+    ("java/lang/ref/WeakReference", Some("java/lang/ref/Reference"), &[], false),
+    ("java/lang/reflect/AccessibleObject", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedElement"], false),
+    ("java/lang/reflect/AnnotatedArrayType", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedType"], true),
+    ("java/lang/reflect/AnnotatedElement", Some("java/lang/Object"), &[], true),
+    ("java/lang/reflect/AnnotatedParameterizedType", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedType"], true),
+    ("java/lang/reflect/AnnotatedType", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedElement"], true),
+    ("java/lang/reflect/AnnotatedTypeVariable", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedType"], true),
+    ("java/lang/reflect/AnnotatedWildcardType", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedType"], true),
+    ("java/lang/reflect/Array", Some("java/lang/Object"), &[], false),
+    ("java/lang/reflect/Constructor", Some("java/lang/reflect/Executable"), &[], false),
+    ("java/lang/reflect/Executable", Some("java/lang/reflect/AccessibleObject"), &["java/lang/reflect/Member", "java/lang/reflect/GenericDeclaration"], false),
+    ("java/lang/reflect/Field", Some("java/lang/reflect/AccessibleObject"), &["java/lang/reflect/Member"], false),
+    ("java/lang/reflect/GenericArrayType", Some("java/lang/Object"), &["java/lang/reflect/Type"], true),
+    ("java/lang/reflect/GenericDeclaration", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedElement"], true),
+    ("java/lang/reflect/GenericSignatureFormatError", Some("java/lang/ClassFormatError"), &[], false),
+    ("java/lang/reflect/InaccessibleObjectException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/reflect/InvocationHandler", Some("java/lang/Object"), &[], true),
+    ("java/lang/reflect/InvocationTargetException", Some("java/lang/ReflectiveOperationException"), &[], false),
+    ("java/lang/reflect/MalformedParameterizedTypeException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/reflect/MalformedParametersException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/reflect/Member", Some("java/lang/Object"), &[], true),
+    ("java/lang/reflect/Method", Some("java/lang/reflect/Executable"), &[], false),
+    ("java/lang/reflect/Modifier", Some("java/lang/Object"), &[], false),
+    ("java/lang/reflect/Parameter", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedElement"], false),
+    ("java/lang/reflect/ParameterizedType", Some("java/lang/Object"), &["java/lang/reflect/Type"], true),
+    ("java/lang/reflect/Proxy", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/lang/reflect/RecordComponent", Some("java/lang/Object"), &["java/lang/reflect/AnnotatedElement"], false),
+    ("java/lang/reflect/ReflectPermission", Some("java/security/BasicPermission"), &[], false),
+    ("java/lang/reflect/Type", Some("java/lang/Object"), &[], true),
+    ("java/lang/reflect/TypeVariable", Some("java/lang/Object"), &["java/lang/reflect/Type", "java/lang/reflect/AnnotatedElement"], true),
+    ("java/lang/reflect/UndeclaredThrowableException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/lang/reflect/WildcardType", Some("java/lang/Object"), &["java/lang/reflect/Type"], true),
+    ("java/lang/runtime/ObjectMethods", Some("java/lang/Object"), &[], false),
+    ("java/lang/runtime/SwitchBootstraps", Some("java/lang/Object"), &[], false),
+    ("java/math/BigDecimal", Some("java/lang/Number"), &["java/lang/Comparable"], false),
+    ("java/math/BigInteger", Some("java/lang/Number"), &["java/lang/Comparable"], false),
+    ("java/math/MathContext", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/math/RoundingMode", Some("java/lang/Enum"), &[], false),
+    ("java/net/Authenticator", Some("java/lang/Object"), &[], false),
+    ("java/net/Authenticator$RequestorType", Some("java/lang/Enum"), &[], false),
+    ("java/net/BindException", Some("java/net/SocketException"), &[], false),
+    ("java/net/CacheRequest", Some("java/lang/Object"), &[], false),
+    ("java/net/CacheResponse", Some("java/lang/Object"), &[], false),
+    ("java/net/ConnectException", Some("java/net/SocketException"), &[], false),
+    ("java/net/ContentHandler", Some("java/lang/Object"), &[], false),
+    ("java/net/ContentHandlerFactory", Some("java/lang/Object"), &[], true),
+    ("java/net/CookieHandler", Some("java/lang/Object"), &[], false),
+    ("java/net/CookieManager", Some("java/net/CookieHandler"), &[], false),
+    ("java/net/CookiePolicy", Some("java/lang/Object"), &[], true),
+    ("java/net/CookieStore", Some("java/lang/Object"), &[], true),
+    ("java/net/DatagramPacket", Some("java/lang/Object"), &[], false),
+    ("java/net/DatagramSocket", Some("java/lang/Object"), &["java/io/Closeable"], false),
+    ("java/net/DatagramSocketImpl", Some("java/lang/Object"), &["java/net/SocketOptions"], false),
+    ("java/net/DatagramSocketImplFactory", Some("java/lang/Object"), &[], true),
+    ("java/net/FileNameMap", Some("java/lang/Object"), &[], true),
+    ("java/net/HttpCookie", Some("java/lang/Object"), &["java/lang/Cloneable"], false),
+    ("java/net/HttpRetryException", Some("java/io/IOException"), &[], false),
+    ("java/net/HttpURLConnection", Some("java/net/URLConnection"), &[], false),
+    ("java/net/IDN", Some("java/lang/Object"), &[], false),
+    ("java/net/Inet4Address", Some("java/net/InetAddress"), &[], false),
+    ("java/net/Inet6Address", Some("java/net/InetAddress"), &[], false),
+    ("java/net/InetAddress", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/net/InetSocketAddress", Some("java/net/SocketAddress"), &[], false),
+    ("java/net/InterfaceAddress", Some("java/lang/Object"), &[], false),
+    ("java/net/JarURLConnection", Some("java/net/URLConnection"), &[], false),
+    ("java/net/MalformedURLException", Some("java/io/IOException"), &[], false),
+    ("java/net/MulticastSocket", Some("java/net/DatagramSocket"), &[], false),
+    ("java/net/NetPermission", Some("java/security/BasicPermission"), &[], false),
+    ("java/net/NetworkInterface", Some("java/lang/Object"), &[], false),
+    ("java/net/NoRouteToHostException", Some("java/net/SocketException"), &[], false),
+    ("java/net/PasswordAuthentication", Some("java/lang/Object"), &[], false),
+    ("java/net/PortUnreachableException", Some("java/net/SocketException"), &[], false),
+    ("java/net/ProtocolException", Some("java/io/IOException"), &[], false),
+    ("java/net/ProtocolFamily", Some("java/lang/Object"), &[], true),
+    ("java/net/Proxy", Some("java/lang/Object"), &[], false),
+    ("java/net/Proxy$Type", Some("java/lang/Enum"), &[], false),
+    ("java/net/ProxySelector", Some("java/lang/Object"), &[], false),
+    ("java/net/ResponseCache", Some("java/lang/Object"), &[], false),
+    ("java/net/SecureCacheResponse", Some("java/net/CacheResponse"), &[], false),
+    ("java/net/ServerSocket", Some("java/lang/Object"), &["java/io/Closeable"], false),
+    ("java/net/Socket", Some("java/lang/Object"), &["java/io/Closeable"], false),
+    ("java/net/SocketAddress", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/net/SocketException", Some("java/io/IOException"), &[], false),
+    ("java/net/SocketImpl", Some("java/lang/Object"), &["java/net/SocketOptions"], false),
+    ("java/net/SocketImplFactory", Some("java/lang/Object"), &[], true),
+    ("java/net/SocketOption", Some("java/lang/Object"), &[], true),
+    ("java/net/SocketOptions", Some("java/lang/Object"), &[], true),
+    ("java/net/SocketPermission", Some("java/security/Permission"), &["java/io/Serializable"], false),
+    ("java/net/SocketTimeoutException", Some("java/io/InterruptedIOException"), &[], false),
+    ("java/net/StandardProtocolFamily", Some("java/lang/Enum"), &["java/net/ProtocolFamily"], false),
+    ("java/net/StandardSocketOptions", Some("java/lang/Object"), &[], false),
+    ("java/net/URI", Some("java/lang/Object"), &["java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/net/URISyntaxException", Some("java/lang/Exception"), &[], false),
+    ("java/net/URL", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/net/URLClassLoader", Some("java/security/SecureClassLoader"), &["java/io/Closeable"], false),
+    ("java/net/URLConnection", Some("java/lang/Object"), &[], false),
+    ("java/net/URLDecoder", Some("java/lang/Object"), &[], false),
+    ("java/net/URLEncoder", Some("java/lang/Object"), &[], false),
+    ("java/net/URLPermission", Some("java/security/Permission"), &[], false),
+    ("java/net/URLStreamHandler", Some("java/lang/Object"), &[], false),
+    ("java/net/URLStreamHandlerFactory", Some("java/lang/Object"), &[], true),
+    ("java/net/UnixDomainSocketAddress", Some("java/net/SocketAddress"), &[], false),
+    ("java/net/UnknownHostException", Some("java/io/IOException"), &[], false),
+    ("java/net/UnknownServiceException", Some("java/io/IOException"), &[], false),
+    ("java/net/spi/URLStreamHandlerProvider", Some("java/lang/Object"), &["java/net/URLStreamHandlerFactory"], false),
+    ("java/nio/Buffer", Some("java/lang/Object"), &[], false),
+    ("java/nio/BufferOverflowException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/nio/BufferUnderflowException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/nio/ByteBuffer", Some("java/nio/Buffer"), &["java/lang/Comparable"], false),
+    ("java/nio/ByteOrder", Some("java/lang/Object"), &[], false),
+    ("java/nio/CharBuffer", Some("java/nio/Buffer"), &["java/lang/Comparable", "java/lang/Appendable", "java/lang/CharSequence", "java/lang/Readable"], false),
+    ("java/nio/DoubleBuffer", Some("java/nio/Buffer"), &["java/lang/Comparable"], false),
+    ("java/nio/FloatBuffer", Some("java/nio/Buffer"), &["java/lang/Comparable"], false),
+    ("java/nio/IntBuffer", Some("java/nio/Buffer"), &["java/lang/Comparable"], false),
+    ("java/nio/InvalidMarkException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/LongBuffer", Some("java/nio/Buffer"), &["java/lang/Comparable"], false),
+    ("java/nio/MappedByteBuffer", Some("java/nio/ByteBuffer"), &[], false),
+    ("java/nio/ReadOnlyBufferException", Some("java/lang/UnsupportedOperationException"), &[], false),
+    ("java/nio/ShortBuffer", Some("java/nio/Buffer"), &["java/lang/Comparable"], false),
+    ("java/nio/channels/AcceptPendingException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/AlreadyBoundException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/AlreadyConnectedException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/AsynchronousByteChannel", Some("java/lang/Object"), &["java/nio/channels/AsynchronousChannel"], true),
+    ("java/nio/channels/AsynchronousChannel", Some("java/lang/Object"), &["java/nio/channels/Channel"], true),
+    ("java/nio/channels/AsynchronousChannelGroup", Some("java/lang/Object"), &[], false),
+    ("java/nio/channels/AsynchronousCloseException", Some("java/nio/channels/ClosedChannelException"), &[], false),
+    ("java/nio/channels/AsynchronousFileChannel", Some("java/lang/Object"), &["java/nio/channels/AsynchronousChannel"], false),
+    ("java/nio/channels/AsynchronousServerSocketChannel", Some("java/lang/Object"), &["java/nio/channels/AsynchronousChannel", "java/nio/channels/NetworkChannel"], false),
+    ("java/nio/channels/AsynchronousSocketChannel", Some("java/lang/Object"), &["java/nio/channels/AsynchronousByteChannel", "java/nio/channels/NetworkChannel"], false),
+    ("java/nio/channels/ByteChannel", Some("java/lang/Object"), &["java/nio/channels/ReadableByteChannel", "java/nio/channels/WritableByteChannel"], true),
+    ("java/nio/channels/CancelledKeyException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/Channel", Some("java/lang/Object"), &["java/io/Closeable"], true),
+    ("java/nio/channels/Channels", Some("java/lang/Object"), &[], false),
+    ("java/nio/channels/ClosedByInterruptException", Some("java/nio/channels/AsynchronousCloseException"), &[], false),
+    ("java/nio/channels/ClosedChannelException", Some("java/io/IOException"), &[], false),
+    ("java/nio/channels/ClosedSelectorException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/CompletionHandler", Some("java/lang/Object"), &[], true),
+    ("java/nio/channels/ConnectionPendingException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/DatagramChannel", Some("java/nio/channels/spi/AbstractSelectableChannel"), &["java/nio/channels/ByteChannel", "java/nio/channels/ScatteringByteChannel", "java/nio/channels/GatheringByteChannel", "java/nio/channels/MulticastChannel"], false),
+    ("java/nio/channels/FileChannel", Some("java/nio/channels/spi/AbstractInterruptibleChannel"), &["java/nio/channels/SeekableByteChannel", "java/nio/channels/GatheringByteChannel", "java/nio/channels/ScatteringByteChannel"], false),
+    ("java/nio/channels/FileChannel$MapMode", Some("java/lang/Object"), &[], false),
+    ("java/nio/channels/FileLock", Some("java/lang/Object"), &["java/lang/AutoCloseable"], false),
+    ("java/nio/channels/FileLockInterruptionException", Some("java/io/IOException"), &[], false),
+    ("java/nio/channels/GatheringByteChannel", Some("java/lang/Object"), &["java/nio/channels/WritableByteChannel"], true),
+    ("java/nio/channels/IllegalBlockingModeException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/IllegalChannelGroupException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/channels/IllegalSelectorException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/channels/InterruptedByTimeoutException", Some("java/io/IOException"), &[], false),
+    ("java/nio/channels/InterruptibleChannel", Some("java/lang/Object"), &["java/nio/channels/Channel"], true),
+    ("java/nio/channels/MembershipKey", Some("java/lang/Object"), &[], false),
+    ("java/nio/channels/MulticastChannel", Some("java/lang/Object"), &["java/nio/channels/NetworkChannel"], true),
+    ("java/nio/channels/NetworkChannel", Some("java/lang/Object"), &["java/nio/channels/Channel"], true),
+    ("java/nio/channels/NoConnectionPendingException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/NonReadableChannelException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/NonWritableChannelException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/NotYetBoundException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/NotYetConnectedException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/OverlappingFileLockException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/Pipe", Some("java/lang/Object"), &[], false),
+    ("java/nio/channels/Pipe$SinkChannel", Some("java/nio/channels/spi/AbstractSelectableChannel"), &["java/nio/channels/WritableByteChannel", "java/nio/channels/GatheringByteChannel"], false),
+    ("java/nio/channels/Pipe$SourceChannel", Some("java/nio/channels/spi/AbstractSelectableChannel"), &["java/nio/channels/ReadableByteChannel", "java/nio/channels/ScatteringByteChannel"], false),
+    ("java/nio/channels/ReadPendingException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/ReadableByteChannel", Some("java/lang/Object"), &["java/nio/channels/Channel"], true),
+    ("java/nio/channels/ScatteringByteChannel", Some("java/lang/Object"), &["java/nio/channels/ReadableByteChannel"], true),
+    ("java/nio/channels/SeekableByteChannel", Some("java/lang/Object"), &["java/nio/channels/ByteChannel"], true),
+    ("java/nio/channels/SelectableChannel", Some("java/nio/channels/spi/AbstractInterruptibleChannel"), &["java/nio/channels/Channel"], false),
+    ("java/nio/channels/SelectionKey", Some("java/lang/Object"), &[], false),
+    ("java/nio/channels/Selector", Some("java/lang/Object"), &["java/io/Closeable"], false),
+    ("java/nio/channels/ServerSocketChannel", Some("java/nio/channels/spi/AbstractSelectableChannel"), &["java/nio/channels/NetworkChannel"], false),
+    ("java/nio/channels/ShutdownChannelGroupException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/SocketChannel", Some("java/nio/channels/spi/AbstractSelectableChannel"), &["java/nio/channels/ByteChannel", "java/nio/channels/ScatteringByteChannel", "java/nio/channels/GatheringByteChannel", "java/nio/channels/NetworkChannel"], false),
+    ("java/nio/channels/UnresolvedAddressException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/channels/UnsupportedAddressTypeException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/channels/WritableByteChannel", Some("java/lang/Object"), &["java/nio/channels/Channel"], true),
+    ("java/nio/channels/WritePendingException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/channels/spi/AbstractInterruptibleChannel", Some("java/lang/Object"), &["java/nio/channels/Channel", "java/nio/channels/InterruptibleChannel"], false),
+    ("java/nio/channels/spi/AbstractSelectableChannel", Some("java/nio/channels/SelectableChannel"), &[], false),
+    ("java/nio/channels/spi/AbstractSelectionKey", Some("java/nio/channels/SelectionKey"), &[], false),
+    ("java/nio/channels/spi/AbstractSelector", Some("java/nio/channels/Selector"), &[], false),
+    ("java/nio/channels/spi/AsynchronousChannelProvider", Some("java/lang/Object"), &[], false),
+    ("java/nio/channels/spi/SelectorProvider", Some("java/lang/Object"), &[], false),
+    ("java/nio/charset/CharacterCodingException", Some("java/io/IOException"), &[], false),
+    ("java/nio/charset/Charset", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/nio/charset/CharsetDecoder", Some("java/lang/Object"), &[], false),
+    ("java/nio/charset/CharsetEncoder", Some("java/lang/Object"), &[], false),
+    ("java/nio/charset/CoderMalfunctionError", Some("java/lang/Error"), &[], false),
+    ("java/nio/charset/CoderResult", Some("java/lang/Object"), &[], false),
+    ("java/nio/charset/CodingErrorAction", Some("java/lang/Object"), &[], false),
+    ("java/nio/charset/IllegalCharsetNameException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/charset/MalformedInputException", Some("java/nio/charset/CharacterCodingException"), &[], false),
+    ("java/nio/charset/StandardCharsets", Some("java/lang/Object"), &[], false),
+    ("java/nio/charset/UnmappableCharacterException", Some("java/nio/charset/CharacterCodingException"), &[], false),
+    ("java/nio/charset/UnsupportedCharsetException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/charset/spi/CharsetProvider", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/AccessDeniedException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/AccessMode", Some("java/lang/Enum"), &[], false),
+    ("java/nio/file/AtomicMoveNotSupportedException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/ClosedDirectoryStreamException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/file/ClosedFileSystemException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/file/ClosedWatchServiceException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/nio/file/CopyOption", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/DirectoryIteratorException", Some("java/util/ConcurrentModificationException"), &[], false),
+    ("java/nio/file/DirectoryNotEmptyException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/DirectoryStream", Some("java/lang/Object"), &["java/io/Closeable", "java/lang/Iterable"], true),
+    ("java/nio/file/DirectoryStream$Filter", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/FileAlreadyExistsException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/FileStore", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/FileSystem", Some("java/lang/Object"), &["java/io/Closeable"], false),
+    ("java/nio/file/FileSystemAlreadyExistsException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/nio/file/FileSystemException", Some("java/io/IOException"), &[], false),
+    ("java/nio/file/FileSystemLoopException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/FileSystemNotFoundException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/nio/file/FileSystems", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/FileVisitOption", Some("java/lang/Enum"), &[], false),
+    ("java/nio/file/FileVisitResult", Some("java/lang/Enum"), &[], false),
+    ("java/nio/file/FileVisitor", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/Files", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/InvalidPathException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/file/LinkOption", Some("java/lang/Enum"), &["java/nio/file/OpenOption", "java/nio/file/CopyOption"], false),
+    ("java/nio/file/LinkPermission", Some("java/security/BasicPermission"), &[], false),
+    ("java/nio/file/NoSuchFileException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/NotDirectoryException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/NotLinkException", Some("java/nio/file/FileSystemException"), &[], false),
+    ("java/nio/file/OpenOption", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/Path", Some("java/lang/Object"), &["java/lang/Comparable", "java/lang/Iterable", "java/nio/file/Watchable"], true),
+    ("java/nio/file/PathMatcher", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/Paths", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/ProviderMismatchException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/nio/file/ProviderNotFoundException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/nio/file/ReadOnlyFileSystemException", Some("java/lang/UnsupportedOperationException"), &[], false),
+    ("java/nio/file/SecureDirectoryStream", Some("java/lang/Object"), &["java/nio/file/DirectoryStream"], true),
+    ("java/nio/file/SimpleFileVisitor", Some("java/lang/Object"), &["java/nio/file/FileVisitor"], false),
+    ("java/nio/file/StandardCopyOption", Some("java/lang/Enum"), &["java/nio/file/CopyOption"], false),
+    ("java/nio/file/StandardOpenOption", Some("java/lang/Enum"), &["java/nio/file/OpenOption"], false),
+    ("java/nio/file/StandardWatchEventKinds", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/WatchEvent", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/WatchEvent$Kind", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/WatchEvent$Modifier", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/WatchKey", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/WatchService", Some("java/lang/Object"), &["java/io/Closeable"], true),
+    ("java/nio/file/Watchable", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/attribute/AclEntry", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/attribute/AclEntry$Builder", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/attribute/AclEntryFlag", Some("java/lang/Enum"), &[], false),
+    ("java/nio/file/attribute/AclEntryPermission", Some("java/lang/Enum"), &[], false),
+    ("java/nio/file/attribute/AclEntryType", Some("java/lang/Enum"), &[], false),
+    ("java/nio/file/attribute/AclFileAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/FileOwnerAttributeView"], true),
+    ("java/nio/file/attribute/AttributeView", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/attribute/BasicFileAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/FileAttributeView"], true),
+    ("java/nio/file/attribute/BasicFileAttributes", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/attribute/DosFileAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/BasicFileAttributeView"], true),
+    ("java/nio/file/attribute/DosFileAttributes", Some("java/lang/Object"), &["java/nio/file/attribute/BasicFileAttributes"], true),
+    ("java/nio/file/attribute/FileAttribute", Some("java/lang/Object"), &[], true),
+    ("java/nio/file/attribute/FileAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/AttributeView"], true),
+    ("java/nio/file/attribute/FileOwnerAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/FileAttributeView"], true),
+    ("java/nio/file/attribute/FileStoreAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/AttributeView"], true),
+    ("java/nio/file/attribute/FileTime", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/nio/file/attribute/GroupPrincipal", Some("java/lang/Object"), &["java/nio/file/attribute/UserPrincipal"], true),
+    ("java/nio/file/attribute/PosixFileAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/BasicFileAttributeView", "java/nio/file/attribute/FileOwnerAttributeView"], true),
+    ("java/nio/file/attribute/PosixFileAttributes", Some("java/lang/Object"), &["java/nio/file/attribute/BasicFileAttributes"], true),
+    ("java/nio/file/attribute/PosixFilePermission", Some("java/lang/Enum"), &[], false),
+    ("java/nio/file/attribute/PosixFilePermissions", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/attribute/UserDefinedFileAttributeView", Some("java/lang/Object"), &["java/nio/file/attribute/FileAttributeView"], true),
+    ("java/nio/file/attribute/UserPrincipal", Some("java/lang/Object"), &["java/security/Principal"], true),
+    ("java/nio/file/attribute/UserPrincipalLookupService", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/attribute/UserPrincipalNotFoundException", Some("java/io/IOException"), &[], false),
+    ("java/nio/file/spi/FileSystemProvider", Some("java/lang/Object"), &[], false),
+    ("java/nio/file/spi/FileTypeDetector", Some("java/lang/Object"), &[], false),
+    ("java/security/AccessControlContext", Some("java/lang/Object"), &[], false),
+    ("java/security/AccessControlException", Some("java/lang/SecurityException"), &[], false),
+    ("java/security/AccessController", Some("java/lang/Object"), &[], false),
+    ("java/security/AlgorithmConstraints", Some("java/lang/Object"), &[], true),
+    ("java/security/AlgorithmParameterGenerator", Some("java/lang/Object"), &[], false),
+    ("java/security/AlgorithmParameterGeneratorSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/AlgorithmParameters", Some("java/lang/Object"), &[], false),
+    ("java/security/AlgorithmParametersSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/AllPermission", Some("java/security/Permission"), &[], false),
+    ("java/security/AuthProvider", Some("java/security/Provider"), &[], false),
+    ("java/security/BasicPermission", Some("java/security/Permission"), &["java/io/Serializable"], false),
+    ("java/security/Certificate", Some("java/lang/Object"), &[], true),
+    ("java/security/CodeSigner", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/CodeSource", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/CryptoPrimitive", Some("java/lang/Enum"), &[], false),
+    ("java/security/DigestException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/DigestInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("java/security/DigestOutputStream", Some("java/io/FilterOutputStream"), &[], false),
+    ("java/security/DomainCombiner", Some("java/lang/Object"), &[], true),
+    ("java/security/DomainLoadStoreParameter", Some("java/lang/Object"), &["java/security/KeyStore$LoadStoreParameter"], false),
+    ("java/security/DrbgParameters", Some("java/lang/Object"), &[], false),
+    ("java/security/DrbgParameters$Capability", Some("java/lang/Enum"), &[], false),
+    ("java/security/DrbgParameters$Instantiation", Some("java/lang/Object"), &["java/security/SecureRandomParameters"], false),
+    ("java/security/DrbgParameters$NextBytes", Some("java/lang/Object"), &["java/security/SecureRandomParameters"], false),
+    ("java/security/DrbgParameters$Reseed", Some("java/lang/Object"), &["java/security/SecureRandomParameters"], false),
+    ("java/security/GeneralSecurityException", Some("java/lang/Exception"), &[], false),
+    ("java/security/Guard", Some("java/lang/Object"), &[], true),
+    ("java/security/GuardedObject", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/Identity", Some("java/lang/Object"), &["java/security/Principal", "java/io/Serializable"], false),
+    ("java/security/IdentityScope", Some("java/security/Identity"), &[], false),
+    ("java/security/InvalidAlgorithmParameterException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/InvalidKeyException", Some("java/security/KeyException"), &[], false),
+    ("java/security/InvalidParameterException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/security/Key", Some("java/lang/Object"), &["java/io/Serializable"], true),
+    ("java/security/KeyException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/KeyFactory", Some("java/lang/Object"), &[], false),
+    ("java/security/KeyFactorySpi", Some("java/lang/Object"), &[], false),
+    ("java/security/KeyManagementException", Some("java/security/KeyException"), &[], false),
+    ("java/security/KeyPair", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/KeyPairGenerator", Some("java/security/KeyPairGeneratorSpi"), &[], false),
+    ("java/security/KeyPairGeneratorSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/KeyRep", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/KeyRep$Type", Some("java/lang/Enum"), &[], false),
+    ("java/security/KeyStore", Some("java/lang/Object"), &[], false),
+    ("java/security/KeyStore$Builder", Some("java/lang/Object"), &[], false),
+    ("java/security/KeyStore$CallbackHandlerProtection", Some("java/lang/Object"), &["java/security/KeyStore$ProtectionParameter"], false),
+    ("java/security/KeyStore$Entry", Some("java/lang/Object"), &[], true),
+    ("java/security/KeyStore$Entry$Attribute", Some("java/lang/Object"), &[], true),
+    ("java/security/KeyStore$LoadStoreParameter", Some("java/lang/Object"), &[], true),
+    ("java/security/KeyStore$PasswordProtection", Some("java/lang/Object"), &["java/security/KeyStore$ProtectionParameter", "javax/security/auth/Destroyable"], false),
+    ("java/security/KeyStore$PrivateKeyEntry", Some("java/lang/Object"), &["java/security/KeyStore$Entry"], false),
+    ("java/security/KeyStore$ProtectionParameter", Some("java/lang/Object"), &[], true),
+    ("java/security/KeyStore$SecretKeyEntry", Some("java/lang/Object"), &["java/security/KeyStore$Entry"], false),
+    ("java/security/KeyStore$TrustedCertificateEntry", Some("java/lang/Object"), &["java/security/KeyStore$Entry"], false),
+    ("java/security/KeyStoreException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/KeyStoreSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/MessageDigest", Some("java/security/MessageDigestSpi"), &[], false),
+    ("java/security/MessageDigestSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/NoSuchAlgorithmException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/NoSuchProviderException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/PKCS12Attribute", Some("java/lang/Object"), &["java/security/KeyStore$Entry$Attribute"], false),
+    ("java/security/Permission", Some("java/lang/Object"), &["java/security/Guard", "java/io/Serializable"], false),
+    ("java/security/PermissionCollection", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/Permissions", Some("java/security/PermissionCollection"), &["java/io/Serializable"], false),
+    ("java/security/Policy", Some("java/lang/Object"), &[], false),
+    ("java/security/Policy$Parameters", Some("java/lang/Object"), &[], true),
+    ("java/security/PolicySpi", Some("java/lang/Object"), &[], false),
+    ("java/security/Principal", Some("java/lang/Object"), &[], true),
+    ("java/security/PrivateKey", Some("java/lang/Object"), &["java/security/Key", "javax/security/auth/Destroyable"], true),
+    ("java/security/PrivilegedAction", Some("java/lang/Object"), &[], true),
+    ("java/security/PrivilegedActionException", Some("java/lang/Exception"), &[], false),
+    ("java/security/PrivilegedExceptionAction", Some("java/lang/Object"), &[], true),
+    ("java/security/ProtectionDomain", Some("java/lang/Object"), &[], false),
+    ("java/security/Provider", Some("java/util/Properties"), &[], false),
+    ("java/security/Provider$Service", Some("java/lang/Object"), &[], false),
+    ("java/security/ProviderException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/security/PublicKey", Some("java/lang/Object"), &["java/security/Key"], true),
+    ("java/security/SecureClassLoader", Some("java/lang/ClassLoader"), &[], false),
+    ("java/security/SecureRandom", Some("java/util/Random"), &[], false),
+    ("java/security/SecureRandomParameters", Some("java/lang/Object"), &[], true),
+    ("java/security/SecureRandomSpi", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/Security", Some("java/lang/Object"), &[], false),
+    ("java/security/SecurityPermission", Some("java/security/BasicPermission"), &[], false),
+    ("java/security/Signature", Some("java/security/SignatureSpi"), &[], false),
+    ("java/security/SignatureException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/SignatureSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/SignedObject", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/Signer", Some("java/security/Identity"), &[], false),
+    ("java/security/Timestamp", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/URIParameter", Some("java/lang/Object"), &["java/security/Policy$Parameters", "javax/security/auth/login/Configuration$Parameters"], false),
+    ("java/security/UnrecoverableEntryException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/UnrecoverableKeyException", Some("java/security/UnrecoverableEntryException"), &[], false),
+    ("java/security/UnresolvedPermission", Some("java/security/Permission"), &["java/io/Serializable"], false),
+    ("java/security/cert/CRL", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CRLException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/cert/CRLReason", Some("java/lang/Enum"), &[], false),
+    ("java/security/cert/CRLSelector", Some("java/lang/Object"), &["java/lang/Cloneable"], true),
+    ("java/security/cert/CertPath", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/cert/CertPathBuilder", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CertPathBuilderException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/cert/CertPathBuilderResult", Some("java/lang/Object"), &["java/lang/Cloneable"], true),
+    ("java/security/cert/CertPathBuilderSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CertPathChecker", Some("java/lang/Object"), &[], true),
+    ("java/security/cert/CertPathParameters", Some("java/lang/Object"), &["java/lang/Cloneable"], true),
+    ("java/security/cert/CertPathValidator", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CertPathValidatorException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/cert/CertPathValidatorException$BasicReason", Some("java/lang/Enum"), &["java/security/cert/CertPathValidatorException$Reason"], false),
+    ("java/security/cert/CertPathValidatorException$Reason", Some("java/lang/Object"), &["java/io/Serializable"], true),
+    ("java/security/cert/CertPathValidatorResult", Some("java/lang/Object"), &["java/lang/Cloneable"], true),
+    ("java/security/cert/CertPathValidatorSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CertSelector", Some("java/lang/Object"), &["java/lang/Cloneable"], true),
+    ("java/security/cert/CertStore", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CertStoreException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/cert/CertStoreParameters", Some("java/lang/Object"), &["java/lang/Cloneable"], true),
+    ("java/security/cert/CertStoreSpi", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/Certificate", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/security/cert/CertificateEncodingException", Some("java/security/cert/CertificateException"), &[], false),
+    ("java/security/cert/CertificateException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/cert/CertificateExpiredException", Some("java/security/cert/CertificateException"), &[], false),
+    ("java/security/cert/CertificateFactory", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CertificateFactorySpi", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/CertificateNotYetValidException", Some("java/security/cert/CertificateException"), &[], false),
+    ("java/security/cert/CertificateParsingException", Some("java/security/cert/CertificateException"), &[], false),
+    ("java/security/cert/CertificateRevokedException", Some("java/security/cert/CertificateException"), &[], false),
+    ("java/security/cert/CollectionCertStoreParameters", Some("java/lang/Object"), &["java/security/cert/CertStoreParameters"], false),
+    ("java/security/cert/Extension", Some("java/lang/Object"), &[], true),
+    ("java/security/cert/LDAPCertStoreParameters", Some("java/lang/Object"), &["java/security/cert/CertStoreParameters"], false),
+    ("java/security/cert/PKIXBuilderParameters", Some("java/security/cert/PKIXParameters"), &[], false),
+    ("java/security/cert/PKIXCertPathBuilderResult", Some("java/security/cert/PKIXCertPathValidatorResult"), &["java/security/cert/CertPathBuilderResult"], false),
+    ("java/security/cert/PKIXCertPathChecker", Some("java/lang/Object"), &["java/security/cert/CertPathChecker", "java/lang/Cloneable"], false),
+    ("java/security/cert/PKIXCertPathValidatorResult", Some("java/lang/Object"), &["java/security/cert/CertPathValidatorResult"], false),
+    ("java/security/cert/PKIXParameters", Some("java/lang/Object"), &["java/security/cert/CertPathParameters"], false),
+    ("java/security/cert/PKIXReason", Some("java/lang/Enum"), &["java/security/cert/CertPathValidatorException$Reason"], false),
+    ("java/security/cert/PKIXRevocationChecker", Some("java/security/cert/PKIXCertPathChecker"), &[], false),
+    ("java/security/cert/PKIXRevocationChecker$Option", Some("java/lang/Enum"), &[], false),
+    ("java/security/cert/PolicyNode", Some("java/lang/Object"), &[], true),
+    ("java/security/cert/PolicyQualifierInfo", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/TrustAnchor", Some("java/lang/Object"), &[], false),
+    ("java/security/cert/URICertStoreParameters", Some("java/lang/Object"), &["java/security/cert/CertStoreParameters"], false),
+    ("java/security/cert/X509CRL", Some("java/security/cert/CRL"), &["java/security/cert/X509Extension"], false),
+    ("java/security/cert/X509CRLEntry", Some("java/lang/Object"), &["java/security/cert/X509Extension"], false),
+    ("java/security/cert/X509CRLSelector", Some("java/lang/Object"), &["java/security/cert/CRLSelector"], false),
+    ("java/security/cert/X509CertSelector", Some("java/lang/Object"), &["java/security/cert/CertSelector"], false),
+    ("java/security/cert/X509Certificate", Some("java/security/cert/Certificate"), &["java/security/cert/X509Extension"], false),
+    ("java/security/cert/X509Extension", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/DSAKey", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/DSAKeyPairGenerator", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/DSAParams", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/DSAPrivateKey", Some("java/lang/Object"), &["java/security/interfaces/DSAKey", "java/security/PrivateKey"], true),
+    ("java/security/interfaces/DSAPublicKey", Some("java/lang/Object"), &["java/security/interfaces/DSAKey", "java/security/PublicKey"], true),
+    ("java/security/interfaces/ECKey", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/ECPrivateKey", Some("java/lang/Object"), &["java/security/PrivateKey", "java/security/interfaces/ECKey"], true),
+    ("java/security/interfaces/ECPublicKey", Some("java/lang/Object"), &["java/security/PublicKey", "java/security/interfaces/ECKey"], true),
+    ("java/security/interfaces/EdECKey", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/EdECPrivateKey", Some("java/lang/Object"), &["java/security/interfaces/EdECKey", "java/security/PrivateKey"], true),
+    ("java/security/interfaces/EdECPublicKey", Some("java/lang/Object"), &["java/security/interfaces/EdECKey", "java/security/PublicKey"], true),
+    ("java/security/interfaces/RSAKey", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/RSAMultiPrimePrivateCrtKey", Some("java/lang/Object"), &["java/security/interfaces/RSAPrivateKey"], true),
+    ("java/security/interfaces/RSAPrivateCrtKey", Some("java/lang/Object"), &["java/security/interfaces/RSAPrivateKey"], true),
+    ("java/security/interfaces/RSAPrivateKey", Some("java/lang/Object"), &["java/security/PrivateKey", "java/security/interfaces/RSAKey"], true),
+    ("java/security/interfaces/RSAPublicKey", Some("java/lang/Object"), &["java/security/PublicKey", "java/security/interfaces/RSAKey"], true),
+    ("java/security/interfaces/XECKey", Some("java/lang/Object"), &[], true),
+    ("java/security/interfaces/XECPrivateKey", Some("java/lang/Object"), &["java/security/interfaces/XECKey", "java/security/PrivateKey"], true),
+    ("java/security/interfaces/XECPublicKey", Some("java/lang/Object"), &["java/security/interfaces/XECKey", "java/security/PublicKey"], true),
+    ("java/security/spec/AlgorithmParameterSpec", Some("java/lang/Object"), &[], true),
+    ("java/security/spec/DSAGenParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("java/security/spec/DSAParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec", "java/security/interfaces/DSAParams"], false),
+    ("java/security/spec/DSAPrivateKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/DSAPublicKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/ECField", Some("java/lang/Object"), &[], true),
+    ("java/security/spec/ECFieldF2m", Some("java/lang/Object"), &["java/security/spec/ECField"], false),
+    ("java/security/spec/ECFieldFp", Some("java/lang/Object"), &["java/security/spec/ECField"], false),
+    ("java/security/spec/ECGenParameterSpec", Some("java/security/spec/NamedParameterSpec"), &[], false),
+    ("java/security/spec/ECParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("java/security/spec/ECPoint", Some("java/lang/Object"), &[], false),
+    ("java/security/spec/ECPrivateKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/ECPublicKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/EdDSAParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("java/security/spec/EdECPoint", Some("java/lang/Object"), &[], false),
+    ("java/security/spec/EdECPrivateKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/EdECPublicKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/EllipticCurve", Some("java/lang/Object"), &[], false),
+    ("java/security/spec/EncodedKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/InvalidKeySpecException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/spec/InvalidParameterSpecException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("java/security/spec/KeySpec", Some("java/lang/Object"), &[], true),
+    ("java/security/spec/MGF1ParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("java/security/spec/NamedParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("java/security/spec/PKCS8EncodedKeySpec", Some("java/security/spec/EncodedKeySpec"), &[], false),
+    ("java/security/spec/PSSParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("java/security/spec/RSAKeyGenParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("java/security/spec/RSAMultiPrimePrivateCrtKeySpec", Some("java/security/spec/RSAPrivateKeySpec"), &[], false),
+    ("java/security/spec/RSAOtherPrimeInfo", Some("java/lang/Object"), &[], false),
+    ("java/security/spec/RSAPrivateCrtKeySpec", Some("java/security/spec/RSAPrivateKeySpec"), &[], false),
+    ("java/security/spec/RSAPrivateKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/RSAPublicKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/X509EncodedKeySpec", Some("java/security/spec/EncodedKeySpec"), &[], false),
+    ("java/security/spec/XECPrivateKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/security/spec/XECPublicKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("java/text/Annotation", Some("java/lang/Object"), &[], false),
+    ("java/text/AttributedCharacterIterator", Some("java/lang/Object"), &["java/text/CharacterIterator"], true),
+    ("java/text/AttributedCharacterIterator$Attribute", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/text/AttributedString", Some("java/lang/Object"), &[], false),
+    ("java/text/Bidi", Some("java/lang/Object"), &[], false),
+    ("java/text/BreakIterator", Some("java/lang/Object"), &["java/lang/Cloneable"], false),
+    ("java/text/CharacterIterator", Some("java/lang/Object"), &["java/lang/Cloneable"], true),
+    ("java/text/ChoiceFormat", Some("java/text/NumberFormat"), &[], false),
+    ("java/text/CollationElementIterator", Some("java/lang/Object"), &[], false),
+    ("java/text/CollationKey", Some("java/lang/Object"), &["java/lang/Comparable"], false),
+    ("java/text/Collator", Some("java/lang/Object"), &["java/util/Comparator", "java/lang/Cloneable"], false),
+    ("java/text/CompactNumberFormat", Some("java/text/NumberFormat"), &[], false),
+    ("java/text/DateFormat", Some("java/text/Format"), &[], false),
+    ("java/text/DateFormat$Field", Some("java/text/Format$Field"), &[], false),
+    ("java/text/DateFormatSymbols", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Cloneable"], false),
+    ("java/text/DecimalFormat", Some("java/text/NumberFormat"), &[], false),
+    ("java/text/DecimalFormatSymbols", Some("java/lang/Object"), &["java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/text/FieldPosition", Some("java/lang/Object"), &[], false),
+    ("java/text/Format", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Cloneable"], false),
+    ("java/text/Format$Field", Some("java/text/AttributedCharacterIterator$Attribute"), &[], false),
+    ("java/text/MessageFormat", Some("java/text/Format"), &[], false),
+    ("java/text/MessageFormat$Field", Some("java/text/Format$Field"), &[], false),
+    ("java/text/Normalizer", Some("java/lang/Object"), &[], false),
+    ("java/text/Normalizer$Form", Some("java/lang/Enum"), &[], false),
+    ("java/text/NumberFormat", Some("java/text/Format"), &[], false),
+    ("java/text/NumberFormat$Field", Some("java/text/Format$Field"), &[], false),
+    ("java/text/NumberFormat$Style", Some("java/lang/Enum"), &[], false),
+    ("java/text/ParseException", Some("java/lang/Exception"), &[], false),
+    ("java/text/ParsePosition", Some("java/lang/Object"), &[], false),
+    ("java/text/RuleBasedCollator", Some("java/text/Collator"), &[], false),
+    ("java/text/SimpleDateFormat", Some("java/text/DateFormat"), &[], false),
+    ("java/text/StringCharacterIterator", Some("java/lang/Object"), &["java/text/CharacterIterator"], false),
+    ("java/text/spi/BreakIteratorProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/text/spi/CollatorProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/text/spi/DateFormatProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/text/spi/DateFormatSymbolsProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/text/spi/DecimalFormatSymbolsProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/text/spi/NumberFormatProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/time/Clock", Some("java/lang/Object"), &["java/time/InstantSource"], false),
+    ("java/time/DateTimeException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/time/DayOfWeek", Some("java/lang/Enum"), &["java/time/temporal/TemporalAccessor", "java/time/temporal/TemporalAdjuster"], false),
+    ("java/time/Duration", Some("java/lang/Object"), &["java/time/temporal/TemporalAmount", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/Instant", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/InstantSource", Some("java/lang/Object"), &[], true),
+    ("java/time/LocalDate", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/time/chrono/ChronoLocalDate", "java/io/Serializable"], false),
+    ("java/time/LocalDateTime", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/time/chrono/ChronoLocalDateTime", "java/io/Serializable"], false),
+    ("java/time/LocalTime", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/Month", Some("java/lang/Enum"), &["java/time/temporal/TemporalAccessor", "java/time/temporal/TemporalAdjuster"], false),
+    ("java/time/MonthDay", Some("java/lang/Object"), &["java/time/temporal/TemporalAccessor", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/OffsetDateTime", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/OffsetTime", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/Period", Some("java/lang/Object"), &["java/time/chrono/ChronoPeriod", "java/io/Serializable"], false),
+    ("java/time/Year", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/YearMonth", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/ZoneId", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/time/ZoneOffset", Some("java/time/ZoneId"), &["java/time/temporal/TemporalAccessor", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/ZonedDateTime", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/chrono/ChronoZonedDateTime", "java/io/Serializable"], false),
+    ("java/time/chrono/AbstractChronology", Some("java/lang/Object"), &["java/time/chrono/Chronology"], false),
+    ("java/time/chrono/ChronoLocalDate", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable"], true),
+    ("java/time/chrono/ChronoLocalDateImpl", Some("java/lang/Object"), &["java/time/chrono/ChronoLocalDate", "java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/io/Serializable"], false),
+    ("java/time/chrono/ChronoLocalDateTime", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/time/temporal/TemporalAdjuster", "java/lang/Comparable"], true),
+    ("java/time/chrono/ChronoPeriod", Some("java/lang/Object"), &["java/time/temporal/TemporalAmount"], true),
+    ("java/time/chrono/ChronoZonedDateTime", Some("java/lang/Object"), &["java/time/temporal/Temporal", "java/lang/Comparable"], true),
+    ("java/time/chrono/Chronology", Some("java/lang/Object"), &["java/lang/Comparable"], true),
+    ("java/time/chrono/Era", Some("java/lang/Object"), &["java/time/temporal/TemporalAccessor", "java/time/temporal/TemporalAdjuster"], true),
+    ("java/time/chrono/HijrahChronology", Some("java/time/chrono/AbstractChronology"), &["java/io/Serializable"], false),
+    ("java/time/chrono/HijrahDate", Some("java/time/chrono/ChronoLocalDateImpl"), &["java/time/chrono/ChronoLocalDate", "java/io/Serializable"], false),
+    ("java/time/chrono/HijrahEra", Some("java/lang/Enum"), &["java/time/chrono/Era"], false),
+    ("java/time/chrono/IsoChronology", Some("java/time/chrono/AbstractChronology"), &["java/io/Serializable"], false),
+    ("java/time/chrono/IsoEra", Some("java/lang/Enum"), &["java/time/chrono/Era"], false),
+    ("java/time/chrono/JapaneseChronology", Some("java/time/chrono/AbstractChronology"), &["java/io/Serializable"], false),
+    ("java/time/chrono/JapaneseDate", Some("java/time/chrono/ChronoLocalDateImpl"), &["java/time/chrono/ChronoLocalDate", "java/io/Serializable"], false),
+    ("java/time/chrono/JapaneseEra", Some("java/lang/Object"), &["java/time/chrono/Era", "java/io/Serializable"], false),
+    ("java/time/chrono/MinguoChronology", Some("java/time/chrono/AbstractChronology"), &["java/io/Serializable"], false),
+    ("java/time/chrono/MinguoDate", Some("java/time/chrono/ChronoLocalDateImpl"), &["java/time/chrono/ChronoLocalDate", "java/io/Serializable"], false),
+    ("java/time/chrono/MinguoEra", Some("java/lang/Enum"), &["java/time/chrono/Era"], false),
+    ("java/time/chrono/ThaiBuddhistChronology", Some("java/time/chrono/AbstractChronology"), &["java/io/Serializable"], false),
+    ("java/time/chrono/ThaiBuddhistDate", Some("java/time/chrono/ChronoLocalDateImpl"), &["java/time/chrono/ChronoLocalDate", "java/io/Serializable"], false),
+    ("java/time/chrono/ThaiBuddhistEra", Some("java/lang/Enum"), &["java/time/chrono/Era"], false),
+    ("java/time/format/DateTimeFormatter", Some("java/lang/Object"), &[], false),
+    ("java/time/format/DateTimeFormatterBuilder", Some("java/lang/Object"), &[], false),
+    ("java/time/format/DateTimeParseException", Some("java/time/DateTimeException"), &[], false),
+    ("java/time/format/DecimalStyle", Some("java/lang/Object"), &[], false),
+    ("java/time/format/FormatStyle", Some("java/lang/Enum"), &[], false),
+    ("java/time/format/ResolverStyle", Some("java/lang/Enum"), &[], false),
+    ("java/time/format/SignStyle", Some("java/lang/Enum"), &[], false),
+    ("java/time/format/TextStyle", Some("java/lang/Enum"), &[], false),
+    ("java/time/temporal/ChronoField", Some("java/lang/Enum"), &["java/time/temporal/TemporalField"], false),
+    ("java/time/temporal/ChronoUnit", Some("java/lang/Enum"), &["java/time/temporal/TemporalUnit"], false),
+    ("java/time/temporal/IsoFields", Some("java/lang/Object"), &[], false),
+    ("java/time/temporal/JulianFields", Some("java/lang/Object"), &[], false),
+    ("java/time/temporal/Temporal", Some("java/lang/Object"), &["java/time/temporal/TemporalAccessor"], true),
+    ("java/time/temporal/TemporalAccessor", Some("java/lang/Object"), &[], true),
+    ("java/time/temporal/TemporalAdjuster", Some("java/lang/Object"), &[], true),
+    ("java/time/temporal/TemporalAdjusters", Some("java/lang/Object"), &[], false),
+    ("java/time/temporal/TemporalAmount", Some("java/lang/Object"), &[], true),
+    ("java/time/temporal/TemporalField", Some("java/lang/Object"), &[], true),
+    ("java/time/temporal/TemporalQueries", Some("java/lang/Object"), &[], false),
+    ("java/time/temporal/TemporalQuery", Some("java/lang/Object"), &[], true),
+    ("java/time/temporal/TemporalUnit", Some("java/lang/Object"), &[], true),
+    ("java/time/temporal/UnsupportedTemporalTypeException", Some("java/time/DateTimeException"), &[], false),
+    ("java/time/temporal/ValueRange", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/time/temporal/WeekFields", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/time/zone/ZoneOffsetTransition", Some("java/lang/Object"), &["java/lang/Comparable", "java/io/Serializable"], false),
+    ("java/time/zone/ZoneOffsetTransitionRule", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/time/zone/ZoneOffsetTransitionRule$TimeDefinition", Some("java/lang/Enum"), &[], false),
+    ("java/time/zone/ZoneRules", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/time/zone/ZoneRulesException", Some("java/time/DateTimeException"), &[], false),
+    ("java/time/zone/ZoneRulesProvider", Some("java/lang/Object"), &[], false),
+    ("java/util/AbstractCollection", Some("java/lang/Object"), &["java/util/Collection"], false),
+    ("java/util/AbstractList", Some("java/util/AbstractCollection"), &["java/util/List"], false),
+    ("java/util/AbstractMap", Some("java/lang/Object"), &["java/util/Map"], false),
+    ("java/util/AbstractMap$SimpleEntry", Some("java/lang/Object"), &["java/util/Map$Entry", "java/io/Serializable"], false),
+    ("java/util/AbstractMap$SimpleImmutableEntry", Some("java/lang/Object"), &["java/util/Map$Entry", "java/io/Serializable"], false),
+    ("java/util/AbstractQueue", Some("java/util/AbstractCollection"), &["java/util/Queue"], false),
+    ("java/util/AbstractSequentialList", Some("java/util/AbstractList"), &[], false),
+    ("java/util/AbstractSet", Some("java/util/AbstractCollection"), &["java/util/Set"], false),
+    ("java/util/ArrayDeque", Some("java/util/AbstractCollection"), &["java/util/Deque", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/ArrayList", Some("java/util/AbstractList"), &["java/util/List", "java/util/RandomAccess", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/Arrays", Some("java/lang/Object"), &[], false),
+    ("java/util/Base64", Some("java/lang/Object"), &[], false),
+    ("java/util/Base64$Decoder", Some("java/lang/Object"), &[], false),
+    ("java/util/Base64$Encoder", Some("java/lang/Object"), &[], false),
+    ("java/util/BitSet", Some("java/lang/Object"), &["java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/Calendar", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Cloneable", "java/lang/Comparable"], false),
+    ("java/util/Calendar$Builder", Some("java/lang/Object"), &[], false),
+    ("java/util/Collection", Some("java/lang/Object"), &["java/lang/Iterable"], true),
+    ("java/util/Collections", Some("java/lang/Object"), &[], false),
+    ("java/util/Comparator", Some("java/lang/Object"), &[], true),
+    ("java/util/ConcurrentModificationException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/util/Currency", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/Date", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Cloneable", "java/lang/Comparable"], false),
+    ("java/util/Deque", Some("java/lang/Object"), &["java/util/Queue"], true),
+    ("java/util/Dictionary", Some("java/lang/Object"), &[], false),
+    ("java/util/DoubleSummaryStatistics", Some("java/lang/Object"), &["java/util/function/DoubleConsumer"], false),
+    ("java/util/DuplicateFormatFlagsException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/EmptyStackException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/util/EnumMap", Some("java/util/AbstractMap"), &["java/io/Serializable", "java/lang/Cloneable"], false),
+    ("java/util/EnumSet", Some("java/util/AbstractSet"), &["java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/Enumeration", Some("java/lang/Object"), &[], true),
+    ("java/util/EventListener", Some("java/lang/Object"), &[], true),
+    ("java/util/EventListenerProxy", Some("java/lang/Object"), &["java/util/EventListener"], false),
+    ("java/util/EventObject", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/FormatFlagsConversionMismatchException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/Formattable", Some("java/lang/Object"), &[], true),
+    ("java/util/FormattableFlags", Some("java/lang/Object"), &[], false),
+    ("java/util/Formatter", Some("java/lang/Object"), &["java/io/Closeable", "java/io/Flushable"], false),
+    ("java/util/Formatter$BigDecimalLayoutForm", Some("java/lang/Enum"), &[], false),
+    ("java/util/FormatterClosedException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/util/GregorianCalendar", Some("java/util/Calendar"), &[], false),
+    ("java/util/HashMap", Some("java/util/AbstractMap"), &["java/util/Map", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/HashSet", Some("java/util/AbstractSet"), &["java/util/Set", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/Hashtable", Some("java/util/Dictionary"), &["java/util/Map", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/HexFormat", Some("java/lang/Object"), &[], false),
+    ("java/util/IdentityHashMap", Some("java/util/AbstractMap"), &["java/util/Map", "java/io/Serializable", "java/lang/Cloneable"], false),
+    ("java/util/IllegalFormatCodePointException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/IllegalFormatConversionException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/IllegalFormatException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/util/IllegalFormatFlagsException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/IllegalFormatPrecisionException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/IllegalFormatWidthException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/IllformedLocaleException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/util/InputMismatchException", Some("java/util/NoSuchElementException"), &[], false),
+    ("java/util/IntSummaryStatistics", Some("java/lang/Object"), &["java/util/function/IntConsumer"], false),
+    ("java/util/InvalidPropertiesFormatException", Some("java/io/IOException"), &[], false),
+    ("java/util/Iterator", Some("java/lang/Object"), &[], true),
+    ("java/util/LinkedHashMap", Some("java/util/HashMap"), &["java/util/Map"], false),
+    ("java/util/LinkedHashSet", Some("java/util/HashSet"), &["java/util/Set", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/LinkedList", Some("java/util/AbstractSequentialList"), &["java/util/List", "java/util/Deque", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/List", Some("java/lang/Object"), &["java/util/Collection"], true),
+    ("java/util/ListIterator", Some("java/lang/Object"), &["java/util/Iterator"], true),
+    ("java/util/ListResourceBundle", Some("java/util/ResourceBundle"), &[], false),
+    ("java/util/Locale", Some("java/lang/Object"), &["java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/Locale$Builder", Some("java/lang/Object"), &[], false),
+    ("java/util/Locale$Category", Some("java/lang/Enum"), &[], false),
+    ("java/util/Locale$FilteringMode", Some("java/lang/Enum"), &[], false),
+    ("java/util/Locale$IsoCountryCode", Some("java/lang/Enum"), &[], false),
+    ("java/util/Locale$LanguageRange", Some("java/lang/Object"), &[], false),
+    ("java/util/LongSummaryStatistics", Some("java/lang/Object"), &["java/util/function/LongConsumer", "java/util/function/IntConsumer"], false),
+    ("java/util/Map", Some("java/lang/Object"), &[], true),
+    ("java/util/Map$Entry", Some("java/lang/Object"), &[], true),
+    ("java/util/MissingFormatArgumentException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/MissingFormatWidthException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/MissingResourceException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/util/NavigableMap", Some("java/lang/Object"), &["java/util/SortedMap"], true),
+    ("java/util/NavigableSet", Some("java/lang/Object"), &["java/util/SortedSet"], true),
+    ("java/util/NoSuchElementException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/util/Objects", Some("java/lang/Object"), &[], false),
+    ("java/util/Observable", Some("java/lang/Object"), &[], false),
+    ("java/util/Observer", Some("java/lang/Object"), &[], true),
+    ("java/util/Optional", Some("java/lang/Object"), &[], false),
+    ("java/util/OptionalDouble", Some("java/lang/Object"), &[], false),
+    ("java/util/OptionalInt", Some("java/lang/Object"), &[], false),
+    ("java/util/OptionalLong", Some("java/lang/Object"), &[], false),
+    ("java/util/PrimitiveIterator", Some("java/lang/Object"), &["java/util/Iterator"], true),
+    ("java/util/PrimitiveIterator$OfDouble", Some("java/lang/Object"), &["java/util/PrimitiveIterator"], true),
+    ("java/util/PrimitiveIterator$OfInt", Some("java/lang/Object"), &["java/util/PrimitiveIterator"], true),
+    ("java/util/PrimitiveIterator$OfLong", Some("java/lang/Object"), &["java/util/PrimitiveIterator"], true),
+    ("java/util/PriorityQueue", Some("java/util/AbstractQueue"), &["java/io/Serializable"], false),
+    ("java/util/Properties", Some("java/util/Hashtable"), &[], false),
+    ("java/util/PropertyPermission", Some("java/security/BasicPermission"), &[], false),
+    ("java/util/PropertyResourceBundle", Some("java/util/ResourceBundle"), &[], false),
+    ("java/util/Queue", Some("java/lang/Object"), &["java/util/Collection"], true),
+    ("java/util/Random", Some("java/lang/Object"), &["java/util/random/RandomGenerator", "java/io/Serializable"], false),
+    ("java/util/RandomAccess", Some("java/lang/Object"), &[], true),
+    ("java/util/ResourceBundle", Some("java/lang/Object"), &[], false),
+    ("java/util/ResourceBundle$Control", Some("java/lang/Object"), &[], false),
+    ("java/util/Scanner", Some("java/lang/Object"), &["java/util/Iterator", "java/io/Closeable"], false),
+    ("java/util/ServiceConfigurationError", Some("java/lang/Error"), &[], false),
+    ("java/util/ServiceLoader", Some("java/lang/Object"), &["java/lang/Iterable"], false),
+    ("java/util/ServiceLoader$Provider", Some("java/lang/Object"), &["java/util/function/Supplier"], true),
+    ("java/util/Set", Some("java/lang/Object"), &["java/util/Collection"], true),
+    ("java/util/SimpleTimeZone", Some("java/util/TimeZone"), &[], false),
+    ("java/util/SortedMap", Some("java/lang/Object"), &["java/util/Map"], true),
+    ("java/util/SortedSet", Some("java/lang/Object"), &["java/util/Set"], true),
+    ("java/util/Spliterator", Some("java/lang/Object"), &[], true),
+    ("java/util/Spliterator$OfDouble", Some("java/lang/Object"), &["java/util/Spliterator$OfPrimitive"], true),
+    ("java/util/Spliterator$OfInt", Some("java/lang/Object"), &["java/util/Spliterator$OfPrimitive"], true),
+    ("java/util/Spliterator$OfLong", Some("java/lang/Object"), &["java/util/Spliterator$OfPrimitive"], true),
+    ("java/util/Spliterator$OfPrimitive", Some("java/lang/Object"), &["java/util/Spliterator"], true),
+    ("java/util/Spliterators", Some("java/lang/Object"), &[], false),
+    ("java/util/Spliterators$AbstractDoubleSpliterator", Some("java/lang/Object"), &["java/util/Spliterator$OfDouble"], false),
+    ("java/util/Spliterators$AbstractIntSpliterator", Some("java/lang/Object"), &["java/util/Spliterator$OfInt"], false),
+    ("java/util/Spliterators$AbstractLongSpliterator", Some("java/lang/Object"), &["java/util/Spliterator$OfLong"], false),
+    ("java/util/Spliterators$AbstractSpliterator", Some("java/lang/Object"), &["java/util/Spliterator"], false),
+    ("java/util/SplittableRandom", Some("java/lang/Object"), &["java/util/random/RandomGenerator", "java/util/random/RandomGenerator$SplittableGenerator"], false),
+    ("java/util/Stack", Some("java/util/Vector"), &[], false),
+    ("java/util/StringJoiner", Some("java/lang/Object"), &[], false),
+    ("java/util/StringTokenizer", Some("java/lang/Object"), &["java/util/Enumeration"], false),
+    ("java/util/TimeZone", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Cloneable"], false),
+    ("java/util/Timer", Some("java/lang/Object"), &[], false),
+    ("java/util/TimerTask", Some("java/lang/Object"), &["java/lang/Runnable"], false),
+    ("java/util/TooManyListenersException", Some("java/lang/Exception"), &[], false),
+    ("java/util/TreeMap", Some("java/util/AbstractMap"), &["java/util/NavigableMap", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/TreeSet", Some("java/util/AbstractSet"), &["java/util/NavigableSet", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/UUID", Some("java/lang/Object"), &["java/io/Serializable", "java/lang/Comparable"], false),
+    ("java/util/UnknownFormatConversionException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/UnknownFormatFlagsException", Some("java/util/IllegalFormatException"), &[], false),
+    ("java/util/Vector", Some("java/util/AbstractList"), &["java/util/List", "java/util/RandomAccess", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/WeakHashMap", Some("java/util/AbstractMap"), &["java/util/Map"], false),
+    ("java/util/concurrent/AbstractExecutorService", Some("java/lang/Object"), &["java/util/concurrent/ExecutorService"], false),
+    ("java/util/concurrent/ArrayBlockingQueue", Some("java/util/AbstractQueue"), &["java/util/concurrent/BlockingQueue", "java/io/Serializable"], false),
+    ("java/util/concurrent/BlockingDeque", Some("java/lang/Object"), &["java/util/concurrent/BlockingQueue", "java/util/Deque"], true),
+    ("java/util/concurrent/BlockingQueue", Some("java/lang/Object"), &["java/util/Queue"], true),
+    ("java/util/concurrent/BrokenBarrierException", Some("java/lang/Exception"), &[], false),
+    ("java/util/concurrent/Callable", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/CancellationException", Some("java/lang/IllegalStateException"), &[], false),
+    ("java/util/concurrent/CompletableFuture", Some("java/lang/Object"), &["java/util/concurrent/Future", "java/util/concurrent/CompletionStage"], false),
+    ("java/util/concurrent/CompletableFuture$AsynchronousCompletionTask", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/CompletionException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/util/concurrent/CompletionService", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/CompletionStage", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/ConcurrentHashMap", Some("java/util/AbstractMap"), &["java/util/concurrent/ConcurrentMap", "java/io/Serializable"], false),
+    ("java/util/concurrent/ConcurrentHashMap$CollectionView", Some("java/lang/Object"), &["java/util/Collection", "java/io/Serializable"], false),
+    ("java/util/concurrent/ConcurrentHashMap$KeySetView", Some("java/util/concurrent/ConcurrentHashMap$CollectionView"), &["java/util/Set", "java/io/Serializable"], false),
+    ("java/util/concurrent/ConcurrentLinkedDeque", Some("java/util/AbstractCollection"), &["java/util/Deque", "java/io/Serializable"], false),
+    ("java/util/concurrent/ConcurrentLinkedQueue", Some("java/util/AbstractQueue"), &["java/util/Queue", "java/io/Serializable"], false),
+    ("java/util/concurrent/ConcurrentMap", Some("java/lang/Object"), &["java/util/Map"], true),
+    ("java/util/concurrent/ConcurrentNavigableMap", Some("java/lang/Object"), &["java/util/concurrent/ConcurrentMap", "java/util/NavigableMap"], true),
+    ("java/util/concurrent/ConcurrentSkipListMap", Some("java/util/AbstractMap"), &["java/util/concurrent/ConcurrentNavigableMap", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/concurrent/ConcurrentSkipListSet", Some("java/util/AbstractSet"), &["java/util/NavigableSet", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/concurrent/CopyOnWriteArrayList", Some("java/lang/Object"), &["java/util/List", "java/util/RandomAccess", "java/lang/Cloneable", "java/io/Serializable"], false),
+    ("java/util/concurrent/CopyOnWriteArraySet", Some("java/util/AbstractSet"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/CountDownLatch", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/CountedCompleter", Some("java/util/concurrent/ForkJoinTask"), &[], false),
+    ("java/util/concurrent/CyclicBarrier", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/DelayQueue", Some("java/util/AbstractQueue"), &["java/util/concurrent/BlockingQueue"], false),
+    ("java/util/concurrent/Delayed", Some("java/lang/Object"), &["java/lang/Comparable"], true),
+    ("java/util/concurrent/Exchanger", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/ExecutionException", Some("java/lang/Exception"), &[], false),
+    ("java/util/concurrent/Executor", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/ExecutorCompletionService", Some("java/lang/Object"), &["java/util/concurrent/CompletionService"], false),
+    ("java/util/concurrent/ExecutorService", Some("java/lang/Object"), &["java/util/concurrent/Executor"], true),
+    ("java/util/concurrent/Executors", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/Flow", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/Flow$Processor", Some("java/lang/Object"), &["java/util/concurrent/Flow$Subscriber", "java/util/concurrent/Flow$Publisher"], true),
+    ("java/util/concurrent/Flow$Publisher", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/Flow$Subscriber", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/Flow$Subscription", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/ForkJoinPool", Some("java/util/concurrent/AbstractExecutorService"), &[], false),
+    ("java/util/concurrent/ForkJoinPool$ForkJoinWorkerThreadFactory", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/ForkJoinPool$ManagedBlocker", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/ForkJoinTask", Some("java/lang/Object"), &["java/util/concurrent/Future", "java/io/Serializable"], false),
+    ("java/util/concurrent/ForkJoinWorkerThread", Some("java/lang/Thread"), &[], false),
+    ("java/util/concurrent/Future", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/FutureTask", Some("java/lang/Object"), &["java/util/concurrent/RunnableFuture"], false),
+    ("java/util/concurrent/LinkedBlockingDeque", Some("java/util/AbstractQueue"), &["java/util/concurrent/BlockingDeque", "java/io/Serializable"], false),
+    ("java/util/concurrent/LinkedBlockingQueue", Some("java/util/AbstractQueue"), &["java/util/concurrent/BlockingQueue", "java/io/Serializable"], false),
+    ("java/util/concurrent/LinkedTransferQueue", Some("java/util/AbstractQueue"), &["java/util/concurrent/TransferQueue", "java/io/Serializable"], false),
+    ("java/util/concurrent/Phaser", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/PriorityBlockingQueue", Some("java/util/AbstractQueue"), &["java/util/concurrent/BlockingQueue", "java/io/Serializable"], false),
+    ("java/util/concurrent/RecursiveAction", Some("java/util/concurrent/ForkJoinTask"), &[], false),
+    ("java/util/concurrent/RecursiveTask", Some("java/util/concurrent/ForkJoinTask"), &[], false),
+    ("java/util/concurrent/RejectedExecutionException", Some("java/lang/RuntimeException"), &[], false),
+    ("java/util/concurrent/RejectedExecutionHandler", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/RunnableFuture", Some("java/lang/Object"), &["java/lang/Runnable", "java/util/concurrent/Future"], true),
+    ("java/util/concurrent/RunnableScheduledFuture", Some("java/lang/Object"), &["java/util/concurrent/RunnableFuture", "java/util/concurrent/ScheduledFuture"], true),
+    ("java/util/concurrent/ScheduledExecutorService", Some("java/lang/Object"), &["java/util/concurrent/ExecutorService"], true),
+    ("java/util/concurrent/ScheduledFuture", Some("java/lang/Object"), &["java/util/concurrent/Delayed", "java/util/concurrent/Future"], true),
+    ("java/util/concurrent/ScheduledThreadPoolExecutor", Some("java/util/concurrent/ThreadPoolExecutor"), &["java/util/concurrent/ScheduledExecutorService"], false),
+    ("java/util/concurrent/Semaphore", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/SubmissionPublisher", Some("java/lang/Object"), &["java/util/concurrent/Flow$Publisher", "java/lang/AutoCloseable"], false),
+    ("java/util/concurrent/SynchronousQueue", Some("java/util/AbstractQueue"), &["java/util/concurrent/BlockingQueue", "java/io/Serializable"], false),
+    ("java/util/concurrent/ThreadFactory", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/ThreadLocalRandom", Some("java/util/Random"), &[], false),
+    ("java/util/concurrent/ThreadPoolExecutor", Some("java/util/concurrent/AbstractExecutorService"), &[], false),
+    ("java/util/concurrent/ThreadPoolExecutor$AbortPolicy", Some("java/lang/Object"), &["java/util/concurrent/RejectedExecutionHandler"], false),
+    ("java/util/concurrent/ThreadPoolExecutor$CallerRunsPolicy", Some("java/lang/Object"), &["java/util/concurrent/RejectedExecutionHandler"], false),
+    ("java/util/concurrent/ThreadPoolExecutor$DiscardOldestPolicy", Some("java/lang/Object"), &["java/util/concurrent/RejectedExecutionHandler"], false),
+    ("java/util/concurrent/ThreadPoolExecutor$DiscardPolicy", Some("java/lang/Object"), &["java/util/concurrent/RejectedExecutionHandler"], false),
+    ("java/util/concurrent/TimeUnit", Some("java/lang/Enum"), &[], false),
+    ("java/util/concurrent/TimeoutException", Some("java/lang/Exception"), &[], false),
+    ("java/util/concurrent/TransferQueue", Some("java/lang/Object"), &["java/util/concurrent/BlockingQueue"], true),
+    ("java/util/concurrent/atomic/AtomicBoolean", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/AtomicInteger", Some("java/lang/Number"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/AtomicIntegerArray", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/AtomicIntegerFieldUpdater", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/atomic/AtomicLong", Some("java/lang/Number"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/AtomicLongArray", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/AtomicLongFieldUpdater", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/atomic/AtomicMarkableReference", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/atomic/AtomicReference", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/AtomicReferenceArray", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/AtomicReferenceFieldUpdater", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/atomic/AtomicStampedReference", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/atomic/DoubleAccumulator", Some("java/util/concurrent/atomic/Striped64"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/DoubleAdder", Some("java/util/concurrent/atomic/Striped64"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/LongAccumulator", Some("java/util/concurrent/atomic/Striped64"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/LongAdder", Some("java/util/concurrent/atomic/Striped64"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/atomic/Striped64", Some("java/lang/Number"), &[], false),
+    ("java/util/concurrent/locks/AbstractOwnableSynchronizer", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/locks/AbstractQueuedLongSynchronizer", Some("java/util/concurrent/locks/AbstractOwnableSynchronizer"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/locks/AbstractQueuedLongSynchronizer$ConditionObject", Some("java/lang/Object"), &["java/util/concurrent/locks/Condition", "java/io/Serializable"], false),
+    ("java/util/concurrent/locks/AbstractQueuedSynchronizer", Some("java/util/concurrent/locks/AbstractOwnableSynchronizer"), &["java/io/Serializable"], false),
+    ("java/util/concurrent/locks/AbstractQueuedSynchronizer$ConditionObject", Some("java/lang/Object"), &["java/util/concurrent/locks/Condition", "java/io/Serializable"], false),
+    ("java/util/concurrent/locks/Condition", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/locks/Lock", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/locks/LockSupport", Some("java/lang/Object"), &[], false),
+    ("java/util/concurrent/locks/ReadWriteLock", Some("java/lang/Object"), &[], true),
+    ("java/util/concurrent/locks/ReentrantLock", Some("java/lang/Object"), &["java/util/concurrent/locks/Lock", "java/io/Serializable"], false),
+    ("java/util/concurrent/locks/ReentrantReadWriteLock", Some("java/lang/Object"), &["java/util/concurrent/locks/ReadWriteLock", "java/io/Serializable"], false),
+    ("java/util/concurrent/locks/ReentrantReadWriteLock$ReadLock", Some("java/lang/Object"), &["java/util/concurrent/locks/Lock", "java/io/Serializable"], false),
+    ("java/util/concurrent/locks/ReentrantReadWriteLock$WriteLock", Some("java/lang/Object"), &["java/util/concurrent/locks/Lock", "java/io/Serializable"], false),
+    ("java/util/concurrent/locks/StampedLock", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/function/BiConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/BiFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/BiPredicate", Some("java/lang/Object"), &[], true),
+    ("java/util/function/BinaryOperator", Some("java/lang/Object"), &["java/util/function/BiFunction"], true),
+    ("java/util/function/BooleanSupplier", Some("java/lang/Object"), &[], true),
+    ("java/util/function/Consumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoubleBinaryOperator", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoubleConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoubleFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoublePredicate", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoubleSupplier", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoubleToIntFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoubleToLongFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/DoubleUnaryOperator", Some("java/lang/Object"), &[], true),
+    ("java/util/function/Function", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntBinaryOperator", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntPredicate", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntSupplier", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntToDoubleFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntToLongFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/IntUnaryOperator", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongBinaryOperator", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongPredicate", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongSupplier", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongToDoubleFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongToIntFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/LongUnaryOperator", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ObjDoubleConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ObjIntConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ObjLongConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/function/Predicate", Some("java/lang/Object"), &[], true),
+    ("java/util/function/Supplier", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ToDoubleBiFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ToDoubleFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ToIntBiFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ToIntFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ToLongBiFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/ToLongFunction", Some("java/lang/Object"), &[], true),
+    ("java/util/function/UnaryOperator", Some("java/lang/Object"), &["java/util/function/Function"], true),
+    ("java/util/jar/Attributes", Some("java/lang/Object"), &["java/util/Map", "java/lang/Cloneable"], false),
+    ("java/util/jar/Attributes$Name", Some("java/lang/Object"), &[], false),
+    ("java/util/jar/JarEntry", Some("java/util/zip/ZipEntry"), &[], false),
+    ("java/util/jar/JarException", Some("java/util/zip/ZipException"), &[], false),
+    ("java/util/jar/JarFile", Some("java/util/zip/ZipFile"), &[], false),
+    ("java/util/jar/JarInputStream", Some("java/util/zip/ZipInputStream"), &[], false),
+    ("java/util/jar/JarOutputStream", Some("java/util/zip/ZipOutputStream"), &[], false),
+    ("java/util/jar/Manifest", Some("java/lang/Object"), &["java/lang/Cloneable"], false),
+    ("java/util/random/RandomGenerator", Some("java/lang/Object"), &[], true),
+    ("java/util/random/RandomGenerator$ArbitrarilyJumpableGenerator", Some("java/lang/Object"), &["java/util/random/RandomGenerator$LeapableGenerator"], true),
+    ("java/util/random/RandomGenerator$JumpableGenerator", Some("java/lang/Object"), &["java/util/random/RandomGenerator$StreamableGenerator"], true),
+    ("java/util/random/RandomGenerator$LeapableGenerator", Some("java/lang/Object"), &["java/util/random/RandomGenerator$JumpableGenerator"], true),
+    ("java/util/random/RandomGenerator$SplittableGenerator", Some("java/lang/Object"), &["java/util/random/RandomGenerator$StreamableGenerator"], true),
+    ("java/util/random/RandomGenerator$StreamableGenerator", Some("java/lang/Object"), &["java/util/random/RandomGenerator"], true),
+    ("java/util/random/RandomGeneratorFactory", Some("java/lang/Object"), &[], false),
+    ("java/util/regex/MatchResult", Some("java/lang/Object"), &[], true),
+    ("java/util/regex/Matcher", Some("java/lang/Object"), &["java/util/regex/MatchResult"], false),
+    ("java/util/regex/Pattern", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("java/util/regex/PatternSyntaxException", Some("java/lang/IllegalArgumentException"), &[], false),
+    ("java/util/spi/AbstractResourceBundleProvider", Some("java/lang/Object"), &["java/util/spi/ResourceBundleProvider"], false),
+    ("java/util/spi/CalendarDataProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/util/spi/CalendarNameProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/util/spi/CurrencyNameProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/util/spi/LocaleNameProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/util/spi/LocaleServiceProvider", Some("java/lang/Object"), &[], false),
+    ("java/util/spi/ResourceBundleControlProvider", Some("java/lang/Object"), &[], true),
+    ("java/util/spi/ResourceBundleProvider", Some("java/lang/Object"), &[], true),
+    ("java/util/spi/TimeZoneNameProvider", Some("java/util/spi/LocaleServiceProvider"), &[], false),
+    ("java/util/spi/ToolProvider", Some("java/lang/Object"), &[], true),
+    ("java/util/stream/BaseStream", Some("java/lang/Object"), &["java/lang/AutoCloseable"], true),
+    ("java/util/stream/Collector", Some("java/lang/Object"), &[], true),
+    ("java/util/stream/Collector$Characteristics", Some("java/lang/Enum"), &[], false),
+    ("java/util/stream/Collectors", Some("java/lang/Object"), &[], false),
+    ("java/util/stream/DoubleStream", Some("java/lang/Object"), &["java/util/stream/BaseStream"], true),
+    ("java/util/stream/DoubleStream$Builder", Some("java/lang/Object"), &["java/util/function/DoubleConsumer"], true),
+    ("java/util/stream/DoubleStream$DoubleMapMultiConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/stream/IntStream", Some("java/lang/Object"), &["java/util/stream/BaseStream"], true),
+    ("java/util/stream/IntStream$Builder", Some("java/lang/Object"), &["java/util/function/IntConsumer"], true),
+    ("java/util/stream/IntStream$IntMapMultiConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/stream/LongStream", Some("java/lang/Object"), &["java/util/stream/BaseStream"], true),
+    ("java/util/stream/LongStream$Builder", Some("java/lang/Object"), &["java/util/function/LongConsumer"], true),
+    ("java/util/stream/LongStream$LongMapMultiConsumer", Some("java/lang/Object"), &[], true),
+    ("java/util/stream/Node", Some("java/lang/Object"), &[], true),
+    ("java/util/stream/Node$Builder", Some("java/lang/Object"), &["java/util/stream/Sink"], true),
+    ("java/util/stream/Node$Builder$OfDouble", Some("java/lang/Object"), &["java/util/stream/Node$Builder", "java/util/stream/Sink$OfDouble"], true),
+    ("java/util/stream/Node$Builder$OfInt", Some("java/lang/Object"), &["java/util/stream/Node$Builder", "java/util/stream/Sink$OfInt"], true),
+    ("java/util/stream/Node$Builder$OfLong", Some("java/lang/Object"), &["java/util/stream/Node$Builder", "java/util/stream/Sink$OfLong"], true),
+    ("java/util/stream/Node$OfDouble", Some("java/lang/Object"), &["java/util/stream/Node$OfPrimitive"], true),
+    ("java/util/stream/Node$OfInt", Some("java/lang/Object"), &["java/util/stream/Node$OfPrimitive"], true),
+    ("java/util/stream/Node$OfLong", Some("java/lang/Object"), &["java/util/stream/Node$OfPrimitive"], true),
+    ("java/util/stream/Node$OfPrimitive", Some("java/lang/Object"), &["java/util/stream/Node"], true),
+    ("java/util/stream/Sink", Some("java/lang/Object"), &["java/util/function/Consumer"], true),
+    ("java/util/stream/Sink$ChainedDouble", Some("java/lang/Object"), &["java/util/stream/Sink$OfDouble"], false),
+    ("java/util/stream/Sink$ChainedInt", Some("java/lang/Object"), &["java/util/stream/Sink$OfInt"], false),
+    ("java/util/stream/Sink$ChainedLong", Some("java/lang/Object"), &["java/util/stream/Sink$OfLong"], false),
+    ("java/util/stream/Sink$ChainedReference", Some("java/lang/Object"), &["java/util/stream/Sink"], false),
+    ("java/util/stream/Sink$OfDouble", Some("java/lang/Object"), &["java/util/stream/Sink", "java/util/function/DoubleConsumer"], true),
+    ("java/util/stream/Sink$OfInt", Some("java/lang/Object"), &["java/util/stream/Sink", "java/util/function/IntConsumer"], true),
+    ("java/util/stream/Sink$OfLong", Some("java/lang/Object"), &["java/util/stream/Sink", "java/util/function/LongConsumer"], true),
+    ("java/util/stream/Stream", Some("java/lang/Object"), &["java/util/stream/BaseStream"], true),
+    ("java/util/stream/Stream$Builder", Some("java/lang/Object"), &["java/util/function/Consumer"], true),
+    ("java/util/stream/StreamSupport", Some("java/lang/Object"), &[], false),
+    ("java/util/zip/Adler32", Some("java/lang/Object"), &["java/util/zip/Checksum"], false),
+    ("java/util/zip/CRC32", Some("java/lang/Object"), &["java/util/zip/Checksum"], false),
+    ("java/util/zip/CRC32C", Some("java/lang/Object"), &["java/util/zip/Checksum"], false),
+    ("java/util/zip/CheckedInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("java/util/zip/CheckedOutputStream", Some("java/io/FilterOutputStream"), &[], false),
+    ("java/util/zip/Checksum", Some("java/lang/Object"), &[], true),
+    ("java/util/zip/DataFormatException", Some("java/lang/Exception"), &[], false),
+    ("java/util/zip/Deflater", Some("java/lang/Object"), &[], false),
+    ("java/util/zip/DeflaterInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("java/util/zip/DeflaterOutputStream", Some("java/io/FilterOutputStream"), &[], false),
+    ("java/util/zip/GZIPInputStream", Some("java/util/zip/InflaterInputStream"), &[], false),
+    ("java/util/zip/GZIPOutputStream", Some("java/util/zip/DeflaterOutputStream"), &[], false),
+    ("java/util/zip/Inflater", Some("java/lang/Object"), &[], false),
+    ("java/util/zip/InflaterInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("java/util/zip/InflaterOutputStream", Some("java/io/FilterOutputStream"), &[], false),
+    ("java/util/zip/ZipConstants", Some("java/lang/Object"), &[], true),
+    ("java/util/zip/ZipEntry", Some("java/lang/Object"), &["java/util/zip/ZipConstants", "java/lang/Cloneable"], false),
+    ("java/util/zip/ZipError", Some("java/lang/InternalError"), &[], false),
+    ("java/util/zip/ZipException", Some("java/io/IOException"), &[], false),
+    ("java/util/zip/ZipFile", Some("java/lang/Object"), &["java/util/zip/ZipConstants", "java/io/Closeable"], false),
+    ("java/util/zip/ZipInputStream", Some("java/util/zip/InflaterInputStream"), &["java/util/zip/ZipConstants"], false),
+    ("java/util/zip/ZipOutputStream", Some("java/util/zip/DeflaterOutputStream"), &["java/util/zip/ZipConstants"], false),
+    ("javax/crypto/AEADBadTagException", Some("javax/crypto/BadPaddingException"), &[], false),
+    ("javax/crypto/BadPaddingException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("javax/crypto/Cipher", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/CipherInputStream", Some("java/io/FilterInputStream"), &[], false),
+    ("javax/crypto/CipherOutputStream", Some("java/io/FilterOutputStream"), &[], false),
+    ("javax/crypto/CipherSpi", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/DecapsulateException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("javax/crypto/EncryptedPrivateKeyInfo", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/ExemptionMechanism", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/ExemptionMechanismException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("javax/crypto/ExemptionMechanismSpi", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/IllegalBlockSizeException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("javax/crypto/KEM", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/KEM$Decapsulator", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/KEM$Encapsulated", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/KEM$Encapsulator", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/KEMSpi", Some("java/lang/Object"), &[], true),
+    ("javax/crypto/KEMSpi$DecapsulatorSpi", Some("java/lang/Object"), &[], true),
+    ("javax/crypto/KEMSpi$EncapsulatorSpi", Some("java/lang/Object"), &[], true),
+    ("javax/crypto/KeyAgreement", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/KeyAgreementSpi", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/KeyGenerator", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/KeyGeneratorSpi", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/Mac", Some("java/lang/Object"), &["java/lang/Cloneable"], false),
+    ("javax/crypto/MacSpi", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/NoSuchPaddingException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("javax/crypto/NullCipher", Some("javax/crypto/Cipher"), &[], false),
+    ("javax/crypto/SealedObject", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("javax/crypto/SecretKey", Some("java/lang/Object"), &["java/security/Key", "javax/security/auth/Destroyable"], true),
+    ("javax/crypto/SecretKeyFactory", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/SecretKeyFactorySpi", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/ShortBufferException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("javax/crypto/interfaces/DHKey", Some("java/lang/Object"), &[], true),
+    ("javax/crypto/interfaces/DHPrivateKey", Some("java/lang/Object"), &["javax/crypto/interfaces/DHKey", "java/security/PrivateKey"], true),
+    ("javax/crypto/interfaces/DHPublicKey", Some("java/lang/Object"), &["javax/crypto/interfaces/DHKey", "java/security/PublicKey"], true),
+    ("javax/crypto/interfaces/PBEKey", Some("java/lang/Object"), &["javax/crypto/SecretKey"], true),
+    ("javax/crypto/spec/ChaCha20ParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/DESKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("javax/crypto/spec/DESedeKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("javax/crypto/spec/DHGenParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/DHParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/DHPrivateKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("javax/crypto/spec/DHPublicKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("javax/crypto/spec/GCMParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/IvParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/OAEPParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/PBEKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec"], false),
+    ("javax/crypto/spec/PBEParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/PSource", Some("java/lang/Object"), &[], false),
+    ("javax/crypto/spec/PSource$PSpecified", Some("javax/crypto/spec/PSource"), &[], false),
+    ("javax/crypto/spec/RC2ParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/RC5ParameterSpec", Some("java/lang/Object"), &["java/security/spec/AlgorithmParameterSpec"], false),
+    ("javax/crypto/spec/SecretKeySpec", Some("java/lang/Object"), &["java/security/spec/KeySpec", "javax/crypto/SecretKey"], false),
+    ("javax/net/ServerSocketFactory", Some("java/lang/Object"), &[], false),
+    ("javax/net/SocketFactory", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/CertPathTrustManagerParameters", Some("java/lang/Object"), &["javax/net/ssl/ManagerFactoryParameters"], false),
+    ("javax/net/ssl/ExtendedSSLSession", Some("java/lang/Object"), &["javax/net/ssl/SSLSession"], false),
+    ("javax/net/ssl/HandshakeCompletedEvent", Some("java/util/EventObject"), &[], false),
+    ("javax/net/ssl/HandshakeCompletedListener", Some("java/lang/Object"), &["java/util/EventListener"], true),
+    ("javax/net/ssl/HostnameVerifier", Some("java/lang/Object"), &[], true),
+    ("javax/net/ssl/HttpsURLConnection", Some("java/net/HttpURLConnection"), &[], false),
+    ("javax/net/ssl/KeyManager", Some("java/lang/Object"), &[], true),
+    ("javax/net/ssl/KeyManagerFactory", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/KeyManagerFactorySpi", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/KeyStoreBuilderParameters", Some("java/lang/Object"), &["javax/net/ssl/ManagerFactoryParameters"], false),
+    ("javax/net/ssl/ManagerFactoryParameters", Some("java/lang/Object"), &[], true),
+    ("javax/net/ssl/SNIHostName", Some("javax/net/ssl/SNIServerName"), &[], false),
+    ("javax/net/ssl/SNIMatcher", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/SNIServerName", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/SSLContext", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/SSLContextSpi", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/SSLEngine", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/SSLEngineResult", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/SSLEngineResult$HandshakeStatus", Some("java/lang/Enum"), &[], false),
+    ("javax/net/ssl/SSLEngineResult$Status", Some("java/lang/Enum"), &[], false),
+    ("javax/net/ssl/SSLException", Some("java/io/IOException"), &[], false),
+    ("javax/net/ssl/SSLHandshakeException", Some("javax/net/ssl/SSLException"), &[], false),
+    ("javax/net/ssl/SSLKeyException", Some("javax/net/ssl/SSLException"), &[], false),
+    ("javax/net/ssl/SSLParameters", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/SSLPeerUnverifiedException", Some("javax/net/ssl/SSLException"), &[], false),
+    ("javax/net/ssl/SSLPermission", Some("java/security/BasicPermission"), &[], false),
+    ("javax/net/ssl/SSLProtocolException", Some("javax/net/ssl/SSLException"), &[], false),
+    ("javax/net/ssl/SSLServerSocket", Some("java/net/ServerSocket"), &[], false),
+    ("javax/net/ssl/SSLServerSocketFactory", Some("javax/net/ServerSocketFactory"), &[], false),
+    ("javax/net/ssl/SSLSession", Some("java/lang/Object"), &[], true),
+    ("javax/net/ssl/SSLSessionBindingEvent", Some("java/util/EventObject"), &[], false),
+    ("javax/net/ssl/SSLSessionBindingListener", Some("java/lang/Object"), &["java/util/EventListener"], true),
+    ("javax/net/ssl/SSLSessionContext", Some("java/lang/Object"), &[], true),
+    ("javax/net/ssl/SSLSocket", Some("java/net/Socket"), &[], false),
+    ("javax/net/ssl/SSLSocketFactory", Some("javax/net/SocketFactory"), &[], false),
+    ("javax/net/ssl/StandardConstants", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/TrustManager", Some("java/lang/Object"), &[], true),
+    ("javax/net/ssl/TrustManagerFactory", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/TrustManagerFactorySpi", Some("java/lang/Object"), &[], false),
+    ("javax/net/ssl/X509ExtendedKeyManager", Some("java/lang/Object"), &["javax/net/ssl/X509KeyManager"], false),
+    ("javax/net/ssl/X509ExtendedTrustManager", Some("java/lang/Object"), &["javax/net/ssl/X509TrustManager"], false),
+    ("javax/net/ssl/X509KeyManager", Some("java/lang/Object"), &["javax/net/ssl/KeyManager"], true),
+    ("javax/net/ssl/X509TrustManager", Some("java/lang/Object"), &["javax/net/ssl/TrustManager"], true),
+    ("javax/security/auth/AuthPermission", Some("java/security/BasicPermission"), &[], false),
+    ("javax/security/auth/DestroyFailedException", Some("java/lang/Exception"), &[], false),
+    ("javax/security/auth/Destroyable", Some("java/lang/Object"), &[], true),
+    ("javax/security/auth/PrivateCredentialPermission", Some("java/security/Permission"), &[], false),
+    ("javax/security/auth/RefreshFailedException", Some("java/lang/Exception"), &[], false),
+    ("javax/security/auth/Refreshable", Some("java/lang/Object"), &[], true),
+    ("javax/security/auth/Subject", Some("java/lang/Object"), &["java/io/Serializable"], false),
+    ("javax/security/auth/SubjectDomainCombiner", Some("java/lang/Object"), &["java/security/DomainCombiner"], false),
+    ("javax/security/auth/callback/Callback", Some("java/lang/Object"), &[], true),
+    ("javax/security/auth/callback/CallbackHandler", Some("java/lang/Object"), &[], true),
+    ("javax/security/auth/callback/ChoiceCallback", Some("java/lang/Object"), &["javax/security/auth/callback/Callback", "java/io/Serializable"], false),
+    ("javax/security/auth/callback/ConfirmationCallback", Some("java/lang/Object"), &["javax/security/auth/callback/Callback", "java/io/Serializable"], false),
+    ("javax/security/auth/callback/LanguageCallback", Some("java/lang/Object"), &["javax/security/auth/callback/Callback", "java/io/Serializable"], false),
+    ("javax/security/auth/callback/NameCallback", Some("java/lang/Object"), &["javax/security/auth/callback/Callback", "java/io/Serializable"], false),
+    ("javax/security/auth/callback/PasswordCallback", Some("java/lang/Object"), &["javax/security/auth/callback/Callback", "java/io/Serializable"], false),
+    ("javax/security/auth/callback/TextInputCallback", Some("java/lang/Object"), &["javax/security/auth/callback/Callback", "java/io/Serializable"], false),
+    ("javax/security/auth/callback/TextOutputCallback", Some("java/lang/Object"), &["javax/security/auth/callback/Callback", "java/io/Serializable"], false),
+    ("javax/security/auth/callback/UnsupportedCallbackException", Some("java/lang/Exception"), &[], false),
+    ("javax/security/auth/login/AccountException", Some("javax/security/auth/login/LoginException"), &[], false),
+    ("javax/security/auth/login/AccountExpiredException", Some("javax/security/auth/login/AccountException"), &[], false),
+    ("javax/security/auth/login/AccountLockedException", Some("javax/security/auth/login/AccountException"), &[], false),
+    ("javax/security/auth/login/AccountNotFoundException", Some("javax/security/auth/login/AccountException"), &[], false),
+    ("javax/security/auth/login/AppConfigurationEntry", Some("java/lang/Object"), &[], false),
+    ("javax/security/auth/login/AppConfigurationEntry$LoginModuleControlFlag", Some("java/lang/Object"), &[], false),
+    ("javax/security/auth/login/Configuration", Some("java/lang/Object"), &[], false),
+    ("javax/security/auth/login/Configuration$Parameters", Some("java/lang/Object"), &[], true),
+    ("javax/security/auth/login/ConfigurationSpi", Some("java/lang/Object"), &[], false),
+    ("javax/security/auth/login/CredentialException", Some("javax/security/auth/login/LoginException"), &[], false),
+    ("javax/security/auth/login/CredentialExpiredException", Some("javax/security/auth/login/CredentialException"), &[], false),
+    ("javax/security/auth/login/CredentialNotFoundException", Some("javax/security/auth/login/CredentialException"), &[], false),
+    ("javax/security/auth/login/FailedLoginException", Some("javax/security/auth/login/LoginException"), &[], false),
+    ("javax/security/auth/login/LoginContext", Some("java/lang/Object"), &[], false),
+    ("javax/security/auth/login/LoginException", Some("java/security/GeneralSecurityException"), &[], false),
+    ("javax/security/auth/spi/LoginModule", Some("java/lang/Object"), &[], true),
+    ("javax/security/auth/x500/X500Principal", Some("java/lang/Object"), &["java/security/Principal", "java/io/Serializable"], false),
+    ("javax/security/auth/x500/X500PrivateCredential", Some("java/lang/Object"), &["javax/security/auth/Destroyable"], false),
+    ("javax/security/cert/Certificate", Some("java/lang/Object"), &[], false),
+    ("javax/security/cert/CertificateEncodingException", Some("javax/security/cert/CertificateException"), &[], false),
+    ("javax/security/cert/CertificateException", Some("java/lang/Exception"), &[], false),
+    ("javax/security/cert/CertificateExpiredException", Some("javax/security/cert/CertificateException"), &[], false),
+    ("javax/security/cert/CertificateNotYetValidException", Some("javax/security/cert/CertificateException"), &[], false),
+    ("javax/security/cert/CertificateParsingException", Some("javax/security/cert/CertificateException"), &[], false),
+    ("javax/security/cert/X509Certificate", Some("javax/security/cert/Certificate"), &[], false),
+];
@@ -1,14 +1,48 @@
 use crate::error::ClassReadError;
+use std::collections::{BTreeSet, HashMap};
+
 use crate::insn::{
-    AbstractInsnNode, FieldInsnNode, IincInsnNode, Insn, InsnList, InsnNode, IntInsnNode,
-    InvokeDynamicInsnNode, InvokeInterfaceInsnNode, JumpInsnNode, LabelNode, LdcInsnNode, LdcValue,
-    LookupSwitchInsnNode, MemberRef, MethodInsnNode, MultiANewArrayInsnNode, TableSwitchInsnNode,
-    TryCatchBlockNode, TypeInsnNode, VarInsnNode,
+    AbstractInsnNode, BootstrapArgument, ConstantDynamic, FieldInsnNode, Handle, IincInsnNode,
+    Insn, IntInsnNode, InvokeDynamicInsnNode, InvokeInterfaceInsnNode, JumpInsnNode,
+    JumpLabelInsnNode, Label, LabelNode, LdcInsnNode, LdcValue, LineNumberInsnNode,
+    LocalVariableNode, LookupSwitchInsnNode, LookupSwitchLabelInsnNode, MemberRef,
+    MethodInsnNode, MultiANewArrayInsnNode, NodeList, TableSwitchInsnNode,
+    TableSwitchLabelInsnNode, TryCatchBlockNode, TypeInsnNode, VarInsnNode,
 };
 use crate::{constants, opcodes};
-use crate::constant_pool::CpInfo;
+use crate::constant_pool::{ConstantPoolBuilder, CpInfo};
+use crate::nodes::{
+    AnnotationNode, AnnotationValue, InsnAnnotationNode, LocalVariableAnnotationNode,
+    ModuleExportNode, ModuleNode, ModuleOpenNode, ModuleProvideNode, ModuleRequireNode,
+    RecordComponentNode, TypeAnnotationNode,
+};
+use crate::type_reference::{TypePath, TypeReference};
 use crate::types::Type;
 
+/// Flag to skip the `Code` attributes of methods.
+///
+/// Methods are visited (or added to the `ClassNode`) without code, and their bytecode is
+/// never decoded. Useful when only class and member signatures are needed.
+pub const SKIP_CODE: u32 = 0x1;
+
+/// Flag to skip the debug information of the class.
+///
+/// The `SourceFile`, `SourceDebugExtension`, `LineNumberTable`, `LocalVariableTable`,
+/// `LocalVariableTypeTable` and `MethodParameters` attributes are neither parsed nor reported.
+pub const SKIP_DEBUG: u32 = 0x2;
+
+/// Flag to skip the `StackMapTable` attributes of methods.
+///
+/// Typically used together with `COMPUTE_FRAMES` on the writer side.
+pub const SKIP_FRAMES: u32 = 0x4;
+
+/// Flag to expand the stack map frames.
+///
+/// Every frame of a `StackMapTable` is converted to a `FullFrame` holding the complete
+/// locals and stack, instead of a delta from the previous frame. Class entries needed by
+/// the initial frame are appended to the constant pool if missing.
+pub const EXPAND_FRAMES: u32 = 0x8;
+
 /// Represents a constant value loadable by the `LDC` (Load Constant) instruction.
 ///
 /// This enum wraps various types of constants that can be stored in the constant pool
@@ -29,49 +63,360 @@ pub enum LdcConstant {
     Class(String),
     /// A method type constant (MethodDescriptor).
     MethodType(String),
-    /// A method handle constant.
-    MethodHandle {
-        reference_kind: u8,
-        reference_index: u16,
+    /// A method handle constant, with its referenced member resolved.
+    MethodHandle(Handle),
+    /// A dynamically-computed constant, produced by its bootstrap method.
+    Dynamic(ConstantDynamic),
+}
+
+/// The initial value of a static field, from its `ConstantValue` attribute.
+///
+/// `int`, `short`, `char`, `byte` and `boolean` fields all have an `Integer` value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstantValue {
+    Integer(i32),
+    Float(f32),
+    Long(i64),
+    Double(f64),
+    String(String),
+}
+
+/// A value in the local variables or operand stack of a stack map frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameValue {
+    Top,
+    Integer,
+    Float,
+    Long,
+    Double,
+    Null,
+    UninitializedThis,
+    /// A reference to an instance of the given class (internal name or array descriptor).
+    Object(String),
+    /// An object created by the `NEW` instruction designated by the label,
+    /// whose constructor has not been called yet.
+    Uninitialized(Label),
+}
+
+/// A stack map frame, reported by [`MethodVisitor::visit_frame`] before the instruction it
+/// applies to.
+///
+/// Compressed frames are deltas from the previous frame; with `EXPAND_FRAMES`, every frame is
+/// reported as `Full`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    /// Same locals as the previous frame and an empty stack.
+    Same,
+    /// Same locals as the previous frame and a single stack value.
+    Same1 { stack: FrameValue },
+    /// The last `count` locals of the previous frame are removed, and the stack is empty.
+    Chop { count: u8 },
+    /// The given locals are added to the previous frame, and the stack is empty.
+    Append { locals: Vec<FrameValue> },
+    /// The complete locals and stack.
+    Full {
+        locals: Vec<FrameValue>,
+        stack: Vec<FrameValue>,
     },
-    /// A dynamic constant (computed via `invokedynamic` bootstrap methods).
-    Dynamic,
 }
 
 /// A visitor to visit a Java field.
 ///
 /// The methods of this trait must be called in the following order:
-/// `visit_end`.
+/// (`visit_annotation` | `visit_type_annotation` | `visit_attribute`)* -> `visit_end`.
+///
+/// Every method forwards to [`FieldVisitor::delegate`] by default, so an adapter only needs to
+/// implement the events it transforms.
 pub trait FieldVisitor {
+    /// Returns the field visitor to which events are forwarded by default, if any.
+    fn delegate(&mut self) -> Option<&mut dyn FieldVisitor> {
+        None
+    }
+
+    /// Visits an annotation of the field.
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a type annotation on the type of the field.
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a non-standard attribute of the field, or a standard one without a dedicated
+    /// event whose content does not refer to the constant pool (`Synthetic`, `Deprecated`).
+    ///
+    /// `content` is the `info` of the attribute, copied as is.
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        if let Some(next) = self.delegate() {
+            next.visit_attribute(name, content);
+        }
+    }
+
     /// Visits the end of the field.
     ///
     /// This method, which is the last one to be called, is used to inform the
     /// visitor that all the annotations and attributes of the field have been visited.
-    fn visit_end(&mut self) {}
+    fn visit_end(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_end();
+        }
+    }
+}
+
+/// A visitor to visit a record component.
+///
+/// The methods of this trait must be called in the following order:
+/// (`visit_annotation` | `visit_type_annotation` | `visit_attribute`)* -> `visit_end`.
+///
+/// Every method forwards to [`RecordComponentVisitor::delegate`] by default.
+pub trait RecordComponentVisitor {
+    /// Returns the record component visitor to which events are forwarded by default, if any.
+    fn delegate(&mut self) -> Option<&mut dyn RecordComponentVisitor> {
+        None
+    }
+
+    /// Visits an annotation of the record component.
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a type annotation on the type of the record component.
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a non-standard attribute of the record component, or a standard one without a
+    /// dedicated event whose content does not refer to the constant pool (`Synthetic`,
+    /// `Deprecated`).
+    ///
+    /// `content` is the `info` of the attribute, copied as is.
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        if let Some(next) = self.delegate() {
+            next.visit_attribute(name, content);
+        }
+    }
+
+    /// Visits the end of the record component.
+    fn visit_end(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_end();
+        }
+    }
 }
 
+/// A visitor to visit a Java method.
+///
+/// Every method forwards to [`MethodVisitor::delegate`] by default, so an adapter only needs to
+/// implement the events it transforms.
 pub trait MethodVisitor {
+    /// Returns the method visitor to which events are forwarded by default, if any.
+    fn delegate(&mut self) -> Option<&mut dyn MethodVisitor> {
+        None
+    }
+
+    /// Visits a parameter of the method, from the `MethodParameters` attribute.
+    ///
+    /// This is called first, once per parameter in declaration order. `name` is `None` for a
+    /// parameter without a name.
+    fn visit_parameter(&mut self, name: Option<&str>, access_flags: u16) {
+        if let Some(next) = self.delegate() {
+            next.visit_parameter(name, access_flags);
+        }
+    }
+
+    /// Visits the default value of this annotation interface method.
+    fn visit_annotation_default(&mut self, value: &AnnotationValue) {
+        if let Some(next) = self.delegate() {
+            next.visit_annotation_default(value);
+        }
+    }
+
+    /// Visits an annotation of the method.
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a type annotation on a type in the method declaration.
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits the number of parameters that can have annotations in the
+    /// `Runtime(In)VisibleParameterAnnotations` attribute, before the annotations of these
+    /// parameters. This may be smaller than the number of parameters in the descriptor.
+    fn visit_annotable_parameter_count(&mut self, count: usize, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_annotable_parameter_count(count, visible);
+        }
+    }
+
+    /// Visits an annotation of a parameter of the method.
+    fn visit_parameter_annotation(
+        &mut self,
+        parameter: usize,
+        annotation: &AnnotationNode,
+        visible: bool,
+    ) {
+        if let Some(next) = self.delegate() {
+            next.visit_parameter_annotation(parameter, annotation, visible);
+        }
+    }
+
+    /// Visits a type annotation on the instruction that was visited just before.
+    fn visit_insn_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_insn_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a type annotation on an exception handler type.
+    ///
+    /// This is called after all instructions have been visited.
+    fn visit_try_catch_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_try_catch_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a type annotation on a local variable.
+    ///
+    /// This is called after all instructions have been visited. Each live range of the variable
+    /// is given by `start[i]` (inclusive), `end[i]` (exclusive) and the variable's slot `index[i]`.
+    fn visit_local_variable_annotation(
+        &mut self,
+        annotation: &TypeAnnotationNode,
+        start: &[Label],
+        end: &[Label],
+        index: &[u16],
+        visible: bool,
+    ) {
+        if let Some(next) = self.delegate() {
+            next.visit_local_variable_annotation(annotation, start, end, index, visible);
+        }
+    }
+
+    /// Visits a non-standard attribute of the method, or a standard one without a dedicated
+    /// event whose content does not refer to the constant pool (`Synthetic`, `Deprecated`).
+    ///
+    /// `content` is the `info` of the attribute, copied as is. This is called before
+    /// `visit_code`, and never for the attributes of the `Code` attribute.
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        if let Some(next) = self.delegate() {
+            next.visit_attribute(name, content);
+        }
+    }
+
     /// Starts the visit of the method's code.
-    fn visit_code(&mut self) {}
+    fn visit_code(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_code();
+        }
+    }
+
+    /// Visits a stack map frame, before the instruction it applies to.
+    fn visit_frame(&mut self, frame: &Frame) {
+        if let Some(next) = self.delegate() {
+            next.visit_frame(frame);
+        }
+    }
+
+    /// Visits a label, which designates the instruction that will be visited just after it.
+    fn visit_label(&mut self, label: Label) {
+        if let Some(next) = self.delegate() {
+            next.visit_label(label);
+        }
+    }
+
+    /// Visits a line number declaration.
+    ///
+    /// `start` is the label of the first instruction of this line; it has already been visited.
+    fn visit_line_number(&mut self, line: u16, start: Label) {
+        if let Some(next) = self.delegate() {
+            next.visit_line_number(line, start);
+        }
+    }
+
+    /// Visits an exception handler.
+    ///
+    /// This is called before the instructions, for each entry of the exception table.
+    /// `catch_type` is the internal name of the caught exception, or `None` for `finally` blocks.
+    fn visit_try_catch_block(
+        &mut self,
+        start: Label,
+        end: Label,
+        handler: Label,
+        catch_type: Option<&str>,
+    ) {
+        if let Some(next) = self.delegate() {
+            next.visit_try_catch_block(start, end, handler, catch_type);
+        }
+    }
+
+    /// Visits a local variable declaration.
+    ///
+    /// This is called after all instructions have been visited. The variable is live from
+    /// `start` (inclusive) to `end` (exclusive).
+    fn visit_local_variable(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        start: Label,
+        end: Label,
+        index: u16,
+    ) {
+        if let Some(next) = self.delegate() {
+            next.visit_local_variable(name, descriptor, signature, start, end, index);
+        }
+    }
 
     /// Visits a zero-operand instruction.
     ///
     /// # Arguments
     /// * `opcode` - The opcode of the instruction to be visited.
-    fn visit_insn(&mut self, _opcode: u8) {}
+    fn visit_insn(&mut self, opcode: u8) {
+        if let Some(next) = self.delegate() {
+            next.visit_insn(opcode);
+        }
+    }
 
     /// Visits an instruction with a single int operand.
-    fn visit_int_insn(&mut self, _opcode: u8, _operand: i32) {}
+    fn visit_int_insn(&mut self, opcode: u8, operand: i32) {
+        if let Some(next) = self.delegate() {
+            next.visit_int_insn(opcode, operand);
+        }
+    }
 
     /// Visits a local variable instruction.
-    fn visit_var_insn(&mut self, _opcode: u8, _var_index: u16) {}
+    fn visit_var_insn(&mut self, opcode: u8, var_index: u16) {
+        if let Some(next) = self.delegate() {
+            next.visit_var_insn(opcode, var_index);
+        }
+    }
 
     /// Visits a type instruction.
     ///
     /// # Arguments
     /// * `opcode` - The opcode of the instruction.
     /// * `type_name` - The internal name of the object or array class.
-    fn visit_type_insn(&mut self, _opcode: u8, _type_name: &str) {}
+    fn visit_type_insn(&mut self, opcode: u8, type_name: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_insn(opcode, type_name);
+        }
+    }
 
     /// Visits a field instruction.
     ///
@@ -80,39 +425,188 @@ pub trait MethodVisitor {
     /// * `owner` - The internal name of the field's owner class.
     /// * `name` - The field's name.
     /// * `desc` - The field's descriptor.
-    fn visit_field_insn(&mut self, _opcode: u8, _owner: &str, _name: &str, _desc: &str) {}
+    fn visit_field_insn(&mut self, opcode: u8, owner: &str, name: &str, desc: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_field_insn(opcode, owner, name, desc);
+        }
+    }
     fn visit_method_insn(
         &mut self,
-        _opcode: u8,
-        _owner: &str,
-        _name: &str,
-        _desc: &str,
-        _is_interface: bool,
+        opcode: u8,
+        owner: &str,
+        name: &str,
+        desc: &str,
+        is_interface: bool,
+    ) {
+        if let Some(next) = self.delegate() {
+            next.visit_method_insn(opcode, owner, name, desc, is_interface);
+        }
+    }
+
+    /// Visits an `INVOKEDYNAMIC` instruction.
+    ///
+    /// # Arguments
+    /// * `name` - The method's name.
+    /// * `desc` - The method's descriptor.
+    /// * `bootstrap_method` - The bootstrap method, resolved from the `BootstrapMethods` attribute.
+    /// * `bootstrap_args` - The static arguments of the bootstrap method.
+    fn visit_invoke_dynamic_insn(
+        &mut self,
+        name: &str,
+        desc: &str,
+        bootstrap_method: &Handle,
+        bootstrap_args: &[BootstrapArgument],
     ) {
+        if let Some(next) = self.delegate() {
+            next.visit_invoke_dynamic_insn(name, desc, bootstrap_method, bootstrap_args);
+        }
     }
-    fn visit_invoke_dynamic_insn(&mut self, _name: &str, _desc: &str) {}
+
     /// Visits a jump instruction.
     ///
     /// # Arguments
     /// * `opcode` - The opcode of the instruction.
-    /// * `target_offset` - The offset of the target instruction relative to the current instruction.
-    fn visit_jump_insn(&mut self, _opcode: u8, _target_offset: i32) {}
+    /// * `target` - The label of the instruction to jump to.
+    fn visit_jump_insn(&mut self, opcode: u8, target: Label) {
+        if let Some(next) = self.delegate() {
+            next.visit_jump_insn(opcode, target);
+        }
+    }
 
     /// Visits an `LDC` instruction.
-    fn visit_ldc_insn(&mut self, _value: LdcConstant) {}
-    fn visit_iinc_insn(&mut self, _var_index: u16, _increment: i16) {}
-    fn visit_table_switch(&mut self, _default: i32, _low: i32, _high: i32, _targets: &[i32]) {}
-    fn visit_lookup_switch(&mut self, _default: i32, _pairs: &[(i32, i32)]) {}
-    fn visit_multi_anewarray_insn(&mut self, _type_name: &str, _dims: u8) {}
-    fn visit_maxs(&mut self, _max_stack: u16, _max_locals: u16) {}
-    fn visit_end(&mut self) {}
+    fn visit_ldc_insn(&mut self, value: LdcConstant) {
+        if let Some(next) = self.delegate() {
+            next.visit_ldc_insn(value);
+        }
+    }
+    fn visit_iinc_insn(&mut self, var_index: u16, increment: i16) {
+        if let Some(next) = self.delegate() {
+            next.visit_iinc_insn(var_index, increment);
+        }
+    }
+
+    /// Visits a `TABLESWITCH` instruction.
+    ///
+    /// `labels[i]` is the target for the key `low + i`.
+    fn visit_table_switch(&mut self, low: i32, high: i32, default: Label, labels: &[Label]) {
+        if let Some(next) = self.delegate() {
+            next.visit_table_switch(low, high, default, labels);
+        }
+    }
+
+    /// Visits a `LOOKUPSWITCH` instruction.
+    ///
+    /// `labels[i]` is the target for `keys[i]`.
+    fn visit_lookup_switch(&mut self, default: Label, keys: &[i32], labels: &[Label]) {
+        if let Some(next) = self.delegate() {
+            next.visit_lookup_switch(default, keys, labels);
+        }
+    }
+    fn visit_multi_anewarray_insn(&mut self, type_name: &str, dims: u8) {
+        if let Some(next) = self.delegate() {
+            next.visit_multi_anewarray_insn(type_name, dims);
+        }
+    }
+    fn visit_maxs(&mut self, max_stack: u16, max_locals: u16) {
+        if let Some(next) = self.delegate() {
+            next.visit_maxs(max_stack, max_locals);
+        }
+    }
+    fn visit_end(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_end();
+        }
+    }
+}
+
+/// A visitor to visit a Java module.
+///
+/// The methods of this trait must be called in the following order:
+/// `visit_main_class`? -> `visit_package`* ->
+/// (`visit_require` | `visit_export` | `visit_open` | `visit_use` | `visit_provide`)* -> `visit_end`.
+///
+/// Every method forwards to [`ModuleVisitor::delegate`] by default.
+pub trait ModuleVisitor {
+    /// Returns the module visitor to which events are forwarded by default, if any.
+    fn delegate(&mut self) -> Option<&mut dyn ModuleVisitor> {
+        None
+    }
+
+    /// Visits the main class of the module.
+    fn visit_main_class(&mut self, main_class: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_main_class(main_class);
+        }
+    }
+
+    /// Visits a package of the module (internal form, e.g., `java/lang`).
+    fn visit_package(&mut self, package: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_package(package);
+        }
+    }
+
+    /// Visits a dependence of the module.
+    fn visit_require(&mut self, module: &str, access_flags: u16, version: Option<&str>) {
+        if let Some(next) = self.delegate() {
+            next.visit_require(module, access_flags, version);
+        }
+    }
+
+    /// Visits an exported package. `modules` is empty for an unqualified export.
+    fn visit_export(&mut self, package: &str, access_flags: u16, modules: &[String]) {
+        if let Some(next) = self.delegate() {
+            next.visit_export(package, access_flags, modules);
+        }
+    }
+
+    /// Visits an open package. `modules` is empty for an unqualified open.
+    fn visit_open(&mut self, package: &str, access_flags: u16, modules: &[String]) {
+        if let Some(next) = self.delegate() {
+            next.visit_open(package, access_flags, modules);
+        }
+    }
+
+    /// Visits a service used by the module.
+    fn visit_use(&mut self, service: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_use(service);
+        }
+    }
+
+    /// Visits an implementation of a service.
+    fn visit_provide(&mut self, service: &str, providers: &[String]) {
+        if let Some(next) = self.delegate() {
+            next.visit_provide(service, providers);
+        }
+    }
+
+    /// Visits the end of the module.
+    fn visit_end(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_end();
+        }
+    }
 }
 
 /// A visitor to visit a Java class.
 ///
 /// The methods of this trait must be called in the following order:
-/// `visit` -> `visit_source` -> (`visit_field` | `visit_method`)* -> `visit_end`.
+/// `visit` -> `visit_source` -> `visit_module`? -> `visit_nest_host`? -> `visit_outer_class`? ->
+/// (`visit_annotation` | `visit_type_annotation` | `visit_attribute`)* ->
+/// (`visit_nest_member` | `visit_permitted_subclass` | `visit_inner_class`)* -> `visit_record`? ->
+/// (`visit_record_component` | `visit_field` | `visit_method`)* -> `visit_end`.
+///
+/// Every method forwards to [`ClassVisitor::delegate`] by default, so a transforming adapter
+/// only implements `delegate` and the events it changes. For instance, an adapter that wraps a
+/// [`ClassWriter`](crate::class_writer::ClassWriter) and overrides `visit_method` to return
+/// `None` for some methods removes them from the written class.
 pub trait ClassVisitor {
+    /// Returns the class visitor to which events are forwarded by default, if any.
+    fn delegate(&mut self) -> Option<&mut dyn ClassVisitor> {
+        None
+    }
+
     /// Visits the header of the class.
     ///
     /// # Arguments
@@ -120,58 +614,217 @@ pub trait ClassVisitor {
     /// * `minor` - The minor version number of the class file.
     /// * `access_flags` - The class's access flags (see `Opcodes`).
     /// * `name` - The internal name of the class.
+    /// * `signature` - The generic signature of the class (`Signature`), if any.
     /// * `super_name` - The internal name of the super class (e.g., `java/lang/String`, `a/b/c`).
     ///   Use `None` for `Object`.
     /// * `interfaces` - The internal names of the class's interfaces.
+    #[allow(clippy::too_many_arguments)]
     fn visit(
         &mut self,
-        _major: u16,
-        _minor: u16,
-        _access_flags: u16,
-        _name: &str,
-        _super_name: Option<&str>,
-        _interfaces: &[String],
+        major: u16,
+        minor: u16,
+        access_flags: u16,
+        name: &str,
+        signature: Option<&str>,
+        super_name: Option<&str>,
+        interfaces: &[&str],
     ) {
+        if let Some(next) = self.delegate() {
+            next.visit(major, minor, access_flags, name, signature, super_name, interfaces);
+        }
     }
 
     /// Visits the source file name of the class.
-    fn visit_source(&mut self, _source: &str) {}
+    fn visit_source(&mut self, source: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_source(source);
+        }
+    }
+
+    /// Visits the module corresponding to the class.
+    ///
+    /// Returns an optional `ModuleVisitor` to visit the module's content.
+    fn visit_module(
+        &mut self,
+        name: &str,
+        access_flags: u16,
+        version: Option<&str>,
+    ) -> Option<Box<dyn ModuleVisitor>> {
+        self.delegate()
+            .and_then(|next| next.visit_module(name, access_flags, version))
+    }
+
+    /// Visits the nest host class of the class (internal name).
+    fn visit_nest_host(&mut self, nest_host: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_nest_host(nest_host);
+        }
+    }
+
+    /// Visits the enclosing class of a local or anonymous class (`EnclosingMethod`).
+    ///
+    /// `name` and `descriptor` give the enclosing method, if the class is declared in one.
+    fn visit_outer_class(&mut self, owner: &str, name: Option<&str>, descriptor: Option<&str>) {
+        if let Some(next) = self.delegate() {
+            next.visit_outer_class(owner, name, descriptor);
+        }
+    }
+
+    /// Visits an annotation of the class.
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a type annotation on a type in the class declaration
+    /// (type parameters and bounds, super class, interfaces).
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_annotation(annotation, visible);
+        }
+    }
+
+    /// Visits a non-standard attribute of the class, or a standard one without a dedicated
+    /// event whose content does not refer to the constant pool (`SourceDebugExtension`,
+    /// `Synthetic`, `Deprecated`).
+    ///
+    /// `content` is the `info` of the attribute, copied as is.
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        if let Some(next) = self.delegate() {
+            next.visit_attribute(name, content);
+        }
+    }
+
+    /// Visits a member of the nest for which this class is the host (internal name).
+    fn visit_nest_member(&mut self, nest_member: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_nest_member(nest_member);
+        }
+    }
+
+    /// Visits a permitted subclass of this sealed class (internal name).
+    fn visit_permitted_subclass(&mut self, permitted_subclass: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_permitted_subclass(permitted_subclass);
+        }
+    }
+
+    /// Visits an entry of the `InnerClasses` attribute.
+    ///
+    /// `outer_name` is `None` for a class that is not a member, and `inner_name` is `None` for
+    /// an anonymous class.
+    fn visit_inner_class(
+        &mut self,
+        name: &str,
+        outer_name: Option<&str>,
+        inner_name: Option<&str>,
+        access_flags: u16,
+    ) {
+        if let Some(next) = self.delegate() {
+            next.visit_inner_class(name, outer_name, inner_name, access_flags);
+        }
+    }
+
+    /// Visits the `Record` attribute of a record class.
+    ///
+    /// This is called for every record class, including one without components, before its
+    /// components are visited.
+    fn visit_record(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_record();
+        }
+    }
+
+    /// Visits a record component of the class.
+    ///
+    /// Returns an optional `RecordComponentVisitor` to visit the component's annotations.
+    fn visit_record_component(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+    ) -> Option<Box<dyn RecordComponentVisitor>> {
+        self.delegate()
+            .and_then(|next| next.visit_record_component(name, descriptor, signature))
+    }
 
     /// Visits a field of the class.
     ///
-    /// Returns an optional `FieldVisitor` to visit the field's content.
+    /// `signature` is the generic signature of the field (`Signature`) and `value` its initial
+    /// value (`ConstantValue`), if any. Returns an optional `FieldVisitor` to visit the field's
+    /// content.
     fn visit_field(
         &mut self,
-        _access_flags: u16,
-        _name: &str,
-        _descriptor: &str,
+        access_flags: u16,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        value: Option<&ConstantValue>,
     ) -> Option<Box<dyn FieldVisitor>> {
-        None
+        self.delegate()
+            .and_then(|next| next.visit_field(access_flags, name, descriptor, signature, value))
     }
 
     /// Visits a method of the class.
     ///
-    /// Returns an optional `MethodVisitor` to visit the method's code.
+    /// `signature` is the generic signature of the method (`Signature`), and `exceptions` the
+    /// internal names of its declared exceptions (`Exceptions`). Returns an optional
+    /// `MethodVisitor` to visit the method's code.
     fn visit_method(
         &mut self,
-        _access_flags: u16,
-        _name: &str,
-        _descriptor: &str,
+        access_flags: u16,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        exceptions: &[&str],
     ) -> Option<Box<dyn MethodVisitor>> {
-        None
+        self.delegate().and_then(|next| {
+            next.visit_method(access_flags, name, descriptor, signature, exceptions)
+        })
     }
 
     /// Visits the end of the class.
-    fn visit_end(&mut self) {}
+    fn visit_end(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_end();
+        }
+    }
 }
 
-/// A parser to make a [`ClassVisitor`] visit a `ClassFile` structure.
+/// The class file major versions accepted when reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersionPolicy {
+    /// Accept versions up to [`constants::LATEST_VERSION`].
+    #[default]
+    Latest,
+    /// Accept versions up to and including the given major version.
+    UpTo(u16),
+    /// Accept any version. Class files newer than [`constants::LATEST_VERSION`] are read on a
+    /// best-effort basis: unknown attributes are kept as `Unknown`, but unknown constant pool
+    /// entries or opcodes still fail.
+    Any,
+}
+
+impl VersionPolicy {
+    /// Returns whether class files with the given major version are accepted.
+    pub fn accepts(self, major_version: u16) -> bool {
+        match self {
+            VersionPolicy::Latest => major_version <= constants::LATEST_VERSION,
+            VersionPolicy::UpTo(max_version) => major_version <= max_version,
+            VersionPolicy::Any => true,
+        }
+    }
+}
+
+/// A parser to make a [`ClassVisitor`] visit a `ClassFile` structure.
 ///
 /// This class parses a byte array conforming to the Java class file format and
 /// calls the appropriate methods of a given class visitor for each field, method,
 /// and bytecode instruction encountered.
 pub struct ClassReader {
     bytes: Vec<u8>,
+    version_policy: VersionPolicy,
 }
 
 impl ClassReader {
@@ -183,9 +836,17 @@ impl ClassReader {
     pub fn new(bytes: &[u8]) -> Self {
         Self {
             bytes: bytes.to_vec(),
+            version_policy: VersionPolicy::default(),
         }
     }
 
+    /// Sets the class file versions this reader accepts (by default, up to
+    /// [`constants::LATEST_VERSION`]).
+    pub fn with_version_policy(mut self, version_policy: VersionPolicy) -> Self {
+        self.version_policy = version_policy;
+        self
+    }
+
     /// Makes the given visitor visit the Java class of this `ClassReader`.
     ///
     /// This method parses the class file data and drives the visitor events.
@@ -193,17 +854,29 @@ impl ClassReader {
     /// # Arguments
     ///
     /// * `visitor` - The visitor that must visit this class.
-    /// * `_options` - Option flags (currently unused, reserve for future parsing options like skipping debug info).
+    /// * `options` - Option flags used to parse this class
+    ///   (`SKIP_CODE`, `SKIP_DEBUG`, `SKIP_FRAMES`, `EXPAND_FRAMES`).
     ///
     /// # Errors
     ///
     /// Returns a [`ClassReadError`] if the class file is malformed or contains unsupported versions.
+    /// Errors past the class header report where they occurred through
+    /// [`ClassReadError::location`].
     pub fn accept(
         &self,
         visitor: &mut dyn ClassVisitor,
-        _options: u32,
+        options: u32,
+    ) -> Result<(), ClassReadError> {
+        let class_file =
+            read_class_file_with_version_policy(&self.bytes, options, self.version_policy)?;
+        Self::accept_class_file(&class_file, visitor)
+            .map_err(|error| error.in_class(class_file.class_name(class_file.this_class).ok()))
+    }
+
+    fn accept_class_file(
+        class_file: &ClassFile,
+        visitor: &mut dyn ClassVisitor,
     ) -> Result<(), ClassReadError> {
-        let class_file = read_class_file(&self.bytes)?;
         let name = class_file.class_name(class_file.this_class)?.to_string();
         let super_name = if class_file.super_class == 0 {
             None
@@ -212,14 +885,16 @@ impl ClassReader {
         };
         let mut interfaces = Vec::with_capacity(class_file.interfaces.len());
         for index in &class_file.interfaces {
-            interfaces.push(class_file.class_name(*index)?.to_string());
+            interfaces.push(class_file.class_name(*index)?);
         }
+        let cp = &class_file.constant_pool;
 
         visitor.visit(
             class_file.major_version,
             class_file.minor_version,
             class_file.access_flags,
             &name,
+            attribute_signature(cp, &class_file.attributes)?,
             super_name.as_deref(),
             &interfaces,
         );
@@ -231,10 +906,126 @@ impl ClassReader {
             }
         }
 
+        if let Some(module) = class_file.decode_module(&class_file.attributes)?
+            && let Some(mut mv) =
+                visitor.visit_module(&module.name, module.access_flags, module.version.as_deref())
+        {
+            if let Some(main_class) = &module.main_class {
+                mv.visit_main_class(main_class);
+            }
+            for package in &module.packages {
+                mv.visit_package(package);
+            }
+            for require in &module.requires {
+                mv.visit_require(&require.module, require.access_flags, require.version.as_deref());
+            }
+            for export in &module.exports {
+                mv.visit_export(&export.package, export.access_flags, &export.modules);
+            }
+            for open in &module.opens {
+                mv.visit_open(&open.package, open.access_flags, &open.modules);
+            }
+            for service in &module.uses {
+                mv.visit_use(service);
+            }
+            for provide in &module.provides {
+                mv.visit_provide(&provide.service, &provide.providers);
+            }
+            mv.visit_end();
+        }
+
+        let nest = class_file.decode_nest(&class_file.attributes)?;
+        if let Some(nest_host) = &nest.host {
+            visitor.visit_nest_host(nest_host);
+        }
+        accept_outer_class(cp, &class_file.attributes, visitor)?;
+
+        let annotations = class_file.decode_annotations(&class_file.attributes)?;
+        for annotation in &annotations.visible {
+            visitor.visit_annotation(annotation, true);
+        }
+        for annotation in &annotations.invisible {
+            visitor.visit_annotation(annotation, false);
+        }
+        for annotation in &annotations.visible_types {
+            visitor.visit_type_annotation(annotation, true);
+        }
+        for annotation in &annotations.invisible_types {
+            visitor.visit_type_annotation(annotation, false);
+        }
+        for (name, content) in class_file.attributes.iter().filter_map(raw_attribute) {
+            visitor.visit_attribute(name, content);
+        }
+
+        for nest_member in &nest.members {
+            visitor.visit_nest_member(nest_member);
+        }
+        for permitted_subclass in &nest.permitted_subclasses {
+            visitor.visit_permitted_subclass(permitted_subclass);
+        }
+        accept_inner_classes(cp, &class_file.attributes, visitor)?;
+
+        let record_components = class_file.decode_record_components(&class_file.attributes)?;
+        if record_components.is_some() {
+            visitor.visit_record();
+        }
+        for component in record_components.into_iter().flatten() {
+            if let Some(mut rv) = visitor.visit_record_component(
+                &component.name,
+                &component.descriptor,
+                component.signature.as_deref(),
+            ) {
+                for annotation in &component.visible_annotations {
+                    rv.visit_annotation(annotation, true);
+                }
+                for annotation in &component.invisible_annotations {
+                    rv.visit_annotation(annotation, false);
+                }
+                for annotation in &component.visible_type_annotations {
+                    rv.visit_type_annotation(annotation, true);
+                }
+                for annotation in &component.invisible_type_annotations {
+                    rv.visit_type_annotation(annotation, false);
+                }
+                for (name, content) in component.attributes.iter().filter_map(raw_attribute) {
+                    rv.visit_attribute(name, content);
+                }
+                rv.visit_end();
+            }
+        }
+
         for field in &class_file.fields {
             let field_name = class_file.cp_utf8(field.name_index)?;
             let field_desc = class_file.cp_utf8(field.descriptor_index)?;
-            if let Some(mut fv) = visitor.visit_field(field.access_flags, field_name, field_desc) {
+            let located =
+                |error: ClassReadError| error.in_member(Some(field_name), Some(field_desc));
+            let signature = attribute_signature(cp, &field.attributes).map_err(located)?;
+            let value = attribute_constant_value(cp, &field.attributes).map_err(located)?;
+            if let Some(mut fv) = visitor.visit_field(
+                field.access_flags,
+                field_name,
+                field_desc,
+                signature,
+                value.as_ref(),
+            ) {
+                let annotations = class_file
+                    .decode_annotations(&field.attributes)
+                    .map_err(located)?;
+                for annotation in &annotations.visible {
+                    fv.visit_annotation(annotation, true);
+                }
+                for annotation in &annotations.invisible {
+                    fv.visit_annotation(annotation, false);
+                }
+                for annotation in &annotations.visible_types {
+                    fv.visit_type_annotation(annotation, true);
+                }
+                for annotation in &annotations.invisible_types {
+                    fv.visit_type_annotation(annotation, false);
+                }
+                for (name, content) in field.attributes.iter().filter_map(raw_attribute) {
+                    fv.visit_attribute(name, content);
+                }
                 fv.visit_end();
             }
         }
@@ -242,24 +1033,49 @@ impl ClassReader {
         for method in &class_file.methods {
             let method_name = class_file.cp_utf8(method.name_index)?;
             let method_desc = class_file.cp_utf8(method.descriptor_index)?;
-            if let Some(mut mv) =
-                visitor.visit_method(method.access_flags, method_name, method_desc)
-            {
+            let located =
+                |error: ClassReadError| error.in_member(Some(method_name), Some(method_desc));
+            let signature = attribute_signature(cp, &method.attributes).map_err(located)?;
+            let exceptions = attribute_exceptions(cp, &method.attributes).map_err(located)?;
+            if let Some(mut mv) = visitor.visit_method(
+                method.access_flags,
+                method_name,
+                method_desc,
+                signature,
+                &exceptions,
+            ) {
+                accept_method_parameters(&class_file.constant_pool, &method.attributes, &mut *mv)
+                    .map_err(located)?;
+                let annotations = class_file
+                    .decode_annotations(&method.attributes)
+                    .map_err(located)?;
+                if let Some(value) = &annotations.default {
+                    mv.visit_annotation_default(value);
+                }
+                for annotation in &annotations.visible {
+                    mv.visit_annotation(annotation, true);
+                }
+                for annotation in &annotations.invisible {
+                    mv.visit_annotation(annotation, false);
+                }
+                for annotation in &annotations.visible_types {
+                    mv.visit_type_annotation(annotation, true);
+                }
+                for annotation in &annotations.invisible_types {
+                    mv.visit_type_annotation(annotation, false);
+                }
+                accept_parameter_annotations(&annotations.visible_parameters, true, &mut *mv);
+                accept_parameter_annotations(&annotations.invisible_parameters, false, &mut *mv);
+                for (name, content) in method.attributes.iter().filter_map(raw_attribute) {
+                    mv.visit_attribute(name, content);
+                }
                 let code = method.attributes.iter().find_map(|attr| match attr {
                     AttributeInfo::Code(code) => Some(code),
                     _ => None,
                 });
                 if let Some(code) = code {
                     mv.visit_code();
-                    let instructions = parse_code_instructions_with_offsets(&code.code)?;
-                    for instruction in instructions {
-                        visit_instruction(
-                            &class_file.constant_pool,
-                            instruction.offset as i32,
-                            instruction.insn,
-                            &mut *mv,
-                        )?;
-                    }
+                    accept_code(class_file, code, &mut *mv).map_err(located)?;
                     mv.visit_maxs(code.max_stack, code.max_locals);
                 }
                 mv.visit_end();
@@ -275,8 +1091,20 @@ impl ClassReader {
     /// This is a convenience method that parses the bytes and builds a
     /// complete object model of the class.
     pub fn to_class_node(&self) -> Result<crate::nodes::ClassNode, ClassReadError> {
-        let class_file = read_class_file(&self.bytes)?;
-        class_file.to_class_node()
+        self.to_class_node_with_options(0)
+    }
+
+    /// Converts the read class data into a `ClassNode`, parsing it with the given option flags
+    /// (`SKIP_CODE`, `SKIP_DEBUG`, `SKIP_FRAMES`, `EXPAND_FRAMES`).
+    pub fn to_class_node_with_options(
+        &self,
+        options: u32,
+    ) -> Result<crate::nodes::ClassNode, ClassReadError> {
+        let class_file =
+            read_class_file_with_version_policy(&self.bytes, options, self.version_policy)?;
+        class_file
+            .to_class_node()
+            .map_err(|error| error.in_class(class_file.class_name(class_file.this_class).ok()))
     }
 }
 
@@ -340,11 +1168,20 @@ impl ClassFile {
         for field in &self.fields {
             let name = self.cp_utf8(field.name_index)?.to_string();
             let descriptor = self.cp_utf8(field.descriptor_index)?.to_string();
+            let annotations = self
+                .decode_annotations(&field.attributes)
+                .map_err(|error| error.in_member(Some(&name), Some(&descriptor)))?;
+            let mut attributes = field.attributes.clone();
+            attributes.retain(|attr| !is_annotation_attribute(attr));
             fields.push(crate::nodes::FieldNode {
                 access_flags: field.access_flags,
                 name,
                 descriptor,
-                attributes: field.attributes.clone(),
+                attributes,
+                visible_annotations: annotations.visible,
+                invisible_annotations: annotations.invisible,
+                visible_type_annotations: annotations.visible_types,
+                invisible_type_annotations: annotations.invisible_types,
             });
         }
 
@@ -352,43 +1189,83 @@ impl ClassFile {
         for method in &self.methods {
             let name = self.cp_utf8(method.name_index)?.to_string();
             let descriptor = self.cp_utf8(method.descriptor_index)?.to_string();
+            let located =
+                |error: ClassReadError| error.in_member(Some(&name), Some(&descriptor));
+            let annotations = self.decode_annotations(&method.attributes).map_err(located)?;
             let mut method_attributes = method.attributes.clone();
-            method_attributes.retain(|attr| !matches!(attr, AttributeInfo::Code(_)));
+            method_attributes.retain(|attr| {
+                !matches!(attr, AttributeInfo::Code(_)) && !is_annotation_attribute(attr)
+            });
             let code = method.attributes.iter().find_map(|attr| match attr {
                 AttributeInfo::Code(code) => Some(code),
                 _ => None,
             });
 
-            let (has_code, max_stack, max_locals, instructions, exception_table, code_attributes) =
-                if let Some(code) = code {
-                    let mut list = InsnList::new();
-                    for insn in &code.instructions {
-                        list.add(insn.clone());
+            // The frames, line numbers and local variables of the tables removed here are held by
+            // `insn_nodes`, which the writer lays out in preference to `instructions`.
+            let mut code_annotations = DecodedCodeAnnotations::default();
+            let (has_code, max_stack, max_locals, code_attributes) = if let Some(code) = code {
+                code_annotations = self.decode_code_annotations(code).map_err(located)?;
+                let mut code_attributes = code.attributes.clone();
+                code_attributes.retain(|attr| {
+                    !is_annotation_attribute(attr)
+                        && !matches!(
+                            attr,
+                            AttributeInfo::StackMapTable { .. }
+                                | AttributeInfo::LineNumberTable { .. }
+                                | AttributeInfo::LocalVariableTable { .. }
+                                | AttributeInfo::LocalVariableTypeTable { .. }
+                        )
+                });
+                (true, code.max_stack, code.max_locals, code_attributes)
+            } else {
+                (false, 0, 0, Vec::new())
+            };
+            let mut insn_nodes = NodeList::new();
+            let (try_catch_blocks, local_variables) = match code {
+                Some(code) => {
+                    for node in &code.insn_nodes {
+                        insn_nodes.add_node(node.clone());
                     }
-                    (
-                        true,
-                        code.max_stack,
-                        code.max_locals,
-                        list,
-                        code.exception_table.clone(),
-                        code.attributes.clone(),
-                    )
-                } else {
-                    (false, 0, 0, InsnList::new(), Vec::new(), Vec::new())
-                };
+                    (code.try_catch_blocks.clone(), code.local_variables.clone())
+                }
+                None => (Vec::new(), Vec::new()),
+            };
 
-            methods.push(crate::nodes::MethodNode {
+            let mut method_node = crate::nodes::MethodNode {
                 access_flags: method.access_flags,
                 name,
                 descriptor,
                 has_code,
                 max_stack,
                 max_locals,
-                instructions,
-                exception_table,
                 code_attributes,
+                insn_nodes,
+                try_catch_blocks,
+                local_variables,
                 attributes: method_attributes,
-            });
+                visible_annotations: annotations.visible,
+                invisible_annotations: annotations.invisible,
+                visible_parameter_annotations: annotations.visible_parameters,
+                invisible_parameter_annotations: annotations.invisible_parameters,
+                annotation_default: annotations.default,
+                visible_type_annotations: annotations.visible_types,
+                invisible_type_annotations: annotations.invisible_types,
+                visible_insn_annotations: Vec::new(),
+                invisible_insn_annotations: Vec::new(),
+                visible_try_catch_annotations: Vec::new(),
+                invisible_try_catch_annotations: Vec::new(),
+                visible_local_variable_annotations: Vec::new(),
+                invisible_local_variable_annotations: Vec::new(),
+            };
+            if let Some(code) = code {
+                anchor_code_annotations(code, code_annotations, &mut method_node).map_err(
+                    |error| {
+                        error.in_member(Some(&method_node.name), Some(&method_node.descriptor))
+                    },
+                )?;
+            }
+            methods.push(method_node);
         }
 
         let mut inner_classes = Vec::new();
@@ -437,6 +1314,30 @@ impl ClassFile {
             }
         }
 
+        let annotations = self.decode_annotations(&self.attributes)?;
+        let module = self.decode_module(&self.attributes)?;
+        let record_components = self.decode_record_components(&self.attributes)?;
+        let nest = self.decode_nest(&self.attributes)?;
+        let mut attributes = self.attributes.clone();
+        attributes.retain(|attr| {
+            let decoded_module = module.is_some()
+                && matches!(
+                    attr,
+                    AttributeInfo::Module(_)
+                        | AttributeInfo::ModulePackages { .. }
+                        | AttributeInfo::ModuleMainClass { .. }
+                );
+            !decoded_module
+                && !is_annotation_attribute(attr)
+                && !matches!(
+                    attr,
+                    AttributeInfo::Record { .. }
+                        | AttributeInfo::NestHost { .. }
+                        | AttributeInfo::NestMembers { .. }
+                        | AttributeInfo::PermittedSubclasses { .. }
+                )
+        });
+
         Ok(crate::nodes::ClassNode {
             minor_version: self.minor_version,
             major_version: self.major_version,
@@ -450,11 +1351,586 @@ impl ClassFile {
             interface_indices: self.interfaces.clone(),
             fields,
             methods,
-            attributes: self.attributes.clone(),
+            attributes,
             inner_classes,
             outer_class,
+            visible_annotations: annotations.visible,
+            invisible_annotations: annotations.invisible,
+            visible_type_annotations: annotations.visible_types,
+            invisible_type_annotations: annotations.invisible_types,
+            module,
+            record_components,
+            nest_host: nest.host,
+            nest_members: nest.members,
+            permitted_subclasses: nest.permitted_subclasses,
+        })
+    }
+
+    /// Resolves the `NestHost`, `NestMembers` and `PermittedSubclasses` attributes among
+    /// `attributes` to internal names.
+    fn decode_nest(&self, attributes: &[AttributeInfo]) -> Result<DecodedNest, ClassReadError> {
+        let mut decoded = DecodedNest::default();
+        for attr in attributes.iter() {
+            match attr {
+                AttributeInfo::NestHost { host_class_index } => {
+                    decoded.host = Some(self.class_name(*host_class_index)?.to_string());
+                }
+                AttributeInfo::NestMembers { classes } => {
+                    for index in classes {
+                        decoded.members.push(self.class_name(*index)?.to_string());
+                    }
+                }
+                AttributeInfo::PermittedSubclasses { classes } => {
+                    for index in classes {
+                        decoded.permitted_subclasses.push(self.class_name(*index)?.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(decoded)
+    }
+
+    /// Decodes the components of the `Record` attribute among `attributes`, or returns `None`
+    /// if there is no `Record` attribute.
+    ///
+    /// The `Signature` and annotation attributes of each component are decoded;
+    /// any other component attribute is kept as is.
+    fn decode_record_components(
+        &self,
+        attributes: &[AttributeInfo],
+    ) -> Result<Option<Vec<RecordComponentNode>>, ClassReadError> {
+        let mut record = None;
+        for attr in attributes.iter() {
+            if let AttributeInfo::Record { components: entries } = attr {
+                let components = record.get_or_insert_with(Vec::new);
+                for entry in entries {
+                    let mut attributes = entry.attributes.clone();
+                    let annotations = self.decode_annotations(&attributes)?;
+                    attributes.retain(|attr| !is_annotation_attribute(attr));
+                    let mut signature = None;
+                    for attr in &attributes {
+                        if let AttributeInfo::Signature { signature_index } = attr {
+                            signature = Some(self.cp_utf8(*signature_index)?.to_string());
+                        }
+                    }
+                    attributes.retain(|attr| !matches!(attr, AttributeInfo::Signature { .. }));
+                    components.push(RecordComponentNode {
+                        name: self.cp_utf8(entry.name_index)?.to_string(),
+                        descriptor: self.cp_utf8(entry.descriptor_index)?.to_string(),
+                        signature,
+                        attributes,
+                        visible_annotations: annotations.visible,
+                        invisible_annotations: annotations.invisible,
+                        visible_type_annotations: annotations.visible_types,
+                        invisible_type_annotations: annotations.invisible_types,
+                    });
+                }
+            }
+        }
+        Ok(record)
+    }
+
+    /// Decodes the module attributes among `attributes`.
+    fn decode_module(
+        &self,
+        attributes: &[AttributeInfo],
+    ) -> Result<Option<ModuleNode>, ClassReadError> {
+        let Some(module) = attributes.iter().find_map(|attr| match attr {
+            AttributeInfo::Module(module) => Some(module),
+            _ => None,
+        }) else {
+            return Ok(None);
+        };
+
+        let mut node = ModuleNode {
+            name: self.module_name(module.module_name_index)?.to_string(),
+            access_flags: module.module_flags,
+            version: self.optional_utf8(module.module_version_index)?,
+            ..Default::default()
+        };
+        for entry in &module.requires {
+            node.requires.push(ModuleRequireNode {
+                module: self.module_name(entry.requires_index)?.to_string(),
+                access_flags: entry.requires_flags,
+                version: self.optional_utf8(entry.requires_version_index)?,
+            });
+        }
+        for entry in &module.exports {
+            let mut modules = Vec::with_capacity(entry.exports_to_index.len());
+            for index in &entry.exports_to_index {
+                modules.push(self.module_name(*index)?.to_string());
+            }
+            node.exports.push(ModuleExportNode {
+                package: self.package_name(entry.exports_index)?.to_string(),
+                access_flags: entry.exports_flags,
+                modules,
+            });
+        }
+        for entry in &module.opens {
+            let mut modules = Vec::with_capacity(entry.opens_to_index.len());
+            for index in &entry.opens_to_index {
+                modules.push(self.module_name(*index)?.to_string());
+            }
+            node.opens.push(ModuleOpenNode {
+                package: self.package_name(entry.opens_index)?.to_string(),
+                access_flags: entry.opens_flags,
+                modules,
+            });
+        }
+        for index in &module.uses_index {
+            node.uses.push(self.class_name(*index)?.to_string());
+        }
+        for entry in &module.provides {
+            let mut providers = Vec::with_capacity(entry.provides_with_index.len());
+            for index in &entry.provides_with_index {
+                providers.push(self.class_name(*index)?.to_string());
+            }
+            node.provides.push(ModuleProvideNode {
+                service: self.class_name(entry.provides_index)?.to_string(),
+                providers,
+            });
+        }
+
+        for attr in attributes.iter() {
+            match attr {
+                AttributeInfo::ModulePackages {
+                    package_index_table,
+                } => {
+                    for index in package_index_table {
+                        node.packages.push(self.package_name(*index)?.to_string());
+                    }
+                }
+                AttributeInfo::ModuleMainClass { main_class_index } => {
+                    node.main_class = Some(self.class_name(*main_class_index)?.to_string());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Some(node))
+    }
+
+    fn module_name(&self, index: u16) -> Result<&str, ClassReadError> {
+        match self
+            .constant_pool
+            .get(index as usize)
+            .ok_or(ClassReadError::InvalidIndex(index))?
+        {
+            CpInfo::Module { name_index } => self.cp_utf8(*name_index),
+            _ => Err(ClassReadError::InvalidIndex(index)),
+        }
+    }
+
+    fn package_name(&self, index: u16) -> Result<&str, ClassReadError> {
+        match self
+            .constant_pool
+            .get(index as usize)
+            .ok_or(ClassReadError::InvalidIndex(index))?
+        {
+            CpInfo::Package { name_index } => self.cp_utf8(*name_index),
+            _ => Err(ClassReadError::InvalidIndex(index)),
+        }
+    }
+
+    /// Resolves a utf8 index that may be zero to indicate "no value".
+    fn optional_utf8(&self, index: u16) -> Result<Option<String>, ClassReadError> {
+        if index == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(self.cp_utf8(index)?.to_string()))
+        }
+    }
+
+    /// Decodes the annotation attributes among `attributes`.
+    fn decode_annotations(
+        &self,
+        attributes: &[AttributeInfo],
+    ) -> Result<DecodedAnnotations, ClassReadError> {
+        let mut decoded = DecodedAnnotations::default();
+        for attr in attributes.iter() {
+            match attr {
+                AttributeInfo::RuntimeVisibleAnnotations { annotations } => {
+                    for annotation in annotations {
+                        decoded.visible.push(self.annotation_node(annotation)?);
+                    }
+                }
+                AttributeInfo::RuntimeInvisibleAnnotations { annotations } => {
+                    for annotation in annotations {
+                        decoded.invisible.push(self.annotation_node(annotation)?);
+                    }
+                }
+                AttributeInfo::RuntimeVisibleParameterAnnotations { parameters } => {
+                    decoded.visible_parameters = self.parameter_annotation_nodes(parameters)?;
+                }
+                AttributeInfo::RuntimeInvisibleParameterAnnotations { parameters } => {
+                    decoded.invisible_parameters = self.parameter_annotation_nodes(parameters)?;
+                }
+                AttributeInfo::AnnotationDefault { default_value } => {
+                    decoded.default = Some(self.annotation_value(default_value)?);
+                }
+                AttributeInfo::RuntimeVisibleTypeAnnotations { annotations } => {
+                    for annotation in annotations {
+                        decoded.visible_types.push(self.type_annotation_node(annotation)?);
+                    }
+                }
+                AttributeInfo::RuntimeInvisibleTypeAnnotations { annotations } => {
+                    for annotation in annotations {
+                        decoded.invisible_types.push(self.type_annotation_node(annotation)?);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(decoded)
+    }
+
+    /// Decodes the type annotation attributes among the attributes of `code`, checking that
+    /// their bytecode offsets are instruction boundaries.
+    fn decode_code_annotations(
+        &self,
+        code: &CodeAttribute,
+    ) -> Result<DecodedCodeAnnotations, ClassReadError> {
+        let attributes = &code.attributes;
+        let mut decoded = DecodedCodeAnnotations::default();
+        let has_annotations = attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                AttributeInfo::RuntimeVisibleTypeAnnotations { .. }
+                    | AttributeInfo::RuntimeInvisibleTypeAnnotations { .. }
+            )
+        });
+        if !has_annotations {
+            return Ok(decoded);
+        }
+
+        let mut boundaries = std::collections::HashSet::new();
+        for offset in &code.instruction_offsets {
+            boundaries.insert(*offset as usize);
+        }
+        boundaries.insert(code.code.len());
+        let boundary = |offset: usize| {
+            if boundaries.contains(&offset) {
+                Ok(offset as u16)
+            } else {
+                Err(ClassReadError::InvalidAttribute(format!("type annotation offset {offset}")))
+            }
+        };
+
+        for attr in attributes.iter() {
+            let (annotations, visible) = match attr {
+                AttributeInfo::RuntimeVisibleTypeAnnotations { annotations } => (annotations, true),
+                AttributeInfo::RuntimeInvisibleTypeAnnotations { annotations } => {
+                    (annotations, false)
+                }
+                _ => continue,
+            };
+            for annotation in annotations {
+                let type_annotation = self.type_annotation_node(annotation)?;
+                match &annotation.target_info {
+                    TypeAnnotationTarget::LocalVar { table } => {
+                        let mut node = OffsetLocalVariableAnnotation {
+                            start: Vec::with_capacity(table.len()),
+                            end: Vec::with_capacity(table.len()),
+                            index: Vec::with_capacity(table.len()),
+                            type_annotation,
+                        };
+                        for entry in table {
+                            node.start.push(boundary(entry.start_pc as usize)?);
+                            node.end
+                                .push(boundary(entry.start_pc as usize + entry.length as usize)?);
+                            node.index.push(entry.index);
+                        }
+                        if visible {
+                            decoded.visible_local_variables.push(node);
+                        } else {
+                            decoded.invisible_local_variables.push(node);
+                        }
+                    }
+                    TypeAnnotationTarget::Catch { .. } => {
+                        if visible {
+                            decoded.visible_try_catches.push(type_annotation);
+                        } else {
+                            decoded.invisible_try_catches.push(type_annotation);
+                        }
+                    }
+                    TypeAnnotationTarget::Offset { offset }
+                    | TypeAnnotationTarget::TypeArgument { offset, .. } => {
+                        let node = (boundary(*offset as usize)?, type_annotation);
+                        if visible {
+                            decoded.visible_insns.push(node);
+                        } else {
+                            decoded.invisible_insns.push(node);
+                        }
+                    }
+                    _ => {
+                        return Err(ClassReadError::InvalidAttribute(format!(
+                            "type_annotation target_type 0x{:02x} in Code",
+                            annotation.target_type
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(decoded)
+    }
+
+    fn type_annotation_node(
+        &self,
+        annotation: &TypeAnnotation,
+    ) -> Result<TypeAnnotationNode, ClassReadError> {
+        let sort = annotation.target_type;
+        let type_ref = match &annotation.target_info {
+            TypeAnnotationTarget::TypeParameter {
+                type_parameter_index,
+            } => TypeReference::new_type_parameter_reference(sort, *type_parameter_index),
+            TypeAnnotationTarget::SuperType { supertype_index } => {
+                TypeReference::new_super_type_reference(*supertype_index as i16 as i32)
+            }
+            TypeAnnotationTarget::TypeParameterBound {
+                type_parameter_index,
+                bound_index,
+            } => TypeReference::new_type_parameter_bound_reference(
+                sort,
+                *type_parameter_index,
+                *bound_index,
+            ),
+            TypeAnnotationTarget::FormalParameter {
+                formal_parameter_index,
+            } => TypeReference::new_formal_parameter_reference(*formal_parameter_index),
+            TypeAnnotationTarget::Throws { throws_type_index } => {
+                TypeReference::new_exception_reference(*throws_type_index)
+            }
+            TypeAnnotationTarget::Catch {
+                exception_table_index,
+            } => TypeReference::new_try_catch_reference(*exception_table_index),
+            TypeAnnotationTarget::TypeArgument {
+                type_argument_index,
+                ..
+            } => TypeReference::new_type_argument_reference(sort, *type_argument_index),
+            TypeAnnotationTarget::Empty
+            | TypeAnnotationTarget::LocalVar { .. }
+            | TypeAnnotationTarget::Offset { .. } => TypeReference::new_type_reference(sort),
+        };
+        let type_path = if annotation.target_path.is_empty() {
+            None
+        } else {
+            Some(annotation.target_path.clone())
+        };
+        Ok(TypeAnnotationNode {
+            type_ref,
+            type_path,
+            annotation: self.annotation_node(&annotation.annotation)?,
+        })
+    }
+
+    fn parameter_annotation_nodes(
+        &self,
+        parameters: &[Vec<Annotation>],
+    ) -> Result<Vec<Vec<AnnotationNode>>, ClassReadError> {
+        let mut nodes = Vec::with_capacity(parameters.len());
+        for annotations in parameters {
+            let mut parameter = Vec::with_capacity(annotations.len());
+            for annotation in annotations {
+                parameter.push(self.annotation_node(annotation)?);
+            }
+            nodes.push(parameter);
+        }
+        Ok(nodes)
+    }
+
+    fn annotation_node(&self, annotation: &Annotation) -> Result<AnnotationNode, ClassReadError> {
+        let mut values = Vec::with_capacity(annotation.element_value_pairs.len());
+        for pair in &annotation.element_value_pairs {
+            values.push((
+                self.cp_utf8(pair.element_name_index)?.to_string(),
+                self.annotation_value(&pair.value)?,
+            ));
+        }
+        Ok(AnnotationNode {
+            descriptor: self.cp_utf8(annotation.type_index)?.to_string(),
+            values,
         })
     }
+
+    fn annotation_value(&self, value: &ElementValue) -> Result<AnnotationValue, ClassReadError> {
+        let value = match value {
+            ElementValue::Const {
+                tag,
+                const_value_index,
+            } => {
+                let index = *const_value_index;
+                let entry = self
+                    .constant_pool
+                    .get(index as usize)
+                    .ok_or(ClassReadError::InvalidIndex(index))?;
+                match (*tag, entry) {
+                    (b'B', CpInfo::Integer(value)) => AnnotationValue::Byte(*value as i8),
+                    (b'C', CpInfo::Integer(value)) => AnnotationValue::Char(*value as u16),
+                    (b'I', CpInfo::Integer(value)) => AnnotationValue::Int(*value),
+                    (b'S', CpInfo::Integer(value)) => AnnotationValue::Short(*value as i16),
+                    (b'Z', CpInfo::Integer(value)) => AnnotationValue::Boolean(*value != 0),
+                    (b'J', CpInfo::Long(value)) => AnnotationValue::Long(*value),
+                    (b'F', CpInfo::Float(value)) => AnnotationValue::Float(*value),
+                    (b'D', CpInfo::Double(value)) => AnnotationValue::Double(*value),
+                    (b's', CpInfo::Utf8(value)) => AnnotationValue::String(value.clone()),
+                    _ => return Err(ClassReadError::InvalidIndex(index)),
+                }
+            }
+            ElementValue::Enum {
+                type_name_index,
+                const_name_index,
+            } => AnnotationValue::Enum {
+                descriptor: self.cp_utf8(*type_name_index)?.to_string(),
+                value: self.cp_utf8(*const_name_index)?.to_string(),
+            },
+            ElementValue::Class { class_info_index } => {
+                AnnotationValue::Class(self.cp_utf8(*class_info_index)?.to_string())
+            }
+            ElementValue::Annotation(annotation) => {
+                AnnotationValue::Annotation(self.annotation_node(annotation)?)
+            }
+            ElementValue::Array(values) => {
+                let mut items = Vec::with_capacity(values.len());
+                for item in values {
+                    items.push(self.annotation_value(item)?);
+                }
+                AnnotationValue::Array(items)
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[derive(Debug, Default)]
+struct DecodedAnnotations {
+    visible: Vec<AnnotationNode>,
+    invisible: Vec<AnnotationNode>,
+    visible_parameters: Vec<Vec<AnnotationNode>>,
+    invisible_parameters: Vec<Vec<AnnotationNode>>,
+    default: Option<AnnotationValue>,
+    visible_types: Vec<TypeAnnotationNode>,
+    invisible_types: Vec<TypeAnnotationNode>,
+}
+
+#[derive(Debug, Default)]
+struct DecodedNest {
+    host: Option<String>,
+    members: Vec<String>,
+    permitted_subclasses: Vec<String>,
+}
+
+/// The type annotations of a `Code` attribute, with the bytecode offsets they refer to.
+#[derive(Debug, Default)]
+struct DecodedCodeAnnotations {
+    /// The annotations paired with the offset of the annotated instruction.
+    visible_insns: Vec<(u16, TypeAnnotationNode)>,
+    invisible_insns: Vec<(u16, TypeAnnotationNode)>,
+    visible_try_catches: Vec<TypeAnnotationNode>,
+    invisible_try_catches: Vec<TypeAnnotationNode>,
+    visible_local_variables: Vec<OffsetLocalVariableAnnotation>,
+    invisible_local_variables: Vec<OffsetLocalVariableAnnotation>,
+}
+
+/// A local variable type annotation whose ranges are given as bytecode offsets.
+#[derive(Debug)]
+struct OffsetLocalVariableAnnotation {
+    start: Vec<u16>,
+    end: Vec<u16>,
+    index: Vec<u16>,
+    type_annotation: TypeAnnotationNode,
+}
+
+/// Sets the code type annotations of `method`, whose `insn_nodes` hold the label-based form of
+/// `code`, attaching the offsets of `annotations` to the instruction handles and labels.
+fn anchor_code_annotations(
+    code: &CodeAttribute,
+    annotations: DecodedCodeAnnotations,
+    method: &mut crate::nodes::MethodNode,
+) -> Result<(), ClassReadError> {
+    let mut insn_ids = HashMap::new();
+    let mut labels = HashMap::new();
+    if !annotations.visible_insns.is_empty()
+        || !annotations.invisible_insns.is_empty()
+        || !annotations.visible_local_variables.is_empty()
+        || !annotations.invisible_local_variables.is_empty()
+    {
+        let mut offsets = code
+            .instruction_offsets
+            .iter()
+            .copied()
+            .chain([code.code.len() as u16]);
+        let mut offset = offsets.next();
+        for (id, node) in method.insn_nodes.iter() {
+            match (node, offset) {
+                (AbstractInsnNode::Label(label), Some(offset)) => {
+                    labels.entry(offset).or_insert(*label);
+                }
+                (AbstractInsnNode::LineNumber(_) | AbstractInsnNode::Frame(_), _) => {}
+                (_, Some(current)) => {
+                    insn_ids.insert(current, id);
+                    offset = offsets.next();
+                }
+                (_, None) => {}
+            }
+        }
+    }
+    let insn_at = |offset: u16| {
+        insn_ids.get(&offset).copied().ok_or_else(|| {
+            ClassReadError::InvalidAttribute(format!("type annotation offset {offset}"))
+        })
+    };
+    let label_at = |offset: u16| {
+        labels.get(&offset).copied().ok_or_else(|| {
+            ClassReadError::InvalidAttribute(format!("no label at code offset {offset}"))
+        })
+    };
+    let insn_annotations = |annotations: Vec<(u16, TypeAnnotationNode)>| {
+        annotations
+            .into_iter()
+            .map(|(offset, type_annotation)| {
+                Ok(InsnAnnotationNode {
+                    insn: insn_at(offset)?,
+                    type_annotation,
+                })
+            })
+            .collect::<Result<Vec<_>, ClassReadError>>()
+    };
+    let local_variable_annotations = |annotations: Vec<OffsetLocalVariableAnnotation>| {
+        annotations
+            .into_iter()
+            .map(|annotation| {
+                Ok(LocalVariableAnnotationNode {
+                    start: annotation.start.into_iter().map(label_at).collect::<Result<_, _>>()?,
+                    end: annotation.end.into_iter().map(label_at).collect::<Result<_, _>>()?,
+                    index: annotation.index,
+                    type_annotation: annotation.type_annotation,
+                })
+            })
+            .collect::<Result<Vec<_>, ClassReadError>>()
+    };
+    method.visible_insn_annotations = insn_annotations(annotations.visible_insns)?;
+    method.invisible_insn_annotations = insn_annotations(annotations.invisible_insns)?;
+    method.visible_try_catch_annotations = annotations.visible_try_catches;
+    method.invisible_try_catch_annotations = annotations.invisible_try_catches;
+    method.visible_local_variable_annotations =
+        local_variable_annotations(annotations.visible_local_variables)?;
+    method.invisible_local_variable_annotations =
+        local_variable_annotations(annotations.invisible_local_variables)?;
+    Ok(())
+}
+
+fn is_annotation_attribute(attr: &AttributeInfo) -> bool {
+    matches!(
+        attr,
+        AttributeInfo::RuntimeVisibleAnnotations { .. }
+            | AttributeInfo::RuntimeInvisibleAnnotations { .. }
+            | AttributeInfo::RuntimeVisibleParameterAnnotations { .. }
+            | AttributeInfo::RuntimeInvisibleParameterAnnotations { .. }
+            | AttributeInfo::AnnotationDefault { .. }
+            | AttributeInfo::RuntimeVisibleTypeAnnotations { .. }
+            | AttributeInfo::RuntimeInvisibleTypeAnnotations { .. }
+    )
 }
 
 #[derive(Debug, Clone)]
@@ -481,6 +1957,8 @@ pub enum AttributeInfo {
     SourceFile { sourcefile_index: u16 },
     LineNumberTable { entries: Vec<LineNumber> },
     LocalVariableTable { entries: Vec<LocalVariable> },
+    LocalVariableTypeTable { entries: Vec<LocalVariableType> },
+    SourceDebugExtension { debug_extension: Vec<u8> },
     Signature { signature_index: u16 },
     StackMapTable { entries: Vec<StackMapFrame> },
     Deprecated,
@@ -489,6 +1967,20 @@ pub enum AttributeInfo {
     EnclosingMethod { class_index: u16, method_index: u16 },
     BootstrapMethods { methods: Vec<BootstrapMethod> },
     MethodParameters { parameters: Vec<MethodParameter> },
+    RuntimeVisibleAnnotations { annotations: Vec<Annotation> },
+    RuntimeInvisibleAnnotations { annotations: Vec<Annotation> },
+    RuntimeVisibleParameterAnnotations { parameters: Vec<Vec<Annotation>> },
+    RuntimeInvisibleParameterAnnotations { parameters: Vec<Vec<Annotation>> },
+    AnnotationDefault { default_value: ElementValue },
+    RuntimeVisibleTypeAnnotations { annotations: Vec<TypeAnnotation> },
+    RuntimeInvisibleTypeAnnotations { annotations: Vec<TypeAnnotation> },
+    Module(ModuleAttribute),
+    ModulePackages { package_index_table: Vec<u16> },
+    ModuleMainClass { main_class_index: u16 },
+    Record { components: Vec<RecordComponentInfo> },
+    NestHost { host_class_index: u16 },
+    NestMembers { classes: Vec<u16> },
+    PermittedSubclasses { classes: Vec<u16> },
     Unknown { name: String, info: Vec<u8> },
 }
 
@@ -498,9 +1990,12 @@ pub struct CodeAttribute {
     pub max_locals: u16,
     pub code: Vec<u8>,
     pub instructions: Vec<Insn>,
+    /// The bytecode offset of each of `instructions`.
+    pub instruction_offsets: Vec<u16>,
     pub insn_nodes: Vec<AbstractInsnNode>,
     pub exception_table: Vec<ExceptionTableEntry>,
     pub try_catch_blocks: Vec<TryCatchBlockNode>,
+    pub local_variables: Vec<LocalVariableNode>,
     pub attributes: Vec<AttributeInfo>,
 }
 
@@ -527,6 +2022,17 @@ pub struct LocalVariable {
     pub index: u16,
 }
 
+/// An entry of the `LocalVariableTypeTable` attribute, giving the generic signature
+/// of a local variable.
+#[derive(Debug, Clone)]
+pub struct LocalVariableType {
+    pub start_pc: u16,
+    pub length: u16,
+    pub name_index: u16,
+    pub signature_index: u16,
+    pub index: u16,
+}
+
 #[derive(Debug, Clone)]
 pub struct InnerClass {
     pub inner_class_info_index: u16,
@@ -547,11 +2053,152 @@ pub struct MethodParameter {
     pub access_flags: u16,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum VerificationTypeInfo {
-    Top,
-    Integer,
-    Float,
+/// A raw `Module` attribute.
+///
+/// # See Also
+/// * [JVM Specification: The Module Attribute](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.25)
+#[derive(Debug, Clone)]
+pub struct ModuleAttribute {
+    pub module_name_index: u16,
+    pub module_flags: u16,
+    pub module_version_index: u16,
+    pub requires: Vec<ModuleRequires>,
+    pub exports: Vec<ModuleExports>,
+    pub opens: Vec<ModuleOpens>,
+    pub uses_index: Vec<u16>,
+    pub provides: Vec<ModuleProvides>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleRequires {
+    pub requires_index: u16,
+    pub requires_flags: u16,
+    pub requires_version_index: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleExports {
+    pub exports_index: u16,
+    pub exports_flags: u16,
+    pub exports_to_index: Vec<u16>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleOpens {
+    pub opens_index: u16,
+    pub opens_flags: u16,
+    pub opens_to_index: Vec<u16>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleProvides {
+    pub provides_index: u16,
+    pub provides_with_index: Vec<u16>,
+}
+
+/// A raw `record_component_info` structure of the `Record` attribute.
+///
+/// # See Also
+/// * [JVM Specification: The Record Attribute](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.30)
+#[derive(Debug, Clone)]
+pub struct RecordComponentInfo {
+    pub name_index: u16,
+    pub descriptor_index: u16,
+    pub attributes: Vec<AttributeInfo>,
+}
+
+/// A raw `annotation` structure as stored in the `Runtime*Annotations` attributes.
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    pub type_index: u16,
+    pub element_value_pairs: Vec<ElementValuePair>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ElementValuePair {
+    pub element_name_index: u16,
+    pub value: ElementValue,
+}
+
+/// A raw `element_value` structure.
+///
+/// Constant values keep their tag (`B`, `C`, `D`, `F`, `I`, `J`, `S`, `Z` or `s`)
+/// so the exact Java type can be recovered from the constant pool entry.
+#[derive(Debug, Clone)]
+pub enum ElementValue {
+    Const {
+        tag: u8,
+        const_value_index: u16,
+    },
+    Enum {
+        type_name_index: u16,
+        const_name_index: u16,
+    },
+    Class {
+        class_info_index: u16,
+    },
+    Annotation(Annotation),
+    Array(Vec<ElementValue>),
+}
+
+/// A raw `type_annotation` structure as stored in the `Runtime*TypeAnnotations` attributes.
+#[derive(Debug, Clone)]
+pub struct TypeAnnotation {
+    pub target_type: u8,
+    pub target_info: TypeAnnotationTarget,
+    pub target_path: TypePath,
+    pub annotation: Annotation,
+}
+
+/// The `target_info` union of a `type_annotation`.
+///
+/// Offsets are bytecode offsets within the enclosing `Code` attribute.
+#[derive(Debug, Clone)]
+pub enum TypeAnnotationTarget {
+    TypeParameter {
+        type_parameter_index: u8,
+    },
+    SuperType {
+        supertype_index: u16,
+    },
+    TypeParameterBound {
+        type_parameter_index: u8,
+        bound_index: u8,
+    },
+    Empty,
+    FormalParameter {
+        formal_parameter_index: u8,
+    },
+    Throws {
+        throws_type_index: u16,
+    },
+    LocalVar {
+        table: Vec<LocalVarTarget>,
+    },
+    Catch {
+        exception_table_index: u16,
+    },
+    Offset {
+        offset: u16,
+    },
+    TypeArgument {
+        offset: u16,
+        type_argument_index: u8,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalVarTarget {
+    pub start_pc: u16,
+    pub length: u16,
+    pub index: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationTypeInfo {
+    Top,
+    Integer,
+    Float,
     Long,
     Double,
     Null,
@@ -591,7 +2238,54 @@ pub enum StackMapFrame {
     },
 }
 
+impl StackMapFrame {
+    /// Returns the offset delta of this frame from the previous one.
+    pub fn offset_delta(&self) -> u16 {
+        match self {
+            StackMapFrame::SameFrame { offset_delta }
+            | StackMapFrame::SameLocals1StackItemFrame { offset_delta, .. }
+            | StackMapFrame::SameLocals1StackItemFrameExtended { offset_delta, .. }
+            | StackMapFrame::ChopFrame { offset_delta, .. }
+            | StackMapFrame::SameFrameExtended { offset_delta }
+            | StackMapFrame::AppendFrame { offset_delta, .. }
+            | StackMapFrame::FullFrame { offset_delta, .. } => *offset_delta,
+        }
+    }
+
+    /// Returns the verification types explicitly listed by this frame, as `(locals, stack)`.
+    fn verification_types(&self) -> (&[VerificationTypeInfo], &[VerificationTypeInfo]) {
+        match self {
+            StackMapFrame::SameLocals1StackItemFrame { stack, .. }
+            | StackMapFrame::SameLocals1StackItemFrameExtended { stack, .. } => {
+                (&[], std::slice::from_ref(stack))
+            }
+            StackMapFrame::AppendFrame { locals, .. } => (locals, &[]),
+            StackMapFrame::FullFrame { locals, stack, .. } => (locals, stack),
+            _ => (&[], &[]),
+        }
+    }
+}
+
 pub fn read_class_file(bytes: &[u8]) -> Result<ClassFile, ClassReadError> {
+    read_class_file_with_options(bytes, 0)
+}
+
+/// Parses a class file, honoring the `SKIP_CODE`, `SKIP_DEBUG`, `SKIP_FRAMES` and
+/// `EXPAND_FRAMES` option flags.
+pub fn read_class_file_with_options(
+    bytes: &[u8],
+    options: u32,
+) -> Result<ClassFile, ClassReadError> {
+    read_class_file_with_version_policy(bytes, options, VersionPolicy::default())
+}
+
+/// Parses a class file with the given option flags, accepting the class file versions allowed
+/// by `version_policy`.
+pub fn read_class_file_with_version_policy(
+    bytes: &[u8],
+    options: u32,
+    version_policy: VersionPolicy,
+) -> Result<ClassFile, ClassReadError> {
     let mut reader = ByteReader::new(bytes);
     let magic = reader.read_u4()?;
     if magic != 0xCAFEBABE {
@@ -599,19 +2293,27 @@ pub fn read_class_file(bytes: &[u8]) -> Result<ClassFile, ClassReadError> {
     }
     let minor_version = reader.read_u2()?;
     let major_version = reader.read_u2()?;
-    if major_version > constants::V25 {
+    if !version_policy.accepts(major_version) {
         return Err(ClassReadError::InvalidClassVersion(major_version));
     }
-    let constant_pool = read_constant_pool(&mut reader)?;
+    let constant_pool =
+        read_constant_pool(&mut reader).map_err(|error| error.at_offset(reader.offset()))?;
     let access_flags = reader.read_u2()?;
     let this_class = reader.read_u2()?;
     let super_class = reader.read_u2()?;
-    let interfaces = read_u2_table(&mut reader)?;
-    let fields = read_fields(&mut reader, &constant_pool)?;
-    let methods = read_methods(&mut reader, &constant_pool)?;
-    let attributes = read_attributes(&mut reader, &constant_pool)?;
+    let class_name = cp_class_name(&constant_pool, this_class).ok();
+    let located =
+        |error: ClassReadError, offset: usize| error.in_class(class_name).at_offset(offset);
+    let interfaces =
+        read_u2_table(&mut reader).map_err(|error| located(error, reader.offset()))?;
+    let fields = read_fields(&mut reader, &constant_pool, options)
+        .map_err(|error| located(error, reader.offset()))?;
+    let methods = read_methods(&mut reader, &constant_pool, options)
+        .map_err(|error| located(error, reader.offset()))?;
+    let attributes = read_attributes(&mut reader, &constant_pool, options)
+        .map_err(|error| located(error, reader.offset()))?;
 
-    Ok(ClassFile {
+    let mut class_file = ClassFile {
         minor_version,
         major_version,
         constant_pool,
@@ -622,7 +2324,128 @@ pub fn read_class_file(bytes: &[u8]) -> Result<ClassFile, ClassReadError> {
         fields,
         methods,
         attributes,
-    })
+    };
+    if expands_frames(options) {
+        expand_frames(&mut class_file)?;
+    }
+    Ok(class_file)
+}
+
+/// Returns whether `options` ask for the stack map frames to be expanded.
+fn expands_frames(options: u32) -> bool {
+    options & EXPAND_FRAMES != 0 && options & SKIP_FRAMES == 0
+}
+
+/// Rewrites every `StackMapTable` of `class_file` as a list of `FullFrame`s, and builds the
+/// label-based form of every `Code` attribute from the expanded frames.
+fn expand_frames(class_file: &mut ClassFile) -> Result<(), ClassReadError> {
+    let class_name = class_file.class_name(class_file.this_class)?.to_string();
+    let mut signatures = Vec::with_capacity(class_file.methods.len());
+    for method in &class_file.methods {
+        signatures.push((
+            class_file.cp_utf8(method.name_index)?.to_string(),
+            class_file.cp_utf8(method.descriptor_index)?.to_string(),
+        ));
+    }
+
+    let mut cp = ConstantPoolBuilder::from_pool(std::mem::take(&mut class_file.constant_pool));
+    for (method, (name, descriptor)) in class_file.methods.iter_mut().zip(&signatures) {
+        for attr in &mut method.attributes {
+            let AttributeInfo::Code(code) = attr else {
+                continue;
+            };
+            for code_attr in &mut code.attributes {
+                let AttributeInfo::StackMapTable { entries } = code_attr else {
+                    continue;
+                };
+                let mut locals = Vec::new();
+                if method.access_flags & constants::ACC_STATIC == 0 {
+                    if name == "<init>" {
+                        locals.push(VerificationTypeInfo::UninitializedThis);
+                    } else {
+                        locals.push(VerificationTypeInfo::Object {
+                            cpool_index: cp.class(&class_name),
+                        });
+                    }
+                }
+                let method_type = Type::try_get_method_type(descriptor).map_err(|error| {
+                    ClassReadError::from(error).in_member(Some(name), Some(descriptor))
+                })?;
+                for argument in method_type.get_argument_types().unwrap_or_default() {
+                    locals.push(match argument {
+                        Type::Boolean | Type::Char | Type::Byte | Type::Short | Type::Int => {
+                            VerificationTypeInfo::Integer
+                        }
+                        Type::Float => VerificationTypeInfo::Float,
+                        Type::Long => VerificationTypeInfo::Long,
+                        Type::Double => VerificationTypeInfo::Double,
+                        _ => VerificationTypeInfo::Object {
+                            cpool_index: cp.class(&argument.internal_name().unwrap_or_default()),
+                        },
+                    });
+                }
+                *entries = expand_stack_map_frames(entries, locals);
+            }
+        }
+    }
+    class_file.constant_pool = cp.into_pool();
+    for (method, (name, descriptor)) in class_file.methods.iter_mut().zip(&signatures) {
+        for attr in &mut method.attributes {
+            if let AttributeInfo::Code(code) = attr {
+                (code.insn_nodes, code.try_catch_blocks, code.local_variables) =
+                    build_insn_nodes(code, &class_file.constant_pool).map_err(|error| {
+                        error.in_attribute("Code").in_member(Some(name), Some(descriptor))
+                    })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn expand_stack_map_frames(
+    frames: &[StackMapFrame],
+    mut locals: Vec<VerificationTypeInfo>,
+) -> Vec<StackMapFrame> {
+    let mut expanded = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let (offset_delta, stack) = match frame {
+            StackMapFrame::SameFrame { offset_delta }
+            | StackMapFrame::SameFrameExtended { offset_delta } => (*offset_delta, Vec::new()),
+            StackMapFrame::SameLocals1StackItemFrame {
+                offset_delta,
+                stack,
+            }
+            | StackMapFrame::SameLocals1StackItemFrameExtended {
+                offset_delta,
+                stack,
+            } => (*offset_delta, vec![stack.clone()]),
+            StackMapFrame::ChopFrame { offset_delta, k } => {
+                locals.truncate(locals.len().saturating_sub(*k as usize));
+                (*offset_delta, Vec::new())
+            }
+            StackMapFrame::AppendFrame {
+                offset_delta,
+                locals: appended,
+            } => {
+                locals.extend(appended.iter().cloned());
+                (*offset_delta, Vec::new())
+            }
+            StackMapFrame::FullFrame {
+                offset_delta,
+                locals: full_locals,
+                stack,
+            } => {
+                locals = full_locals.clone();
+                (*offset_delta, stack.clone())
+            }
+        };
+        expanded.push(StackMapFrame::FullFrame {
+            offset_delta,
+            locals: locals.clone(),
+            stack,
+        });
+    }
+    expanded
 }
 
 fn read_constant_pool(reader: &mut ByteReader<'_>) -> Result<Vec<CpInfo>, ClassReadError> {
@@ -632,75 +2455,9 @@ fn read_constant_pool(reader: &mut ByteReader<'_>) -> Result<Vec<CpInfo>, ClassR
 
     let mut index = 1;
     while index < count {
-        let tag = reader.read_u1()?;
-        let entry = match tag {
-            1 => {
-                let len = reader.read_u2()? as usize;
-                let bytes = reader.read_bytes(len)?;
-                let value = decode_modified_utf8(&bytes)?;
-                CpInfo::Utf8(value)
-            }
-            3 => {
-                let value = reader.read_u4()? as i32;
-                CpInfo::Integer(value)
-            }
-            4 => {
-                let value = f32::from_bits(reader.read_u4()?);
-                CpInfo::Float(value)
-            }
-            5 => {
-                let value = reader.read_u8()? as i64;
-                CpInfo::Long(value)
-            }
-            6 => {
-                let value = f64::from_bits(reader.read_u8()?);
-                CpInfo::Double(value)
-            }
-            7 => CpInfo::Class {
-                name_index: reader.read_u2()?,
-            },
-            8 => CpInfo::String {
-                string_index: reader.read_u2()?,
-            },
-            9 => CpInfo::Fieldref {
-                class_index: reader.read_u2()?,
-                name_and_type_index: reader.read_u2()?,
-            },
-            10 => CpInfo::Methodref {
-                class_index: reader.read_u2()?,
-                name_and_type_index: reader.read_u2()?,
-            },
-            11 => CpInfo::InterfaceMethodref {
-                class_index: reader.read_u2()?,
-                name_and_type_index: reader.read_u2()?,
-            },
-            12 => CpInfo::NameAndType {
-                name_index: reader.read_u2()?,
-                descriptor_index: reader.read_u2()?,
-            },
-            15 => CpInfo::MethodHandle {
-                reference_kind: reader.read_u1()?,
-                reference_index: reader.read_u2()?,
-            },
-            16 => CpInfo::MethodType {
-                descriptor_index: reader.read_u2()?,
-            },
-            17 => CpInfo::Dynamic {
-                bootstrap_method_attr_index: reader.read_u2()?,
-                name_and_type_index: reader.read_u2()?,
-            },
-            18 => CpInfo::InvokeDynamic {
-                bootstrap_method_attr_index: reader.read_u2()?,
-                name_and_type_index: reader.read_u2()?,
-            },
-            19 => CpInfo::Module {
-                name_index: reader.read_u2()?,
-            },
-            20 => CpInfo::Package {
-                name_index: reader.read_u2()?,
-            },
-            _ => return Err(ClassReadError::InvalidConstantPoolTag(tag)),
-        };
+        let offset = reader.offset();
+        let (tag, entry) =
+            read_constant_pool_entry(reader).map_err(|error| error.at_offset(offset))?;
 
         pool.push(entry);
 
@@ -715,6 +2472,79 @@ fn read_constant_pool(reader: &mut ByteReader<'_>) -> Result<Vec<CpInfo>, ClassR
     Ok(pool)
 }
 
+fn read_constant_pool_entry(reader: &mut ByteReader<'_>) -> Result<(u8, CpInfo), ClassReadError> {
+    let tag = reader.read_u1()?;
+    let entry = match tag {
+        1 => {
+            let len = reader.read_u2()? as usize;
+            let bytes = reader.read_bytes(len)?;
+            let value = decode_modified_utf8(&bytes)?;
+            CpInfo::Utf8(value)
+        }
+        3 => {
+            let value = reader.read_u4()? as i32;
+            CpInfo::Integer(value)
+        }
+        4 => {
+            let value = f32::from_bits(reader.read_u4()?);
+            CpInfo::Float(value)
+        }
+        5 => {
+            let value = reader.read_u8()? as i64;
+            CpInfo::Long(value)
+        }
+        6 => {
+            let value = f64::from_bits(reader.read_u8()?);
+            CpInfo::Double(value)
+        }
+        7 => CpInfo::Class {
+            name_index: reader.read_u2()?,
+        },
+        8 => CpInfo::String {
+            string_index: reader.read_u2()?,
+        },
+        9 => CpInfo::Fieldref {
+            class_index: reader.read_u2()?,
+            name_and_type_index: reader.read_u2()?,
+        },
+        10 => CpInfo::Methodref {
+            class_index: reader.read_u2()?,
+            name_and_type_index: reader.read_u2()?,
+        },
+        11 => CpInfo::InterfaceMethodref {
+            class_index: reader.read_u2()?,
+            name_and_type_index: reader.read_u2()?,
+        },
+        12 => CpInfo::NameAndType {
+            name_index: reader.read_u2()?,
+            descriptor_index: reader.read_u2()?,
+        },
+        15 => CpInfo::MethodHandle {
+            reference_kind: reader.read_u1()?,
+            reference_index: reader.read_u2()?,
+        },
+        16 => CpInfo::MethodType {
+            descriptor_index: reader.read_u2()?,
+        },
+        17 => CpInfo::Dynamic {
+            bootstrap_method_attr_index: reader.read_u2()?,
+            name_and_type_index: reader.read_u2()?,
+        },
+        18 => CpInfo::InvokeDynamic {
+            bootstrap_method_attr_index: reader.read_u2()?,
+            name_and_type_index: reader.read_u2()?,
+        },
+        19 => CpInfo::Module {
+            name_index: reader.read_u2()?,
+        },
+        20 => CpInfo::Package {
+            name_index: reader.read_u2()?,
+        },
+        _ => return Err(ClassReadError::InvalidConstantPoolTag(tag)),
+    };
+    Ok((tag, entry))
+}
+
 fn read_u2_table(reader: &mut ByteReader<'_>) -> Result<Vec<u16>, ClassReadError> {
     let count = reader.read_u2()? as usize;
     let mut values = Vec::with_capacity(count);
@@ -727,14 +2557,20 @@ fn read_u2_table(reader: &mut ByteReader<'_>) -> Result<Vec<u16>, ClassReadError
 fn read_fields(
     reader: &mut ByteReader<'_>,
     cp: &[CpInfo],
+    options: u32,
 ) -> Result<Vec<FieldInfo>, ClassReadError> {
     let count = reader.read_u2()? as usize;
     let mut fields = Vec::with_capacity(count);
     for _ in 0..count {
+        let offset = reader.offset();
         let access_flags = reader.read_u2()?;
         let name_index = reader.read_u2()?;
         let descriptor_index = reader.read_u2()?;
-        let attributes = read_attributes(reader, cp)?;
+        let attributes = read_attributes(reader, cp, options).map_err(|error| {
+            error
+                .in_member(cp_utf8(cp, name_index).ok(), cp_utf8(cp, descriptor_index).ok())
+                .at_offset(offset)
+        })?;
         fields.push(FieldInfo {
             access_flags,
             name_index,
@@ -748,14 +2584,20 @@ fn read_fields(
 fn read_methods(
     reader: &mut ByteReader<'_>,
     cp: &[CpInfo],
+    options: u32,
 ) -> Result<Vec<MethodInfo>, ClassReadError> {
     let count = reader.read_u2()? as usize;
     let mut methods = Vec::with_capacity(count);
     for _ in 0..count {
+        let offset = reader.offset();
         let access_flags = reader.read_u2()?;
         let name_index = reader.read_u2()?;
         let descriptor_index = reader.read_u2()?;
-        let attributes = read_attributes(reader, cp)?;
+        let attributes = read_attributes(reader, cp, options).map_err(|error| {
+            error
+                .in_member(cp_utf8(cp, name_index).ok(), cp_utf8(cp, descriptor_index).ok())
+                .at_offset(offset)
+        })?;
         methods.push(MethodInfo {
             access_flags,
             name_index,
@@ -769,33 +2611,58 @@ fn read_methods(
 fn read_attributes(
     reader: &mut ByteReader<'_>,
     cp: &[CpInfo],
+    options: u32,
 ) -> Result<Vec<AttributeInfo>, ClassReadError> {
     let count = reader.read_u2()? as usize;
     let mut attributes = Vec::with_capacity(count);
     for _ in 0..count {
+        let offset = reader.offset();
         let name_index = reader.read_u2()?;
         let length = reader.read_u4()? as usize;
-        let name = cp_utf8(cp, name_index)?;
-        let info = reader.read_bytes(length)?;
-        let attribute = parse_attribute(name, info, cp)?;
+        let name = cp_utf8(cp, name_index).map_err(|error| error.at_offset(offset))?;
+        let located = |error: ClassReadError| error.in_attribute(name).at_offset(offset);
+        if is_skipped_attribute(name, options) {
+            reader.skip(length).map_err(located)?;
+            continue;
+        }
+        let info = reader.read_bytes(length).map_err(located)?;
+        let attribute = parse_attribute(name, info, cp, options, offset + 6).map_err(located)?;
         attributes.push(attribute);
     }
     Ok(attributes)
 }
 
+fn is_skipped_attribute(name: &str, options: u32) -> bool {
+    match name {
+        "Code" => options & SKIP_CODE != 0,
+        "StackMapTable" => options & SKIP_FRAMES != 0,
+        "SourceFile"
+        | "SourceDebugExtension"
+        | "LineNumberTable"
+        | "LocalVariableTable"
+        | "LocalVariableTypeTable"
+        | "MethodParameters" => options & SKIP_DEBUG != 0,
+        _ => false,
+    }
+}
+
+/// Parses the content of the `name` attribute, which starts at byte offset `offset` of the class
+/// file.
 fn parse_attribute(
     name: &str,
     info: Vec<u8>,
     cp: &[CpInfo],
+    options: u32,
+    offset: usize,
 ) -> Result<AttributeInfo, ClassReadError> {
-    let mut reader = ByteReader::new(&info);
+    let mut reader = ByteReader::with_base(&info, offset);
     let attribute = match name {
         "Code" => {
             let max_stack = reader.read_u2()?;
             let max_locals = reader.read_u2()?;
             let code_length = reader.read_u4()? as usize;
             let code = reader.read_bytes(code_length)?;
-            let instructions = parse_code_instructions(&code)?;
+            let parsed = parse_code_instructions_with_offsets(&code)?;
             let exception_table_length = reader.read_u2()? as usize;
             let mut exception_table = Vec::with_capacity(exception_table_length);
             for _ in 0..exception_table_length {
@@ -806,16 +2673,27 @@ fn parse_attribute(
                     catch_type: reader.read_u2()?,
                 });
             }
-            let attributes = read_attributes(&mut reader, cp)?;
-            let (insn_nodes, try_catch_blocks) = build_insn_nodes(&code, &exception_table, cp)?;
+            let attributes = read_attributes(&mut reader, cp, options)?;
+            // With expanded frames, the label-based form is built by `expand_frames` instead.
+            let (insn_nodes, try_catch_blocks, local_variables) = if expands_frames(options) {
+                (Vec::new(), Vec::new(), Vec::new())
+            } else {
+                label_insn_nodes(parsed.clone(), code.len(), &exception_table, &attributes, cp)?
+            };
+            let (instruction_offsets, instructions) = parsed
+                .into_iter()
+                .map(|instruction| (instruction.offset, instruction.insn))
+                .unzip();
             AttributeInfo::Code(CodeAttribute {
                 max_stack,
                 max_locals,
                 code,
                 instructions,
+                instruction_offsets,
                 insn_nodes,
                 exception_table,
                 try_catch_blocks,
+                local_variables,
                 attributes,
             })
         }
@@ -860,6 +2738,23 @@ fn parse_attribute(
             }
             AttributeInfo::LocalVariableTable { entries }
         }
+        "LocalVariableTypeTable" => {
+            let count = reader.read_u2()? as usize;
+            let mut entries = Vec::with_capacity(count);
+            for _ in 0..count {
+                entries.push(LocalVariableType {
+                    start_pc: reader.read_u2()?,
+                    length: reader.read_u2()?,
+                    name_index: reader.read_u2()?,
+                    signature_index: reader.read_u2()?,
+                    index: reader.read_u2()?,
+                });
+            }
+            AttributeInfo::LocalVariableTypeTable { entries }
+        }
+        "SourceDebugExtension" => AttributeInfo::SourceDebugExtension {
+            debug_extension: reader.read_bytes(info.len())?,
+        },
         "Signature" => AttributeInfo::Signature {
             signature_index: reader.read_u2()?,
         },
@@ -918,9 +2813,9 @@ fn parse_attribute(
                         }
                     }
                     _ => {
-                        return Err(ClassReadError::InvalidAttribute(
-                            "StackMapTable".to_string(),
-                        ));
+                        return Err(ClassReadError::InvalidAttribute(format!(
+                            "invalid stack map frame type {frame_type}"
+                        )));
                     }
                 };
                 entries.push(frame);
@@ -974,6 +2869,57 @@ fn parse_attribute(
             }
             AttributeInfo::MethodParameters { parameters }
         }
+        "RuntimeVisibleAnnotations" => AttributeInfo::RuntimeVisibleAnnotations {
+            annotations: parse_annotations(&mut reader)?,
+        },
+        "RuntimeInvisibleAnnotations" => AttributeInfo::RuntimeInvisibleAnnotations {
+            annotations: parse_annotations(&mut reader)?,
+        },
+        "RuntimeVisibleParameterAnnotations" => AttributeInfo::RuntimeVisibleParameterAnnotations {
+            parameters: parse_parameter_annotations(&mut reader)?,
+        },
+        "RuntimeInvisibleParameterAnnotations" => {
+            AttributeInfo::RuntimeInvisibleParameterAnnotations {
+                parameters: parse_parameter_annotations(&mut reader)?,
+            }
+        }
+        "AnnotationDefault" => AttributeInfo::AnnotationDefault {
+            default_value: parse_element_value(&mut reader, 0)?,
+        },
+        "Module" => AttributeInfo::Module(parse_module(&mut reader)?),
+        "ModulePackages" => AttributeInfo::ModulePackages {
+            package_index_table: read_u2_table(&mut reader)?,
+        },
+        "ModuleMainClass" => AttributeInfo::ModuleMainClass {
+            main_class_index: reader.read_u2()?,
+        },
+        "NestHost" => AttributeInfo::NestHost {
+            host_class_index: reader.read_u2()?,
+        },
+        "NestMembers" => AttributeInfo::NestMembers {
+            classes: read_u2_table(&mut reader)?,
+        },
+        "PermittedSubclasses" => AttributeInfo::PermittedSubclasses {
+            classes: read_u2_table(&mut reader)?,
+        },
+        "Record" => {
+            let count = reader.read_u2()? as usize;
+            let mut components = Vec::with_capacity(count);
+            for _ in 0..count {
+                components.push(RecordComponentInfo {
+                    name_index: reader.read_u2()?,
+                    descriptor_index: reader.read_u2()?,
+                    attributes: read_attributes(&mut reader, cp, options)?,
+                });
+            }
+            AttributeInfo::Record { components }
+        }
+        "RuntimeVisibleTypeAnnotations" => AttributeInfo::RuntimeVisibleTypeAnnotations {
+            annotations: parse_type_annotations(&mut reader)?,
+        },
+        "RuntimeInvisibleTypeAnnotations" => AttributeInfo::RuntimeInvisibleTypeAnnotations {
+            annotations: parse_type_annotations(&mut reader)?,
+        },
         _ => {
             return Ok(AttributeInfo::Unknown {
                 name: name.to_string(),
@@ -983,78 +2929,299 @@ fn parse_attribute(
     };
 
     if reader.remaining() != 0 {
-        return Err(ClassReadError::InvalidAttribute(name.to_string()));
+        return Err(ClassReadError::InvalidAttribute(format!(
+            "{} trailing bytes",
+            reader.remaining()
+        )));
     }
 
     Ok(attribute)
 }
 
-fn parse_verification_type(
-    reader: &mut ByteReader<'_>,
-) -> Result<VerificationTypeInfo, ClassReadError> {
-    let tag = reader.read_u1()?;
-    let kind = match tag {
-        0 => VerificationTypeInfo::Top,
-        1 => VerificationTypeInfo::Integer,
-        2 => VerificationTypeInfo::Float,
-        3 => VerificationTypeInfo::Double,
-        4 => VerificationTypeInfo::Long,
-        5 => VerificationTypeInfo::Null,
-        6 => VerificationTypeInfo::UninitializedThis,
-        7 => VerificationTypeInfo::Object {
-            cpool_index: reader.read_u2()?,
-        },
-        8 => VerificationTypeInfo::Uninitialized {
-            offset: reader.read_u2()?,
-        },
-        _ => {
-            return Err(ClassReadError::InvalidAttribute(
-                "StackMapTable".to_string(),
-            ));
-        }
-    };
-    Ok(kind)
-}
+fn parse_module(reader: &mut ByteReader<'_>) -> Result<ModuleAttribute, ClassReadError> {
+    let module_name_index = reader.read_u2()?;
+    let module_flags = reader.read_u2()?;
+    let module_version_index = reader.read_u2()?;
 
-fn cp_utf8(cp: &[CpInfo], index: u16) -> Result<&str, ClassReadError> {
-    match cp.get(index as usize) {
-        Some(CpInfo::Utf8(value)) => Ok(value.as_str()),
-        _ => Err(ClassReadError::InvalidIndex(index)),
+    let count = reader.read_u2()? as usize;
+    let mut requires = Vec::with_capacity(count);
+    for _ in 0..count {
+        requires.push(ModuleRequires {
+            requires_index: reader.read_u2()?,
+            requires_flags: reader.read_u2()?,
+            requires_version_index: reader.read_u2()?,
+        });
     }
-}
 
-fn cp_class_name(cp: &[CpInfo], index: u16) -> Result<&str, ClassReadError> {
-    match cp.get(index as usize) {
-        Some(CpInfo::Class { name_index }) => cp_utf8(cp, *name_index),
-        _ => Err(ClassReadError::InvalidIndex(index)),
+    let count = reader.read_u2()? as usize;
+    let mut exports = Vec::with_capacity(count);
+    for _ in 0..count {
+        exports.push(ModuleExports {
+            exports_index: reader.read_u2()?,
+            exports_flags: reader.read_u2()?,
+            exports_to_index: read_u2_table(reader)?,
+        });
+    }
+
+    let count = reader.read_u2()? as usize;
+    let mut opens = Vec::with_capacity(count);
+    for _ in 0..count {
+        opens.push(ModuleOpens {
+            opens_index: reader.read_u2()?,
+            opens_flags: reader.read_u2()?,
+            opens_to_index: read_u2_table(reader)?,
+        });
+    }
+
+    let uses_index = read_u2_table(reader)?;
+
+    let count = reader.read_u2()? as usize;
+    let mut provides = Vec::with_capacity(count);
+    for _ in 0..count {
+        provides.push(ModuleProvides {
+            provides_index: reader.read_u2()?,
+            provides_with_index: read_u2_table(reader)?,
+        });
     }
+
+    Ok(ModuleAttribute {
+        module_name_index,
+        module_flags,
+        module_version_index,
+        requires,
+        exports,
+        opens,
+        uses_index,
+        provides,
+    })
 }
 
-fn cp_name_and_type(cp: &[CpInfo], index: u16) -> Result<(&str, &str), ClassReadError> {
-    match cp.get(index as usize) {
-        Some(CpInfo::NameAndType {
-            name_index,
-            descriptor_index,
-        }) => Ok((cp_utf8(cp, *name_index)?, cp_utf8(cp, *descriptor_index)?)),
-        _ => Err(ClassReadError::InvalidIndex(index)),
+fn parse_annotations(reader: &mut ByteReader<'_>) -> Result<Vec<Annotation>, ClassReadError> {
+    let count = reader.read_u2()? as usize;
+    let mut annotations = Vec::with_capacity(count);
+    for _ in 0..count {
+        annotations.push(parse_annotation(reader)?);
     }
+    Ok(annotations)
 }
 
-fn cp_field_ref(cp: &[CpInfo], index: u16) -> Result<(&str, &str, &str), ClassReadError> {
-    match cp.get(index as usize) {
-        Some(CpInfo::Fieldref {
-            class_index,
-            name_and_type_index,
-        }) => {
-            let owner = cp_class_name(cp, *class_index)?;
-            let (name, desc) = cp_name_and_type(cp, *name_and_type_index)?;
-            Ok((owner, name, desc))
-        }
-        _ => Err(ClassReadError::InvalidIndex(index)),
+fn parse_parameter_annotations(
+    reader: &mut ByteReader<'_>,
+) -> Result<Vec<Vec<Annotation>>, ClassReadError> {
+    let count = reader.read_u1()? as usize;
+    let mut parameters = Vec::with_capacity(count);
+    for _ in 0..count {
+        parameters.push(parse_annotations(reader)?);
     }
+    Ok(parameters)
 }
 
-fn cp_method_ref(cp: &[CpInfo], index: u16) -> Result<(&str, &str, &str, bool), ClassReadError> {
+/// The deepest nesting of annotation and array element values accepted by the reader, so that a
+/// malformed class file cannot exhaust the stack.
+const MAX_ELEMENT_VALUE_DEPTH: usize = 256;
+
+fn parse_annotation(reader: &mut ByteReader<'_>) -> Result<Annotation, ClassReadError> {
+    parse_nested_annotation(reader, 0)
+}
+
+/// Parses an annotation whose element values are nested `depth` levels deep.
+fn parse_nested_annotation(
+    reader: &mut ByteReader<'_>,
+    depth: usize,
+) -> Result<Annotation, ClassReadError> {
+    let type_index = reader.read_u2()?;
+    let count = reader.read_u2()? as usize;
+    let mut element_value_pairs = Vec::with_capacity(count);
+    for _ in 0..count {
+        element_value_pairs.push(ElementValuePair {
+            element_name_index: reader.read_u2()?,
+            value: parse_element_value(reader, depth)?,
+        });
+    }
+    Ok(Annotation {
+        type_index,
+        element_value_pairs,
+    })
+}
+
+fn parse_type_annotations(
+    reader: &mut ByteReader<'_>,
+) -> Result<Vec<TypeAnnotation>, ClassReadError> {
+    let count = reader.read_u2()? as usize;
+    let mut annotations = Vec::with_capacity(count);
+    for _ in 0..count {
+        annotations.push(parse_type_annotation(reader)?);
+    }
+    Ok(annotations)
+}
+
+fn parse_type_annotation(reader: &mut ByteReader<'_>) -> Result<TypeAnnotation, ClassReadError> {
+    let target_type = reader.read_u1()?;
+    let target_info = match target_type {
+        0x00 | 0x01 => TypeAnnotationTarget::TypeParameter {
+            type_parameter_index: reader.read_u1()?,
+        },
+        0x10 => TypeAnnotationTarget::SuperType {
+            supertype_index: reader.read_u2()?,
+        },
+        0x11 | 0x12 => TypeAnnotationTarget::TypeParameterBound {
+            type_parameter_index: reader.read_u1()?,
+            bound_index: reader.read_u1()?,
+        },
+        0x13..=0x15 => TypeAnnotationTarget::Empty,
+        0x16 => TypeAnnotationTarget::FormalParameter {
+            formal_parameter_index: reader.read_u1()?,
+        },
+        0x17 => TypeAnnotationTarget::Throws {
+            throws_type_index: reader.read_u2()?,
+        },
+        0x40 | 0x41 => {
+            let count = reader.read_u2()? as usize;
+            let mut table = Vec::with_capacity(count);
+            for _ in 0..count {
+                table.push(LocalVarTarget {
+                    start_pc: reader.read_u2()?,
+                    length: reader.read_u2()?,
+                    index: reader.read_u2()?,
+                });
+            }
+            TypeAnnotationTarget::LocalVar { table }
+        }
+        0x42 => TypeAnnotationTarget::Catch {
+            exception_table_index: reader.read_u2()?,
+        },
+        0x43..=0x46 => TypeAnnotationTarget::Offset {
+            offset: reader.read_u2()?,
+        },
+        0x47..=0x4B => TypeAnnotationTarget::TypeArgument {
+            offset: reader.read_u2()?,
+            type_argument_index: reader.read_u1()?,
+        },
+        _ => {
+            return Err(ClassReadError::InvalidAttribute(format!(
+                "type_annotation target_type 0x{target_type:02x}"
+            )));
+        }
+    };
+    let path_length = reader.read_u1()? as usize;
+    let mut steps = Vec::with_capacity(path_length);
+    for _ in 0..path_length {
+        steps.push((reader.read_u1()?, reader.read_u1()?));
+    }
+    Ok(TypeAnnotation {
+        target_type,
+        target_info,
+        target_path: TypePath::new(steps),
+        annotation: parse_annotation(reader)?,
+    })
+}
+
+fn parse_element_value(
+    reader: &mut ByteReader<'_>,
+    depth: usize,
+) -> Result<ElementValue, ClassReadError> {
+    if depth >= MAX_ELEMENT_VALUE_DEPTH {
+        return Err(ClassReadError::InvalidAttribute(format!(
+            "element_value nested more than {MAX_ELEMENT_VALUE_DEPTH} levels deep"
+        )));
+    }
+    let tag = reader.read_u1()?;
+    let value = match tag {
+        b'B' | b'C' | b'D' | b'F' | b'I' | b'J' | b'S' | b'Z' | b's' => ElementValue::Const {
+            tag,
+            const_value_index: reader.read_u2()?,
+        },
+        b'e' => ElementValue::Enum {
+            type_name_index: reader.read_u2()?,
+            const_name_index: reader.read_u2()?,
+        },
+        b'c' => ElementValue::Class {
+            class_info_index: reader.read_u2()?,
+        },
+        b'@' => ElementValue::Annotation(parse_nested_annotation(reader, depth + 1)?),
+        b'[' => {
+            let count = reader.read_u2()? as usize;
+            let mut values = Vec::with_capacity(count);
+            for _ in 0..count {
+                values.push(parse_element_value(reader, depth + 1)?);
+            }
+            ElementValue::Array(values)
+        }
+        _ => {
+            return Err(ClassReadError::InvalidAttribute(format!(
+                "element_value tag 0x{tag:02x}"
+            )));
+        }
+    };
+    Ok(value)
+}
+
+fn parse_verification_type(
+    reader: &mut ByteReader<'_>,
+) -> Result<VerificationTypeInfo, ClassReadError> {
+    let tag = reader.read_u1()?;
+    let kind = match tag {
+        0 => VerificationTypeInfo::Top,
+        1 => VerificationTypeInfo::Integer,
+        2 => VerificationTypeInfo::Float,
+        3 => VerificationTypeInfo::Double,
+        4 => VerificationTypeInfo::Long,
+        5 => VerificationTypeInfo::Null,
+        6 => VerificationTypeInfo::UninitializedThis,
+        7 => VerificationTypeInfo::Object {
+            cpool_index: reader.read_u2()?,
+        },
+        8 => VerificationTypeInfo::Uninitialized {
+            offset: reader.read_u2()?,
+        },
+        _ => {
+            return Err(ClassReadError::InvalidAttribute(format!(
+                "invalid verification type tag {tag}"
+            )));
+        }
+    };
+    Ok(kind)
+}
+
+fn cp_utf8(cp: &[CpInfo], index: u16) -> Result<&str, ClassReadError> {
+    match cp.get(index as usize) {
+        Some(CpInfo::Utf8(value)) => Ok(value.as_str()),
+        _ => Err(ClassReadError::InvalidIndex(index)),
+    }
+}
+
+fn cp_class_name(cp: &[CpInfo], index: u16) -> Result<&str, ClassReadError> {
+    match cp.get(index as usize) {
+        Some(CpInfo::Class { name_index }) => cp_utf8(cp, *name_index),
+        _ => Err(ClassReadError::InvalidIndex(index)),
+    }
+}
+
+fn cp_name_and_type(cp: &[CpInfo], index: u16) -> Result<(&str, &str), ClassReadError> {
+    match cp.get(index as usize) {
+        Some(CpInfo::NameAndType {
+            name_index,
+            descriptor_index,
+        }) => Ok((cp_utf8(cp, *name_index)?, cp_utf8(cp, *descriptor_index)?)),
+        _ => Err(ClassReadError::InvalidIndex(index)),
+    }
+}
+
+fn cp_field_ref(cp: &[CpInfo], index: u16) -> Result<(&str, &str, &str), ClassReadError> {
+    match cp.get(index as usize) {
+        Some(CpInfo::Fieldref {
+            class_index,
+            name_and_type_index,
+        }) => {
+            let owner = cp_class_name(cp, *class_index)?;
+            let (name, desc) = cp_name_and_type(cp, *name_and_type_index)?;
+            Ok((owner, name, desc))
+        }
+        _ => Err(ClassReadError::InvalidIndex(index)),
+    }
+}
+
+fn cp_method_ref(cp: &[CpInfo], index: u16) -> Result<(&str, &str, &str, bool), ClassReadError> {
     match cp.get(index as usize) {
         Some(CpInfo::Methodref {
             class_index,
@@ -1076,17 +3243,120 @@ fn cp_method_ref(cp: &[CpInfo], index: u16) -> Result<(&str, &str, &str, bool),
     }
 }
 
-fn cp_invoke_dynamic(cp: &[CpInfo], index: u16) -> Result<(&str, &str), ClassReadError> {
+fn cp_invoke_dynamic(cp: &[CpInfo], index: u16) -> Result<(u16, &str, &str), ClassReadError> {
     match cp.get(index as usize) {
         Some(CpInfo::InvokeDynamic {
+            bootstrap_method_attr_index,
             name_and_type_index,
-            ..
-        }) => cp_name_and_type(cp, *name_and_type_index),
+        }) => {
+            let (name, desc) = cp_name_and_type(cp, *name_and_type_index)?;
+            Ok((*bootstrap_method_attr_index, name, desc))
+        }
+        _ => Err(ClassReadError::InvalidIndex(index)),
+    }
+}
+
+fn cp_method_handle(cp: &[CpInfo], index: u16) -> Result<Handle, ClassReadError> {
+    match cp.get(index as usize) {
+        Some(CpInfo::MethodHandle {
+            reference_kind,
+            reference_index,
+        }) => {
+            let (owner, name, descriptor, is_interface) = match *reference_kind {
+                constants::REF_GET_FIELD..=constants::REF_PUT_STATIC => {
+                    let (owner, name, desc) = cp_field_ref(cp, *reference_index)?;
+                    (owner, name, desc, false)
+                }
+                _ => cp_method_ref(cp, *reference_index)?,
+            };
+            Ok(Handle {
+                reference_kind: *reference_kind,
+                owner: owner.to_string(),
+                name: name.to_string(),
+                descriptor: descriptor.to_string(),
+                is_interface,
+            })
+        }
+        _ => Err(ClassReadError::InvalidIndex(index)),
+    }
+}
+
+fn cp_constant_dynamic(
+    cp: &[CpInfo],
+    bootstrap_methods: &[BootstrapMethod],
+    index: u16,
+) -> Result<ConstantDynamic, ClassReadError> {
+    match cp.get(index as usize) {
+        Some(CpInfo::Dynamic {
+            bootstrap_method_attr_index,
+            name_and_type_index,
+        }) => {
+            let (name, descriptor) = cp_name_and_type(cp, *name_and_type_index)?;
+            let (bootstrap_method, bootstrap_args) =
+                bootstrap_method(cp, bootstrap_methods, *bootstrap_method_attr_index)?;
+            Ok(ConstantDynamic {
+                name: name.to_string(),
+                descriptor: descriptor.to_string(),
+                bootstrap_method,
+                bootstrap_args,
+            })
+        }
         _ => Err(ClassReadError::InvalidIndex(index)),
     }
 }
 
-fn cp_ldc_constant(cp: &[CpInfo], index: u16) -> Result<LdcConstant, ClassReadError> {
+fn cp_bootstrap_argument(
+    cp: &[CpInfo],
+    bootstrap_methods: &[BootstrapMethod],
+    index: u16,
+) -> Result<BootstrapArgument, ClassReadError> {
+    Ok(match cp.get(index as usize) {
+        Some(CpInfo::Integer(value)) => BootstrapArgument::Integer(*value),
+        Some(CpInfo::Float(value)) => BootstrapArgument::Float(*value),
+        Some(CpInfo::Long(value)) => BootstrapArgument::Long(*value),
+        Some(CpInfo::Double(value)) => BootstrapArgument::Double(*value),
+        Some(CpInfo::String { string_index }) => {
+            BootstrapArgument::String(cp_utf8(cp, *string_index)?.to_string())
+        }
+        Some(CpInfo::Class { name_index }) => {
+            BootstrapArgument::Class(cp_utf8(cp, *name_index)?.to_string())
+        }
+        Some(CpInfo::MethodType { descriptor_index }) => {
+            BootstrapArgument::MethodType(cp_utf8(cp, *descriptor_index)?.to_string())
+        }
+        Some(CpInfo::MethodHandle { .. }) => {
+            BootstrapArgument::Handle(cp_method_handle(cp, index)?)
+        }
+        Some(CpInfo::Dynamic { .. }) => {
+            BootstrapArgument::ConstantDynamic(cp_constant_dynamic(cp, bootstrap_methods, index)?)
+        }
+        _ => return Err(ClassReadError::InvalidIndex(index)),
+    })
+}
+
+/// Resolves the bootstrap method at `index` in the `BootstrapMethods` attribute.
+fn bootstrap_method(
+    cp: &[CpInfo],
+    bootstrap_methods: &[BootstrapMethod],
+    index: u16,
+) -> Result<(Handle, Vec<BootstrapArgument>), ClassReadError> {
+    let method = bootstrap_methods.get(index as usize).ok_or_else(|| {
+        ClassReadError::InvalidAttribute(format!("no bootstrap method at index {index}"))
+    })?;
+    let handle = cp_method_handle(cp, method.bootstrap_method_ref)?;
+    let arguments = method
+        .bootstrap_arguments
+        .iter()
+        .map(|argument| cp_bootstrap_argument(cp, bootstrap_methods, *argument))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((handle, arguments))
+}
+
+fn cp_ldc_constant(
+    cp: &[CpInfo],
+    bootstrap_methods: &[BootstrapMethod],
+    index: u16,
+) -> Result<LdcConstant, ClassReadError> {
     match cp.get(index as usize) {
         Some(CpInfo::Integer(value)) => Ok(LdcConstant::Integer(*value)),
         Some(CpInfo::Float(value)) => Ok(LdcConstant::Float(*value)),
@@ -1096,19 +3366,23 @@ fn cp_ldc_constant(cp: &[CpInfo], index: u16) -> Result<LdcConstant, ClassReadEr
             Ok(LdcConstant::String(cp_utf8(cp, *string_index)?.to_string()))
         }
         Some(CpInfo::Class { name_index }) => {
-            Ok(LdcConstant::Class(cp_utf8(cp, *name_index)?.to_string()))
+            let name = cp_utf8(cp, *name_index)?;
+            Type::try_get_object_type(name)?;
+            Ok(LdcConstant::Class(name.to_string()))
         }
-        Some(CpInfo::MethodType { descriptor_index }) => Ok(LdcConstant::MethodType(
-            cp_utf8(cp, *descriptor_index)?.to_string(),
-        )),
-        Some(CpInfo::MethodHandle {
-            reference_kind,
-            reference_index,
-        }) => Ok(LdcConstant::MethodHandle {
-            reference_kind: *reference_kind,
-            reference_index: *reference_index,
-        }),
-        Some(CpInfo::Dynamic { .. }) => Ok(LdcConstant::Dynamic),
+        Some(CpInfo::MethodType { descriptor_index }) => {
+            let descriptor = cp_utf8(cp, *descriptor_index)?;
+            Type::try_get_method_type(descriptor)?;
+            Ok(LdcConstant::MethodType(descriptor.to_string()))
+        }
+        Some(CpInfo::MethodHandle { .. }) => {
+            Ok(LdcConstant::MethodHandle(cp_method_handle(cp, index)?))
+        }
+        Some(CpInfo::Dynamic { .. }) => Ok(LdcConstant::Dynamic(cp_constant_dynamic(
+            cp,
+            bootstrap_methods,
+            index,
+        )?)),
         _ => Err(ClassReadError::InvalidIndex(index)),
     }
 }
@@ -1157,134 +3431,6 @@ fn decode_modified_utf8(bytes: &[u8]) -> Result<String, ClassReadError> {
         .map_err(|_| ClassReadError::Utf8Error("invalid utf16".to_string()))
 }
 
-fn parse_code_instructions(code: &[u8]) -> Result<Vec<Insn>, ClassReadError> {
-    let mut reader = ByteReader::new(code);
-    let mut insns = Vec::new();
-
-    while reader.remaining() > 0 {
-        let opcode_offset = reader.pos();
-        let opcode = reader.read_u1()?;
-        let insn = match opcode {
-            opcodes::NOP..=opcodes::DCONST_1 => Insn::Simple(opcode.into()),
-            opcodes::BIPUSH => Insn::Int(IntInsnNode {
-                insn: opcode.into(),
-                operand: reader.read_i1()? as i32,
-            }),
-            opcodes::SIPUSH => Insn::Int(IntInsnNode {
-                insn: opcode.into(),
-                operand: reader.read_i2()? as i32,
-            }),
-            opcodes::LDC => Insn::Ldc(LdcInsnNode {
-                insn: opcode.into(),
-                value: LdcValue::Index(reader.read_u1()? as u16),
-            }),
-            opcodes::LDC_W | opcodes::LDC2_W => Insn::Ldc(LdcInsnNode {
-                insn: opcode.into(),
-                value: LdcValue::Index(reader.read_u2()?),
-            }),
-            opcodes::ILOAD..=opcodes::ALOAD => Insn::Var(VarInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-            }),
-            opcodes::ILOAD_0..=opcodes::SALOAD => Insn::Simple(opcode.into()),
-            opcodes::ISTORE..=opcodes::ASTORE => Insn::Var(VarInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-            }),
-            opcodes::ISTORE_0..=opcodes::SASTORE => Insn::Simple(opcode.into()),
-            opcodes::POP..=opcodes::LXOR => Insn::Simple(opcode.into()),
-            opcodes::IINC => Insn::Iinc(IincInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-                increment: reader.read_i1()? as i16,
-            }),
-            opcodes::I2L..=opcodes::DCMPG => Insn::Simple(opcode.into()),
-            opcodes::IFEQ..=opcodes::JSR => Insn::Jump(JumpInsnNode {
-                insn: opcode.into(),
-                offset: reader.read_i2()? as i32,
-            }),
-            opcodes::RET => Insn::Var(VarInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-            }),
-            opcodes::TABLESWITCH => read_table_switch(&mut reader, opcode_offset)?,
-            opcodes::LOOKUPSWITCH => read_lookup_switch(&mut reader, opcode_offset)?,
-            opcodes::IRETURN..=opcodes::RETURN => Insn::Simple(InsnNode { opcode }),
-            opcodes::GETSTATIC..=opcodes::PUTFIELD => Insn::Field(FieldInsnNode {
-                insn: opcode.into(),
-                field_ref: MemberRef::Index(reader.read_u2()?),
-            }),
-            opcodes::INVOKEVIRTUAL..=opcodes::INVOKESTATIC => Insn::Method(MethodInsnNode {
-                insn: opcode.into(),
-                method_ref: MemberRef::Index(reader.read_u2()?),
-            }),
-            opcodes::INVOKEINTERFACE => {
-                let method_index = reader.read_u2()?;
-                let count = reader.read_u1()?;
-                let _ = reader.read_u1()?;
-                Insn::InvokeInterface(InvokeInterfaceInsnNode {
-                    insn: opcode.into(),
-                    method_index,
-                    count,
-                })
-            }
-            opcodes::INVOKEDYNAMIC => {
-                let method_index = reader.read_u2()?;
-                let _ = reader.read_u2()?;
-                Insn::InvokeDynamic(InvokeDynamicInsnNode::from_index(method_index))
-            }
-            opcodes::NEW => Insn::Type(TypeInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-            }),
-            opcodes::NEWARRAY => Insn::Int(IntInsnNode {
-                insn: opcode.into(),
-                operand: reader.read_u1()? as i32,
-            }),
-            opcodes::ANEWARRAY => Insn::Type(TypeInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-            }),
-            opcodes::ARRAYLENGTH | opcodes::ATHROW => Insn::Simple(opcode.into()),
-            opcodes::CHECKCAST | opcodes::INSTANCEOF => Insn::Type(TypeInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-            }),
-            opcodes::MONITORENTER | opcodes::MONITOREXIT => Insn::Simple(opcode.into()),
-            opcodes::WIDE => read_wide(&mut reader)?,
-            opcodes::MULTIANEWARRAY => Insn::MultiANewArray(MultiANewArrayInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-                dimensions: reader.read_u1()?,
-            }),
-            opcodes::IFNULL | opcodes::IFNONNULL => Insn::Jump(JumpInsnNode {
-                insn: opcode.into(),
-                offset: reader.read_i2()? as i32,
-            }),
-            opcodes::GOTO_W | opcodes::JSR_W => Insn::Jump(JumpInsnNode {
-                insn: opcode.into(),
-                offset: reader.read_i4()?,
-            }),
-            opcodes::BREAKPOINT => Insn::Simple(opcode.into()),
-            opcodes::IMPDEP1 | opcodes::IMPDEP2 => Insn::Simple(opcode.into()),
-            _ => {
-                return Err(ClassReadError::InvalidOpcode {
-                    opcode,
-                    offset: opcode_offset,
-                });
-            }
-        };
-
-        insns.push(insn);
-    }
-
-    Ok(insns)
-}
-
-pub(crate) fn parse_code_instructions_public(code: &[u8]) -> Result<Vec<Insn>, ClassReadError> {
-    parse_code_instructions(code)
-}
-
 #[derive(Debug, Clone)]
 struct ParsedInstruction {
     offset: u16,
@@ -1299,117 +3445,8 @@ fn parse_code_instructions_with_offsets(
 
     while reader.remaining() > 0 {
         let opcode_offset = reader.pos();
-        let opcode = reader.read_u1()?;
-        let insn = match opcode {
-            opcodes::NOP..=opcodes::DCONST_1 => Insn::Simple(opcode.into()),
-            opcodes::BIPUSH => Insn::Int(IntInsnNode {
-                insn: opcode.into(),
-                operand: reader.read_i1()? as i32,
-            }),
-            opcodes::SIPUSH => Insn::Int(IntInsnNode {
-                insn: opcode.into(),
-                operand: reader.read_i2()? as i32,
-            }),
-            opcodes::LDC => Insn::Ldc(LdcInsnNode {
-                insn: opcode.into(),
-                value: LdcValue::Index(reader.read_u1()? as u16),
-            }),
-            opcodes::LDC_W | opcodes::LDC2_W => Insn::Ldc(LdcInsnNode {
-                insn: opcode.into(),
-                value: LdcValue::Index(reader.read_u2()?),
-            }),
-            opcodes::ILOAD..=opcodes::ALOAD => Insn::Var(VarInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-            }),
-            opcodes::ILOAD_0..=opcodes::SALOAD => Insn::Simple(opcode.into()),
-            opcodes::ISTORE..=opcodes::ASTORE => Insn::Var(VarInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-            }),
-            opcodes::ISTORE_0..=opcodes::SASTORE => Insn::Simple(opcode.into()),
-            opcodes::POP..=opcodes::LXOR => Insn::Simple(opcode.into()),
-            opcodes::IINC => Insn::Iinc(IincInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-                increment: reader.read_i1()? as i16,
-            }),
-            opcodes::I2L..=opcodes::DCMPG => Insn::Simple(opcode.into()),
-            opcodes::IFEQ..=opcodes::JSR => Insn::Jump(JumpInsnNode {
-                insn: opcode.into(),
-                offset: reader.read_i2()? as i32,
-            }),
-            opcodes::RET => Insn::Var(VarInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u1()? as u16,
-            }),
-            opcodes::TABLESWITCH => read_table_switch(&mut reader, opcode_offset)?,
-            opcodes::LOOKUPSWITCH => read_lookup_switch(&mut reader, opcode_offset)?,
-            opcodes::IRETURN..=opcodes::RETURN => Insn::Simple(opcode.into()),
-            opcodes::GETSTATIC..=opcodes::PUTFIELD => Insn::Field(FieldInsnNode {
-                insn: opcode.into(),
-                field_ref: MemberRef::Index(reader.read_u2()?),
-            }),
-            opcodes::INVOKEVIRTUAL..=opcodes::INVOKESTATIC => Insn::Method(MethodInsnNode {
-                insn: opcode.into(),
-                method_ref: MemberRef::Index(reader.read_u2()?),
-            }),
-            opcodes::INVOKEINTERFACE => {
-                let method_index = reader.read_u2()?;
-                let count = reader.read_u1()?;
-                let _ = reader.read_u1()?;
-                Insn::InvokeInterface(InvokeInterfaceInsnNode {
-                    insn: opcode.into(),
-                    method_index,
-                    count,
-                })
-            }
-            opcodes::INVOKEDYNAMIC => {
-                let method_index = reader.read_u2()?;
-                let _ = reader.read_u2()?;
-                Insn::InvokeDynamic(InvokeDynamicInsnNode::from_index(method_index))
-            }
-            opcodes::NEW => Insn::Type(TypeInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-            }),
-            opcodes::NEWARRAY => Insn::Int(IntInsnNode {
-                insn: opcode.into(),
-                operand: reader.read_u1()? as i32,
-            }),
-            opcodes::ANEWARRAY => Insn::Type(TypeInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-            }),
-            opcodes::ARRAYLENGTH | opcodes::ATHROW => Insn::Simple(opcode.into()),
-            opcodes::CHECKCAST | opcodes::INSTANCEOF => Insn::Type(TypeInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-            }),
-            opcodes::MONITORENTER | opcodes::MONITOREXIT => Insn::Simple(opcode.into()),
-            opcodes::WIDE => read_wide(&mut reader)?,
-            opcodes::MULTIANEWARRAY => Insn::MultiANewArray(MultiANewArrayInsnNode {
-                insn: opcode.into(),
-                type_index: reader.read_u2()?,
-                dimensions: reader.read_u1()?,
-            }),
-            opcodes::IFNULL | opcodes::IFNONNULL => Insn::Jump(JumpInsnNode {
-                insn: opcode.into(),
-                offset: reader.read_i2()? as i32,
-            }),
-            opcodes::GOTO_W | opcodes::JSR_W => Insn::Jump(JumpInsnNode {
-                insn: opcode.into(),
-                offset: reader.read_i4()?,
-            }),
-            opcodes::BREAKPOINT => Insn::Simple(opcode.into()),
-            opcodes::IMPDEP1 | opcodes::IMPDEP2 => Insn::Simple(opcode.into()),
-            _ => {
-                return Err(ClassReadError::InvalidOpcode {
-                    opcode,
-                    offset: opcode_offset,
-                });
-            }
-        };
+        let insn = read_instruction(&mut reader, opcode_offset)
+            .map_err(|error| error.at_code_offset(opcode_offset))?;
 
         insns.push(ParsedInstruction {
             offset: opcode_offset as u16,
@@ -1420,165 +3457,961 @@ fn parse_code_instructions_with_offsets(
     Ok(insns)
 }
 
-fn build_insn_nodes(
-    code: &[u8],
+/// Reads the instruction whose opcode is at `opcode_offset` in the bytecode.
+fn read_instruction(
+    reader: &mut ByteReader<'_>,
+    opcode_offset: usize,
+) -> Result<Insn, ClassReadError> {
+    let opcode = reader.read_u1()?;
+    let insn = match opcode {
+        opcodes::NOP..=opcodes::DCONST_1 => Insn::Simple(opcode.into()),
+        opcodes::BIPUSH => Insn::Int(IntInsnNode {
+            insn: opcode.into(),
+            operand: reader.read_i1()? as i32,
+        }),
+        opcodes::SIPUSH => Insn::Int(IntInsnNode {
+            insn: opcode.into(),
+            operand: reader.read_i2()? as i32,
+        }),
+        opcodes::LDC => Insn::Ldc(LdcInsnNode {
+            insn: opcode.into(),
+            value: LdcValue::Index(reader.read_u1()? as u16),
+        }),
+        opcodes::LDC_W | opcodes::LDC2_W => Insn::Ldc(LdcInsnNode {
+            insn: opcode.into(),
+            value: LdcValue::Index(reader.read_u2()?),
+        }),
+        opcodes::ILOAD..=opcodes::ALOAD => Insn::Var(VarInsnNode {
+            insn: opcode.into(),
+            var_index: reader.read_u1()? as u16,
+        }),
+        opcodes::ILOAD_0..=opcodes::SALOAD => Insn::Simple(opcode.into()),
+        opcodes::ISTORE..=opcodes::ASTORE => Insn::Var(VarInsnNode {
+            insn: opcode.into(),
+            var_index: reader.read_u1()? as u16,
+        }),
+        opcodes::ISTORE_0..=opcodes::SASTORE => Insn::Simple(opcode.into()),
+        opcodes::POP..=opcodes::LXOR => Insn::Simple(opcode.into()),
+        opcodes::IINC => Insn::Iinc(IincInsnNode {
+            insn: opcode.into(),
+            var_index: reader.read_u1()? as u16,
+            increment: reader.read_i1()? as i16,
+        }),
+        opcodes::I2L..=opcodes::DCMPG => Insn::Simple(opcode.into()),
+        opcodes::IFEQ..=opcodes::JSR => Insn::Jump(JumpInsnNode {
+            insn: opcode.into(),
+            offset: reader.read_i2()? as i32,
+        }),
+        opcodes::RET => Insn::Var(VarInsnNode {
+            insn: opcode.into(),
+            var_index: reader.read_u1()? as u16,
+        }),
+        opcodes::TABLESWITCH => read_table_switch(reader, opcode_offset)?,
+        opcodes::LOOKUPSWITCH => read_lookup_switch(reader, opcode_offset)?,
+        opcodes::IRETURN..=opcodes::RETURN => Insn::Simple(opcode.into()),
+        opcodes::GETSTATIC..=opcodes::PUTFIELD => Insn::Field(FieldInsnNode {
+            insn: opcode.into(),
+            field_ref: MemberRef::Index(reader.read_u2()?),
+        }),
+        opcodes::INVOKEVIRTUAL..=opcodes::INVOKESTATIC => Insn::Method(MethodInsnNode {
+            insn: opcode.into(),
+            method_ref: MemberRef::Index(reader.read_u2()?),
+        }),
+        opcodes::INVOKEINTERFACE => {
+            let method_index = reader.read_u2()?;
+            let count = reader.read_u1()?;
+            let _ = reader.read_u1()?;
+            Insn::InvokeInterface(InvokeInterfaceInsnNode {
+                insn: opcode.into(),
+                method_index,
+                count,
+            })
+        }
+        opcodes::INVOKEDYNAMIC => {
+            let method_index = reader.read_u2()?;
+            let _ = reader.read_u2()?;
+            Insn::InvokeDynamic(InvokeDynamicInsnNode::from_index(method_index))
+        }
+        opcodes::NEW => Insn::Type(TypeInsnNode {
+            insn: opcode.into(),
+            type_index: reader.read_u2()?,
+        }),
+        opcodes::NEWARRAY => Insn::Int(IntInsnNode {
+            insn: opcode.into(),
+            operand: reader.read_u1()? as i32,
+        }),
+        opcodes::ANEWARRAY => Insn::Type(TypeInsnNode {
+            insn: opcode.into(),
+            type_index: reader.read_u2()?,
+        }),
+        opcodes::ARRAYLENGTH | opcodes::ATHROW => Insn::Simple(opcode.into()),
+        opcodes::CHECKCAST | opcodes::INSTANCEOF => Insn::Type(TypeInsnNode {
+            insn: opcode.into(),
+            type_index: reader.read_u2()?,
+        }),
+        opcodes::MONITORENTER | opcodes::MONITOREXIT => Insn::Simple(opcode.into()),
+        opcodes::WIDE => read_wide(reader)?,
+        opcodes::MULTIANEWARRAY => Insn::MultiANewArray(MultiANewArrayInsnNode {
+            insn: opcode.into(),
+            type_index: reader.read_u2()?,
+            dimensions: reader.read_u1()?,
+        }),
+        opcodes::IFNULL | opcodes::IFNONNULL => Insn::Jump(JumpInsnNode {
+            insn: opcode.into(),
+            offset: reader.read_i2()? as i32,
+        }),
+        opcodes::GOTO_W | opcodes::JSR_W => Insn::Jump(JumpInsnNode {
+            insn: opcode.into(),
+            offset: reader.read_i4()?,
+        }),
+        opcodes::BREAKPOINT => Insn::Simple(opcode.into()),
+        opcodes::IMPDEP1 | opcodes::IMPDEP2 => Insn::Simple(opcode.into()),
+        _ => {
+            return Err(ClassReadError::InvalidOpcode {
+                opcode,
+                offset: opcode_offset,
+            });
+        }
+    };
+    Ok(insn)
+}
+
+/// The label-based form of a `Code` attribute: instructions, labels, line numbers and frames,
+/// then exception handlers and local variables.
+pub(crate) type CodeNodes = (Vec<AbstractInsnNode>, Vec<TryCatchBlockNode>, Vec<LocalVariableNode>);
+
+/// Builds the label-based form of `code` from its already decoded instructions.
+fn build_insn_nodes(code: &CodeAttribute, cp: &[CpInfo]) -> Result<CodeNodes, ClassReadError> {
+    label_insn_nodes(
+        parsed_instructions(code),
+        code.code.len(),
+        &code.exception_table,
+        &code.attributes,
+        cp,
+    )
+}
+
+/// Returns the decoded instructions of `code` with their offsets.
+fn parsed_instructions(code: &CodeAttribute) -> Vec<ParsedInstruction> {
+    code.instruction_offsets
+        .iter()
+        .zip(&code.instructions)
+        .map(|(&offset, insn)| ParsedInstruction {
+            offset,
+            insn: insn.clone(),
+        })
+        .collect()
+}
+
+fn label_insn_nodes(
+    instructions: Vec<ParsedInstruction>,
+    code_length: usize,
     exception_table: &[ExceptionTableEntry],
+    attributes: &[AttributeInfo],
     cp: &[CpInfo],
-) -> Result<(Vec<AbstractInsnNode>, Vec<TryCatchBlockNode>), ClassReadError> {
-    let instructions = parse_code_instructions_with_offsets(code)?;
-    let mut offsets = std::collections::HashSet::new();
+) -> Result<CodeNodes, ClassReadError> {
+    let mut line_numbers: HashMap<u16, Vec<u16>> = HashMap::new();
+    let mut local_variables = Vec::new();
+    let mut local_variable_types = Vec::new();
+    let mut frames: HashMap<u16, &StackMapFrame> = HashMap::new();
+    let mut annotated_locals = Vec::new();
+    for attr in attributes {
+        match attr {
+            AttributeInfo::LineNumberTable { entries } => {
+                for entry in entries {
+                    line_numbers
+                        .entry(entry.start_pc)
+                        .or_default()
+                        .push(entry.line_number);
+                }
+            }
+            AttributeInfo::LocalVariableTable { entries } => local_variables.extend(entries),
+            AttributeInfo::LocalVariableTypeTable { entries } => {
+                local_variable_types.extend(entries)
+            }
+            AttributeInfo::StackMapTable { entries } => {
+                let mut offset = None;
+                for frame in entries {
+                    let current = match offset {
+                        None => frame.offset_delta(),
+                        Some(previous) => previous + frame.offset_delta() + 1,
+                    };
+                    frames.insert(current, frame);
+                    offset = Some(current);
+                }
+            }
+            AttributeInfo::RuntimeVisibleTypeAnnotations { annotations }
+            | AttributeInfo::RuntimeInvisibleTypeAnnotations { annotations } => {
+                for annotation in annotations {
+                    if let TypeAnnotationTarget::LocalVar { table } = &annotation.target_info {
+                        annotated_locals.extend(table);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut offsets = BTreeSet::new();
     for instruction in &instructions {
-        offsets.insert(instruction.offset);
+        let offset = instruction.offset as i32;
         match &instruction.insn {
             Insn::Jump(node) => {
-                offsets.insert((instruction.offset as i32 + node.offset) as u16);
+                offsets.insert((offset + node.offset) as u16);
             }
             Insn::TableSwitch(node) => {
-                offsets.insert((instruction.offset as i32 + node.default_offset) as u16);
-                for offset in &node.offsets {
-                    offsets.insert((instruction.offset as i32 + *offset) as u16);
+                offsets.insert((offset + node.default_offset) as u16);
+                for target in &node.offsets {
+                    offsets.insert((offset + *target) as u16);
+                }
+            }
+            Insn::LookupSwitch(node) => {
+                offsets.insert((offset + node.default_offset) as u16);
+                for (_, target) in &node.pairs {
+                    offsets.insert((offset + *target) as u16);
+                }
+            }
+            _ => {}
+        }
+    }
+    for entry in exception_table {
+        offsets.extend([entry.start_pc, entry.end_pc, entry.handler_pc]);
+    }
+    offsets.extend(line_numbers.keys().copied());
+    for entry in &local_variables {
+        offsets.extend([entry.start_pc, entry.start_pc + entry.length]);
+    }
+    for entry in &annotated_locals {
+        offsets.extend([entry.start_pc, entry.start_pc.wrapping_add(entry.length)]);
+    }
+    for frame in frames.values() {
+        let (locals, stack) = frame.verification_types();
+        for info in locals.iter().chain(stack) {
+            if let VerificationTypeInfo::Uninitialized { offset } = info {
+                offsets.insert(*offset);
+            }
+        }
+    }
+
+    let label_by_offset: HashMap<u16, LabelNode> = offsets
+        .into_iter()
+        .map(|offset| (offset, LabelNode::new()))
+        .collect();
+    let frame_labels: HashMap<u16, Label> = label_by_offset
+        .iter()
+        .map(|(offset, label)| (*offset, Label { id: label.id }))
+        .collect();
+    let label_at = |offset: i32| {
+        label_by_offset.get(&(offset as u16)).copied().ok_or_else(|| {
+            ClassReadError::InvalidAttribute(format!("no label at code offset {offset}"))
+        })
+    };
+
+    let mut nodes = Vec::new();
+    for instruction in instructions {
+        let offset = instruction.offset as i32;
+        if let Some(label) = label_by_offset.get(&instruction.offset) {
+            nodes.push(AbstractInsnNode::Label(*label));
+            for line in line_numbers.get(&instruction.offset).into_iter().flatten() {
+                nodes.push(AbstractInsnNode::LineNumber(LineNumberInsnNode::new(*line, *label)));
+            }
+        }
+        if let Some(frame) = frames.get(&instruction.offset) {
+            let frame = frame_event(cp, &frame_labels, frame)
+                .map_err(|error| error.at_code_offset(offset as usize))?;
+            nodes.push(AbstractInsnNode::Frame(frame));
+        }
+        let node = match instruction.insn {
+            Insn::Jump(node) => AbstractInsnNode::JumpLabel(JumpLabelInsnNode {
+                insn: node.insn,
+                target: label_at(offset + node.offset)?,
+            }),
+            Insn::TableSwitch(node) => {
+                let mut labels = Vec::with_capacity(node.offsets.len());
+                for target in &node.offsets {
+                    labels.push(label_at(offset + *target)?);
+                }
+                AbstractInsnNode::TableSwitchLabel(TableSwitchLabelInsnNode {
+                    insn: node.insn,
+                    default: label_at(offset + node.default_offset)?,
+                    low: node.low,
+                    high: node.high,
+                    labels,
+                })
+            }
+            Insn::LookupSwitch(node) => {
+                let mut keys = Vec::with_capacity(node.pairs.len());
+                let mut labels = Vec::with_capacity(node.pairs.len());
+                for (key, target) in &node.pairs {
+                    keys.push(*key);
+                    labels.push(label_at(offset + *target)?);
+                }
+                AbstractInsnNode::LookupSwitchLabel(LookupSwitchLabelInsnNode {
+                    insn: node.insn,
+                    default: label_at(offset + node.default_offset)?,
+                    keys,
+                    labels,
+                })
+            }
+            insn => AbstractInsnNode::Insn(insn),
+        };
+        nodes.push(node);
+    }
+    if let Some(label) = label_by_offset.get(&(code_length as u16)) {
+        nodes.push(AbstractInsnNode::Label(*label));
+    }
+
+    let mut try_catch_blocks = Vec::with_capacity(exception_table.len());
+    for entry in exception_table {
+        let catch_type = if entry.catch_type == 0 {
+            None
+        } else {
+            Some(cp_class_name(cp, entry.catch_type)?.to_string())
+        };
+        try_catch_blocks.push(TryCatchBlockNode {
+            start: label_at(entry.start_pc as i32)?,
+            end: label_at(entry.end_pc as i32)?,
+            handler: label_at(entry.handler_pc as i32)?,
+            catch_type,
+        });
+    }
+
+    let mut local_variable_nodes = Vec::with_capacity(local_variables.len());
+    for entry in local_variables {
+        let signature = local_variable_types
+            .iter()
+            .find(|entry_type| {
+                entry_type.start_pc == entry.start_pc
+                    && entry_type.length == entry.length
+                    && entry_type.index == entry.index
+            })
+            .map(|entry_type| cp_utf8(cp, entry_type.signature_index))
+            .transpose()?;
+        local_variable_nodes.push(LocalVariableNode {
+            name: cp_utf8(cp, entry.name_index)?.to_string(),
+            descriptor: cp_utf8(cp, entry.descriptor_index)?.to_string(),
+            signature: signature.map(str::to_string),
+            start: label_at(entry.start_pc as i32)?,
+            end: label_at((entry.start_pc + entry.length) as i32)?,
+            index: entry.index,
+        });
+    }
+
+    Ok((nodes, try_catch_blocks, local_variable_nodes))
+}
+
+fn read_table_switch(
+    reader: &mut ByteReader<'_>,
+    opcode_offset: usize,
+) -> Result<Insn, ClassReadError> {
+    reader.align4(opcode_offset)?;
+    let default_offset = reader.read_i4()?;
+    let low = reader.read_i4()?;
+    let high = reader.read_i4()?;
+    let count = if high < low {
+        0
+    } else {
+        (high - low + 1) as usize
+    };
+    let mut offsets = Vec::with_capacity(count);
+    for _ in 0..count {
+        offsets.push(reader.read_i4()?);
+    }
+    Ok(Insn::TableSwitch(TableSwitchInsnNode {
+        insn: opcodes::TABLESWITCH.into(),
+        default_offset,
+        low,
+        high,
+        offsets,
+    }))
+}
+
+fn read_lookup_switch(
+    reader: &mut ByteReader<'_>,
+    opcode_offset: usize,
+) -> Result<Insn, ClassReadError> {
+    reader.align4(opcode_offset)?;
+    let default_offset = reader.read_i4()?;
+    let npairs = reader.read_i4()? as usize;
+    let mut pairs = Vec::with_capacity(npairs);
+    for _ in 0..npairs {
+        let key = reader.read_i4()?;
+        let offset = reader.read_i4()?;
+        pairs.push((key, offset));
+    }
+    Ok(Insn::LookupSwitch(LookupSwitchInsnNode {
+        insn: opcodes::LOOKUPSWITCH.into(),
+        default_offset,
+        pairs,
+    }))
+}
+
+/// Reads the instruction following a `WIDE` prefix as a plain `Var` or `Iinc` instruction.
+fn read_wide(reader: &mut ByteReader<'_>) -> Result<Insn, ClassReadError> {
+    let opcode = reader.read_u1()?;
+    match opcode {
+        opcodes::ILOAD..=opcodes::ALOAD | opcodes::ISTORE..=opcodes::ASTORE | opcodes::RET => {
+            Ok(Insn::Var(VarInsnNode {
+                insn: opcode.into(),
+                var_index: reader.read_u2()?,
+            }))
+        }
+        opcodes::IINC => Ok(Insn::Iinc(IincInsnNode {
+            insn: opcode.into(),
+            var_index: reader.read_u2()?,
+            increment: reader.read_i2()?,
+        })),
+        _ => Err(ClassReadError::InvalidOpcode {
+            opcode,
+            offset: reader.pos().saturating_sub(1),
+        }),
+    }
+}
+
+/// Makes `mv` visit the content of a `Code` attribute: exception handlers, labels, line numbers,
+/// frames and instructions, then local variables. `visit_code` and `visit_maxs` are left
+/// to the caller.
+fn accept_code(
+    class_file: &ClassFile,
+    code: &CodeAttribute,
+    mv: &mut dyn MethodVisitor,
+) -> Result<(), ClassReadError> {
+    let cp = &class_file.constant_pool;
+    let code_annotations = class_file.decode_code_annotations(code)?;
+    let bootstrap_methods = class_file
+        .attributes
+        .iter()
+        .find_map(|attr| match attr {
+            AttributeInfo::BootstrapMethods { methods } => Some(methods.as_slice()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let instructions = parsed_instructions(code);
+
+    let mut line_numbers: HashMap<u16, Vec<u16>> = HashMap::new();
+    let mut local_variables = Vec::new();
+    let mut local_variable_types = Vec::new();
+    let mut frames: HashMap<u16, &StackMapFrame> = HashMap::new();
+    for attr in &code.attributes {
+        match attr {
+            AttributeInfo::LineNumberTable { entries } => {
+                for entry in entries {
+                    line_numbers
+                        .entry(entry.start_pc)
+                        .or_default()
+                        .push(entry.line_number);
+                }
+            }
+            AttributeInfo::LocalVariableTable { entries } => local_variables.extend(entries),
+            AttributeInfo::LocalVariableTypeTable { entries } => {
+                local_variable_types.extend(entries)
+            }
+            AttributeInfo::StackMapTable { entries } => {
+                let mut offset = None;
+                for frame in entries {
+                    let current = match offset {
+                        None => frame.offset_delta(),
+                        Some(previous) => previous + frame.offset_delta() + 1,
+                    };
+                    frames.insert(current, frame);
+                    offset = Some(current);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut label_offsets = BTreeSet::new();
+    for instruction in &instructions {
+        let offset = instruction.offset as i32;
+        match &instruction.insn {
+            Insn::Jump(node) => {
+                label_offsets.insert((offset + node.offset) as u16);
+            }
+            Insn::TableSwitch(node) => {
+                label_offsets.insert((offset + node.default_offset) as u16);
+                for target in &node.offsets {
+                    label_offsets.insert((offset + *target) as u16);
+                }
+            }
+            Insn::LookupSwitch(node) => {
+                label_offsets.insert((offset + node.default_offset) as u16);
+                for (_, target) in &node.pairs {
+                    label_offsets.insert((offset + *target) as u16);
+                }
+            }
+            _ => {}
+        }
+    }
+    for entry in &code.exception_table {
+        label_offsets.extend([entry.start_pc, entry.end_pc, entry.handler_pc]);
+    }
+    label_offsets.extend(line_numbers.keys().copied());
+    for entry in &local_variables {
+        label_offsets.extend([entry.start_pc, entry.start_pc + entry.length]);
+    }
+    for frame in frames.values() {
+        let (locals, stack) = frame.verification_types();
+        for info in locals.iter().chain(stack) {
+            if let VerificationTypeInfo::Uninitialized { offset } = info {
+                label_offsets.insert(*offset);
+            }
+        }
+    }
+    let annotated_locals = code_annotations
+        .visible_local_variables
+        .iter()
+        .chain(&code_annotations.invisible_local_variables);
+    for annotation in annotated_locals {
+        label_offsets.extend(annotation.start.iter().chain(&annotation.end));
+    }
+    let labels: HashMap<u16, Label> = label_offsets
+        .into_iter()
+        .map(|offset| (offset, Label::new()))
+        .collect();
+    let label_at = |offset: u16| {
+        labels.get(&offset).copied().ok_or_else(|| {
+            ClassReadError::InvalidAttribute(format!("no label at code offset {offset}"))
+        })
+    };
+
+    for entry in &code.exception_table {
+        let catch_type = if entry.catch_type == 0 {
+            None
+        } else {
+            Some(cp_class_name(cp, entry.catch_type)?)
+        };
+        mv.visit_try_catch_block(
+            label_at(entry.start_pc)?,
+            label_at(entry.end_pc)?,
+            label_at(entry.handler_pc)?,
+            catch_type,
+        );
+    }
+    for annotation in &code_annotations.visible_try_catches {
+        mv.visit_try_catch_annotation(annotation, true);
+    }
+    for annotation in &code_annotations.invisible_try_catches {
+        mv.visit_try_catch_annotation(annotation, false);
+    }
+
+    for instruction in instructions {
+        let offset = instruction.offset;
+        if let Some(label) = labels.get(&offset) {
+            mv.visit_label(*label);
+            for line in line_numbers.get(&offset).into_iter().flatten() {
+                mv.visit_line_number(*line, *label);
+            }
+        }
+        if let Some(frame) = frames.get(&offset) {
+            let frame = frame_event(cp, &labels, frame)
+                .map_err(|error| error.at_code_offset(offset as usize))?;
+            mv.visit_frame(&frame);
+        }
+        visit_instruction(
+            cp,
+            offset as i32,
+            instruction.insn,
+            &labels,
+            bootstrap_methods,
+            mv,
+        )
+        .map_err(|error| error.at_code_offset(offset as usize))?;
+        for (annotation_offset, annotation) in &code_annotations.visible_insns {
+            if *annotation_offset == offset {
+                mv.visit_insn_annotation(annotation, true);
+            }
+        }
+        for (annotation_offset, annotation) in &code_annotations.invisible_insns {
+            if *annotation_offset == offset {
+                mv.visit_insn_annotation(annotation, false);
+            }
+        }
+    }
+    if let Some(label) = labels.get(&(code.code.len() as u16)) {
+        mv.visit_label(*label);
+    }
+
+    for entry in &local_variables {
+        let signature = local_variable_types
+            .iter()
+            .find(|entry_type| {
+                entry_type.start_pc == entry.start_pc
+                    && entry_type.length == entry.length
+                    && entry_type.index == entry.index
+            })
+            .map(|entry_type| cp_utf8(cp, entry_type.signature_index))
+            .transpose()?;
+        mv.visit_local_variable(
+            cp_utf8(cp, entry.name_index)?,
+            cp_utf8(cp, entry.descriptor_index)?,
+            signature,
+            label_at(entry.start_pc)?,
+            label_at(entry.start_pc + entry.length)?,
+            entry.index,
+        );
+    }
+    for (annotations, visible) in [
+        (&code_annotations.visible_local_variables, true),
+        (&code_annotations.invisible_local_variables, false),
+    ] {
+        for annotation in annotations {
+            let mut start = Vec::with_capacity(annotation.start.len());
+            for offset in &annotation.start {
+                start.push(label_at(*offset)?);
+            }
+            let mut end = Vec::with_capacity(annotation.end.len());
+            for offset in &annotation.end {
+                end.push(label_at(*offset)?);
+            }
+            mv.visit_local_variable_annotation(
+                &annotation.type_annotation,
+                &start,
+                &end,
+                &annotation.index,
+                visible,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Returns the generic signature of the `Signature` attribute among `attributes`, if any.
+pub(crate) fn attribute_signature<'a>(
+    cp: &'a [CpInfo],
+    attributes: &[AttributeInfo],
+) -> Result<Option<&'a str>, ClassReadError> {
+    for attr in attributes {
+        if let AttributeInfo::Signature { signature_index } = attr {
+            return cp_utf8(cp, *signature_index).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the internal names of the `Exceptions` attribute among `attributes`.
+pub(crate) fn attribute_exceptions<'a>(
+    cp: &'a [CpInfo],
+    attributes: &[AttributeInfo],
+) -> Result<Vec<&'a str>, ClassReadError> {
+    let mut exceptions = Vec::new();
+    for attr in attributes {
+        if let AttributeInfo::Exceptions {
+            exception_index_table,
+        } = attr
+        {
+            for index in exception_index_table {
+                exceptions.push(cp_class_name(cp, *index)?);
+            }
+        }
+    }
+    Ok(exceptions)
+}
+
+/// Returns the value of the `ConstantValue` attribute among `attributes`, if any.
+pub(crate) fn attribute_constant_value(
+    cp: &[CpInfo],
+    attributes: &[AttributeInfo],
+) -> Result<Option<ConstantValue>, ClassReadError> {
+    for attr in attributes {
+        if let AttributeInfo::ConstantValue {
+            constantvalue_index,
+        } = attr
+        {
+            let value = match cp.get(*constantvalue_index as usize) {
+                Some(CpInfo::Integer(value)) => ConstantValue::Integer(*value),
+                Some(CpInfo::Float(value)) => ConstantValue::Float(*value),
+                Some(CpInfo::Long(value)) => ConstantValue::Long(*value),
+                Some(CpInfo::Double(value)) => ConstantValue::Double(*value),
+                Some(CpInfo::String { string_index }) => {
+                    ConstantValue::String(cp_utf8(cp, *string_index)?.to_string())
+                }
+                _ => return Err(ClassReadError::InvalidIndex(*constantvalue_index)),
+            };
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+/// Makes `visitor` visit the `EnclosingMethod` attribute among `attributes`.
+pub(crate) fn accept_outer_class(
+    cp: &[CpInfo],
+    attributes: &[AttributeInfo],
+    visitor: &mut dyn ClassVisitor,
+) -> Result<(), ClassReadError> {
+    for attr in attributes {
+        if let AttributeInfo::EnclosingMethod {
+            class_index,
+            method_index,
+        } = attr
+        {
+            let owner = cp_class_name(cp, *class_index)?;
+            let (name, descriptor) = match method_index {
+                0 => (None, None),
+                index => {
+                    let (name, descriptor) = cp_name_and_type(cp, *index)?;
+                    (Some(name), Some(descriptor))
+                }
+            };
+            visitor.visit_outer_class(owner, name, descriptor);
+        }
+    }
+    Ok(())
+}
+
+/// Makes `visitor` visit the entries of the `InnerClasses` attribute among `attributes`.
+pub(crate) fn accept_inner_classes(
+    cp: &[CpInfo],
+    attributes: &[AttributeInfo],
+    visitor: &mut dyn ClassVisitor,
+) -> Result<(), ClassReadError> {
+    for attr in attributes {
+        if let AttributeInfo::InnerClasses { classes } = attr {
+            for entry in classes {
+                let outer_name = match entry.outer_class_info_index {
+                    0 => None,
+                    index => Some(cp_class_name(cp, index)?),
+                };
+                let inner_name = match entry.inner_name_index {
+                    0 => None,
+                    index => Some(cp_utf8(cp, index)?),
+                };
+                visitor.visit_inner_class(
+                    cp_class_name(cp, entry.inner_class_info_index)?,
+                    outer_name,
+                    inner_name,
+                    entry.inner_class_access_flags,
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the name and content of an attribute reported by `visit_attribute`: a non-standard
+/// attribute, or a standard one without a dedicated event that does not refer to the constant
+/// pool.
+pub(crate) fn raw_attribute(attr: &AttributeInfo) -> Option<(&str, &[u8])> {
+    match attr {
+        AttributeInfo::SourceDebugExtension { debug_extension } => {
+            Some(("SourceDebugExtension", debug_extension))
+        }
+        AttributeInfo::Synthetic => Some(("Synthetic", &[])),
+        AttributeInfo::Deprecated => Some(("Deprecated", &[])),
+        AttributeInfo::Unknown { name, info } => Some((name, info)),
+        _ => None,
+    }
+}
+
+/// Makes `mv` visit the annotable parameter count and the annotations of `parameters`, the
+/// decoded `Runtime(In)VisibleParameterAnnotations` attribute, if it is present.
+pub(crate) fn accept_parameter_annotations(
+    parameters: &[Vec<AnnotationNode>],
+    visible: bool,
+    mv: &mut dyn MethodVisitor,
+) {
+    if parameters.is_empty() {
+        return;
+    }
+    mv.visit_annotable_parameter_count(parameters.len(), visible);
+    for (parameter, annotations) in parameters.iter().enumerate() {
+        for annotation in annotations {
+            mv.visit_parameter_annotation(parameter, annotation, visible);
+        }
+    }
+}
+
+/// Makes `mv` visit the entries of the `MethodParameters` attribute among `attributes`.
+pub(crate) fn accept_method_parameters(
+    cp: &[CpInfo],
+    attributes: &[AttributeInfo],
+    mv: &mut dyn MethodVisitor,
+) -> Result<(), ClassReadError> {
+    for attr in attributes {
+        if let AttributeInfo::MethodParameters { parameters } = attr {
+            for parameter in parameters {
+                let name = match parameter.name_index {
+                    0 => None,
+                    index => Some(cp_utf8(cp, index)?),
+                };
+                mv.visit_parameter(name, parameter.access_flags);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Makes `mv` visit the code of `method`, a method of `class_node`, with the events
+/// `accept_code` emits for a `Code` attribute, except for the stack map frames.
+pub(crate) fn accept_method_code(
+    class_node: &crate::nodes::ClassNode,
+    method: &crate::nodes::MethodNode,
+    mv: &mut dyn MethodVisitor,
+) -> Result<(), ClassReadError> {
+    let cp = &class_node.constant_pool;
+    let bootstrap_methods = class_node
+        .attributes
+        .iter()
+        .find_map(|attr| match attr {
+            AttributeInfo::BootstrapMethods { methods } => Some(methods.as_slice()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let label = |node: &LabelNode| Label { id: node.id };
+
+    for block in &method.try_catch_blocks {
+        mv.visit_try_catch_block(
+            label(&block.start),
+            label(&block.end),
+            label(&block.handler),
+            block.catch_type.as_deref(),
+        );
+    }
+    for annotation in &method.visible_try_catch_annotations {
+        mv.visit_try_catch_annotation(annotation, true);
+    }
+    for annotation in &method.invisible_try_catch_annotations {
+        mv.visit_try_catch_annotation(annotation, false);
+    }
+
+    let no_labels = HashMap::new();
+    for (id, node) in method.insn_nodes.iter() {
+        match node {
+            AbstractInsnNode::Label(node) => mv.visit_label(label(node)),
+            AbstractInsnNode::LineNumber(node) => {
+                mv.visit_line_number(node.line, label(&node.start));
+            }
+            AbstractInsnNode::Frame(frame) => mv.visit_frame(frame),
+            node => {
+                match node {
+                    AbstractInsnNode::JumpLabel(node) => {
+                        mv.visit_jump_insn(node.insn.opcode, label(&node.target));
+                    }
+                    AbstractInsnNode::TableSwitchLabel(node) => {
+                        let labels: Vec<Label> = node.labels.iter().map(label).collect();
+                        mv.visit_table_switch(node.low, node.high, label(&node.default), &labels);
+                    }
+                    AbstractInsnNode::LookupSwitchLabel(node) => {
+                        let labels: Vec<Label> = node.labels.iter().map(label).collect();
+                        mv.visit_lookup_switch(label(&node.default), &node.keys, &labels);
+                    }
+                    AbstractInsnNode::Insn(insn) => {
+                        visit_instruction(cp, 0, insn.clone(), &no_labels, bootstrap_methods, mv)?;
+                    }
+                    AbstractInsnNode::Label(_)
+                    | AbstractInsnNode::LineNumber(_)
+                    | AbstractInsnNode::Frame(_) => {}
                 }
-            }
-            Insn::LookupSwitch(node) => {
-                offsets.insert((instruction.offset as i32 + node.default_offset) as u16);
-                for (_, offset) in &node.pairs {
-                    offsets.insert((instruction.offset as i32 + *offset) as u16);
+                for annotation in &method.visible_insn_annotations {
+                    if annotation.insn == id {
+                        mv.visit_insn_annotation(&annotation.type_annotation, true);
+                    }
+                }
+                for annotation in &method.invisible_insn_annotations {
+                    if annotation.insn == id {
+                        mv.visit_insn_annotation(&annotation.type_annotation, false);
+                    }
                 }
             }
-            _ => {}
         }
     }
-    for entry in exception_table {
-        offsets.insert(entry.start_pc);
-        offsets.insert(entry.end_pc);
-        offsets.insert(entry.handler_pc);
-    }
-    offsets.insert(code.len() as u16);
 
-    let mut label_by_offset = std::collections::HashMap::new();
-    for (next_id, offset) in offsets.into_iter().enumerate() {
-        label_by_offset.insert(offset, LabelNode { id: next_id });
+    for variable in &method.local_variables {
+        mv.visit_local_variable(
+            &variable.name,
+            &variable.descriptor,
+            variable.signature.as_deref(),
+            label(&variable.start),
+            label(&variable.end),
+            variable.index,
+        );
     }
-
-    let mut nodes = Vec::new();
-    for instruction in instructions {
-        if let Some(label) = label_by_offset.get(&{ instruction.offset }) {
-            nodes.push(AbstractInsnNode::Label(*label));
+    for (annotations, visible) in [
+        (&method.visible_local_variable_annotations, true),
+        (&method.invisible_local_variable_annotations, false),
+    ] {
+        for annotation in annotations {
+            let start: Vec<Label> = annotation.start.iter().map(label).collect();
+            let end: Vec<Label> = annotation.end.iter().map(label).collect();
+            mv.visit_local_variable_annotation(
+                &annotation.type_annotation,
+                &start,
+                &end,
+                &annotation.index,
+                visible,
+            );
         }
-        nodes.push(AbstractInsnNode::Insn(instruction.insn));
-    }
-    if let Some(label) = label_by_offset.get(&(code.len() as u16)) {
-        nodes.push(AbstractInsnNode::Label(*label));
-    }
-
-    let mut try_catch_blocks = Vec::new();
-    for entry in exception_table {
-        let start = *label_by_offset
-            .get(&entry.start_pc)
-            .ok_or_else(|| ClassReadError::InvalidAttribute("missing start label".to_string()))?;
-        let end = *label_by_offset
-            .get(&entry.end_pc)
-            .ok_or_else(|| ClassReadError::InvalidAttribute("missing end label".to_string()))?;
-        let handler = *label_by_offset
-            .get(&entry.handler_pc)
-            .ok_or_else(|| ClassReadError::InvalidAttribute("missing handler label".to_string()))?;
-        let catch_type = if entry.catch_type == 0 {
-            None
-        } else {
-            Some(cp_class_name(cp, entry.catch_type)?.to_string())
-        };
-        try_catch_blocks.push(TryCatchBlockNode {
-            start,
-            end,
-            handler,
-            catch_type,
-        });
     }
-
-    Ok((nodes, try_catch_blocks))
+    Ok(())
 }
 
-pub(crate) fn build_insn_nodes_public(
-    code: &[u8],
-    exception_table: &[ExceptionTableEntry],
+fn frame_event(
     cp: &[CpInfo],
-) -> Result<(Vec<AbstractInsnNode>, Vec<TryCatchBlockNode>), ClassReadError> {
-    build_insn_nodes(code, exception_table, cp)
-}
-
-fn read_table_switch(
-    reader: &mut ByteReader<'_>,
-    opcode_offset: usize,
-) -> Result<Insn, ClassReadError> {
-    reader.align4(opcode_offset)?;
-    let default_offset = reader.read_i4()?;
-    let low = reader.read_i4()?;
-    let high = reader.read_i4()?;
-    let count = if high < low {
-        0
-    } else {
-        (high - low + 1) as usize
+    labels: &HashMap<u16, Label>,
+    frame: &StackMapFrame,
+) -> Result<Frame, ClassReadError> {
+    let values = |infos: &[VerificationTypeInfo]| -> Result<Vec<FrameValue>, ClassReadError> {
+        infos
+            .iter()
+            .map(|info| frame_value(cp, labels, info))
+            .collect()
     };
-    let mut offsets = Vec::with_capacity(count);
-    for _ in 0..count {
-        offsets.push(reader.read_i4()?);
-    }
-    Ok(Insn::TableSwitch(TableSwitchInsnNode {
-        insn: opcodes::TABLESWITCH.into(),
-        default_offset,
-        low,
-        high,
-        offsets,
-    }))
-}
-
-fn read_lookup_switch(
-    reader: &mut ByteReader<'_>,
-    opcode_offset: usize,
-) -> Result<Insn, ClassReadError> {
-    reader.align4(opcode_offset)?;
-    let default_offset = reader.read_i4()?;
-    let npairs = reader.read_i4()? as usize;
-    let mut pairs = Vec::with_capacity(npairs);
-    for _ in 0..npairs {
-        let key = reader.read_i4()?;
-        let offset = reader.read_i4()?;
-        pairs.push((key, offset));
-    }
-    Ok(Insn::LookupSwitch(LookupSwitchInsnNode {
-        insn: opcodes::LOOKUPSWITCH.into(),
-        default_offset,
-        pairs,
-    }))
+    Ok(match frame {
+        StackMapFrame::SameFrame { .. } | StackMapFrame::SameFrameExtended { .. } => Frame::Same,
+        StackMapFrame::SameLocals1StackItemFrame { stack, .. }
+        | StackMapFrame::SameLocals1StackItemFrameExtended { stack, .. } => Frame::Same1 {
+            stack: frame_value(cp, labels, stack)?,
+        },
+        StackMapFrame::ChopFrame { k, .. } => Frame::Chop { count: *k },
+        StackMapFrame::AppendFrame { locals, .. } => Frame::Append {
+            locals: values(locals)?,
+        },
+        StackMapFrame::FullFrame { locals, stack, .. } => Frame::Full {
+            locals: values(locals)?,
+            stack: values(stack)?,
+        },
+    })
 }
 
-fn read_wide(reader: &mut ByteReader<'_>) -> Result<Insn, ClassReadError> {
-    let opcode = reader.read_u1()?;
-    match opcode {
-        opcodes::ILOAD..=opcodes::ALOAD | opcodes::ISTORE..=opcodes::ASTORE | opcodes::RET => {
-            Ok(Insn::Var(VarInsnNode {
-                insn: opcode.into(),
-                var_index: reader.read_u2()?,
-            }))
+fn frame_value(
+    cp: &[CpInfo],
+    labels: &HashMap<u16, Label>,
+    info: &VerificationTypeInfo,
+) -> Result<FrameValue, ClassReadError> {
+    Ok(match info {
+        VerificationTypeInfo::Top => FrameValue::Top,
+        VerificationTypeInfo::Integer => FrameValue::Integer,
+        VerificationTypeInfo::Float => FrameValue::Float,
+        VerificationTypeInfo::Long => FrameValue::Long,
+        VerificationTypeInfo::Double => FrameValue::Double,
+        VerificationTypeInfo::Null => FrameValue::Null,
+        VerificationTypeInfo::UninitializedThis => FrameValue::UninitializedThis,
+        VerificationTypeInfo::Object { cpool_index } => {
+            FrameValue::Object(cp_class_name(cp, *cpool_index)?.to_string())
         }
-        opcodes::IINC => Ok(Insn::Iinc(IincInsnNode {
-            insn: opcode.into(),
-            var_index: reader.read_u2()?,
-            increment: reader.read_i2()?,
-        })),
-        _ => Err(ClassReadError::InvalidOpcode {
-            opcode,
-            offset: reader.pos().saturating_sub(1),
-        }),
-    }
+        VerificationTypeInfo::Uninitialized { offset } => {
+            let label = labels.get(offset).copied().ok_or_else(|| {
+                ClassReadError::InvalidAttribute(format!("no label at code offset {offset}"))
+            })?;
+            FrameValue::Uninitialized(label)
+        }
+    })
 }
 
 fn visit_instruction(
     cp: &[CpInfo],
     offset: i32,
     insn: Insn,
+    labels: &HashMap<u16, Label>,
+    bootstrap_methods: &[BootstrapMethod],
     mv: &mut dyn MethodVisitor,
 ) -> Result<(), ClassReadError> {
+    let label_at = |target: i32| {
+        labels.get(&(target as u16)).copied().ok_or_else(|| {
+            ClassReadError::InvalidAttribute(format!("no label at code offset {target}"))
+        })
+    };
     match insn {
         Insn::Simple(node) => {
             mv.visit_insn(node.opcode);
@@ -1593,37 +4426,51 @@ fn visit_instruction(
             let type_name = cp_class_name(cp, node.type_index)?;
             mv.visit_type_insn(node.insn.opcode, type_name);
         }
-        Insn::Field(node) => {
-            let index = match node.field_ref {
-                MemberRef::Index(index) => index,
-                MemberRef::Symbolic { .. } => {
-                    return Err(ClassReadError::InvalidIndex(0));
-                }
-            };
-            let (owner, name, desc) = cp_field_ref(cp, index)?;
-            mv.visit_field_insn(node.insn.opcode, owner, name, desc);
-        }
-        Insn::Method(node) => {
-            let index = match node.method_ref {
-                MemberRef::Index(index) => index,
-                MemberRef::Symbolic { .. } => {
-                    return Err(ClassReadError::InvalidIndex(0));
-                }
-            };
-            let (owner, name, desc, is_interface) = cp_method_ref(cp, index)?;
-            mv.visit_method_insn(node.insn.opcode, owner, name, desc, is_interface);
-        }
+        Insn::Field(node) => match &node.field_ref {
+            MemberRef::Index(index) => {
+                let (owner, name, desc) = cp_field_ref(cp, *index)?;
+                mv.visit_field_insn(node.insn.opcode, owner, name, desc);
+            }
+            MemberRef::Symbolic {
+                owner,
+                name,
+                descriptor,
+                ..
+            } => mv.visit_field_insn(node.insn.opcode, owner, name, descriptor),
+        },
+        Insn::Method(node) => match &node.method_ref {
+            MemberRef::Index(index) => {
+                let (owner, name, desc, is_interface) = cp_method_ref(cp, *index)?;
+                mv.visit_method_insn(node.insn.opcode, owner, name, desc, is_interface);
+            }
+            MemberRef::Symbolic {
+                owner,
+                name,
+                descriptor,
+                is_interface,
+            } => mv.visit_method_insn(node.insn.opcode, owner, name, descriptor, *is_interface),
+        },
         Insn::InvokeInterface(node) => {
             let (owner, name, desc, _is_interface) = cp_method_ref(cp, node.method_index)?;
             mv.visit_method_insn(node.insn.opcode, owner, name, desc, true);
         }
+        Insn::InvokeDynamic(InvokeDynamicInsnNode {
+            method_index: 0,
+            name: Some(name),
+            descriptor: Some(desc),
+            bootstrap_method: Some(handle),
+            bootstrap_args,
+            ..
+        }) => {
+            mv.visit_invoke_dynamic_insn(&name, &desc, &handle, &bootstrap_args);
+        }
         Insn::InvokeDynamic(node) => {
-            let (name, desc) = cp_invoke_dynamic(cp, node.method_index)?;
-            mv.visit_invoke_dynamic_insn(name, desc);
+            let (bsm_index, name, desc) = cp_invoke_dynamic(cp, node.method_index)?;
+            let (handle, arguments) = bootstrap_method(cp, bootstrap_methods, bsm_index)?;
+            mv.visit_invoke_dynamic_insn(name, desc, &handle, &arguments);
         }
         Insn::Jump(node) => {
-            let target = offset + node.offset;
-            mv.visit_jump_insn(node.insn.opcode, target);
+            mv.visit_jump_insn(node.insn.opcode, label_at(offset + node.offset)?);
         }
         Insn::Ldc(node) => {
             let index = match node.value {
@@ -1633,15 +4480,13 @@ fn visit_instruction(
                     return Ok(());
                 }
                 LdcValue::Type(value) => {
-                    match value.clone() {
-                        Type::Method { .. } => {
-                            mv.visit_ldc_insn(LdcConstant::MethodType(value.get_descriptor()));
-                        }
-                        _ => {
-                            mv.visit_ldc_insn(LdcConstant::Class(value.get_descriptor()));
-                        }
-                    }
-                  
+                    let constant = match &value {
+                        Type::Method { .. } => LdcConstant::MethodType(value.get_descriptor()),
+                        _ => LdcConstant::Class(
+                            value.internal_name().unwrap_or_else(|| value.get_descriptor()),
+                        ),
+                    };
+                    mv.visit_ldc_insn(constant);
                     return Ok(());
                 }
                 LdcValue::Int(value) => {
@@ -1660,28 +4505,41 @@ fn visit_instruction(
                     mv.visit_ldc_insn(LdcConstant::Double(value));
                     return Ok(());
                 }
+                LdcValue::Handle(value) => {
+                    mv.visit_ldc_insn(LdcConstant::MethodHandle(value));
+                    return Ok(());
+                }
+                LdcValue::ConstantDynamic(value) => {
+                    mv.visit_ldc_insn(LdcConstant::Dynamic(value));
+                    return Ok(());
+                }
             };
-            let constant = cp_ldc_constant(cp, index)?;
+            let constant = cp_ldc_constant(cp, bootstrap_methods, index)?;
             mv.visit_ldc_insn(constant);
         }
         Insn::Iinc(node) => {
             mv.visit_iinc_insn(node.var_index, node.increment);
         }
         Insn::TableSwitch(node) => {
-            let targets = node
-                .offsets
-                .iter()
-                .map(|value| offset + *value)
-                .collect::<Vec<_>>();
-            mv.visit_table_switch(offset + node.default_offset, node.low, node.high, &targets);
+            let mut targets = Vec::with_capacity(node.offsets.len());
+            for value in &node.offsets {
+                targets.push(label_at(offset + *value)?);
+            }
+            mv.visit_table_switch(
+                node.low,
+                node.high,
+                label_at(offset + node.default_offset)?,
+                &targets,
+            );
         }
         Insn::LookupSwitch(node) => {
-            let pairs = node
-                .pairs
-                .iter()
-                .map(|(key, value)| (*key, offset + *value))
-                .collect::<Vec<_>>();
-            mv.visit_lookup_switch(offset + node.default_offset, &pairs);
+            let mut keys = Vec::with_capacity(node.pairs.len());
+            let mut targets = Vec::with_capacity(node.pairs.len());
+            for (key, value) in &node.pairs {
+                keys.push(*key);
+                targets.push(label_at(offset + *value)?);
+            }
+            mv.visit_lookup_switch(label_at(offset + node.default_offset)?, &keys, &targets);
         }
         Insn::MultiANewArray(node) => {
             let type_name = cp_class_name(cp, node.type_index)?;
@@ -1694,11 +4552,17 @@ fn visit_instruction(
 pub struct ByteReader<'a> {
     data: &'a [u8],
     pos: usize,
+    base: usize,
 }
 
 impl<'a> ByteReader<'a> {
     pub fn new(data: &'a [u8]) -> Self {
-        Self { data, pos: 0 }
+        Self::with_base(data, 0)
+    }
+
+    /// Creates a reader over `data`, which starts at byte offset `base` of the class file.
+    pub fn with_base(data: &'a [u8], base: usize) -> Self {
+        Self { data, pos: 0, base }
     }
 
     pub fn remaining(&self) -> usize {
@@ -1709,6 +4573,11 @@ impl<'a> ByteReader<'a> {
         self.pos
     }
 
+    /// Returns the current position as a byte offset in the class file.
+    pub fn offset(&self) -> usize {
+        self.base + self.pos
+    }
+
     pub fn align4(&mut self, opcode_offset: usize) -> Result<(), ClassReadError> {
         let mut padding = (4 - ((opcode_offset + 1) % 4)) % 4;
         while padding > 0 {
@@ -1757,6 +4626,14 @@ impl<'a> ByteReader<'a> {
         ]))
     }
 
+    pub fn skip(&mut self, len: usize) -> Result<(), ClassReadError> {
+        if self.pos + len > self.data.len() {
+            return Err(ClassReadError::UnexpectedEof);
+        }
+        self.pos += len;
+        Ok(())
+    }
+
     pub fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, ClassReadError> {
         if self.pos + len > self.data.len() {
             return Err(ClassReadError::UnexpectedEof);
@@ -1795,8 +4672,9 @@ mod tests {
             _minor: u16,
             _access_flags: u16,
             name: &str,
+            _signature: Option<&str>,
             _super_name: Option<&str>,
-            _interfaces: &[String],
+            _interfaces: &[&str],
         ) {
             *self.visited_name.borrow_mut() = Some(name.to_string());
         }
@@ -1806,6 +4684,8 @@ mod tests {
             _access_flags: u16,
             name: &str,
             _descriptor: &str,
+            _signature: Option<&str>,
+            _exceptions: &[&str],
         ) -> Option<Box<dyn MethodVisitor>> {
             self.visited_methods.borrow_mut().push(name.to_string());
             None
@@ -1906,4 +4786,471 @@ mod tests {
         assert!(reader.align4(0).is_ok());
         assert_eq!(reader.pos(), 4);
     }
+
+    fn generate_branching_class() -> Vec<u8> {
+        use crate::class_writer::{COMPUTE_FRAMES, ClassWriter};
+
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES);
+        cw.visit(52, 0, 0x0021, "Branching", None, Some("java/lang/Object"), &[]);
+        cw.visit_source_file("Branching.java");
+        let start = Label::new();
+        let positive = Label::new();
+        let mut mv = cw.visit_method(0x0001, "abs", "(JLjava/lang/String;)J", None, &[]);
+        mv.visit_code();
+        mv.visit_label(start);
+        mv.visit_line_number(7, LabelNode::from_label(start));
+        mv.visit_var_insn(opcodes::LLOAD, 1);
+        mv.visit_insn(opcodes::LCONST_0);
+        mv.visit_insn(opcodes::LCMP);
+        mv.visit_jump_insn(opcodes::IFGE, positive);
+        mv.visit_var_insn(opcodes::LLOAD, 1);
+        mv.visit_insn(opcodes::LNEG);
+        mv.visit_insn(opcodes::LRETURN);
+        mv.visit_label(positive);
+        mv.visit_var_insn(opcodes::LLOAD, 1);
+        mv.visit_insn(opcodes::LRETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        cw.to_bytes().expect("Should write branching class")
+    }
+
+    #[derive(Default)]
+    struct RecordingMethodVisitor {
+        events: Rc<RefCell<Vec<String>>>,
+        labels: Rc<RefCell<HashMap<Label, usize>>>,
+    }
+
+    impl RecordingMethodVisitor {
+        fn label_name(&self, label: Label) -> String {
+            let mut labels = self.labels.borrow_mut();
+            let next = labels.len();
+            format!("L{}", labels.entry(label).or_insert(next))
+        }
+    }
+
+    impl MethodVisitor for RecordingMethodVisitor {
+        fn visit_frame(&mut self, frame: &Frame) {
+            let kind = match frame {
+                Frame::Same => "same",
+                Frame::Same1 { .. } => "same1",
+                Frame::Chop { .. } => "chop",
+                Frame::Append { .. } => "append",
+                Frame::Full { .. } => "full",
+            };
+            self.events.borrow_mut().push(format!("frame {kind}"));
+        }
+
+        fn visit_label(&mut self, label: Label) {
+            let name = self.label_name(label);
+            self.events.borrow_mut().push(name);
+        }
+
+        fn visit_line_number(&mut self, line: u16, start: Label) {
+            let name = self.label_name(start);
+            self.events.borrow_mut().push(format!("line {line} {name}"));
+        }
+
+        fn visit_insn(&mut self, opcode: u8) {
+            self.events.borrow_mut().push(format!("insn {opcode}"));
+        }
+
+        fn visit_var_insn(&mut self, opcode: u8, var_index: u16) {
+            self.events
+                .borrow_mut()
+                .push(format!("var {opcode} {var_index}"));
+        }
+
+        fn visit_jump_insn(&mut self, opcode: u8, target: Label) {
+            let name = self.label_name(target);
+            self.events.borrow_mut().push(format!("jump {opcode} {name}"));
+        }
+
+        fn visit_invoke_dynamic_insn(
+            &mut self,
+            name: &str,
+            desc: &str,
+            bootstrap_method: &Handle,
+            bootstrap_args: &[BootstrapArgument],
+        ) {
+            self.events.borrow_mut().push(format!(
+                "indy {name} {desc} {} {}.{} {:?}",
+                bootstrap_method.reference_kind,
+                bootstrap_method.owner,
+                bootstrap_method.name,
+                bootstrap_args
+            ));
+        }
+    }
+
+    struct RecordingClassVisitor {
+        events: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl ClassVisitor for RecordingClassVisitor {
+        fn visit_method(
+            &mut self,
+            _access_flags: u16,
+            _name: &str,
+            _descriptor: &str,
+            _signature: Option<&str>,
+            _exceptions: &[&str],
+        ) -> Option<Box<dyn MethodVisitor>> {
+            Some(Box::new(RecordingMethodVisitor {
+                events: self.events.clone(),
+                ..Default::default()
+            }))
+        }
+    }
+
+    #[test]
+    fn test_label_events() {
+        let bytes = generate_branching_class();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut visitor = RecordingClassVisitor {
+            events: events.clone(),
+        };
+        ClassReader::new(&bytes)
+            .accept(&mut visitor, SKIP_FRAMES)
+            .expect("Should visit class");
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "L0",
+                "line 7 L0",
+                "var 22 1",
+                "insn 9",
+                "insn 148",
+                "jump 156 L1",
+                "var 22 1",
+                "insn 117",
+                "insn 173",
+                "L1",
+                "var 22 1",
+                "insn 173",
+            ]
+        );
+
+        events.borrow_mut().clear();
+        ClassReader::new(&bytes)
+            .accept(&mut visitor, 0)
+            .expect("Should visit class with frames");
+        assert!(events.borrow().iter().any(|event| event.starts_with("frame")));
+    }
+
+    #[test]
+    fn test_class_node_accept() {
+        let bytes = generate_branching_class();
+        let reader = ClassReader::new(&bytes);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut visitor = RecordingClassVisitor {
+            events: events.clone(),
+        };
+        reader.accept(&mut visitor, 0).expect("Should visit class");
+        let expected = events.take();
+        assert!(expected.iter().any(|event| event.starts_with("frame")));
+
+        let node = reader.to_class_node().expect("Should build class node");
+        node.accept(&mut visitor).expect("Should replay class node");
+        assert_eq!(events.take(), expected);
+    }
+
+    #[test]
+    fn test_invoke_dynamic_event() {
+        use crate::class_writer::ClassWriter;
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(55, 0, 0x0021, "Concat", None, Some("java/lang/Object"), &[]);
+        let bootstrap_method = Handle {
+            reference_kind: constants::REF_INVOKE_STATIC,
+            owner: "java/lang/invoke/StringConcatFactory".to_string(),
+            name: "makeConcatWithConstants".to_string(),
+            descriptor: "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;\
+                Ljava/lang/invoke/MethodType;Ljava/lang/String;[Ljava/lang/Object;)\
+                Ljava/lang/invoke/CallSite;"
+                .to_string(),
+            is_interface: false,
+        };
+        let mut mv = cw.visit_method(
+            0x0009,
+            "greet",
+            "(Ljava/lang/String;)Ljava/lang/String;",
+            None,
+            &[],
+        );
+        mv.visit_code();
+        mv.visit_var_insn(opcodes::ALOAD, 0);
+        mv.visit_invokedynamic_insn(
+            "makeConcatWithConstants",
+            "(Ljava/lang/String;)Ljava/lang/String;",
+            bootstrap_method,
+            &[BootstrapArgument::String("Hello \u{1}!".to_string())],
+        );
+        mv.visit_insn(opcodes::ARETURN);
+        mv.visit_maxs(1, 1);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut visitor = RecordingClassVisitor {
+            events: events.clone(),
+        };
+        ClassReader::new(&bytes)
+            .accept(&mut visitor, 0)
+            .expect("Should visit class");
+        assert_eq!(
+            events.borrow()[1],
+            "indy makeConcatWithConstants (Ljava/lang/String;)Ljava/lang/String; 6 \
+             java/lang/invoke/StringConcatFactory.makeConcatWithConstants \
+             [String(\"Hello \\u{1}!\")]"
+        );
+    }
+
+    #[test]
+    fn test_error_location() {
+        use crate::class_writer::ClassWriter;
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0021, "Broken", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "run", "()V", None, &[]);
+        mv.visit_code();
+        mv.visit_insn(opcodes::NOP);
+        mv.visit_field_insn(opcodes::GETSTATIC, "java/lang/System", "out", "Ljava/io/PrintStream;");
+        mv.visit_insn(opcodes::POP);
+        mv.visit_insn(opcodes::RETURN);
+        mv.visit_maxs(1, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+        let code = bytes
+            .windows(6)
+            .position(|window| {
+                window[..2] == [opcodes::NOP, opcodes::GETSTATIC]
+                    && window[4..] == [opcodes::POP, opcodes::RETURN]
+            })
+            .expect("Should find code");
+
+        let mut bad_index = bytes.clone();
+        bad_index[code + 2..code + 4].copy_from_slice(&[0xFF, 0xFF]);
+        let mut visitor = RecordingClassVisitor {
+            events: Rc::new(RefCell::new(Vec::new())),
+        };
+        let error = ClassReader::new(&bad_index)
+            .accept(&mut visitor, 0)
+            .expect_err("Should reject invalid field reference");
+        assert!(matches!(error.root(), ClassReadError::InvalidIndex(0xFFFF)));
+        let location = error.location().expect("Should locate error");
+        assert_eq!(location.class_name.as_deref(), Some("Broken"));
+        assert_eq!(location.member_name.as_deref(), Some("run"));
+        assert_eq!(location.member_descriptor.as_deref(), Some("()V"));
+        assert_eq!(location.code_offset, Some(1));
+
+        let mut bad_opcode = bytes.clone();
+        bad_opcode[code + 4] = 0xCB;
+        let error = ClassReader::new(&bad_opcode)
+            .to_class_node()
+            .expect_err("Should reject invalid opcode");
+        assert!(matches!(
+            error.root(),
+            ClassReadError::InvalidOpcode { opcode: 0xCB, .. }
+        ));
+        let location = error.location().expect("Should locate error");
+        assert_eq!(location.class_name.as_deref(), Some("Broken"));
+        assert_eq!(location.member_name.as_deref(), Some("run"));
+        assert_eq!(location.attribute.as_deref(), Some("Code"));
+        assert_eq!(location.code_offset, Some(4));
+        // The Code attribute header precedes max_stack, max_locals and code_length.
+        assert_eq!(location.offset, Some(code - 14));
+        assert!(error.to_string().contains("class Broken, member run()V, attribute Code"));
+    }
+
+    #[test]
+    fn test_invalid_descriptor_location() {
+        use crate::error::DescriptorError;
+
+        let mut bytes = generate_branching_class();
+        let descriptor = b"(JLjava/lang/String;)J";
+        let position = bytes
+            .windows(descriptor.len())
+            .position(|window| window == descriptor)
+            .expect("Should find method descriptor");
+        bytes[position + descriptor.len() - 1] = b'Q';
+        let error = ClassReader::new(&bytes)
+            .to_class_node_with_options(EXPAND_FRAMES)
+            .expect_err("Should reject malformed descriptor");
+        assert!(matches!(
+            error.root(),
+            ClassReadError::InvalidDescriptor(DescriptorError::InvalidCharacter {
+                position: 21,
+                character: 'Q',
+                ..
+            })
+        ));
+        let location = error.location().expect("Should locate error");
+        assert_eq!(location.member_name.as_deref(), Some("abs"));
+    }
+
+    #[test]
+    fn test_annotation_nesting_limit() {
+        use crate::class_writer::ClassWriter;
+
+        let nested = |depth: usize| {
+            let mut value = AnnotationValue::Int(1);
+            for _ in 1..depth {
+                value = AnnotationValue::Array(vec![value]);
+            }
+            let mut cw = ClassWriter::new(0);
+            cw.visit(52, 0, 0x0021, "Nested", None, Some("java/lang/Object"), &[]);
+            cw.visit_annotation(&AnnotationNode::new("LDeep;").with_value("value", value), true);
+            cw.to_bytes().expect("Should write nested annotation")
+        };
+
+        let bytes = nested(MAX_ELEMENT_VALUE_DEPTH);
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read annotation at the nesting limit");
+        assert_eq!(node.visible_annotations.len(), 1);
+
+        let bytes = nested(MAX_ELEMENT_VALUE_DEPTH + 1);
+        let error = ClassReader::new(&bytes)
+            .accept(&mut MockClassVisitor::new(), 0)
+            .expect_err("Should reject annotation nested past the limit");
+        assert!(matches!(error.root(), ClassReadError::InvalidAttribute(_)));
+        assert_eq!(
+            error.location().and_then(|location| location.attribute.as_deref()),
+            Some("RuntimeVisibleAnnotations")
+        );
+    }
+
+    fn stack_map(node: &crate::nodes::ClassNode) -> Option<&Vec<StackMapFrame>> {
+        node.methods[0]
+            .code_attributes
+            .iter()
+            .find_map(|attr| match attr {
+                AttributeInfo::StackMapTable { entries } => Some(entries),
+                _ => None,
+            })
+    }
+
+    fn with_version(mut bytes: Vec<u8>, major: u16, minor: u16) -> Vec<u8> {
+        bytes[4..6].copy_from_slice(&minor.to_be_bytes());
+        bytes[6..8].copy_from_slice(&major.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_version_policy() {
+        let future = with_version(generate_minimal_class(), constants::LATEST_VERSION + 1, 0);
+        assert!(matches!(
+            ClassReader::new(&future).to_class_node(),
+            Err(ClassReadError::InvalidClassVersion(version))
+                if version == constants::LATEST_VERSION + 1
+        ));
+        let node = ClassReader::new(&future)
+            .with_version_policy(VersionPolicy::UpTo(constants::LATEST_VERSION + 1))
+            .to_class_node()
+            .expect("Should accept the configured maximum version");
+        assert_eq!(node.major_version, constants::LATEST_VERSION + 1);
+        assert!(
+            ClassReader::new(&future)
+                .with_version_policy(VersionPolicy::Any)
+                .to_class_node()
+                .is_ok()
+        );
+        assert!(
+            ClassReader::new(&generate_minimal_class())
+                .with_version_policy(VersionPolicy::UpTo(constants::V1_7))
+                .to_class_node()
+                .is_err()
+        );
+
+        let preview = with_version(
+            generate_minimal_class(),
+            constants::V21,
+            constants::PREVIEW_MINOR_VERSION,
+        );
+        let node = ClassReader::new(&preview)
+            .to_class_node()
+            .expect("Should read preview class");
+        assert!(node.uses_preview_features());
+        let node = ClassReader::new(&generate_minimal_class())
+            .to_class_node()
+            .expect("Should read class");
+        assert!(!node.uses_preview_features());
+        assert!(!constants::is_preview_version(
+            constants::V1_8,
+            constants::PREVIEW_MINOR_VERSION
+        ));
+    }
+
+    #[test]
+    fn test_read_options() {
+        let bytes = generate_branching_class();
+        let reader = ClassReader::new(&bytes);
+
+        let node = reader.to_class_node().expect("Should read class");
+        assert_eq!(node.source_file.as_deref(), Some("Branching.java"));
+        assert!(node.methods[0].has_code);
+        assert!(stack_map(&node).is_none());
+        let frames = |node: &crate::nodes::ClassNode| {
+            node.methods[0]
+                .insn_nodes
+                .nodes()
+                .filter(|node| matches!(node, AbstractInsnNode::Frame(_)))
+                .count()
+        };
+        assert!(frames(&node) > 0);
+
+        let node = reader
+            .to_class_node_with_options(SKIP_CODE)
+            .expect("Should read class without code");
+        assert!(!node.methods[0].has_code);
+        assert!(node.methods[0].insn_nodes.is_empty());
+
+        let node = reader
+            .to_class_node_with_options(SKIP_DEBUG | SKIP_FRAMES)
+            .expect("Should read class without debug info and frames");
+        assert!(node.source_file.is_none());
+        assert!(node.methods[0].has_code);
+        assert_eq!(frames(&node), 0);
+
+        let mut node = reader
+            .to_class_node_with_options(SKIP_FRAMES)
+            .expect("Should read class without frames");
+        let code = &mut node.methods[0].insn_nodes;
+        let last = code.last().expect("Code should not be empty");
+        let load = code.previous(last).expect("Code should have two instructions");
+        code.insert_before(load, Frame::Append { locals: vec![FrameValue::Integer] });
+        code.insert_before(last, Frame::Chop { count: 1 });
+        let bytes = crate::class_writer::ClassWriter::write_class_node(&node, 0)
+            .expect("Should write compressed frames");
+        let node = ClassReader::new(&bytes)
+            .to_class_node_with_options(EXPAND_FRAMES)
+            .expect("Should read class with expanded frames");
+        let frames: Vec<&Frame> = node.methods[0]
+            .insn_nodes
+            .nodes()
+            .filter_map(|node| match node {
+                AbstractInsnNode::Frame(frame) => Some(frame),
+                _ => None,
+            })
+            .collect();
+        let locals_len: Vec<usize> = frames
+            .iter()
+            .map(|frame| match frame {
+                Frame::Full { locals, .. } => locals.len(),
+                _ => panic!("Frame should be expanded: {:?}", frame),
+            })
+            .collect();
+        assert_eq!(locals_len, vec![4, 3]);
+        let Frame::Full { locals, .. } = frames[1] else {
+            unreachable!();
+        };
+        assert_eq!(
+            locals,
+            &[
+                FrameValue::Object("Branching".to_string()),
+                FrameValue::Long,
+                FrameValue::Object("java/lang/String".to_string()),
+            ]
+        );
+    }
 }
@@ -1,19 +1,32 @@
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
+use crate::class_hierarchy::{ClassHierarchy, ClassInfo};
 use crate::class_reader::{
-    AttributeInfo, BootstrapMethod, CodeAttribute, ExceptionTableEntry, InnerClass,
-    LineNumber, LocalVariable, MethodParameter, StackMapFrame, VerificationTypeInfo,
+    self, Annotation, AttributeInfo, BootstrapMethod, CodeAttribute, ConstantValue, ElementValue,
+    ElementValuePair, ExceptionTableEntry, Frame, FrameValue, InnerClass, LineNumber,
+    LocalVarTarget, LocalVariable, LocalVariableType, MethodParameter,
+    ModuleAttribute, ModuleExports, ModuleOpens, ModuleProvides, ModuleRequires,
+    RecordComponentInfo, StackMapFrame, TypeAnnotation, TypeAnnotationTarget, VerificationTypeInfo,
 };
 use crate::constant_pool::{ConstantPoolBuilder, CpInfo};
 use crate::constants;
-use crate::error::ClassWriteError;
+use crate::error::{ClassWriteError, DescriptorError};
 use crate::insn::{
-    AbstractInsnNode, BootstrapArgument, FieldInsnNode, Handle, Insn, InsnList, InsnNode,
-    InvokeInterfaceInsnNode, JumpInsnNode, JumpLabelInsnNode, Label, LabelNode, LdcInsnNode,
-    LdcValue, LineNumberInsnNode, MemberRef, MethodInsnNode, NodeList, TypeInsnNode, VarInsnNode,
+    AbstractInsnNode, BootstrapArgument, ConstantDynamic, FieldInsnNode, Handle, IincInsnNode,
+    Insn, InsnNode, IntInsnNode, InvokeInterfaceInsnNode, JumpInsnNode,
+    JumpLabelInsnNode, Label, LabelNode, LdcInsnNode, LdcValue, LineNumberInsnNode,
+    LocalVariableNode, LookupSwitchInsnNode, LookupSwitchLabelInsnNode, MemberRef,
+    MethodInsnNode, MultiANewArrayInsnNode, NodeId, NodeList, TableSwitchInsnNode,
+    TableSwitchLabelInsnNode, TryCatchBlockNode, TypeInsnNode, VarInsnNode,
+};
+use crate::nodes::{
+    AnnotationNode, AnnotationValue, ClassNode, FieldNode, InnerClassNode, InsnAnnotationNode,
+    LocalVariableAnnotationNode, MethodNode, ModuleExportNode, ModuleNode, ModuleOpenNode,
+    ModuleProvideNode, ModuleRequireNode, RecordComponentNode, TypeAnnotationNode,
 };
-use crate::nodes::{ClassNode, FieldNode, InnerClassNode, MethodNode};
 use crate::opcodes;
+use crate::type_reference::{TypePath, TypeReference};
 use crate::types::Type;
 
 /// Flag to automatically compute the stack map frames.
@@ -30,11 +43,26 @@ pub const COMPUTE_MAXS: u32 = 0x2;
 
 
 
+/// Members whose visitors were returned through the [`class_reader::ClassVisitor`] impl and have
+/// been visited to the end, waiting to be added to the `ClassWriter`.
+type VisitedMembers = Arc<Mutex<Vec<VisitedMember>>>;
+
+enum VisitedMember {
+    Field(FieldData),
+    Method(Box<MethodVisitor>),
+    Module(ModuleVisitor),
+    RecordComponent(RecordComponentVisitor),
+}
+
 struct FieldData {
     access_flags: u16,
     name: String,
     descriptor: String,
     attributes: Vec<AttributeInfo>,
+    visible_annotations: Vec<AnnotationNode>,
+    invisible_annotations: Vec<AnnotationNode>,
+    visible_type_annotations: Vec<TypeAnnotationNode>,
+    invisible_type_annotations: Vec<TypeAnnotationNode>,
 }
 
 struct MethodData {
@@ -44,10 +72,24 @@ struct MethodData {
     has_code: bool,
     max_stack: u16,
     max_locals: u16,
-    instructions: InsnList,
-    exception_table: Vec<ExceptionTableEntry>,
     code_attributes: Vec<AttributeInfo>,
+    insn_nodes: NodeList,
+    try_catch_blocks: Vec<TryCatchBlockNode>,
+    local_variables: Vec<LocalVariableNode>,
     attributes: Vec<AttributeInfo>,
+    visible_annotations: Vec<AnnotationNode>,
+    invisible_annotations: Vec<AnnotationNode>,
+    visible_parameter_annotations: Vec<Vec<AnnotationNode>>,
+    invisible_parameter_annotations: Vec<Vec<AnnotationNode>>,
+    annotation_default: Option<AnnotationValue>,
+    visible_type_annotations: Vec<TypeAnnotationNode>,
+    invisible_type_annotations: Vec<TypeAnnotationNode>,
+    visible_insn_annotations: Vec<InsnAnnotationNode>,
+    invisible_insn_annotations: Vec<InsnAnnotationNode>,
+    visible_try_catch_annotations: Vec<TypeAnnotationNode>,
+    invisible_try_catch_annotations: Vec<TypeAnnotationNode>,
+    visible_local_variable_annotations: Vec<LocalVariableAnnotationNode>,
+    invisible_local_variable_annotations: Vec<LocalVariableAnnotationNode>,
 }
 
 /// A writer that generates a Java Class File structure.
@@ -61,9 +103,9 @@ struct MethodData {
 /// use rust_asm::{class_writer::{ClassWriter, COMPUTE_FRAMES}, opcodes};
 ///
 /// let mut cw = ClassWriter::new(COMPUTE_FRAMES);
-/// cw.visit(52, 0, 1, "com/example/MyClass", Some("java/lang/Object"), &[]);
+/// cw.visit(52, 0, 1, "com/example/MyClass", None, Some("java/lang/Object"), &[]);
 ///
-/// let mut mv = cw.visit_method(1, "myMethod", "()V");
+/// let mut mv = cw.visit_method(1, "myMethod", "()V", None, &[]);
 /// mv.visit_code();
 /// mv.visit_insn(opcodes::RETURN);
 /// mv.visit_maxs(0, 0); // Computed automatically due to COMPUTE_FRAMES
@@ -82,7 +124,21 @@ pub struct ClassWriter {
     methods: Vec<MethodData>,
     attributes: Vec<AttributeInfo>,
     source_file: Option<String>,
+    visible_annotations: Vec<AnnotationNode>,
+    invisible_annotations: Vec<AnnotationNode>,
+    visible_type_annotations: Vec<TypeAnnotationNode>,
+    invisible_type_annotations: Vec<TypeAnnotationNode>,
+    module: Option<ModuleNode>,
+    record_components: Option<Vec<RecordComponentNode>>,
+    nest_host: Option<String>,
+    nest_members: Vec<String>,
+    permitted_subclasses: Vec<String>,
     cp: ConstantPoolBuilder,
+    class_hierarchy: Option<Arc<dyn ClassHierarchy>>,
+    strict_class_hierarchy: bool,
+    visited: VisitedMembers,
+    // The first invalid descriptor of a visited method, reported when the class is written.
+    invalid_descriptor: Option<DescriptorError>,
 }
 
 impl ClassWriter {
@@ -104,10 +160,39 @@ impl ClassWriter {
             methods: Vec::new(),
             attributes: Vec::new(),
             source_file: None,
+            visible_annotations: Vec::new(),
+            invisible_annotations: Vec::new(),
+            visible_type_annotations: Vec::new(),
+            invisible_type_annotations: Vec::new(),
+            module: None,
+            record_components: None,
+            nest_host: None,
+            nest_members: Vec::new(),
+            permitted_subclasses: Vec::new(),
             cp: ConstantPoolBuilder::new(),
+            class_hierarchy: None,
+            strict_class_hierarchy: false,
+            visited: VisitedMembers::default(),
+            invalid_descriptor: None,
         }
     }
 
+    /// Sets the class hierarchy that `COMPUTE_FRAMES` uses to merge class types.
+    ///
+    /// The class being written is always known. Without a hierarchy, or for classes it does
+    /// not know, only a few JDK classes are known.
+    pub fn with_class_hierarchy(mut self, hierarchy: Arc<dyn ClassHierarchy>) -> Self {
+        self.class_hierarchy = Some(hierarchy);
+        self
+    }
+
+    /// Makes `COMPUTE_FRAMES` fail with [`ClassWriteError::UnknownClass`] when it needs a class
+    /// that is not in the class hierarchy, instead of merging it into `java/lang/Object`.
+    pub fn with_strict_class_hierarchy(mut self, strict: bool) -> Self {
+        self.strict_class_hierarchy = strict;
+        self
+    }
+
     /// Creates a `ClassWriter` from an existing `ClassNode`.
     ///
     /// This preserves the constant pool indices from the node and allows
@@ -126,6 +211,15 @@ impl ClassWriter {
             methods,
             mut attributes,
             inner_classes,
+            visible_annotations,
+            invisible_annotations,
+            visible_type_annotations,
+            invisible_type_annotations,
+            module,
+            record_components,
+            nest_host,
+            nest_members,
+            permitted_subclasses,
             ..
         } = class_node;
 
@@ -142,6 +236,10 @@ impl ClassWriter {
                 name: field.name,
                 descriptor: field.descriptor,
                 attributes: field.attributes,
+                visible_annotations: field.visible_annotations,
+                invisible_annotations: field.invisible_annotations,
+                visible_type_annotations: field.visible_type_annotations,
+                invisible_type_annotations: field.invisible_type_annotations,
             });
         }
 
@@ -154,10 +252,24 @@ impl ClassWriter {
                 has_code: method.has_code,
                 max_stack: method.max_stack,
                 max_locals: method.max_locals,
-                instructions: method.instructions,
-                exception_table: method.exception_table,
                 code_attributes: method.code_attributes,
+                insn_nodes: method.insn_nodes,
+                try_catch_blocks: method.try_catch_blocks,
+                local_variables: method.local_variables,
                 attributes: method.attributes,
+                visible_annotations: method.visible_annotations,
+                invisible_annotations: method.invisible_annotations,
+                visible_parameter_annotations: method.visible_parameter_annotations,
+                invisible_parameter_annotations: method.invisible_parameter_annotations,
+                annotation_default: method.annotation_default,
+                visible_type_annotations: method.visible_type_annotations,
+                invisible_type_annotations: method.invisible_type_annotations,
+                visible_insn_annotations: method.visible_insn_annotations,
+                invisible_insn_annotations: method.invisible_insn_annotations,
+                visible_try_catch_annotations: method.visible_try_catch_annotations,
+                invisible_try_catch_annotations: method.invisible_try_catch_annotations,
+                visible_local_variable_annotations: method.visible_local_variable_annotations,
+                invisible_local_variable_annotations: method.invisible_local_variable_annotations,
             });
         }
 
@@ -200,7 +312,20 @@ impl ClassWriter {
             methods: method_data,
             attributes,
             source_file,
+            visible_annotations,
+            invisible_annotations,
+            visible_type_annotations,
+            invisible_type_annotations,
+            module,
+            record_components,
+            nest_host,
+            nest_members,
+            permitted_subclasses,
             cp,
+            class_hierarchy: None,
+            strict_class_hierarchy: false,
+            visited: VisitedMembers::default(),
+            invalid_descriptor: None,
         }
     }
 
@@ -212,15 +337,18 @@ impl ClassWriter {
     /// * `minor` - The minor version.
     /// * `access_flags` - Access modifiers (e.g., public, final).
     /// * `name` - The internal name of the class (e.g., "java/lang/String").
+    /// * `signature` - The generic signature of the class, if any.
     /// * `super_name` - The internal name of the super class (e.g., `java/lang/String`, `a/b/c`).
     ///   Use `None` for `Object`.
     /// * `interfaces` - A list of interfaces implemented by this class.
+    #[allow(clippy::too_many_arguments)]
     pub fn visit(
         &mut self,
         major: u16,
         minor: u16,
         access_flags: u16,
         name: &str,
+        signature: Option<&str>,
         super_name: Option<&str>,
         interfaces: &[&str],
     ) -> &mut Self {
@@ -233,6 +361,13 @@ impl ClassWriter {
             .iter()
             .map(|value| (*value).to_string())
             .collect();
+        self.attributes
+            .retain(|attr| !matches!(attr, AttributeInfo::Signature { .. }));
+        if let Some(signature) = signature {
+            let signature_index = self.cp.utf8(signature);
+            self.attributes
+                .push(AttributeInfo::Signature { signature_index });
+        }
         self
     }
 
@@ -281,8 +416,125 @@ impl ClassWriter {
         self
     }
 
+    /// Sets the enclosing class of a local or anonymous class (`EnclosingMethod`).
+    ///
+    /// `name` and `descriptor` give the enclosing method, if the class is declared in one.
+    pub fn visit_outer_class(
+        &mut self,
+        owner: &str,
+        name: Option<&str>,
+        descriptor: Option<&str>,
+    ) -> &mut Self {
+        let class_index = self.cp.class(owner);
+        let method_index = match (name, descriptor) {
+            (Some(name), Some(descriptor)) => self.cp.name_and_type(name, descriptor),
+            _ => 0,
+        };
+        self.attributes.push(AttributeInfo::EnclosingMethod {
+            class_index,
+            method_index,
+        });
+        self
+    }
+
+    /// Sets the nest host of this class (`NestHost`).
+    pub fn visit_nest_host(&mut self, nest_host: &str) -> &mut Self {
+        self.nest_host = Some(nest_host.to_string());
+        self
+    }
+
+    /// Adds a member to the nest of which this class is the host (`NestMembers`).
+    pub fn visit_nest_member(&mut self, nest_member: &str) -> &mut Self {
+        self.nest_members.push(nest_member.to_string());
+        self
+    }
+
+    /// Adds a permitted subclass to this sealed class (`PermittedSubclasses`).
+    pub fn visit_permitted_subclass(&mut self, permitted_subclass: &str) -> &mut Self {
+        self.permitted_subclasses.push(permitted_subclass.to_string());
+        self
+    }
+
+    /// Adds an annotation to the class.
+    ///
+    /// Visible annotations are written to `RuntimeVisibleAnnotations`, invisible ones to
+    /// `RuntimeInvisibleAnnotations`.
+    pub fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) -> &mut Self {
+        if visible {
+            self.visible_annotations.push(annotation.clone());
+        } else {
+            self.invisible_annotations.push(annotation.clone());
+        }
+        self
+    }
+
+    /// Adds a type annotation on a type in the class declaration
+    /// (type parameters and bounds, super class, interfaces).
+    pub fn visit_type_annotation(
+        &mut self,
+        annotation: &TypeAnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        if visible {
+            self.visible_type_annotations.push(annotation.clone());
+        } else {
+            self.invisible_type_annotations.push(annotation.clone());
+        }
+        self
+    }
+
+    /// Adds a non-standard attribute to the class, or a standard one whose `content` does not
+    /// refer to the constant pool.
+    pub fn visit_attribute(&mut self, name: &str, content: &[u8]) -> &mut Self {
+        self.attributes.push(AttributeInfo::Unknown {
+            name: name.to_string(),
+            info: content.to_vec(),
+        });
+        self
+    }
+
+    /// Visits the module declared by this class (for `module-info` classes).
+    ///
+    /// Returns a `ModuleVisitor` to define the module content.
+    /// The `visit_end` method of the returned visitor must be called to attach it to the class.
+    pub fn visit_module(
+        &mut self,
+        name: &str,
+        access_flags: u16,
+        version: Option<&str>,
+    ) -> ModuleVisitor {
+        let mut visitor = ModuleVisitor::new(name, access_flags, version);
+        visitor.visited = Some(self.visited.clone());
+        visitor
+    }
+
+    /// Marks this class as a record (`Record`), which is needed for a record without
+    /// components. Visiting a component also does.
+    pub fn visit_record(&mut self) -> &mut Self {
+        self.record_components.get_or_insert_with(Vec::new);
+        self
+    }
+
+    /// Visits a component of a record class.
+    ///
+    /// Returns a `RecordComponentVisitor` to add annotations to the component.
+    /// The `visit_end` method of the returned visitor must be called to attach it to the class.
+    pub fn visit_record_component(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+    ) -> RecordComponentVisitor {
+        let mut visitor = RecordComponentVisitor::new(name, descriptor, signature);
+        visitor.visited = Some(self.visited.clone());
+        visitor
+    }
+
     /// Visits a method of the class.
     ///
+    /// `signature` is the generic signature of the method, if any, and `exceptions` the internal
+    /// names of its declared exceptions (`Exceptions`).
+    ///
     /// Returns a `MethodVisitor` that should be used to define the method body.
     /// The `visit_end` method of the returned visitor must be called to attach it to the class.
     pub fn visit_method(
@@ -290,16 +542,63 @@ impl ClassWriter {
         access_flags: u16,
         name: &str,
         descriptor: &str,
+        signature: Option<&str>,
+        exceptions: &[&str],
     ) -> MethodVisitor {
-        MethodVisitor::new(access_flags, name, descriptor)
+        let mut visitor = MethodVisitor::new(access_flags, name, descriptor);
+        if let Some(signature) = signature {
+            let signature_index = self.cp.utf8(signature);
+            visitor
+                .attributes
+                .push(AttributeInfo::Signature { signature_index });
+        }
+        if !exceptions.is_empty() {
+            let exception_index_table =
+                exceptions.iter().map(|name| self.cp.class(name)).collect();
+            visitor.attributes.push(AttributeInfo::Exceptions {
+                exception_index_table,
+            });
+        }
+        visitor.visited = Some(self.visited.clone());
+        visitor
     }
 
     /// Visits a field of the class.
     ///
+    /// `signature` is the generic signature of the field, if any, and `value` the initial value
+    /// of a static field (`ConstantValue`).
+    ///
     /// Returns a `FieldVisitor` to define field attributes.
     /// If `visit_end` is not called, the field is still committed when the visitor is dropped.
-    pub fn visit_field(&mut self, access_flags: u16, name: &str, descriptor: &str) -> FieldVisitor {
-        FieldVisitor::new(access_flags, name, descriptor, self as *mut ClassWriter)
+    pub fn visit_field(
+        &mut self,
+        access_flags: u16,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        value: Option<&ConstantValue>,
+    ) -> FieldVisitor {
+        let mut visitor = FieldVisitor::new(access_flags, name, descriptor);
+        if let Some(signature) = signature {
+            let signature_index = self.cp.utf8(signature);
+            visitor
+                .attributes
+                .push(AttributeInfo::Signature { signature_index });
+        }
+        if let Some(value) = value {
+            let constantvalue_index = match value {
+                ConstantValue::Integer(value) => self.cp.integer(*value),
+                ConstantValue::Float(value) => self.cp.float(*value),
+                ConstantValue::Long(value) => self.cp.long(*value),
+                ConstantValue::Double(value) => self.cp.double(*value),
+                ConstantValue::String(value) => self.cp.string(value),
+            };
+            visitor.attributes.push(AttributeInfo::ConstantValue {
+                constantvalue_index,
+            });
+        }
+        visitor.visited = Some(self.visited.clone());
+        visitor
     }
 
     /// Adds a custom attribute to the class.
@@ -308,52 +607,28 @@ impl ClassWriter {
         self
     }
 
-    fn ensure_bootstrap_method(
-        &mut self,
-        bootstrap_method: &Handle,
-        bootstrap_args: &[BootstrapArgument],
-    ) -> u16 {
-        let bootstrap_method_ref = self.cp.method_handle(bootstrap_method);
-        let mut bootstrap_arguments = Vec::with_capacity(bootstrap_args.len());
-        for arg in bootstrap_args {
-            let index = match arg {
-                BootstrapArgument::Integer(value) => self.cp.integer(*value),
-                BootstrapArgument::Float(value) => self.cp.float(*value),
-                BootstrapArgument::Long(value) => self.cp.long(*value),
-                BootstrapArgument::Double(value) => self.cp.double(*value),
-                BootstrapArgument::String(value) => self.cp.string(value),
-                BootstrapArgument::Class(value) => self.cp.class(value),
-                BootstrapArgument::MethodType(value) => self.cp.method_type(value),
-                BootstrapArgument::Handle(value) => self.cp.method_handle(value),
-            };
-            bootstrap_arguments.push(index);
-        }
-
-        let methods = if let Some(AttributeInfo::BootstrapMethods { methods }) = self
-            .attributes
-            .iter_mut()
-            .find(|attr| matches!(attr, AttributeInfo::BootstrapMethods { .. }))
-        {
-            methods
-        } else {
-            self.attributes
-                .push(AttributeInfo::BootstrapMethods { methods: Vec::new() });
-            if let Some(AttributeInfo::BootstrapMethods { methods }) = self.attributes.last_mut() {
-                methods
-            } else {
-                return 0;
+    /// Adds the members that were visited to the end through the `ClassVisitor` impl, or whose
+    /// `FieldVisitor` was dropped, in that order.
+    fn add_visited_members(&mut self) {
+        let visited = std::mem::take(
+            &mut *self.visited.lock().unwrap_or_else(|error| error.into_inner()),
+        );
+        for member in visited {
+            match member {
+                VisitedMember::Field(field) => self.fields.push(field),
+                VisitedMember::Method(method) => (*method).visit_end(self),
+                VisitedMember::Module(module) => module.visit_end(self),
+                VisitedMember::RecordComponent(component) => component.visit_end(self),
             }
-        };
-
-        methods.push(BootstrapMethod {
-            bootstrap_method_ref,
-            bootstrap_arguments,
-        });
-        (methods.len() - 1) as u16
+        }
     }
 
     /// Converts the builder state into a `ClassNode` object model.
     pub fn to_class_node(mut self) -> Result<ClassNode, String> {
+        self.add_visited_members();
+        if let Some(error) = &self.invalid_descriptor {
+            return Err(error.to_string());
+        }
         if self.name.is_empty() {
             return Err("missing class name, call visit() first".to_string());
         }
@@ -375,6 +650,10 @@ impl ClassWriter {
                 name: field.name,
                 descriptor: field.descriptor,
                 attributes: field.attributes,
+                visible_annotations: field.visible_annotations,
+                invisible_annotations: field.invisible_annotations,
+                visible_type_annotations: field.visible_type_annotations,
+                invisible_type_annotations: field.invisible_type_annotations,
             });
         }
 
@@ -387,10 +666,24 @@ impl ClassWriter {
                 has_code: method.has_code,
                 max_stack: method.max_stack,
                 max_locals: method.max_locals,
-                instructions: method.instructions,
-                exception_table: method.exception_table,
                 code_attributes: method.code_attributes,
+                insn_nodes: method.insn_nodes,
+                try_catch_blocks: method.try_catch_blocks,
+                local_variables: method.local_variables,
                 attributes: method.attributes,
+                visible_annotations: method.visible_annotations,
+                invisible_annotations: method.invisible_annotations,
+                visible_parameter_annotations: method.visible_parameter_annotations,
+                invisible_parameter_annotations: method.invisible_parameter_annotations,
+                annotation_default: method.annotation_default,
+                visible_type_annotations: method.visible_type_annotations,
+                invisible_type_annotations: method.invisible_type_annotations,
+                visible_insn_annotations: method.visible_insn_annotations,
+                invisible_insn_annotations: method.invisible_insn_annotations,
+                visible_try_catch_annotations: method.visible_try_catch_annotations,
+                invisible_try_catch_annotations: method.invisible_try_catch_annotations,
+                visible_local_variable_annotations: method.visible_local_variable_annotations,
+                invisible_local_variable_annotations: method.invisible_local_variable_annotations,
             });
         }
 
@@ -480,18 +773,35 @@ impl ClassWriter {
             attributes: self.attributes,
             inner_classes,
             outer_class,
+            visible_annotations: self.visible_annotations,
+            invisible_annotations: self.invisible_annotations,
+            visible_type_annotations: self.visible_type_annotations,
+            invisible_type_annotations: self.invisible_type_annotations,
+            module: self.module,
+            record_components: self.record_components,
+            nest_host: self.nest_host,
+            nest_members: self.nest_members,
+            permitted_subclasses: self.permitted_subclasses,
         })
     }
     /// Generates the raw byte vector representing the .class file.
     ///
     /// This method performs all necessary computations (stack map frames, max stack size)
     /// based on the options provided in `new`.
-    pub fn to_bytes(self) -> Result<Vec<u8>, ClassWriteError> {
-        let options = self.options;
+    pub fn to_bytes(mut self) -> Result<Vec<u8>, ClassWriteError> {
+        self.add_visited_members();
+        if let Some(error) = self.invalid_descriptor.take() {
+            return Err(error.into());
+        }
+        let writer = ClassFileWriter {
+            options: self.options,
+            class_hierarchy: self.class_hierarchy.clone(),
+            strict_class_hierarchy: self.strict_class_hierarchy,
+        };
         let class_node = self
             .to_class_node()
             .map_err(ClassWriteError::FrameComputation)?;
-        ClassFileWriter::new(options).to_bytes(&class_node)
+        writer.to_bytes(&class_node)
     }
 
     pub fn write_class_node(
@@ -502,6 +812,142 @@ impl ClassWriter {
     }
 }
 
+/// Lets a `ClassWriter` receive the events of a [`ClassReader`](crate::class_reader::ClassReader)
+/// or of an adapter chain, as in `reader.accept(&mut writer, 0)`.
+///
+/// The returned field, method, module and record component visitors are added to this
+/// `ClassWriter`, in the order of their `visit_end` calls, the next time it adds or writes
+/// members. Stack map frames are copied unless `COMPUTE_FRAMES` regenerates them.
+impl class_reader::ClassVisitor for ClassWriter {
+    fn visit(
+        &mut self,
+        major: u16,
+        minor: u16,
+        access_flags: u16,
+        name: &str,
+        signature: Option<&str>,
+        super_name: Option<&str>,
+        interfaces: &[&str],
+    ) {
+        ClassWriter::visit(
+            self,
+            major,
+            minor,
+            access_flags,
+            name,
+            signature,
+            super_name,
+            interfaces,
+        );
+    }
+
+    fn visit_source(&mut self, source: &str) {
+        self.visit_source_file(source);
+    }
+
+    fn visit_module(
+        &mut self,
+        name: &str,
+        access_flags: u16,
+        version: Option<&str>,
+    ) -> Option<Box<dyn class_reader::ModuleVisitor>> {
+        Some(Box::new(ClassWriter::visit_module(self, name, access_flags, version)))
+    }
+
+    fn visit_nest_host(&mut self, nest_host: &str) {
+        ClassWriter::visit_nest_host(self, nest_host);
+    }
+
+    fn visit_outer_class(&mut self, owner: &str, name: Option<&str>, descriptor: Option<&str>) {
+        ClassWriter::visit_outer_class(self, owner, name, descriptor);
+    }
+
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        ClassWriter::visit_annotation(self, annotation, visible);
+    }
+
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        ClassWriter::visit_type_annotation(self, annotation, visible);
+    }
+
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        ClassWriter::visit_attribute(self, name, content);
+    }
+
+    fn visit_nest_member(&mut self, nest_member: &str) {
+        ClassWriter::visit_nest_member(self, nest_member);
+    }
+
+    fn visit_permitted_subclass(&mut self, permitted_subclass: &str) {
+        ClassWriter::visit_permitted_subclass(self, permitted_subclass);
+    }
+
+    fn visit_inner_class(
+        &mut self,
+        name: &str,
+        outer_name: Option<&str>,
+        inner_name: Option<&str>,
+        access_flags: u16,
+    ) {
+        ClassWriter::visit_inner_class(self, name, outer_name, inner_name, access_flags);
+    }
+
+    fn visit_record(&mut self) {
+        ClassWriter::visit_record(self);
+    }
+
+    fn visit_record_component(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+    ) -> Option<Box<dyn class_reader::RecordComponentVisitor>> {
+        Some(Box::new(ClassWriter::visit_record_component(
+            self, name, descriptor, signature,
+        )))
+    }
+
+    fn visit_field(
+        &mut self,
+        access_flags: u16,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        value: Option<&ConstantValue>,
+    ) -> Option<Box<dyn class_reader::FieldVisitor>> {
+        Some(Box::new(ClassWriter::visit_field(
+            self,
+            access_flags,
+            name,
+            descriptor,
+            signature,
+            value,
+        )))
+    }
+
+    fn visit_method(
+        &mut self,
+        access_flags: u16,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        exceptions: &[&str],
+    ) -> Option<Box<dyn class_reader::MethodVisitor>> {
+        Some(Box::new(ClassWriter::visit_method(
+            self,
+            access_flags,
+            name,
+            descriptor,
+            signature,
+            exceptions,
+        )))
+    }
+
+    fn visit_end(&mut self) {
+        self.add_visited_members();
+    }
+}
+
 /// A visitor to visit a Java method.
 ///
 /// Used to generate the bytecode instructions, exception tables, and attributes
@@ -515,9 +961,26 @@ pub struct MethodVisitor {
     max_locals: u16,
     insns: NodeList,
     pending_type_names: Vec<String>,
-    exception_table: Vec<ExceptionTableEntry>,
+    try_catch_blocks: Vec<TryCatchBlockNode>,
+    local_variables: Vec<LocalVariableNode>,
+    parameters: Vec<(Option<String>, u16)>,
     code_attributes: Vec<AttributeInfo>,
     attributes: Vec<AttributeInfo>,
+    visible_annotations: Vec<AnnotationNode>,
+    invisible_annotations: Vec<AnnotationNode>,
+    visible_parameter_annotations: Vec<Vec<AnnotationNode>>,
+    invisible_parameter_annotations: Vec<Vec<AnnotationNode>>,
+    annotation_default: Option<AnnotationValue>,
+    visible_type_annotations: Vec<TypeAnnotationNode>,
+    invisible_type_annotations: Vec<TypeAnnotationNode>,
+    visible_insn_annotations: Vec<InsnAnnotationNode>,
+    invisible_insn_annotations: Vec<InsnAnnotationNode>,
+    visible_try_catch_annotations: Vec<TypeAnnotationNode>,
+    invisible_try_catch_annotations: Vec<TypeAnnotationNode>,
+    visible_local_variable_annotations: Vec<LocalVariableAnnotationNode>,
+    invisible_local_variable_annotations: Vec<LocalVariableAnnotationNode>,
+    invalid_descriptor: Option<DescriptorError>,
+    visited: Option<VisitedMembers>,
 }
 
 impl MethodVisitor {
@@ -531,10 +994,197 @@ impl MethodVisitor {
             max_locals: 0,
             insns: NodeList::new(),
             pending_type_names: Vec::new(),
-            exception_table: Vec::new(),
+            try_catch_blocks: Vec::new(),
+            local_variables: Vec::new(),
+            parameters: Vec::new(),
             code_attributes: Vec::new(),
             attributes: Vec::new(),
+            visible_annotations: Vec::new(),
+            invisible_annotations: Vec::new(),
+            visible_parameter_annotations: Vec::new(),
+            invisible_parameter_annotations: Vec::new(),
+            annotation_default: None,
+            visible_type_annotations: Vec::new(),
+            invisible_type_annotations: Vec::new(),
+            visible_insn_annotations: Vec::new(),
+            invisible_insn_annotations: Vec::new(),
+            visible_try_catch_annotations: Vec::new(),
+            invisible_try_catch_annotations: Vec::new(),
+            visible_local_variable_annotations: Vec::new(),
+            invisible_local_variable_annotations: Vec::new(),
+            invalid_descriptor: None,
+            visited: None,
+        }
+    }
+
+    /// Declares the next parameter of the method in the `MethodParameters` attribute.
+    ///
+    /// `name` is `None` for a parameter without a name; `access_flags` may contain
+    /// `ACC_FINAL`, `ACC_SYNTHETIC` and `ACC_MANDATED`.
+    pub fn visit_parameter(&mut self, name: Option<&str>, access_flags: u16) -> &mut Self {
+        self.parameters.push((name.map(str::to_string), access_flags));
+        self
+    }
+
+    /// Adds an annotation to the method.
+    pub fn visit_annotation(&mut self, annotation: AnnotationNode, visible: bool) -> &mut Self {
+        if visible {
+            self.visible_annotations.push(annotation);
+        } else {
+            self.invisible_annotations.push(annotation);
+        }
+        self
+    }
+
+    /// Sets the number of parameters written in the `Runtime(In)VisibleParameterAnnotations`
+    /// attribute, which is otherwise one more than the last annotated parameter.
+    pub fn visit_annotable_parameter_count(&mut self, count: usize, visible: bool) -> &mut Self {
+        let parameters = if visible {
+            &mut self.visible_parameter_annotations
+        } else {
+            &mut self.invisible_parameter_annotations
+        };
+        parameters.resize(count, Vec::new());
+        self
+    }
+
+    /// Adds an annotation to the parameter at index `parameter`.
+    pub fn visit_parameter_annotation(
+        &mut self,
+        parameter: usize,
+        annotation: AnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        let parameters = if visible {
+            &mut self.visible_parameter_annotations
+        } else {
+            &mut self.invisible_parameter_annotations
+        };
+        if parameters.len() <= parameter {
+            parameters.resize(parameter + 1, Vec::new());
+        }
+        parameters[parameter].push(annotation);
+        self
+    }
+
+    /// Sets the default value of an annotation interface element.
+    pub fn visit_annotation_default(&mut self, value: AnnotationValue) -> &mut Self {
+        self.annotation_default = Some(value);
+        self
+    }
+
+    /// Adds a type annotation on a type in the method declaration
+    /// (type parameters and bounds, return type, receiver, formal parameters, `throws`).
+    pub fn visit_type_annotation(
+        &mut self,
+        type_ref: TypeReference,
+        type_path: Option<TypePath>,
+        annotation: AnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        let node = TypeAnnotationNode::new(type_ref, type_path, annotation);
+        if visible {
+            self.visible_type_annotations.push(node);
+        } else {
+            self.invisible_type_annotations.push(node);
+        }
+        self
+    }
+
+    /// Adds a type annotation on the last visited instruction.
+    ///
+    /// This must be called right after the annotated instruction (e.g., `CHECKCAST`, `NEW`);
+    /// writing the class fails if no instruction was visited before.
+    pub fn visit_insn_annotation(
+        &mut self,
+        type_ref: TypeReference,
+        type_path: Option<TypePath>,
+        annotation: AnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        let insn = self
+            .insns
+            .iter()
+            .rev()
+            .find(|(_, node)| {
+                !matches!(
+                    node,
+                    AbstractInsnNode::Label(_)
+                        | AbstractInsnNode::LineNumber(_)
+                        | AbstractInsnNode::Frame(_)
+                )
+            })
+            // A handle that is in no list, which the writer reports as a missing instruction.
+            .map_or_else(NodeId::next, |(id, _)| id);
+        let node = InsnAnnotationNode {
+            insn,
+            type_annotation: TypeAnnotationNode::new(type_ref, type_path, annotation),
+        };
+        if visible {
+            self.visible_insn_annotations.push(node);
+        } else {
+            self.invisible_insn_annotations.push(node);
+        }
+        self
+    }
+
+    /// Adds a type annotation on the type of an exception handler.
+    ///
+    /// `type_ref` is an `EXCEPTION_PARAMETER` reference whose try-catch block index counts the
+    /// calls to [`visit_try_catch_block`](Self::visit_try_catch_block).
+    pub fn visit_try_catch_annotation(
+        &mut self,
+        type_ref: TypeReference,
+        type_path: Option<TypePath>,
+        annotation: AnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        let node = TypeAnnotationNode::new(type_ref, type_path, annotation);
+        if visible {
+            self.visible_try_catch_annotations.push(node);
+        } else {
+            self.invisible_try_catch_annotations.push(node);
+        }
+        self
+    }
+
+    /// Adds a type annotation on a local variable.
+    ///
+    /// `start`, `end` and `index` describe the ranges in which the variable is live and must have
+    /// the same length. The labels must be visited in the method's code.
+    #[allow(clippy::too_many_arguments)]
+    pub fn visit_local_variable_annotation(
+        &mut self,
+        type_ref: TypeReference,
+        type_path: Option<TypePath>,
+        start: &[Label],
+        end: &[Label],
+        index: &[u16],
+        annotation: AnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        let node = LocalVariableAnnotationNode {
+            start: start.iter().copied().map(LabelNode::from_label).collect(),
+            end: end.iter().copied().map(LabelNode::from_label).collect(),
+            index: index.to_vec(),
+            type_annotation: TypeAnnotationNode::new(type_ref, type_path, annotation),
+        };
+        if visible {
+            self.visible_local_variable_annotations.push(node);
+        } else {
+            self.invisible_local_variable_annotations.push(node);
         }
+        self
+    }
+
+    /// Adds a non-standard attribute to the method, or a standard one whose `content` does not
+    /// refer to the constant pool.
+    pub fn visit_attribute(&mut self, name: &str, content: &[u8]) -> &mut Self {
+        self.attributes.push(AttributeInfo::Unknown {
+            name: name.to_string(),
+            info: content.to_vec(),
+        });
+        self
     }
 
     /// Starts the visit of the method's code.
@@ -549,6 +1199,17 @@ impl MethodVisitor {
         self
     }
 
+    /// Visits an instruction with a single int operand (BIPUSH, SIPUSH or NEWARRAY).
+    ///
+    /// For NEWARRAY, `operand` is the `atype` code of the element type (e.g., 10 for `int`).
+    pub fn visit_int_insn(&mut self, opcode: u8, operand: i32) -> &mut Self {
+        self.insns.add(Insn::Int(IntInsnNode {
+            insn: opcode.into(),
+            operand,
+        }));
+        self
+    }
+
     /// Visits a local variable instruction (e.g., ILOAD, ASTORE).
     pub fn visit_var_insn(&mut self, opcode: u8, var_index: u16) -> &mut Self {
         self.insns.add(Insn::Var(VarInsnNode {
@@ -589,10 +1250,14 @@ impl MethodVisitor {
         owner: &str,
         name: &str,
         descriptor: &str,
-        _is_interface: bool,
+        is_interface: bool,
     ) -> &mut Self {
-        self.insns.add(Insn::Method(MethodInsnNode::new(
-            opcode, owner, name, descriptor,
+        self.insns.add(Insn::Method(MethodInsnNode::with_interface(
+            opcode,
+            owner,
+            name,
+            descriptor,
+            is_interface,
         )));
         self
     }
@@ -638,6 +1303,13 @@ impl MethodVisitor {
         self
     }
 
+    /// Visits a stack map frame, before the instruction it applies to. Frames are written to the
+    /// `StackMapTable` unless `COMPUTE_FRAMES` is set.
+    pub fn visit_frame(&mut self, frame: Frame) -> &mut Self {
+        self.insns.add(frame);
+        self
+    }
+
     pub fn visit_line_number(&mut self, line: u16, start: LabelNode) -> &mut Self {
         self.insns.add(LineNumberInsnNode::new(line, start));
         self
@@ -649,9 +1321,116 @@ impl MethodVisitor {
         self
     }
 
-    /// Visits the maximum stack size and number of local variables.
-    ///
-    /// If `COMPUTE_MAXS` or `COMPUTE_FRAMES` was passed to the ClassWriter,
+    /// Visits an IINC instruction.
+    pub fn visit_iinc_insn(&mut self, var_index: u16, increment: i16) -> &mut Self {
+        self.insns.add(Insn::Iinc(IincInsnNode {
+            insn: opcodes::IINC.into(),
+            var_index,
+            increment,
+        }));
+        self
+    }
+
+    /// Visits a TABLESWITCH instruction.
+    ///
+    /// `labels` holds the targets of the keys `low` to `high`, in order.
+    pub fn visit_table_switch_insn(
+        &mut self,
+        low: i32,
+        high: i32,
+        default: Label,
+        labels: &[Label],
+    ) -> &mut Self {
+        self.insns.add(TableSwitchLabelInsnNode {
+            insn: opcodes::TABLESWITCH.into(),
+            default: LabelNode::from_label(default),
+            low,
+            high,
+            labels: labels.iter().copied().map(LabelNode::from_label).collect(),
+        });
+        self
+    }
+
+    /// Visits a LOOKUPSWITCH instruction.
+    ///
+    /// `labels` holds the target of each key, in the same order as `keys`.
+    pub fn visit_lookup_switch_insn(
+        &mut self,
+        default: Label,
+        keys: &[i32],
+        labels: &[Label],
+    ) -> &mut Self {
+        self.insns.add(LookupSwitchLabelInsnNode {
+            insn: opcodes::LOOKUPSWITCH.into(),
+            default: LabelNode::from_label(default),
+            keys: keys.to_vec(),
+            labels: labels.iter().copied().map(LabelNode::from_label).collect(),
+        });
+        self
+    }
+
+    /// Visits a MULTIANEWARRAY instruction.
+    ///
+    /// `descriptor` is the descriptor of the array type (e.g., `[[I`).
+    pub fn visit_multi_anewarray_insn(&mut self, descriptor: &str, dimensions: u8) -> &mut Self {
+        self.pending_type_names.push(descriptor.to_string());
+        self.insns.add(Insn::MultiANewArray(MultiANewArrayInsnNode {
+            insn: opcodes::MULTIANEWARRAY.into(),
+            type_index: 0,
+            dimensions,
+        }));
+        self
+    }
+
+    /// Visits an exception handler covering the code from `start` (inclusive) to `end`
+    /// (exclusive).
+    ///
+    /// `catch_type` is the internal name of the caught exception, or `None` for `finally`
+    /// blocks. Handlers are tried in the order they are visited; the labels must be visited in
+    /// the method's code.
+    pub fn visit_try_catch_block(
+        &mut self,
+        start: Label,
+        end: Label,
+        handler: Label,
+        catch_type: Option<&str>,
+    ) -> &mut Self {
+        self.try_catch_blocks.push(TryCatchBlockNode {
+            start: LabelNode::from_label(start),
+            end: LabelNode::from_label(end),
+            handler: LabelNode::from_label(handler),
+            catch_type: catch_type.map(str::to_string),
+        });
+        self
+    }
+
+    /// Declares a local variable, live from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// `signature` is the generic signature of the variable's type, if any. The labels must be
+    /// visited in the method's code.
+    pub fn visit_local_variable(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        start: Label,
+        end: Label,
+        index: u16,
+    ) -> &mut Self {
+        self.local_variables.push(LocalVariableNode {
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+            signature: signature.map(str::to_string),
+            start: LabelNode::from_label(start),
+            end: LabelNode::from_label(end),
+            index,
+        });
+        self
+    }
+
+    /// Visits the maximum stack size and number of local variables.
+    ///
+    /// If `COMPUTE_MAXS` or `COMPUTE_FRAMES` was passed to the ClassWriter,
     /// these values may be ignored or recomputed.
     pub fn visit_maxs(&mut self, max_stack: u16, max_locals: u16) -> &mut Self {
         self.max_stack = max_stack;
@@ -661,78 +1440,62 @@ impl MethodVisitor {
 
     /// Finalizes the method and attaches it to the parent `ClassWriter`.
     pub fn visit_end(mut self, class: &mut ClassWriter) {
-        let mut resolved = NodeList::new();
-        let mut pending_type_names = self.pending_type_names.into_iter();
-        for node in self.insns.into_nodes() {
-            let node = match node {
-                AbstractInsnNode::Insn(Insn::Type(mut insn)) => {
+        class.add_visited_members();
+        if let Some(error) = self.invalid_descriptor.take() {
+            class.invalid_descriptor.get_or_insert(error);
+        }
+        // Instructions are resolved in place, so that they keep the handles of their type
+        // annotations.
+        let mut pending_type_names = std::mem::take(&mut self.pending_type_names).into_iter();
+        let mut scratch = Vec::new();
+        for id in self.insns.ids() {
+            let Some(AbstractInsnNode::Insn(insn)) = self.insns.get_mut(id) else {
+                continue;
+            };
+            match insn {
+                Insn::Type(insn) => {
                     if insn.type_index == 0
                         && let Some(type_name) = pending_type_names.next()
                     {
                         insn.type_index = class.cp.class(&type_name);
                     }
-                    AbstractInsnNode::Insn(Insn::Type(insn))
                 }
-                AbstractInsnNode::Insn(Insn::InvokeDynamic(mut insn)) => {
-                    if insn.method_index == 0 {
-                        if let (Some(name), Some(descriptor), Some(bootstrap_method)) = (
-                            insn.name.take(),
-                            insn.descriptor.take(),
-                            insn.bootstrap_method.take(),
-                        ) {
-                            let bsm_index = class.ensure_bootstrap_method(
-                                &bootstrap_method,
-                                &insn.bootstrap_args,
-                            );
-                            let method_index =
-                                class.cp.invoke_dynamic(bsm_index, &name, &descriptor);
-                            insn.method_index = method_index;
-                        }
+                Insn::MultiANewArray(insn) => {
+                    if insn.type_index == 0
+                        && let Some(type_name) = pending_type_names.next()
+                    {
+                        insn.type_index = class.cp.class(&type_name);
                     }
-                    AbstractInsnNode::Insn(Insn::InvokeDynamic(insn))
                 }
-                other => other,
-            };
-            resolved.add_node(node);
-        }
-        self.insns = resolved;
-        let code = if self.has_code || !self.insns.nodes().is_empty() {
-            Some(build_code_attribute(
-                self.max_stack,
-                self.max_locals,
-                self.insns,
-                &mut class.cp,
-                std::mem::take(&mut self.exception_table),
-                std::mem::take(&mut self.code_attributes),
-            ))
+                _ => {}
+            }
+            // Emitting resolves the symbolic member references and constants, except for call
+            // sites and dynamic constants, whose bootstrap methods are added when writing. An
+            // instruction that cannot be emitted is kept as is, and fails when writing.
+            scratch.clear();
+            if let Ok(emitted) = emit_insn(&mut scratch, insn.clone(), Some(&mut class.cp)) {
+                *insn = emitted;
+            }
+        }
+        if !self.parameters.is_empty() {
+            let parameters = self
+                .parameters
+                .iter()
+                .map(|(name, access_flags)| MethodParameter {
+                    name_index: name.as_deref().map_or(0, |name| class.cp.utf8(name)),
+                    access_flags: *access_flags,
+                })
+                .collect();
+            self.attributes
+                .push(AttributeInfo::MethodParameters { parameters });
+        }
+        // The code stays label-based: it is laid out when the class is written.
+        let has_code = self.has_code || !self.insns.is_empty();
+        let (max_stack, max_locals) = if has_code {
+            (self.max_stack, self.max_locals)
         } else {
-            None
+            (0, 0)
         };
-        let (has_code, max_stack, max_locals, instructions, exception_table, code_attributes) =
-            if let Some(code) = code {
-                let CodeAttribute {
-                    max_stack,
-                    max_locals,
-                    instructions,
-                    exception_table,
-                    attributes,
-                    ..
-                } = code;
-                let mut list = InsnList::new();
-                for insn in instructions {
-                    list.add(insn);
-                }
-                (
-                    true,
-                    max_stack,
-                    max_locals,
-                    list,
-                    exception_table,
-                    attributes,
-                )
-            } else {
-                (false, 0, 0, InsnList::new(), Vec::new(), Vec::new())
-            };
         class.methods.push(MethodData {
             access_flags: self.access_flags,
             name: self.name,
@@ -740,37 +1503,282 @@ impl MethodVisitor {
             has_code,
             max_stack,
             max_locals,
-            instructions,
-            exception_table,
-            code_attributes,
-            attributes: std::mem::take(&mut self.attributes),
+            code_attributes: self.code_attributes,
+            insn_nodes: self.insns,
+            try_catch_blocks: self.try_catch_blocks,
+            local_variables: self.local_variables,
+            attributes: self.attributes,
+            visible_annotations: self.visible_annotations,
+            invisible_annotations: self.invisible_annotations,
+            visible_parameter_annotations: self.visible_parameter_annotations,
+            invisible_parameter_annotations: self.invisible_parameter_annotations,
+            annotation_default: self.annotation_default,
+            visible_type_annotations: self.visible_type_annotations,
+            invisible_type_annotations: self.invisible_type_annotations,
+            visible_insn_annotations: self.visible_insn_annotations,
+            invisible_insn_annotations: self.invisible_insn_annotations,
+            visible_try_catch_annotations: self.visible_try_catch_annotations,
+            invisible_try_catch_annotations: self.invisible_try_catch_annotations,
+            visible_local_variable_annotations: self.visible_local_variable_annotations,
+            invisible_local_variable_annotations: self.invisible_local_variable_annotations,
         });
     }
 }
 
+/// Receives method events from a reader or an adapter chain.
+///
+/// The method is attached to its `ClassWriter` after `visit_end` is called. Frames are kept as
+/// frame nodes, which are written to the `StackMapTable` unless `COMPUTE_FRAMES` is set.
+///
+/// A class constant that is not a valid internal name or a method type constant that is not a
+/// method descriptor is reported as `ClassWriteError::InvalidDescriptor` when writing the class.
+impl class_reader::MethodVisitor for MethodVisitor {
+    fn visit_parameter(&mut self, name: Option<&str>, access_flags: u16) {
+        MethodVisitor::visit_parameter(self, name, access_flags);
+    }
+
+    fn visit_annotation_default(&mut self, value: &AnnotationValue) {
+        MethodVisitor::visit_annotation_default(self, value.clone());
+    }
+
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        MethodVisitor::visit_annotation(self, annotation.clone(), visible);
+    }
+
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if visible {
+            self.visible_type_annotations.push(annotation.clone());
+        } else {
+            self.invisible_type_annotations.push(annotation.clone());
+        }
+    }
+
+    fn visit_annotable_parameter_count(&mut self, count: usize, visible: bool) {
+        MethodVisitor::visit_annotable_parameter_count(self, count, visible);
+    }
+
+    fn visit_parameter_annotation(
+        &mut self,
+        parameter: usize,
+        annotation: &AnnotationNode,
+        visible: bool,
+    ) {
+        MethodVisitor::visit_parameter_annotation(self, parameter, annotation.clone(), visible);
+    }
+
+    fn visit_insn_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        MethodVisitor::visit_insn_annotation(
+            self,
+            annotation.type_ref,
+            annotation.type_path.clone(),
+            annotation.annotation.clone(),
+            visible,
+        );
+    }
+
+    fn visit_try_catch_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if visible {
+            self.visible_try_catch_annotations.push(annotation.clone());
+        } else {
+            self.invisible_try_catch_annotations.push(annotation.clone());
+        }
+    }
+
+    fn visit_local_variable_annotation(
+        &mut self,
+        annotation: &TypeAnnotationNode,
+        start: &[Label],
+        end: &[Label],
+        index: &[u16],
+        visible: bool,
+    ) {
+        MethodVisitor::visit_local_variable_annotation(
+            self,
+            annotation.type_ref,
+            annotation.type_path.clone(),
+            start,
+            end,
+            index,
+            annotation.annotation.clone(),
+            visible,
+        );
+    }
+
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        MethodVisitor::visit_attribute(self, name, content);
+    }
+
+    fn visit_code(&mut self) {
+        MethodVisitor::visit_code(self);
+    }
+
+    fn visit_frame(&mut self, frame: &Frame) {
+        MethodVisitor::visit_frame(self, frame.clone());
+    }
+
+    fn visit_try_catch_block(
+        &mut self,
+        start: Label,
+        end: Label,
+        handler: Label,
+        catch_type: Option<&str>,
+    ) {
+        MethodVisitor::visit_try_catch_block(self, start, end, handler, catch_type);
+    }
+
+    fn visit_local_variable(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        signature: Option<&str>,
+        start: Label,
+        end: Label,
+        index: u16,
+    ) {
+        MethodVisitor::visit_local_variable(self, name, descriptor, signature, start, end, index);
+    }
+
+    fn visit_label(&mut self, label: Label) {
+        MethodVisitor::visit_label(self, label);
+    }
+
+    fn visit_line_number(&mut self, line: u16, start: Label) {
+        MethodVisitor::visit_line_number(self, line, LabelNode::from_label(start));
+    }
+
+    fn visit_insn(&mut self, opcode: u8) {
+        MethodVisitor::visit_insn(self, opcode);
+    }
+
+    fn visit_int_insn(&mut self, opcode: u8, operand: i32) {
+        MethodVisitor::visit_int_insn(self, opcode, operand);
+    }
+
+    fn visit_var_insn(&mut self, opcode: u8, var_index: u16) {
+        MethodVisitor::visit_var_insn(self, opcode, var_index);
+    }
+
+    fn visit_type_insn(&mut self, opcode: u8, type_name: &str) {
+        MethodVisitor::visit_type_insn(self, opcode, type_name);
+    }
+
+    fn visit_field_insn(&mut self, opcode: u8, owner: &str, name: &str, desc: &str) {
+        MethodVisitor::visit_field_insn(self, opcode, owner, name, desc);
+    }
+
+    fn visit_method_insn(
+        &mut self,
+        opcode: u8,
+        owner: &str,
+        name: &str,
+        desc: &str,
+        is_interface: bool,
+    ) {
+        MethodVisitor::visit_method_insn(self, opcode, owner, name, desc, is_interface);
+    }
+
+    fn visit_invoke_dynamic_insn(
+        &mut self,
+        name: &str,
+        desc: &str,
+        bootstrap_method: &Handle,
+        bootstrap_args: &[BootstrapArgument],
+    ) {
+        MethodVisitor::visit_invokedynamic_insn(
+            self,
+            name,
+            desc,
+            bootstrap_method.clone(),
+            bootstrap_args,
+        );
+    }
+
+    fn visit_jump_insn(&mut self, opcode: u8, target: Label) {
+        MethodVisitor::visit_jump_insn(self, opcode, target);
+    }
+
+    fn visit_ldc_insn(&mut self, value: class_reader::LdcConstant) {
+        let node = match value {
+            class_reader::LdcConstant::Integer(value) => Ok(LdcInsnNode::int(value)),
+            class_reader::LdcConstant::Float(value) => Ok(LdcInsnNode::float(value)),
+            class_reader::LdcConstant::Long(value) => Ok(LdcInsnNode::long(value)),
+            class_reader::LdcConstant::Double(value) => Ok(LdcInsnNode::double(value)),
+            class_reader::LdcConstant::String(value) => Ok(LdcInsnNode::string(&value)),
+            class_reader::LdcConstant::Class(name) => {
+                Type::try_get_object_type(&name).map(LdcInsnNode::typed)
+            }
+            class_reader::LdcConstant::MethodType(descriptor) => {
+                LdcInsnNode::method_type(&descriptor)
+            }
+            class_reader::LdcConstant::MethodHandle(value) => Ok(LdcInsnNode::handle(value)),
+            class_reader::LdcConstant::Dynamic(value) => Ok(LdcInsnNode::constant_dynamic(value)),
+        };
+        match node {
+            Ok(node) => {
+                MethodVisitor::visit_ldc_insn(self, node);
+            }
+            Err(error) => {
+                self.invalid_descriptor.get_or_insert(error);
+            }
+        }
+    }
+
+    fn visit_iinc_insn(&mut self, var_index: u16, increment: i16) {
+        MethodVisitor::visit_iinc_insn(self, var_index, increment);
+    }
+
+    fn visit_table_switch(&mut self, low: i32, high: i32, default: Label, labels: &[Label]) {
+        MethodVisitor::visit_table_switch_insn(self, low, high, default, labels);
+    }
+
+    fn visit_lookup_switch(&mut self, default: Label, keys: &[i32], labels: &[Label]) {
+        MethodVisitor::visit_lookup_switch_insn(self, default, keys, labels);
+    }
+
+    fn visit_multi_anewarray_insn(&mut self, type_name: &str, dims: u8) {
+        MethodVisitor::visit_multi_anewarray_insn(self, type_name, dims);
+    }
+
+    fn visit_maxs(&mut self, max_stack: u16, max_locals: u16) {
+        MethodVisitor::visit_maxs(self, max_stack, max_locals);
+    }
+
+    fn visit_end(&mut self) {
+        let Some(visited) = self.visited.take() else {
+            return;
+        };
+        let visitor = std::mem::replace(self, MethodVisitor::new(0, "", ""));
+        let mut visited = visited.lock().unwrap_or_else(|error| error.into_inner());
+        visited.push(VisitedMember::Method(Box::new(visitor)));
+    }
+}
+
 /// A visitor to visit a Java field.
 pub struct FieldVisitor {
     access_flags: u16,
     name: String,
     descriptor: String,
     attributes: Vec<AttributeInfo>,
-    class_ptr: Option<*mut ClassWriter>,
+    visible_annotations: Vec<AnnotationNode>,
+    invisible_annotations: Vec<AnnotationNode>,
+    visible_type_annotations: Vec<TypeAnnotationNode>,
+    invisible_type_annotations: Vec<TypeAnnotationNode>,
+    visited: Option<VisitedMembers>,
     committed: bool,
 }
 
 impl FieldVisitor {
-    pub fn new(
-        access_flags: u16,
-        name: &str,
-        descriptor: &str,
-        class_ptr: *mut ClassWriter,
-    ) -> Self {
+    pub fn new(access_flags: u16, name: &str, descriptor: &str) -> Self {
         Self {
             access_flags,
             name: name.to_string(),
             descriptor: descriptor.to_string(),
             attributes: Vec::new(),
-            class_ptr: Some(class_ptr),
+            visible_annotations: Vec::new(),
+            invisible_annotations: Vec::new(),
+            visible_type_annotations: Vec::new(),
+            invisible_type_annotations: Vec::new(),
+            visited: None,
             committed: false,
         }
     }
@@ -781,204 +1789,751 @@ impl FieldVisitor {
         self
     }
 
+    /// Adds an annotation to the field.
+    pub fn visit_annotation(&mut self, annotation: AnnotationNode, visible: bool) -> &mut Self {
+        if visible {
+            self.visible_annotations.push(annotation);
+        } else {
+            self.invisible_annotations.push(annotation);
+        }
+        self
+    }
+
+    /// Adds a type annotation on the type of the field.
+    pub fn visit_type_annotation(
+        &mut self,
+        type_ref: TypeReference,
+        type_path: Option<TypePath>,
+        annotation: AnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        let node = TypeAnnotationNode::new(type_ref, type_path, annotation);
+        if visible {
+            self.visible_type_annotations.push(node);
+        } else {
+            self.invisible_type_annotations.push(node);
+        }
+        self
+    }
+
+    /// Adds a non-standard attribute to the field, or a standard one whose `content` does not
+    /// refer to the constant pool.
+    pub fn visit_attribute(&mut self, name: &str, content: &[u8]) -> &mut Self {
+        self.attributes.push(AttributeInfo::Unknown {
+            name: name.to_string(),
+            info: content.to_vec(),
+        });
+        self
+    }
+
     /// Finalizes the field and attaches it to the parent `ClassWriter`.
     /// If you don't call this, the field is still attached when the visitor is dropped.
     pub fn visit_end(mut self, class: &mut ClassWriter) {
-        class.fields.push(FieldData {
+        class.add_visited_members();
+        let field = self.take_field();
+        class.fields.push(field);
+    }
+
+    /// Hands the field to the `ClassWriter` that created this visitor, if any.
+    fn commit_visited(&mut self) {
+        if self.committed {
+            return;
+        }
+        let Some(visited) = self.visited.take() else {
+            return;
+        };
+        let field = self.take_field();
+        let mut visited = visited.lock().unwrap_or_else(|error| error.into_inner());
+        visited.push(VisitedMember::Field(field));
+    }
+
+    fn take_field(&mut self) -> FieldData {
+        self.committed = true;
+        FieldData {
             access_flags: self.access_flags,
             name: std::mem::take(&mut self.name),
             descriptor: std::mem::take(&mut self.descriptor),
             attributes: std::mem::take(&mut self.attributes),
-        });
-        self.committed = true;
-        self.class_ptr = None;
+            visible_annotations: std::mem::take(&mut self.visible_annotations),
+            invisible_annotations: std::mem::take(&mut self.invisible_annotations),
+            visible_type_annotations: std::mem::take(&mut self.visible_type_annotations),
+            invisible_type_annotations: std::mem::take(&mut self.invisible_type_annotations),
+        }
     }
 }
 
 impl Drop for FieldVisitor {
     fn drop(&mut self) {
-        if self.committed {
-            return;
-        }
-        let Some(ptr) = self.class_ptr else {
-            return;
-        };
-        // Safety: FieldVisitor is expected to be dropped before the ClassWriter it was created from.
-        unsafe {
-            let class = &mut *ptr;
-            class.fields.push(FieldData {
-                access_flags: self.access_flags,
-                name: std::mem::take(&mut self.name),
-                descriptor: std::mem::take(&mut self.descriptor),
-                attributes: std::mem::take(&mut self.attributes),
-            });
+        self.commit_visited();
+    }
+}
+
+impl class_reader::FieldVisitor for FieldVisitor {
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        FieldVisitor::visit_annotation(self, annotation.clone(), visible);
+    }
+
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if visible {
+            self.visible_type_annotations.push(annotation.clone());
+        } else {
+            self.invisible_type_annotations.push(annotation.clone());
         }
-        self.committed = true;
-        self.class_ptr = None;
+    }
+
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        FieldVisitor::visit_attribute(self, name, content);
+    }
+
+    fn visit_end(&mut self) {
+        self.commit_visited();
     }
 }
 
-pub struct CodeBody {
-    max_stack: u16,
-    max_locals: u16,
-    insns: NodeList,
-    exception_table: Vec<ExceptionTableEntry>,
-    attributes: Vec<AttributeInfo>,
+/// A visitor to visit a record component.
+pub struct RecordComponentVisitor {
+    component: RecordComponentNode,
+    visited: Option<VisitedMembers>,
 }
 
-impl CodeBody {
-    pub fn new(max_stack: u16, max_locals: u16, insns: NodeList) -> Self {
+impl RecordComponentVisitor {
+    pub fn new(name: &str, descriptor: &str, signature: Option<&str>) -> Self {
         Self {
-            max_stack,
-            max_locals,
-            insns,
-            exception_table: Vec::new(),
-            attributes: Vec::new(),
+            component: RecordComponentNode::new(name, descriptor, signature),
+            visited: None,
         }
     }
 
-    pub fn build(self, cp: &mut ConstantPoolBuilder) -> CodeAttribute {
-        let mut code = Vec::new();
-        let mut instructions = Vec::new();
-        let mut insn_nodes = Vec::new();
-        let mut label_offsets: HashMap<usize, u16> = HashMap::new();
-        let mut pending_lines: Vec<LineNumberInsnNode> = Vec::new();
-        let mut jump_fixups: Vec<JumpFixup> = Vec::new();
-        for node in self.insns.into_nodes() {
-            match node {
-                AbstractInsnNode::Insn(insn) => {
-                    let resolved = emit_insn(&mut code, insn, cp);
-                    instructions.push(resolved.clone());
-                    insn_nodes.push(AbstractInsnNode::Insn(resolved));
-                }
-                AbstractInsnNode::JumpLabel(node) => {
-                    let opcode = node.insn.opcode;
-                    let start = code.len();
-                    code.push(opcode);
-                    if is_wide_jump(opcode) {
-                        write_i4(&mut code, 0);
-                    } else {
-                        write_i2(&mut code, 0);
-                    }
-                    let insn = Insn::Jump(JumpInsnNode {
-                        insn: InsnNode { opcode },
-                        offset: 0,
-                    });
-                    instructions.push(insn.clone());
-                    insn_nodes.push(AbstractInsnNode::Insn(insn.clone()));
-                    jump_fixups.push(JumpFixup {
-                        start,
-                        opcode,
-                        target: node.target,
-                        insn_index: instructions.len() - 1,
-                        node_index: insn_nodes.len() - 1,
-                    });
-                }
-                AbstractInsnNode::Label(label) => {
-                    let offset = code.len();
-                    if offset <= u16::MAX as usize {
-                        label_offsets.insert(label.id, offset as u16);
-                    }
-                    insn_nodes.push(AbstractInsnNode::Label(label));
-                }
-                AbstractInsnNode::LineNumber(line) => {
-                    pending_lines.push(line);
-                    insn_nodes.push(AbstractInsnNode::LineNumber(line));
-                }
-            }
+    /// Adds an attribute to the record component.
+    pub fn add_attribute(&mut self, attr: AttributeInfo) -> &mut Self {
+        self.component.attributes.push(attr);
+        self
+    }
+
+    /// Adds an annotation to the record component.
+    pub fn visit_annotation(&mut self, annotation: AnnotationNode, visible: bool) -> &mut Self {
+        if visible {
+            self.component.visible_annotations.push(annotation);
+        } else {
+            self.component.invisible_annotations.push(annotation);
         }
-        for fixup in jump_fixups {
-            if let Some(target_offset) = label_offsets.get(&fixup.target.id) {
-                let offset = *target_offset as i32 - fixup.start as i32;
-                if is_wide_jump(fixup.opcode) {
-                    write_i4_at(&mut code, fixup.start + 1, offset);
-                } else {
-                    write_i2_at(&mut code, fixup.start + 1, offset as i16);
-                }
-                let resolved = Insn::Jump(JumpInsnNode {
-                    insn: InsnNode {
-                        opcode: fixup.opcode,
-                    },
-                    offset,
-                });
-                instructions[fixup.insn_index] = resolved.clone();
-                insn_nodes[fixup.node_index] = AbstractInsnNode::Insn(resolved);
-            }
+        self
+    }
+
+    /// Adds a type annotation on the type of the record component.
+    pub fn visit_type_annotation(
+        &mut self,
+        type_ref: TypeReference,
+        type_path: Option<TypePath>,
+        annotation: AnnotationNode,
+        visible: bool,
+    ) -> &mut Self {
+        let node = TypeAnnotationNode::new(type_ref, type_path, annotation);
+        if visible {
+            self.component.visible_type_annotations.push(node);
+        } else {
+            self.component.invisible_type_annotations.push(node);
         }
-        let mut attributes = self.attributes;
-        if !pending_lines.is_empty() {
-            let mut entries = Vec::new();
-            for line in pending_lines {
-                if let Some(start_pc) = label_offsets.get(&line.start.id) {
-                    entries.push(LineNumber {
-                        start_pc: *start_pc,
-                        line_number: line.line,
-                    });
-                }
-            }
-            if !entries.is_empty() {
-                attributes.push(AttributeInfo::LineNumberTable { entries });
+        self
+    }
+
+    /// Adds a non-standard attribute to the record component, or a standard one whose `content`
+    /// does not refer to the constant pool.
+    pub fn visit_attribute(&mut self, name: &str, content: &[u8]) -> &mut Self {
+        self.component.attributes.push(AttributeInfo::Unknown {
+            name: name.to_string(),
+            info: content.to_vec(),
+        });
+        self
+    }
+
+    /// Finalizes the record component and attaches it to the parent `ClassWriter`.
+    pub fn visit_end(self, class: &mut ClassWriter) {
+        class.add_visited_members();
+        class
+            .record_components
+            .get_or_insert_with(Vec::new)
+            .push(self.component);
+    }
+}
+
+impl class_reader::RecordComponentVisitor for RecordComponentVisitor {
+    fn visit_annotation(&mut self, annotation: &AnnotationNode, visible: bool) {
+        RecordComponentVisitor::visit_annotation(self, annotation.clone(), visible);
+    }
+
+    fn visit_type_annotation(&mut self, annotation: &TypeAnnotationNode, visible: bool) {
+        if visible {
+            self.component.visible_type_annotations.push(annotation.clone());
+        } else {
+            self.component.invisible_type_annotations.push(annotation.clone());
+        }
+    }
+
+    fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+        RecordComponentVisitor::visit_attribute(self, name, content);
+    }
+
+    fn visit_end(&mut self) {
+        let Some(visited) = self.visited.take() else {
+            return;
+        };
+        let visitor = std::mem::replace(self, RecordComponentVisitor::new("", "", None));
+        let mut visited = visited.lock().unwrap_or_else(|error| error.into_inner());
+        visited.push(VisitedMember::RecordComponent(visitor));
+    }
+}
+
+/// A visitor to visit a Java module.
+///
+/// Package names are in internal form (e.g., `com/example/api`) and class names are internal names.
+pub struct ModuleVisitor {
+    module: ModuleNode,
+    visited: Option<VisitedMembers>,
+}
+
+impl ModuleVisitor {
+    pub fn new(name: &str, access_flags: u16, version: Option<&str>) -> Self {
+        Self {
+            module: ModuleNode::new(name, access_flags, version),
+            visited: None,
+        }
+    }
+
+    /// Sets the main class of the module.
+    pub fn visit_main_class(&mut self, main_class: &str) -> &mut Self {
+        self.module.main_class = Some(main_class.to_string());
+        self
+    }
+
+    /// Adds a package of the module.
+    pub fn visit_package(&mut self, package: &str) -> &mut Self {
+        self.module.packages.push(package.to_string());
+        self
+    }
+
+    /// Adds a dependence of the module.
+    pub fn visit_require(
+        &mut self,
+        module: &str,
+        access_flags: u16,
+        version: Option<&str>,
+    ) -> &mut Self {
+        self.module.requires.push(ModuleRequireNode {
+            module: module.to_string(),
+            access_flags,
+            version: version.map(|value| value.to_string()),
+        });
+        self
+    }
+
+    /// Adds an exported package. Pass no modules for an unqualified export.
+    pub fn visit_export(&mut self, package: &str, access_flags: u16, modules: &[&str]) -> &mut Self {
+        self.module.exports.push(ModuleExportNode {
+            package: package.to_string(),
+            access_flags,
+            modules: modules.iter().map(|value| (*value).to_string()).collect(),
+        });
+        self
+    }
+
+    /// Adds an open package. Pass no modules for an unqualified open.
+    pub fn visit_open(&mut self, package: &str, access_flags: u16, modules: &[&str]) -> &mut Self {
+        self.module.opens.push(ModuleOpenNode {
+            package: package.to_string(),
+            access_flags,
+            modules: modules.iter().map(|value| (*value).to_string()).collect(),
+        });
+        self
+    }
+
+    /// Adds a service used by the module.
+    pub fn visit_use(&mut self, service: &str) -> &mut Self {
+        self.module.uses.push(service.to_string());
+        self
+    }
+
+    /// Adds implementations of a service.
+    pub fn visit_provide(&mut self, service: &str, providers: &[&str]) -> &mut Self {
+        self.module.provides.push(ModuleProvideNode {
+            service: service.to_string(),
+            providers: providers.iter().map(|value| (*value).to_string()).collect(),
+        });
+        self
+    }
+
+    /// Finalizes the module and attaches it to the parent `ClassWriter`.
+    pub fn visit_end(self, class: &mut ClassWriter) {
+        class.add_visited_members();
+        class.cp.module(&self.module.name);
+        for package in &self.module.packages {
+            class.cp.package(package);
+        }
+        class.module = Some(self.module);
+    }
+}
+
+impl class_reader::ModuleVisitor for ModuleVisitor {
+    fn visit_main_class(&mut self, main_class: &str) {
+        ModuleVisitor::visit_main_class(self, main_class);
+    }
+
+    fn visit_package(&mut self, package: &str) {
+        ModuleVisitor::visit_package(self, package);
+    }
+
+    fn visit_require(&mut self, module: &str, access_flags: u16, version: Option<&str>) {
+        ModuleVisitor::visit_require(self, module, access_flags, version);
+    }
+
+    fn visit_export(&mut self, package: &str, access_flags: u16, modules: &[String]) {
+        let modules: Vec<&str> = modules.iter().map(String::as_str).collect();
+        ModuleVisitor::visit_export(self, package, access_flags, &modules);
+    }
+
+    fn visit_open(&mut self, package: &str, access_flags: u16, modules: &[String]) {
+        let modules: Vec<&str> = modules.iter().map(String::as_str).collect();
+        ModuleVisitor::visit_open(self, package, access_flags, &modules);
+    }
+
+    fn visit_use(&mut self, service: &str) {
+        ModuleVisitor::visit_use(self, service);
+    }
+
+    fn visit_provide(&mut self, service: &str, providers: &[String]) {
+        let providers: Vec<&str> = providers.iter().map(String::as_str).collect();
+        ModuleVisitor::visit_provide(self, service, &providers);
+    }
+
+    fn visit_end(&mut self) {
+        let Some(visited) = self.visited.take() else {
+            return;
+        };
+        let visitor = std::mem::replace(self, ModuleVisitor::new("", 0, None));
+        let mut visited = visited.lock().unwrap_or_else(|error| error.into_inner());
+        visited.push(VisitedMember::Module(visitor));
+    }
+}
+
+pub struct CodeBody {
+    max_stack: u16,
+    max_locals: u16,
+    insns: NodeList,
+    exception_table: Vec<ExceptionTableEntry>,
+    try_catch_blocks: Vec<TryCatchBlockNode>,
+    local_variables: Vec<LocalVariableNode>,
+    attributes: Vec<AttributeInfo>,
+}
+
+impl CodeBody {
+    pub fn new(max_stack: u16, max_locals: u16, insns: NodeList) -> Self {
+        Self {
+            max_stack,
+            max_locals,
+            insns,
+            exception_table: Vec::new(),
+            try_catch_blocks: Vec::new(),
+            local_variables: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Lays out the code and resolves the labels of its try-catch blocks and local variables,
+    /// which are appended to the exception table and the `LocalVariableTable` and
+    /// `LocalVariableTypeTable` attributes.
+    pub fn build(self, cp: &mut ConstantPoolBuilder) -> Result<CodeAttribute, ClassWriteError> {
+        let layout = layout_code(self.insns.into_nodes(), |code, insn| {
+            emit_insn(code, insn, Some(cp))
+        })?;
+        // Like branch targets, labels that are not placed in the code resolve to offset 0.
+        let offset = |label: &LabelNode| layout.label_offsets.get(&label.id).copied().unwrap_or(0);
+
+        let mut exception_table = self.exception_table;
+        for block in &self.try_catch_blocks {
+            exception_table.push(ExceptionTableEntry {
+                start_pc: offset(&block.start),
+                end_pc: offset(&block.end),
+                handler_pc: offset(&block.handler),
+                catch_type: block.catch_type.as_deref().map_or(0, |name| cp.class(name)),
+            });
+        }
+
+        let mut attributes = self.attributes;
+        let entries = line_number_entries(&layout);
+        if !entries.is_empty() {
+            attributes.push(AttributeInfo::LineNumberTable { entries });
+        }
+        let mut local_variables = Vec::new();
+        let mut local_variable_types = Vec::new();
+        for variable in &self.local_variables {
+            let start_pc = offset(&variable.start);
+            let length = offset(&variable.end).saturating_sub(start_pc);
+            let name_index = cp.utf8(&variable.name);
+            local_variables.push(LocalVariable {
+                start_pc,
+                length,
+                name_index,
+                descriptor_index: cp.utf8(&variable.descriptor),
+                index: variable.index,
+            });
+            if let Some(signature) = &variable.signature {
+                local_variable_types.push(LocalVariableType {
+                    start_pc,
+                    length,
+                    name_index,
+                    signature_index: cp.utf8(signature),
+                    index: variable.index,
+                });
             }
         }
-        CodeAttribute {
+        if !local_variables.is_empty() {
+            attributes.push(AttributeInfo::LocalVariableTable {
+                entries: local_variables,
+            });
+        }
+        if !local_variable_types.is_empty() {
+            attributes.push(AttributeInfo::LocalVariableTypeTable {
+                entries: local_variable_types,
+            });
+        }
+
+        Ok(CodeAttribute {
             max_stack: self.max_stack,
             max_locals: self.max_locals,
-            code,
-            instructions,
-            insn_nodes,
-            exception_table: self.exception_table,
-            try_catch_blocks: Vec::new(),
+            code: layout.code,
+            instructions: layout.instructions,
+            instruction_offsets: layout.insn_offsets.iter().map(|&offset| offset as u16).collect(),
+            insn_nodes: layout.insn_nodes,
+            exception_table,
+            try_catch_blocks: self.try_catch_blocks,
+            local_variables: self.local_variables,
             attributes,
-        }
+        })
+    }
+}
+
+/// The bytecode of a label-based node list, with the branch targets resolved.
+#[derive(Default)]
+struct CodeLayout {
+    code: Vec<u8>,
+    instructions: Vec<Insn>,
+    insn_nodes: Vec<AbstractInsnNode>,
+    label_offsets: HashMap<usize, u16>,
+    line_numbers: Vec<LineNumberInsnNode>,
+    /// The stack map frames of the node list, with the offset of the instruction they apply to.
+    frames: Vec<(usize, Frame)>,
+    /// Whether a conditional jump was widened by inverting it over a `GOTO_W`, which adds a
+    /// branch target that has no frame in `frames`.
+    inverted_jumps: bool,
+    /// Branch targets that are not placed in the node list; they resolve to offset 0.
+    missing_labels: Vec<LabelNode>,
+    /// The bytecode offset of each instruction of the node list, in order.
+    insn_offsets: Vec<usize>,
+}
+
+impl CodeLayout {
+    fn label_offset(&self, label: &LabelNode) -> Result<u16, ClassWriteError> {
+        self.label_offsets
+            .get(&label.id)
+            .copied()
+            .ok_or(ClassWriteError::MissingLabel(label.id))
+    }
+}
+
+/// Encodes the frames of `layout` as `StackMapTable` entries, with `class_index` giving the
+/// constant pool index of a class name.
+fn stack_map_entries(
+    layout: &CodeLayout,
+    mut class_index: impl FnMut(&str) -> u16,
+) -> Result<Vec<StackMapFrame>, ClassWriteError> {
+    let mut value = |value: &FrameValue| -> Result<VerificationTypeInfo, ClassWriteError> {
+        Ok(match value {
+            FrameValue::Top => VerificationTypeInfo::Top,
+            FrameValue::Integer => VerificationTypeInfo::Integer,
+            FrameValue::Float => VerificationTypeInfo::Float,
+            FrameValue::Long => VerificationTypeInfo::Long,
+            FrameValue::Double => VerificationTypeInfo::Double,
+            FrameValue::Null => VerificationTypeInfo::Null,
+            FrameValue::UninitializedThis => VerificationTypeInfo::UninitializedThis,
+            FrameValue::Object(name) => VerificationTypeInfo::Object {
+                cpool_index: class_index(name),
+            },
+            FrameValue::Uninitialized(label) => VerificationTypeInfo::Uninitialized {
+                offset: layout.label_offset(&LabelNode::from_label(*label))?,
+            },
+        })
+    };
+    let mut entries = Vec::with_capacity(layout.frames.len());
+    let mut previous = None;
+    for (offset, frame) in &layout.frames {
+        let offset_delta = match previous {
+            None => *offset,
+            Some(previous) if *offset > previous => offset - previous - 1,
+            Some(_) => {
+                return Err(ClassWriteError::FrameComputation(format!(
+                    "two stack map frames at code offset {offset}"
+                )));
+            }
+        } as u16;
+        previous = Some(*offset);
+        entries.push(match frame {
+            Frame::Same if offset_delta < 64 => StackMapFrame::SameFrame { offset_delta },
+            Frame::Same => StackMapFrame::SameFrameExtended { offset_delta },
+            Frame::Same1 { stack } if offset_delta < 64 => {
+                StackMapFrame::SameLocals1StackItemFrame {
+                    offset_delta,
+                    stack: value(stack)?,
+                }
+            }
+            Frame::Same1 { stack } => StackMapFrame::SameLocals1StackItemFrameExtended {
+                offset_delta,
+                stack: value(stack)?,
+            },
+            Frame::Chop { count } => StackMapFrame::ChopFrame {
+                offset_delta,
+                k: *count,
+            },
+            Frame::Append { locals } => StackMapFrame::AppendFrame {
+                offset_delta,
+                locals: locals.iter().map(&mut value).collect::<Result<_, _>>()?,
+            },
+            Frame::Full { locals, stack } => StackMapFrame::FullFrame {
+                offset_delta,
+                locals: locals.iter().map(&mut value).collect::<Result<_, _>>()?,
+                stack: stack.iter().map(&mut value).collect::<Result<_, _>>()?,
+            },
+        });
     }
+    Ok(entries)
 }
 
-#[derive(Debug, Clone, Copy)]
-struct JumpFixup {
+/// A jump or switch whose targets are patched once every label has an offset.
+struct TargetFixup {
     start: usize,
-    opcode: u8,
-    target: LabelNode,
+    node: AbstractInsnNode,
     insn_index: usize,
     node_index: usize,
+    /// The index of the jump or switch in the node list being laid out.
+    source_index: usize,
 }
 
-fn is_wide_jump(opcode: u8) -> bool {
-    matches!(opcode, opcodes::GOTO_W | opcodes::JSR_W)
+/// Emits `nodes` with `emit`, then patches the jump and switch offsets to their labels.
+///
+/// In the returned layout, label-based jumps and switches are replaced by their
+/// offset-based form. Jumps whose offset does not fit in 16 bits are widened as in ASM:
+/// `GOTO` and `JSR` become `GOTO_W` and `JSR_W`, and a conditional jump is inverted to skip
+/// over a `GOTO_W` to its target. Since widening moves the code that follows, the layout is
+/// redone until every offset fits.
+fn layout_code<E>(
+    nodes: Vec<AbstractInsnNode>,
+    mut emit: impl FnMut(&mut Vec<u8>, Insn) -> Result<Insn, E>,
+) -> Result<CodeLayout, E> {
+    let mut widened = std::collections::HashSet::new();
+    loop {
+        let (layout, overflowing) = layout_code_pass(&nodes, &widened, &mut emit)?;
+        if overflowing.is_empty() {
+            return Ok(layout);
+        }
+        widened.extend(overflowing);
+    }
+}
+
+/// Lays out `nodes` once, widening the jumps at the indices in `widened`, and returns the
+/// indices of the other jumps whose offset overflows.
+fn layout_code_pass<E>(
+    nodes: &[AbstractInsnNode],
+    widened: &std::collections::HashSet<usize>,
+    emit: &mut impl FnMut(&mut Vec<u8>, Insn) -> Result<Insn, E>,
+) -> Result<(CodeLayout, Vec<usize>), E> {
+    let mut layout = CodeLayout::default();
+    let mut fixups = Vec::new();
+    // Emits an instruction, recording a fixup for the targets of jumps and switches.
+    let mut emit_insn_node = |layout: &mut CodeLayout, node: AbstractInsnNode, source_index| {
+        let start = layout.code.len();
+        let emitted = emit(&mut layout.code, resolve_targets(&node, |_| 0))?;
+        layout.instructions.push(emitted.clone());
+        layout.insn_nodes.push(AbstractInsnNode::Insn(emitted));
+        if !matches!(node, AbstractInsnNode::Insn(_)) {
+            fixups.push(TargetFixup {
+                start,
+                node,
+                insn_index: layout.instructions.len() - 1,
+                node_index: layout.insn_nodes.len() - 1,
+                source_index,
+            });
+        }
+        Ok(())
+    };
+    for (index, node) in nodes.iter().enumerate() {
+        let start = layout.code.len();
+        match node {
+            AbstractInsnNode::Label(label) => {
+                if start <= u16::MAX as usize {
+                    layout.label_offsets.insert(label.id, start as u16);
+                }
+                layout.insn_nodes.push(AbstractInsnNode::Label(*label));
+            }
+            AbstractInsnNode::LineNumber(line) => {
+                layout.line_numbers.push(*line);
+                layout.insn_nodes.push(AbstractInsnNode::LineNumber(*line));
+            }
+            AbstractInsnNode::Frame(frame) => {
+                layout.frames.push((start, frame.clone()));
+                layout.insn_nodes.push(AbstractInsnNode::Frame(frame.clone()));
+            }
+            AbstractInsnNode::JumpLabel(jump) if widened.contains(&index) => {
+                layout.insn_offsets.push(start);
+                let wide_jump = |opcode: u8, target: LabelNode| {
+                    AbstractInsnNode::JumpLabel(JumpLabelInsnNode {
+                        insn: opcode.into(),
+                        target,
+                    })
+                };
+                let goto_w = wide_jump(opcodes::GOTO_W, jump.target);
+                match jump.insn.opcode {
+                    opcodes::GOTO => emit_insn_node(&mut layout, goto_w, index)?,
+                    opcodes::JSR => {
+                        let jsr_w = wide_jump(opcodes::JSR_W, jump.target);
+                        emit_insn_node(&mut layout, jsr_w, index)?;
+                    }
+                    opcode => {
+                        let skip = LabelNode::new();
+                        let inverted = wide_jump(inverted_jump_opcode(opcode), skip);
+                        emit_insn_node(&mut layout, inverted, index)?;
+                        emit_insn_node(&mut layout, goto_w, index)?;
+                        layout.inverted_jumps = true;
+                        if layout.code.len() <= u16::MAX as usize {
+                            layout.label_offsets.insert(skip.id, layout.code.len() as u16);
+                        }
+                        layout.insn_nodes.push(AbstractInsnNode::Label(skip));
+                    }
+                }
+            }
+            node => {
+                layout.insn_offsets.push(start);
+                emit_insn_node(&mut layout, node.clone(), index)?;
+            }
+        }
+    }
+    let mut overflowing = Vec::new();
+    for fixup in fixups {
+        let resolved = resolve_targets(&fixup.node, |label| {
+            match layout.label_offsets.get(&label.id) {
+                Some(offset) => *offset as i32 - fixup.start as i32,
+                None => {
+                    layout.missing_labels.push(*label);
+                    0
+                }
+            }
+        });
+        if let Insn::Jump(jump) = &resolved
+            && !is_wide_jump(jump.insn.opcode)
+            && i16::try_from(jump.offset).is_err()
+        {
+            overflowing.push(fixup.source_index);
+            continue;
+        }
+        write_targets(&mut layout.code, fixup.start, &resolved);
+        layout.instructions[fixup.insn_index] = resolved.clone();
+        layout.insn_nodes[fixup.node_index] = AbstractInsnNode::Insn(resolved);
+    }
+    Ok((layout, overflowing))
 }
 
-fn jump_size(opcode: u8) -> usize {
-    if is_wide_jump(opcode) { 5 } else { 3 }
+/// Returns the conditional jump opcode whose condition is the negation of that of `opcode`
+/// (e.g., `IFNE` for `IFEQ`).
+fn inverted_jump_opcode(opcode: u8) -> u8 {
+    match opcode {
+        opcodes::IFNULL => opcodes::IFNONNULL,
+        opcodes::IFNONNULL => opcodes::IFNULL,
+        // IFEQ..=IF_ACMPNE come in pairs of opposite conditions, starting at an odd opcode.
+        opcode => ((opcode + 1) ^ 1) - 1,
+    }
 }
 
-fn build_code_attribute(
-    max_stack: u16,
-    max_locals: u16,
-    insns: NodeList,
-    cp: &mut ConstantPoolBuilder,
-    exception_table: Vec<ExceptionTableEntry>,
-    attributes: Vec<AttributeInfo>,
-) -> CodeAttribute {
-    CodeBody {
-        max_stack,
-        max_locals,
-        insns,
-        exception_table,
-        attributes,
+/// Converts a label-based jump or switch to its offset-based form, using `offset` to compute
+/// the offset of each target relative to the instruction.
+fn resolve_targets(node: &AbstractInsnNode, mut offset: impl FnMut(&LabelNode) -> i32) -> Insn {
+    match node {
+        AbstractInsnNode::JumpLabel(node) => Insn::Jump(JumpInsnNode {
+            insn: node.insn.clone(),
+            offset: offset(&node.target),
+        }),
+        AbstractInsnNode::TableSwitchLabel(node) => Insn::TableSwitch(TableSwitchInsnNode {
+            insn: node.insn.clone(),
+            default_offset: offset(&node.default),
+            low: node.low,
+            high: node.high,
+            offsets: node.labels.iter().map(&mut offset).collect(),
+        }),
+        AbstractInsnNode::LookupSwitchLabel(node) => Insn::LookupSwitch(LookupSwitchInsnNode {
+            insn: node.insn.clone(),
+            default_offset: offset(&node.default),
+            pairs: node
+                .keys
+                .iter()
+                .zip(&node.labels)
+                .map(|(key, label)| (*key, offset(label)))
+                .collect(),
+        }),
+        AbstractInsnNode::Insn(insn) => insn.clone(),
+        AbstractInsnNode::Label(_)
+        | AbstractInsnNode::LineNumber(_)
+        | AbstractInsnNode::Frame(_) => {
+            unreachable!("labels, line numbers and frames are not instructions")
+        }
     }
-    .build(cp)
 }
 
-fn build_code_from_insn_list(insns: &InsnList) -> Result<(Vec<u8>, Vec<Insn>), ClassWriteError> {
-    let mut code = Vec::new();
-    let mut instructions = Vec::with_capacity(insns.insns().len());
-    for insn in insns.insns() {
-        let emitted = emit_insn_raw(&mut code, insn.clone())?;
-        instructions.push(emitted);
+/// Overwrites the branch offsets of the jump or switch emitted at `start`.
+fn write_targets(code: &mut [u8], start: usize, insn: &Insn) {
+    let operands = start + 1 + (4 - ((start + 1) % 4)) % 4;
+    match insn {
+        Insn::Jump(node) if is_wide_jump(node.insn.opcode) => {
+            write_i4_at(code, start + 1, node.offset);
+        }
+        Insn::Jump(node) => write_i2_at(code, start + 1, node.offset as i16),
+        Insn::TableSwitch(node) => {
+            write_i4_at(code, operands, node.default_offset);
+            for (index, offset) in node.offsets.iter().enumerate() {
+                write_i4_at(code, operands + 12 + 4 * index, *offset);
+            }
+        }
+        Insn::LookupSwitch(node) => {
+            write_i4_at(code, operands, node.default_offset);
+            for (index, (_, offset)) in node.pairs.iter().enumerate() {
+                write_i4_at(code, operands + 12 + 8 * index, *offset);
+            }
+        }
+        _ => {}
     }
-    Ok((code, instructions))
 }
 
-fn emit_insn_raw(code: &mut Vec<u8>, insn: Insn) -> Result<Insn, ClassWriteError> {
+fn line_number_entries(layout: &CodeLayout) -> Vec<LineNumber> {
+    layout
+        .line_numbers
+        .iter()
+        .filter_map(|line| {
+            let start_pc = *layout.label_offsets.get(&line.start.id)?;
+            Some(LineNumber {
+                start_pc,
+                line_number: line.line,
+            })
+        })
+        .collect()
+}
+
+fn is_wide_jump(opcode: u8) -> bool {
+    matches!(opcode, opcodes::GOTO_W | opcodes::JSR_W)
+}
+
+/// Writes the bytecode of `insn` and returns it with its constants resolved to indices.
+/// Symbolic member references and constants are added to `cp`, and are an error without one.
+fn emit_insn(
+    code: &mut Vec<u8>,
+    insn: Insn,
+    cp: Option<&mut ConstantPoolBuilder>,
+) -> Result<Insn, ClassWriteError> {
     let offset = code.len();
     let out = match insn {
         Insn::Simple(node) => {
@@ -986,9 +2541,21 @@ fn emit_insn_raw(code: &mut Vec<u8>, insn: Insn) -> Result<Insn, ClassWriteError
             Insn::Simple(node)
         }
         Insn::Int(node) => {
+            let range = match node.insn.opcode {
+                opcodes::BIPUSH => i8::MIN as i32..=i8::MAX as i32,
+                opcodes::SIPUSH => i16::MIN as i32..=i16::MAX as i32,
+                // The `atype` codes of `boolean` to `long`.
+                opcodes::NEWARRAY => 4..=11,
+                _ => i8::MIN as i32..=i8::MAX as i32,
+            };
+            if !range.contains(&node.operand) {
+                return Err(ClassWriteError::InvalidOperand {
+                    opcode: node.insn.opcode,
+                    operand: node.operand,
+                });
+            }
             code.push(node.insn.opcode);
             match node.insn.opcode {
-                opcodes::BIPUSH => write_i1(code, node.operand as i8),
                 opcodes::SIPUSH => write_i2(code, node.operand as i16),
                 opcodes::NEWARRAY => write_u1(code, node.operand as u8),
                 _ => write_i1(code, node.operand as i8),
@@ -996,8 +2563,7 @@ fn emit_insn_raw(code: &mut Vec<u8>, insn: Insn) -> Result<Insn, ClassWriteError
             Insn::Int(node)
         }
         Insn::Var(node) => {
-            code.push(node.insn.opcode);
-            write_u1(code, node.var_index as u8);
+            write_var_insn(code, node.insn.opcode, node.var_index);
             Insn::Var(node)
         }
         Insn::Type(node) => {
@@ -1006,30 +2572,47 @@ fn emit_insn_raw(code: &mut Vec<u8>, insn: Insn) -> Result<Insn, ClassWriteError
             Insn::Type(node)
         }
         Insn::Field(node) => {
-            let index = match node.field_ref {
-                MemberRef::Index(index) => index,
-                MemberRef::Symbolic { .. } => {
+            let (index, resolved) = match (&node.field_ref, cp) {
+                (MemberRef::Index(index), _) => (*index, node),
+                (MemberRef::Symbolic { .. }, Some(cp)) => resolve_field_ref(node, cp),
+                (MemberRef::Symbolic { .. }, None) => {
                     return Err(ClassWriteError::FrameComputation(
                         "symbolic field ref in method instructions".to_string(),
                     ));
                 }
             };
-            code.push(node.insn.opcode);
+            code.push(resolved.insn.opcode);
             write_u2(code, index);
-            Insn::Field(node)
+            Insn::Field(resolved)
         }
         Insn::Method(node) => {
-            let index = match node.method_ref {
-                MemberRef::Index(index) => index,
-                MemberRef::Symbolic { .. } => {
+            let interface_count = if node.insn.opcode == opcodes::INVOKEINTERFACE {
+                method_ref_interface_count(&node.method_ref)
+            } else {
+                0
+            };
+            let (index, resolved) = match (&node.method_ref, cp) {
+                (MemberRef::Index(index), _) => (*index, node),
+                (MemberRef::Symbolic { .. }, Some(cp)) => resolve_method_ref(node, cp),
+                (MemberRef::Symbolic { .. }, None) => {
                     return Err(ClassWriteError::FrameComputation(
                         "symbolic method ref in method instructions".to_string(),
                     ));
                 }
             };
-            code.push(node.insn.opcode);
+            code.push(resolved.insn.opcode);
             write_u2(code, index);
-            Insn::Method(node)
+            if resolved.insn.opcode == opcodes::INVOKEINTERFACE {
+                write_u1(code, interface_count);
+                write_u1(code, 0);
+                Insn::InvokeInterface(InvokeInterfaceInsnNode {
+                    insn: opcodes::INVOKEINTERFACE.into(),
+                    method_index: index,
+                    count: interface_count,
+                })
+            } else {
+                Insn::Method(resolved)
+            }
         }
         Insn::InvokeInterface(node) => {
             code.push(node.insn.opcode);
@@ -1053,39 +2636,25 @@ fn emit_insn_raw(code: &mut Vec<u8>, insn: Insn) -> Result<Insn, ClassWriteError
             Insn::Jump(node)
         }
         Insn::Ldc(node) => {
-            let index = match node.value {
-                LdcValue::Index(index) => index,
-                _ => {
+            let (opcode, index, resolved) = match (&node.value, cp) {
+                (LdcValue::Index(index), _) => ldc_index_insn(node.insn.opcode, *index),
+                (_, Some(cp)) => resolve_ldc(node, cp),
+                (_, None) => {
                     return Err(ClassWriteError::FrameComputation(
                         "non-index ldc in method instructions".to_string(),
                     ));
                 }
             };
-            let opcode = if matches!(
-                node.insn.opcode,
-                opcodes::LDC | opcodes::LDC_W | opcodes::LDC2_W
-            ) {
-                node.insn.opcode
-            } else if index <= 0xFF {
-                opcodes::LDC
-            } else {
-                opcodes::LDC_W
-            };
             code.push(opcode);
             if opcode == opcodes::LDC {
                 write_u1(code, index as u8);
             } else {
                 write_u2(code, index);
             }
-            Insn::Ldc(LdcInsnNode {
-                insn: opcode.into(),
-                value: LdcValue::Index(index),
-            })
+            Insn::Ldc(resolved)
         }
         Insn::Iinc(node) => {
-            code.push(node.insn.opcode);
-            write_u1(code, node.var_index as u8);
-            write_i1(code, node.increment as i8);
+            write_iinc_insn(code, node.var_index, node.increment);
             Insn::Iinc(node)
         }
         Insn::TableSwitch(node) => {
@@ -1120,128 +2689,6 @@ fn emit_insn_raw(code: &mut Vec<u8>, insn: Insn) -> Result<Insn, ClassWriteError
     Ok(out)
 }
 
-fn emit_insn(code: &mut Vec<u8>, insn: Insn, cp: &mut ConstantPoolBuilder) -> Insn {
-    let offset = code.len();
-    match insn {
-        Insn::Simple(node) => {
-            code.push(node.opcode);
-            Insn::Simple(node)
-        }
-        Insn::Int(node) => {
-            code.push(node.insn.opcode);
-            match node.insn.opcode {
-                opcodes::BIPUSH => write_i1(code, node.operand as i8),
-                opcodes::SIPUSH => write_i2(code, node.operand as i16),
-                opcodes::NEWARRAY => write_u1(code, node.operand as u8),
-                _ => write_i1(code, node.operand as i8),
-            }
-            Insn::Int(node)
-        }
-        Insn::Var(node) => {
-            code.push(node.insn.opcode);
-            write_u1(code, node.var_index as u8);
-            Insn::Var(node)
-        }
-        Insn::Type(node) => {
-            code.push(node.insn.opcode);
-            write_u2(code, node.type_index);
-            Insn::Type(node)
-        }
-        Insn::Field(node) => {
-            code.push(node.insn.opcode);
-            let (index, resolved) = resolve_field_ref(node, cp);
-            write_u2(code, index);
-            Insn::Field(resolved)
-        }
-        Insn::Method(node) => {
-            code.push(node.insn.opcode);
-            let interface_count = if node.insn.opcode == opcodes::INVOKEINTERFACE {
-                method_ref_interface_count(&node.method_ref)
-            } else {
-                0
-            };
-            let (index, resolved) = resolve_method_ref(node, cp);
-            write_u2(code, index);
-            if resolved.insn.opcode == opcodes::INVOKEINTERFACE {
-                write_u1(code, interface_count);
-                write_u1(code, 0);
-                Insn::InvokeInterface(InvokeInterfaceInsnNode {
-                    insn: opcodes::INVOKEINTERFACE.into(),
-                    method_index: index,
-                    count: interface_count,
-                })
-            } else {
-                Insn::Method(resolved)
-            }
-        }
-        Insn::InvokeInterface(node) => {
-            code.push(node.insn.opcode);
-            write_u2(code, node.method_index);
-            write_u1(code, node.count);
-            write_u1(code, 0);
-            Insn::InvokeInterface(node)
-        }
-        Insn::InvokeDynamic(node) => {
-            code.push(node.insn.opcode);
-            write_u2(code, node.method_index);
-            write_u2(code, 0);
-            Insn::InvokeDynamic(node)
-        }
-        Insn::Jump(node) => {
-            code.push(node.insn.opcode);
-            match node.insn.opcode {
-                opcodes::GOTO_W | opcodes::JSR_W => write_i4(code, node.offset),
-                _ => write_i2(code, node.offset as i16),
-            }
-            Insn::Jump(node)
-        }
-        Insn::Ldc(node) => {
-            let (opcode, index, resolved) = resolve_ldc(node, cp);
-            code.push(opcode);
-            if opcode == opcodes::LDC {
-                write_u1(code, index as u8);
-            } else {
-                write_u2(code, index);
-            }
-            Insn::Ldc(resolved)
-        }
-        Insn::Iinc(node) => {
-            code.push(node.insn.opcode);
-            write_u1(code, node.var_index as u8);
-            write_i1(code, node.increment as i8);
-            Insn::Iinc(node)
-        }
-        Insn::TableSwitch(node) => {
-            code.push(node.insn.opcode);
-            write_switch_padding(code, offset);
-            write_i4(code, node.default_offset);
-            write_i4(code, node.low);
-            write_i4(code, node.high);
-            for value in &node.offsets {
-                write_i4(code, *value);
-            }
-            Insn::TableSwitch(node)
-        }
-        Insn::LookupSwitch(node) => {
-            code.push(node.insn.opcode);
-            write_switch_padding(code, offset);
-            write_i4(code, node.default_offset);
-            write_i4(code, node.pairs.len() as i32);
-            for (key, value) in &node.pairs {
-                write_i4(code, *key);
-                write_i4(code, *value);
-            }
-            Insn::LookupSwitch(node)
-        }
-        Insn::MultiANewArray(node) => {
-            code.push(node.insn.opcode);
-            write_u2(code, node.type_index);
-            write_u1(code, node.dimensions);
-            Insn::MultiANewArray(node)
-        }
-    }
-}
-
 fn resolve_field_ref(node: FieldInsnNode, cp: &mut ConstantPoolBuilder) -> (u16, FieldInsnNode) {
     match node.field_ref {
         MemberRef::Index(index) => (index, node),
@@ -1249,6 +2696,7 @@ fn resolve_field_ref(node: FieldInsnNode, cp: &mut ConstantPoolBuilder) -> (u16,
             owner,
             name,
             descriptor,
+            ..
         } => {
             let index = cp.field_ref(&owner, &name, &descriptor);
             (
@@ -1269,8 +2717,9 @@ fn resolve_method_ref(node: MethodInsnNode, cp: &mut ConstantPoolBuilder) -> (u1
             owner,
             name,
             descriptor,
+            is_interface,
         } => {
-            let index = if node.insn.opcode == opcodes::INVOKEINTERFACE {
+            let index = if is_interface || node.insn.opcode == opcodes::INVOKEINTERFACE {
                 cp.interface_method_ref(&owner, &name, &descriptor)
             } else {
                 cp.method_ref(&owner, &name, &descriptor)
@@ -1291,36 +2740,29 @@ fn method_ref_interface_count(method_ref: &MemberRef) -> u8 {
         MemberRef::Symbolic { descriptor, .. } => descriptor.as_str(),
         MemberRef::Index(_) => return 1,
     };
-    let Ok((args, _ret)) = parse_method_descriptor(descriptor) else {
+    let Ok(method_type) = Type::try_get_method_type(descriptor) else {
         return 1;
     };
     let mut count = 1u16; // include receiver
-    for arg in args {
-        count += match arg {
-            FieldType::Long | FieldType::Double => 2,
-            _ => 1,
-        };
+    for arg in method_type.get_argument_types().unwrap_or_default() {
+        count += arg.get_size() as u16;
     }
     count.min(u8::MAX as u16) as u8
 }
 
+/// Keeps an explicit `LDC_W` or `LDC2_W` opcode of a resolved `LDC`, and otherwise picks the
+/// shortest one that fits `index`.
+fn ldc_index_insn(opcode: u8, index: u16) -> (u8, u16, LdcInsnNode) {
+    let opcode = match opcode {
+        opcodes::LDC_W | opcodes::LDC2_W => opcode,
+        _ => ldc_opcode(index, false),
+    };
+    (opcode, index, LdcInsnNode::from_index(opcode, index))
+}
+
 fn resolve_ldc(node: LdcInsnNode, cp: &mut ConstantPoolBuilder) -> (u8, u16, LdcInsnNode) {
     match node.value {
-        LdcValue::Index(index) => {
-            let opcode = if index <= 0xFF {
-                opcodes::LDC
-            } else {
-                opcodes::LDC_W
-            };
-            (
-                opcode,
-                index,
-                LdcInsnNode {
-                    insn: opcode.into(),
-                    value: LdcValue::Index(index),
-                },
-            )
-        }
+        LdcValue::Index(index) => ldc_index_insn(node.insn.opcode, index),
         LdcValue::String(value) => {
             let index = cp.string(&value);
             let opcode = if index <= 0xFF {
@@ -1417,20 +2859,71 @@ fn resolve_ldc(node: LdcInsnNode, cp: &mut ConstantPoolBuilder) -> (u8, u16, Ldc
                 },
             )
         }
+        LdcValue::Handle(value) => {
+            let index = cp.method_handle(&value);
+            let opcode = if index <= 0xFF {
+                opcodes::LDC
+            } else {
+                opcodes::LDC_W
+            };
+            (
+                opcode,
+                index,
+                LdcInsnNode {
+                    insn: opcode.into(),
+                    value: LdcValue::Index(index),
+                },
+            )
+        }
+        // Dynamic constants need a bootstrap method entry, so they are resolved to an index
+        // together with `INVOKEDYNAMIC` call sites before the code is built.
+        LdcValue::ConstantDynamic(value) => {
+            let opcode = if value.is_wide() {
+                opcodes::LDC2_W
+            } else {
+                opcodes::LDC_W
+            };
+            (
+                opcode,
+                0,
+                LdcInsnNode {
+                    insn: opcode.into(),
+                    value: LdcValue::ConstantDynamic(value),
+                },
+            )
+        }
     }
 }
 
 pub struct ClassFileWriter {
     options: u32,
+    class_hierarchy: Option<Arc<dyn ClassHierarchy>>,
+    strict_class_hierarchy: bool,
 }
 
 impl ClassFileWriter {
     pub fn new(compute_frames_options: u32) -> Self {
         Self {
             options: compute_frames_options,
+            class_hierarchy: None,
+            strict_class_hierarchy: false,
         }
     }
 
+    /// Sets the class hierarchy that `COMPUTE_FRAMES` uses to merge class types
+    /// (see [`ClassWriter::with_class_hierarchy`]).
+    pub fn with_class_hierarchy(mut self, hierarchy: Arc<dyn ClassHierarchy>) -> Self {
+        self.class_hierarchy = Some(hierarchy);
+        self
+    }
+
+    /// Makes `COMPUTE_FRAMES` fail with [`ClassWriteError::UnknownClass`] when it needs a class
+    /// that is not in the class hierarchy, instead of merging it into `java/lang/Object`.
+    pub fn with_strict_class_hierarchy(mut self, strict: bool) -> Self {
+        self.strict_class_hierarchy = strict;
+        self
+    }
+
     pub fn to_bytes(&self, class_node: &ClassNode) -> Result<Vec<u8>, ClassWriteError> {
         if class_node.constant_pool.is_empty() {
             return Err(ClassWriteError::MissingConstantPool);
@@ -1443,8 +2936,79 @@ impl ClassFileWriter {
         write_u2(&mut out, class_node.major_version);
 
         let mut class_attributes = class_node.attributes.clone();
+        let mut fields = class_node.fields.clone();
         let mut methods = class_node.methods.clone();
-        resolve_invokedynamic_methods(&mut methods, &mut cp, &mut class_attributes);
+        resolve_symbolic_insns(&mut methods, &mut cp, &mut class_attributes)?;
+        let compute_frames = self.options & COMPUTE_FRAMES != 0;
+        // The laid out code of each method, and the offsets its code type annotations refer to.
+        let mut laid_out = Vec::with_capacity(methods.len());
+        for method in &mut methods {
+            laid_out.push(if method.has_code {
+                Some(layout_method_code(&class_node.name, method, &mut cp, compute_frames)?)
+            } else {
+                None
+            });
+        }
+        class_attributes.extend(annotation_attributes(
+            &mut cp,
+            &class_node.visible_annotations,
+            &class_node.invisible_annotations,
+        ));
+        class_attributes.extend(type_annotation_attributes(
+            &mut cp,
+            &class_node.visible_type_annotations,
+            &class_node.invisible_type_annotations,
+        ));
+        if let Some(module) = &class_node.module {
+            class_attributes.extend(module_attributes(&mut cp, module));
+        }
+        if let Some(nest_host) = &class_node.nest_host {
+            class_attributes.push(AttributeInfo::NestHost {
+                host_class_index: ensure_class(&mut cp, nest_host),
+            });
+        }
+        if !class_node.nest_members.is_empty() {
+            class_attributes.push(AttributeInfo::NestMembers {
+                classes: class_node
+                    .nest_members
+                    .iter()
+                    .map(|name| ensure_class(&mut cp, name))
+                    .collect(),
+            });
+        }
+        if !class_node.permitted_subclasses.is_empty() {
+            class_attributes.push(AttributeInfo::PermittedSubclasses {
+                classes: class_node
+                    .permitted_subclasses
+                    .iter()
+                    .map(|name| ensure_class(&mut cp, name))
+                    .collect(),
+            });
+        }
+        if let Some(components) = &class_node.record_components {
+            class_attributes.push(record_attribute(&mut cp, components));
+        }
+        for field in &mut fields {
+            let mut attributes = annotation_attributes(
+                &mut cp,
+                &field.visible_annotations,
+                &field.invisible_annotations,
+            );
+            attributes.extend(type_annotation_attributes(
+                &mut cp,
+                &field.visible_type_annotations,
+                &field.invisible_type_annotations,
+            ));
+            field.attributes.extend(attributes);
+        }
+        for (method, code) in methods.iter_mut().zip(&laid_out) {
+            let attributes = method_annotation_attributes(&mut cp, method);
+            method.attributes.extend(attributes);
+            if let Some((_, offsets)) = code {
+                let attributes = code_type_annotation_attributes(&mut cp, method, offsets)?;
+                method.code_attributes.extend(attributes);
+            }
+        }
         if let Some(source_file) = &class_node.source_file {
             class_attributes.retain(|attr| !matches!(attr, AttributeInfo::SourceFile { .. }));
             let source_index = ensure_utf8(&mut cp, source_file);
@@ -1455,7 +3019,7 @@ impl ClassFileWriter {
 
         let mut attribute_names = Vec::new();
         collect_attribute_names(&class_attributes, &mut attribute_names);
-        for field in &class_node.fields {
+        for field in &fields {
             collect_attribute_names(&field.attributes, &mut attribute_names);
         }
         for method in &methods {
@@ -1468,30 +3032,49 @@ impl ClassFileWriter {
         for name in attribute_names {
             ensure_utf8(&mut cp, &name);
         }
+        for field in &fields {
+            ensure_utf8(&mut cp, &field.name);
+            ensure_utf8(&mut cp, &field.descriptor);
+        }
         for method in &methods {
             ensure_utf8(&mut cp, &method.name);
             ensure_utf8(&mut cp, &method.descriptor);
         }
 
+        let codes = methods
+            .iter()
+            .zip(laid_out)
+            .map(|(method, code)| {
+                code.map(|(mut code, _)| {
+                    code.attributes = method.code_attributes.clone();
+                    code
+                })
+            })
+            .collect::<Vec<_>>();
+        check_class_limits(class_node, &fields, &methods, &codes)?;
+
         let mut precomputed_stack_maps: Vec<Option<Vec<StackMapFrame>>> =
             Vec::with_capacity(methods.len());
         let mut precomputed_maxs: Vec<Option<(u16, u16)>> =
             Vec::with_capacity(methods.len());
-        let compute_frames = self.options & COMPUTE_FRAMES != 0;
         let compute_maxs_flag = self.options & COMPUTE_MAXS != 0;
+        let hierarchy = FrameHierarchy {
+            class_node,
+            hierarchy: self.class_hierarchy.as_deref(),
+            strict: self.strict_class_hierarchy,
+        };
         if compute_frames {
             ensure_utf8(&mut cp, "StackMapTable");
-            for method in &methods {
-                if method.has_code {
-                    let code = method_code_attribute(method)?;
+            for (method, code) in methods.iter().zip(&codes) {
+                if let Some(code) = code {
                     let maxs = if compute_maxs_flag {
-                        Some(compute_maxs(method, class_node, &code, &cp)?)
+                        Some(compute_maxs(method, &hierarchy, code, &cp)?)
                     } else {
                         None
                     };
                     let max_locals = maxs.map(|item| item.1).unwrap_or(code.max_locals);
                     let stack_map =
-                        compute_stack_map_table(method, class_node, &code, &mut cp, max_locals)?;
+                        compute_stack_map_table(method, &hierarchy, code, &mut cp, max_locals)?;
                     precomputed_stack_maps.push(Some(stack_map));
                     precomputed_maxs.push(maxs);
                 } else {
@@ -1500,10 +3083,9 @@ impl ClassFileWriter {
                 }
             }
         } else if compute_maxs_flag {
-            for method in &methods {
-                if method.has_code {
-                    let code = method_code_attribute(method)?;
-                    precomputed_maxs.push(Some(compute_maxs(method, class_node, &code, &cp)?));
+            for (method, code) in methods.iter().zip(&codes) {
+                if let Some(code) = code {
+                    precomputed_maxs.push(Some(compute_maxs(method, &hierarchy, code, &cp)?));
                 } else {
                     precomputed_maxs.push(None);
                 }
@@ -1525,6 +3107,12 @@ impl ClassFileWriter {
             }
         };
 
+        if cp.len() > u16::MAX as usize {
+            return Err(ClassWriteError::ClassTooLarge {
+                class: class_node.name.clone(),
+                constant_pool_count: cp.len(),
+            });
+        }
         write_constant_pool(&mut out, &cp)?;
         write_u2(&mut out, class_node.access_flags);
         write_u2(&mut out, class_node.this_class);
@@ -1534,31 +3122,36 @@ impl ClassFileWriter {
             write_u2(&mut out, *index);
         }
 
-        write_u2(&mut out, class_node.fields.len() as u16);
-        for field in &class_node.fields {
+        write_u2(&mut out, fields.len() as u16);
+        for field in &fields {
             write_field(&mut out, field, &mut cp)?;
         }
 
         write_u2(&mut out, methods.len() as u16);
-        for (index, method) in methods.iter().enumerate() {
+        for (index, (method, mut code)) in methods.iter().zip(codes).enumerate() {
             let stack_map = precomputed_stack_maps
                 .get(index)
                 .and_then(|item| item.as_ref());
-            let maxs = precomputed_maxs.get(index).and_then(|item| *item);
+            if let Some(code) = &mut code
+                && let Some((max_stack, max_locals)) = precomputed_maxs[index]
+            {
+                code.max_stack = max_stack;
+                code.max_locals = max_locals;
+            }
             write_method(
                 &mut out,
                 method,
-                class_node,
+                code,
+                &hierarchy,
                 &mut cp,
                 self.options,
                 stack_map,
-                maxs,
             )?;
         }
 
-        write_u2(&mut out, class_attributes.len() as u16);
+        write_count(&mut out, "class attributes", class_attributes.len())?;
         for attr in &class_attributes {
-            write_attribute(&mut out, attr, &mut cp, None, self.options, None, None)?;
+            write_attribute(&mut out, attr, &mut cp, None, self.options, None)?;
         }
 
         Ok(out)
@@ -1575,9 +3168,9 @@ fn write_field(
     write_u2(out, field.access_flags);
     write_u2(out, name_index);
     write_u2(out, descriptor_index);
-    write_u2(out, field.attributes.len() as u16);
+    write_count(out, "field attributes", field.attributes.len())?;
     for attr in &field.attributes {
-        write_attribute(out, attr, cp, None, 0, None, None)?;
+        write_attribute(out, attr, cp, None, 0, None)?;
     }
     Ok(())
 }
@@ -1585,11 +3178,11 @@ fn write_field(
 fn write_method(
     out: &mut Vec<u8>,
     method: &MethodNode,
-    class_node: &ClassNode,
+    code: Option<CodeAttribute>,
+    hierarchy: &FrameHierarchy<'_>,
     cp: &mut Vec<CpInfo>,
     options: u32,
     precomputed_stack_map: Option<&Vec<StackMapFrame>>,
-    precomputed_maxs: Option<(u16, u16)>,
 ) -> Result<(), ClassWriteError> {
     let name_index = ensure_utf8(cp, &method.name);
     let descriptor_index = ensure_utf8(cp, &method.descriptor);
@@ -1598,66 +3191,117 @@ fn write_method(
     write_u2(out, descriptor_index);
 
     let mut attributes = method.attributes.clone();
-    if method.has_code {
-        let code = method_code_attribute(method)?;
+    if let Some(code) = code {
         attributes.retain(|attr| !matches!(attr, AttributeInfo::Code(_)));
         attributes.push(AttributeInfo::Code(code));
     }
 
-    write_u2(out, attributes.len() as u16);
+    write_count(out, "method attributes", attributes.len())?;
     for attr in &attributes {
         write_attribute(
             out,
             attr,
             cp,
-            Some((method, class_node)),
+            Some((method, hierarchy)),
             options,
             precomputed_stack_map,
-            precomputed_maxs,
         )?;
     }
     Ok(())
 }
 
-fn method_code_attribute(method: &MethodNode) -> Result<CodeAttribute, ClassWriteError> {
-    let (code, instructions) = build_code_from_insn_list(&method.instructions)?;
-    Ok(CodeAttribute {
-        max_stack: method.max_stack,
-        max_locals: method.max_locals,
-        code,
-        instructions,
-        insn_nodes: Vec::new(),
-        exception_table: method.exception_table.clone(),
-        try_catch_blocks: Vec::new(),
-        attributes: method.code_attributes.clone(),
-    })
+/// Checks the counts that the class file format stores in two bytes, other than the constant
+/// pool size, which is only known once the whole class has been laid out.
+fn check_class_limits(
+    class_node: &ClassNode,
+    fields: &[FieldNode],
+    methods: &[MethodNode],
+    codes: &[Option<CodeAttribute>],
+) -> Result<(), ClassWriteError> {
+    let limit = u16::MAX as usize;
+    let class = || class_node.name.clone();
+    if class_node.interface_indices.len() > limit {
+        return Err(ClassWriteError::TooManyInterfaces {
+            class: class(),
+            count: class_node.interface_indices.len(),
+        });
+    }
+    if fields.len() > limit {
+        return Err(ClassWriteError::TooManyFields {
+            class: class(),
+            count: fields.len(),
+        });
+    }
+    if methods.len() > limit {
+        return Err(ClassWriteError::TooManyMethods {
+            class: class(),
+            count: methods.len(),
+        });
+    }
+    for (method, code) in methods.iter().zip(codes) {
+        let Some(code) = code else {
+            continue;
+        };
+        if code.code.len() > limit {
+            return Err(ClassWriteError::MethodTooLarge {
+                class: class(),
+                method: method.name.clone(),
+                descriptor: method.descriptor.clone(),
+                size: code.code.len(),
+            });
+        }
+        let max_locals = required_locals(&code.code)?.max(code.max_locals as usize);
+        if max_locals > limit {
+            return Err(ClassWriteError::TooManyLocals {
+                class: class(),
+                method: method.name.clone(),
+                descriptor: method.descriptor.clone(),
+                max_locals,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns the number of local variable slots that the wide variable instructions of `code`
+/// address. Other instructions address at most 256 slots.
+fn required_locals(code: &[u8]) -> Result<usize, ClassWriteError> {
+    let mut max_locals = 0;
+    for insn in parse_instructions(code)? {
+        if let Operand::Wide { opcode, index, .. } = insn.operand {
+            let size = match opcode {
+                opcodes::LLOAD | opcodes::DLOAD | opcodes::LSTORE | opcodes::DSTORE => 2,
+                _ => 1,
+            };
+            max_locals = max_locals.max(index as usize + size);
+        }
+    }
+    Ok(max_locals)
 }
 
 fn write_attribute(
     out: &mut Vec<u8>,
     attr: &AttributeInfo,
     cp: &mut Vec<CpInfo>,
-    method_ctx: Option<(&MethodNode, &ClassNode)>,
+    method_ctx: Option<(&MethodNode, &FrameHierarchy<'_>)>,
     options: u32,
     precomputed_stack_map: Option<&Vec<StackMapFrame>>,
-    precomputed_maxs: Option<(u16, u16)>,
 ) -> Result<(), ClassWriteError> {
     match attr {
         AttributeInfo::Code(code) => {
             let name_index = ensure_utf8(cp, "Code");
             let mut info = Vec::new();
             let mut code_attributes = code.attributes.clone();
-            let (max_stack, max_locals) =
-                precomputed_maxs.unwrap_or((code.max_stack, code.max_locals));
+            let (max_stack, max_locals) = (code.max_stack, code.max_locals);
             if options & COMPUTE_FRAMES != 0 {
                 code_attributes.retain(|item| !matches!(item, AttributeInfo::StackMapTable { .. }));
                 let stack_map = if let Some(precomputed) = precomputed_stack_map {
                     precomputed.clone()
                 } else {
-                    let (method, class_node) = method_ctx.ok_or_else(|| {
+                    let (method, hierarchy) = method_ctx.ok_or_else(|| {
                         ClassWriteError::FrameComputation("missing method".to_string())
                     })?;
-                    compute_stack_map_table(method, class_node, code, cp, max_locals)?
+                    compute_stack_map_table(method, hierarchy, code, cp, max_locals)?
                 };
                 code_attributes.push(AttributeInfo::StackMapTable { entries: stack_map });
             }
@@ -1666,13 +3310,13 @@ fn write_attribute(
             write_u2(&mut info, max_locals);
             write_u4(&mut info, code.code.len() as u32);
             info.extend_from_slice(&code.code);
-            write_u2(&mut info, code.exception_table.len() as u16);
+            write_count(&mut info, "exception table", code.exception_table.len())?;
             for entry in &code.exception_table {
                 write_exception_table_entry(&mut info, entry);
             }
-            write_u2(&mut info, code_attributes.len() as u16);
+            write_count(&mut info, "Code attributes", code_attributes.len())?;
             for nested in &code_attributes {
-                write_attribute(&mut info, nested, cp, method_ctx, options, None, None)?;
+                write_attribute(&mut info, nested, cp, method_ctx, options, None)?;
             }
             write_attribute_with_info(out, name_index, &info);
         }
@@ -1689,7 +3333,7 @@ fn write_attribute(
         } => {
             let name_index = ensure_utf8(cp, "Exceptions");
             let mut info = Vec::new();
-            write_u2(&mut info, exception_index_table.len() as u16);
+            write_count(&mut info, "Exceptions", exception_index_table.len())?;
             for index in exception_index_table {
                 write_u2(&mut info, *index);
             }
@@ -1704,7 +3348,7 @@ fn write_attribute(
         AttributeInfo::LineNumberTable { entries } => {
             let name_index = ensure_utf8(cp, "LineNumberTable");
             let mut info = Vec::new();
-            write_u2(&mut info, entries.len() as u16);
+            write_count(&mut info, "LineNumberTable", entries.len())?;
             for entry in entries {
                 write_line_number(&mut info, entry);
             }
@@ -1713,12 +3357,25 @@ fn write_attribute(
         AttributeInfo::LocalVariableTable { entries } => {
             let name_index = ensure_utf8(cp, "LocalVariableTable");
             let mut info = Vec::new();
-            write_u2(&mut info, entries.len() as u16);
+            write_count(&mut info, "LocalVariableTable", entries.len())?;
             for entry in entries {
                 write_local_variable(&mut info, entry);
             }
             write_attribute_with_info(out, name_index, &info);
         }
+        AttributeInfo::LocalVariableTypeTable { entries } => {
+            let name_index = ensure_utf8(cp, "LocalVariableTypeTable");
+            let mut info = Vec::new();
+            write_count(&mut info, "LocalVariableTypeTable", entries.len())?;
+            for entry in entries {
+                write_local_variable_type(&mut info, entry);
+            }
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::SourceDebugExtension { debug_extension } => {
+            let name_index = ensure_utf8(cp, "SourceDebugExtension");
+            write_attribute_with_info(out, name_index, debug_extension);
+        }
         AttributeInfo::Signature { signature_index } => {
             let name_index = ensure_utf8(cp, "Signature");
             let mut info = Vec::new();
@@ -1728,9 +3385,9 @@ fn write_attribute(
         AttributeInfo::StackMapTable { entries } => {
             let name_index = ensure_utf8(cp, "StackMapTable");
             let mut info = Vec::new();
-            write_u2(&mut info, entries.len() as u16);
+            write_count(&mut info, "StackMapTable", entries.len())?;
             for entry in entries {
-                write_stack_map_frame(&mut info, entry);
+                write_stack_map_frame(&mut info, entry)?;
             }
             write_attribute_with_info(out, name_index, &info);
         }
@@ -1745,7 +3402,7 @@ fn write_attribute(
         AttributeInfo::InnerClasses { classes } => {
             let name_index = ensure_utf8(cp, "InnerClasses");
             let mut info = Vec::new();
-            write_u2(&mut info, classes.len() as u16);
+            write_count(&mut info, "InnerClasses", classes.len())?;
             for class in classes {
                 write_inner_class(&mut info, class);
             }
@@ -1764,21 +3421,115 @@ fn write_attribute(
         AttributeInfo::BootstrapMethods { methods } => {
             let name_index = ensure_utf8(cp, "BootstrapMethods");
             let mut info = Vec::new();
-            write_u2(&mut info, methods.len() as u16);
+            write_count(&mut info, "BootstrapMethods", methods.len())?;
             for method in methods {
-                write_bootstrap_method(&mut info, method);
+                write_bootstrap_method(&mut info, method)?;
             }
             write_attribute_with_info(out, name_index, &info);
         }
         AttributeInfo::MethodParameters { parameters } => {
             let name_index = ensure_utf8(cp, "MethodParameters");
             let mut info = Vec::new();
-            write_u1(&mut info, parameters.len() as u8);
+            write_u1_count(&mut info, "MethodParameters", parameters.len())?;
             for parameter in parameters {
                 write_method_parameter(&mut info, parameter);
             }
             write_attribute_with_info(out, name_index, &info);
         }
+        AttributeInfo::RuntimeVisibleAnnotations { annotations } => {
+            let name_index = ensure_utf8(cp, "RuntimeVisibleAnnotations");
+            let mut info = Vec::new();
+            write_annotations(&mut info, annotations)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::RuntimeInvisibleAnnotations { annotations } => {
+            let name_index = ensure_utf8(cp, "RuntimeInvisibleAnnotations");
+            let mut info = Vec::new();
+            write_annotations(&mut info, annotations)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::RuntimeVisibleParameterAnnotations { parameters } => {
+            let name_index = ensure_utf8(cp, "RuntimeVisibleParameterAnnotations");
+            let mut info = Vec::new();
+            write_parameter_annotations(&mut info, parameters)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::RuntimeInvisibleParameterAnnotations { parameters } => {
+            let name_index = ensure_utf8(cp, "RuntimeInvisibleParameterAnnotations");
+            let mut info = Vec::new();
+            write_parameter_annotations(&mut info, parameters)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::AnnotationDefault { default_value } => {
+            let name_index = ensure_utf8(cp, "AnnotationDefault");
+            let mut info = Vec::new();
+            write_element_value(&mut info, default_value)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::RuntimeVisibleTypeAnnotations { annotations } => {
+            let name_index = ensure_utf8(cp, "RuntimeVisibleTypeAnnotations");
+            let mut info = Vec::new();
+            write_type_annotations(&mut info, annotations)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::RuntimeInvisibleTypeAnnotations { annotations } => {
+            let name_index = ensure_utf8(cp, "RuntimeInvisibleTypeAnnotations");
+            let mut info = Vec::new();
+            write_type_annotations(&mut info, annotations)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::Module(module) => {
+            let name_index = ensure_utf8(cp, "Module");
+            let mut info = Vec::new();
+            write_module(&mut info, module)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::ModulePackages {
+            package_index_table,
+        } => {
+            let name_index = ensure_utf8(cp, "ModulePackages");
+            let mut info = Vec::new();
+            write_u2_table(&mut info, "ModulePackages", package_index_table)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::ModuleMainClass { main_class_index } => {
+            let name_index = ensure_utf8(cp, "ModuleMainClass");
+            let mut info = Vec::new();
+            write_u2(&mut info, *main_class_index);
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::NestHost { host_class_index } => {
+            let name_index = ensure_utf8(cp, "NestHost");
+            let mut info = Vec::new();
+            write_u2(&mut info, *host_class_index);
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::NestMembers { classes } => {
+            let name_index = ensure_utf8(cp, "NestMembers");
+            let mut info = Vec::new();
+            write_u2_table(&mut info, "NestMembers", classes)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::PermittedSubclasses { classes } => {
+            let name_index = ensure_utf8(cp, "PermittedSubclasses");
+            let mut info = Vec::new();
+            write_u2_table(&mut info, "PermittedSubclasses", classes)?;
+            write_attribute_with_info(out, name_index, &info);
+        }
+        AttributeInfo::Record { components } => {
+            let name_index = ensure_utf8(cp, "Record");
+            let mut info = Vec::new();
+            write_count(&mut info, "Record", components.len())?;
+            for component in components {
+                write_u2(&mut info, component.name_index);
+                write_u2(&mut info, component.descriptor_index);
+                write_count(&mut info, "record component attributes", component.attributes.len())?;
+                for nested in &component.attributes {
+                    write_attribute(&mut info, nested, cp, None, options, None)?;
+                }
+            }
+            write_attribute_with_info(out, name_index, &info);
+        }
         AttributeInfo::Unknown { name, info } => {
             let name_index = ensure_utf8(cp, name);
             write_attribute_with_info(out, name_index, info);
@@ -1814,19 +3565,31 @@ fn write_local_variable(out: &mut Vec<u8>, entry: &LocalVariable) {
     write_u2(out, entry.index);
 }
 
-fn write_inner_class(out: &mut Vec<u8>, entry: &InnerClass) {
-    write_u2(out, entry.inner_class_info_index);
-    write_u2(out, entry.outer_class_info_index);
-    write_u2(out, entry.inner_name_index);
+fn write_local_variable_type(out: &mut Vec<u8>, entry: &LocalVariableType) {
+    write_u2(out, entry.start_pc);
+    write_u2(out, entry.length);
+    write_u2(out, entry.name_index);
+    write_u2(out, entry.signature_index);
+    write_u2(out, entry.index);
+}
+
+fn write_inner_class(out: &mut Vec<u8>, entry: &InnerClass) {
+    write_u2(out, entry.inner_class_info_index);
+    write_u2(out, entry.outer_class_info_index);
+    write_u2(out, entry.inner_name_index);
     write_u2(out, entry.inner_class_access_flags);
 }
 
-fn write_bootstrap_method(out: &mut Vec<u8>, entry: &BootstrapMethod) {
+fn write_bootstrap_method(
+    out: &mut Vec<u8>,
+    entry: &BootstrapMethod,
+) -> Result<(), ClassWriteError> {
     write_u2(out, entry.bootstrap_method_ref);
-    write_u2(out, entry.bootstrap_arguments.len() as u16);
+    write_count(out, "bootstrap method arguments", entry.bootstrap_arguments.len())?;
     for arg in &entry.bootstrap_arguments {
         write_u2(out, *arg);
     }
+    Ok(())
 }
 
 fn write_method_parameter(out: &mut Vec<u8>, entry: &MethodParameter) {
@@ -1834,7 +3597,201 @@ fn write_method_parameter(out: &mut Vec<u8>, entry: &MethodParameter) {
     write_u2(out, entry.access_flags);
 }
 
-fn write_stack_map_frame(out: &mut Vec<u8>, frame: &StackMapFrame) {
+fn write_module(out: &mut Vec<u8>, module: &ModuleAttribute) -> Result<(), ClassWriteError> {
+    write_u2(out, module.module_name_index);
+    write_u2(out, module.module_flags);
+    write_u2(out, module.module_version_index);
+    write_count(out, "Module requires", module.requires.len())?;
+    for entry in &module.requires {
+        write_u2(out, entry.requires_index);
+        write_u2(out, entry.requires_flags);
+        write_u2(out, entry.requires_version_index);
+    }
+    write_count(out, "Module exports", module.exports.len())?;
+    for entry in &module.exports {
+        write_u2(out, entry.exports_index);
+        write_u2(out, entry.exports_flags);
+        write_u2_table(out, "Module exports_to", &entry.exports_to_index)?;
+    }
+    write_count(out, "Module opens", module.opens.len())?;
+    for entry in &module.opens {
+        write_u2(out, entry.opens_index);
+        write_u2(out, entry.opens_flags);
+        write_u2_table(out, "Module opens_to", &entry.opens_to_index)?;
+    }
+    write_u2_table(out, "Module uses", &module.uses_index)?;
+    write_count(out, "Module provides", module.provides.len())?;
+    for entry in &module.provides {
+        write_u2(out, entry.provides_index);
+        write_u2_table(out, "Module provides_with", &entry.provides_with_index)?;
+    }
+    Ok(())
+}
+
+fn write_u2_table(
+    out: &mut Vec<u8>,
+    table: &'static str,
+    values: &[u16],
+) -> Result<(), ClassWriteError> {
+    write_count(out, table, values.len())?;
+    for value in values {
+        write_u2(out, *value);
+    }
+    Ok(())
+}
+
+/// Writes the number of entries of `table`, which the class file format stores in two bytes.
+fn write_count(
+    out: &mut Vec<u8>,
+    table: &'static str,
+    count: usize,
+) -> Result<(), ClassWriteError> {
+    let value = u16::try_from(count).map_err(|_| ClassWriteError::TooManyEntries {
+        table,
+        count,
+        max: u16::MAX as usize,
+    })?;
+    write_u2(out, value);
+    Ok(())
+}
+
+/// Writes the number of entries of `table`, which the class file format stores in one byte.
+fn write_u1_count(
+    out: &mut Vec<u8>,
+    table: &'static str,
+    count: usize,
+) -> Result<(), ClassWriteError> {
+    let value = u8::try_from(count).map_err(|_| ClassWriteError::TooManyEntries {
+        table,
+        count,
+        max: u8::MAX as usize,
+    })?;
+    write_u1(out, value);
+    Ok(())
+}
+
+fn write_annotations(out: &mut Vec<u8>, annotations: &[Annotation]) -> Result<(), ClassWriteError> {
+    write_count(out, "annotations", annotations.len())?;
+    for annotation in annotations {
+        write_annotation(out, annotation)?;
+    }
+    Ok(())
+}
+
+fn write_parameter_annotations(
+    out: &mut Vec<u8>,
+    parameters: &[Vec<Annotation>],
+) -> Result<(), ClassWriteError> {
+    write_u1_count(out, "parameter annotations", parameters.len())?;
+    for annotations in parameters {
+        write_annotations(out, annotations)?;
+    }
+    Ok(())
+}
+
+fn write_annotation(out: &mut Vec<u8>, annotation: &Annotation) -> Result<(), ClassWriteError> {
+    write_u2(out, annotation.type_index);
+    write_count(out, "annotation elements", annotation.element_value_pairs.len())?;
+    for pair in &annotation.element_value_pairs {
+        write_u2(out, pair.element_name_index);
+        write_element_value(out, &pair.value)?;
+    }
+    Ok(())
+}
+
+fn write_type_annotations(
+    out: &mut Vec<u8>,
+    annotations: &[TypeAnnotation],
+) -> Result<(), ClassWriteError> {
+    write_count(out, "type annotations", annotations.len())?;
+    for annotation in annotations {
+        write_u1(out, annotation.target_type);
+        match &annotation.target_info {
+            TypeAnnotationTarget::TypeParameter {
+                type_parameter_index,
+            } => write_u1(out, *type_parameter_index),
+            TypeAnnotationTarget::SuperType { supertype_index } => write_u2(out, *supertype_index),
+            TypeAnnotationTarget::TypeParameterBound {
+                type_parameter_index,
+                bound_index,
+            } => {
+                write_u1(out, *type_parameter_index);
+                write_u1(out, *bound_index);
+            }
+            TypeAnnotationTarget::Empty => {}
+            TypeAnnotationTarget::FormalParameter {
+                formal_parameter_index,
+            } => write_u1(out, *formal_parameter_index),
+            TypeAnnotationTarget::Throws { throws_type_index } => {
+                write_u2(out, *throws_type_index)
+            }
+            TypeAnnotationTarget::LocalVar { table } => {
+                write_count(out, "local variable target table", table.len())?;
+                for entry in table {
+                    write_u2(out, entry.start_pc);
+                    write_u2(out, entry.length);
+                    write_u2(out, entry.index);
+                }
+            }
+            TypeAnnotationTarget::Catch {
+                exception_table_index,
+            } => write_u2(out, *exception_table_index),
+            TypeAnnotationTarget::Offset { offset } => write_u2(out, *offset),
+            TypeAnnotationTarget::TypeArgument {
+                offset,
+                type_argument_index,
+            } => {
+                write_u2(out, *offset);
+                write_u1(out, *type_argument_index);
+            }
+        }
+        write_u1_count(out, "type path", annotation.target_path.len())?;
+        for (kind, argument) in annotation.target_path.steps() {
+            write_u1(out, *kind);
+            write_u1(out, *argument);
+        }
+        write_annotation(out, &annotation.annotation)?;
+    }
+    Ok(())
+}
+
+fn write_element_value(out: &mut Vec<u8>, value: &ElementValue) -> Result<(), ClassWriteError> {
+    match value {
+        ElementValue::Const {
+            tag,
+            const_value_index,
+        } => {
+            write_u1(out, *tag);
+            write_u2(out, *const_value_index);
+        }
+        ElementValue::Enum {
+            type_name_index,
+            const_name_index,
+        } => {
+            write_u1(out, b'e');
+            write_u2(out, *type_name_index);
+            write_u2(out, *const_name_index);
+        }
+        ElementValue::Class { class_info_index } => {
+            write_u1(out, b'c');
+            write_u2(out, *class_info_index);
+        }
+        ElementValue::Annotation(annotation) => {
+            write_u1(out, b'@');
+            write_annotation(out, annotation)?;
+        }
+        ElementValue::Array(values) => {
+            write_u1(out, b'[');
+            write_count(out, "array element values", values.len())?;
+            for item in values {
+                write_element_value(out, item)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_stack_map_frame(out: &mut Vec<u8>, frame: &StackMapFrame) -> Result<(), ClassWriteError> {
     match frame {
         StackMapFrame::SameFrame { offset_delta } => {
             write_u1(out, *offset_delta as u8);
@@ -1879,16 +3836,17 @@ fn write_stack_map_frame(out: &mut Vec<u8>, frame: &StackMapFrame) {
         } => {
             write_u1(out, 255);
             write_u2(out, *offset_delta);
-            write_u2(out, locals.len() as u16);
+            write_count(out, "stack map frame locals", locals.len())?;
             for local in locals {
                 write_verification_type(out, local);
             }
-            write_u2(out, stack.len() as u16);
+            write_count(out, "stack map frame stack", stack.len())?;
             for value in stack {
                 write_verification_type(out, value);
             }
         }
     }
+    Ok(())
 }
 
 fn write_verification_type(out: &mut Vec<u8>, value: &VerificationTypeInfo) {
@@ -1922,6 +3880,12 @@ fn collect_attribute_names(attributes: &[AttributeInfo], names: &mut Vec<String>
             AttributeInfo::LocalVariableTable { .. } => {
                 names.push("LocalVariableTable".to_string())
             }
+            AttributeInfo::LocalVariableTypeTable { .. } => {
+                names.push("LocalVariableTypeTable".to_string())
+            }
+            AttributeInfo::SourceDebugExtension { .. } => {
+                names.push("SourceDebugExtension".to_string())
+            }
             AttributeInfo::Signature { .. } => names.push("Signature".to_string()),
             AttributeInfo::StackMapTable { .. } => names.push("StackMapTable".to_string()),
             AttributeInfo::Deprecated => names.push("Deprecated".to_string()),
@@ -1930,11 +3894,441 @@ fn collect_attribute_names(attributes: &[AttributeInfo], names: &mut Vec<String>
             AttributeInfo::EnclosingMethod { .. } => names.push("EnclosingMethod".to_string()),
             AttributeInfo::BootstrapMethods { .. } => names.push("BootstrapMethods".to_string()),
             AttributeInfo::MethodParameters { .. } => names.push("MethodParameters".to_string()),
+            AttributeInfo::RuntimeVisibleAnnotations { .. } => {
+                names.push("RuntimeVisibleAnnotations".to_string())
+            }
+            AttributeInfo::RuntimeInvisibleAnnotations { .. } => {
+                names.push("RuntimeInvisibleAnnotations".to_string())
+            }
+            AttributeInfo::RuntimeVisibleParameterAnnotations { .. } => {
+                names.push("RuntimeVisibleParameterAnnotations".to_string())
+            }
+            AttributeInfo::RuntimeInvisibleParameterAnnotations { .. } => {
+                names.push("RuntimeInvisibleParameterAnnotations".to_string())
+            }
+            AttributeInfo::AnnotationDefault { .. } => names.push("AnnotationDefault".to_string()),
+            AttributeInfo::RuntimeVisibleTypeAnnotations { .. } => {
+                names.push("RuntimeVisibleTypeAnnotations".to_string())
+            }
+            AttributeInfo::RuntimeInvisibleTypeAnnotations { .. } => {
+                names.push("RuntimeInvisibleTypeAnnotations".to_string())
+            }
+            AttributeInfo::Module(_) => names.push("Module".to_string()),
+            AttributeInfo::ModulePackages { .. } => names.push("ModulePackages".to_string()),
+            AttributeInfo::ModuleMainClass { .. } => names.push("ModuleMainClass".to_string()),
+            AttributeInfo::NestHost { .. } => names.push("NestHost".to_string()),
+            AttributeInfo::NestMembers { .. } => names.push("NestMembers".to_string()),
+            AttributeInfo::PermittedSubclasses { .. } => {
+                names.push("PermittedSubclasses".to_string())
+            }
+            AttributeInfo::Record { components } => {
+                names.push("Record".to_string());
+                for component in components {
+                    collect_attribute_names(&component.attributes, names);
+                }
+            }
             AttributeInfo::Unknown { name, .. } => names.push(name.clone()),
         }
     }
 }
 
+fn record_attribute(cp: &mut Vec<CpInfo>, components: &[RecordComponentNode]) -> AttributeInfo {
+    let mut entries = Vec::with_capacity(components.len());
+    for component in components {
+        let mut attributes = component.attributes.clone();
+        if let Some(signature) = &component.signature {
+            attributes.push(AttributeInfo::Signature {
+                signature_index: ensure_utf8(cp, signature),
+            });
+        }
+        attributes.extend(annotation_attributes(
+            cp,
+            &component.visible_annotations,
+            &component.invisible_annotations,
+        ));
+        attributes.extend(type_annotation_attributes(
+            cp,
+            &component.visible_type_annotations,
+            &component.invisible_type_annotations,
+        ));
+        entries.push(RecordComponentInfo {
+            name_index: ensure_utf8(cp, &component.name),
+            descriptor_index: ensure_utf8(cp, &component.descriptor),
+            attributes,
+        });
+    }
+    AttributeInfo::Record {
+        components: entries,
+    }
+}
+
+fn module_attributes(cp: &mut Vec<CpInfo>, module: &ModuleNode) -> Vec<AttributeInfo> {
+    let optional_utf8 = |cp: &mut Vec<CpInfo>, value: &Option<String>| {
+        value.as_deref().map(|value| ensure_utf8(cp, value)).unwrap_or(0)
+    };
+    let module_name_index = ensure_module(cp, &module.name);
+    let module_version_index = optional_utf8(cp, &module.version);
+    let mut requires = Vec::with_capacity(module.requires.len());
+    for entry in &module.requires {
+        requires.push(ModuleRequires {
+            requires_index: ensure_module(cp, &entry.module),
+            requires_flags: entry.access_flags,
+            requires_version_index: optional_utf8(cp, &entry.version),
+        });
+    }
+    let mut exports = Vec::with_capacity(module.exports.len());
+    for entry in &module.exports {
+        exports.push(ModuleExports {
+            exports_index: ensure_package(cp, &entry.package),
+            exports_flags: entry.access_flags,
+            exports_to_index: entry.modules.iter().map(|name| ensure_module(cp, name)).collect(),
+        });
+    }
+    let mut opens = Vec::with_capacity(module.opens.len());
+    for entry in &module.opens {
+        opens.push(ModuleOpens {
+            opens_index: ensure_package(cp, &entry.package),
+            opens_flags: entry.access_flags,
+            opens_to_index: entry.modules.iter().map(|name| ensure_module(cp, name)).collect(),
+        });
+    }
+    let uses_index = module
+        .uses
+        .iter()
+        .map(|name| ensure_class(cp, name))
+        .collect();
+    let mut provides = Vec::with_capacity(module.provides.len());
+    for entry in &module.provides {
+        provides.push(ModuleProvides {
+            provides_index: ensure_class(cp, &entry.service),
+            provides_with_index: entry
+                .providers
+                .iter()
+                .map(|name| ensure_class(cp, name))
+                .collect(),
+        });
+    }
+
+    let mut attributes = vec![AttributeInfo::Module(ModuleAttribute {
+        module_name_index,
+        module_flags: module.access_flags,
+        module_version_index,
+        requires,
+        exports,
+        opens,
+        uses_index,
+        provides,
+    })];
+    if !module.packages.is_empty() {
+        attributes.push(AttributeInfo::ModulePackages {
+            package_index_table: module
+                .packages
+                .iter()
+                .map(|name| ensure_package(cp, name))
+                .collect(),
+        });
+    }
+    if let Some(main_class) = &module.main_class {
+        attributes.push(AttributeInfo::ModuleMainClass {
+            main_class_index: ensure_class(cp, main_class),
+        });
+    }
+    attributes
+}
+
+fn annotation_attributes(
+    cp: &mut Vec<CpInfo>,
+    visible: &[AnnotationNode],
+    invisible: &[AnnotationNode],
+) -> Vec<AttributeInfo> {
+    let mut attributes = Vec::new();
+    if !visible.is_empty() {
+        attributes.push(AttributeInfo::RuntimeVisibleAnnotations {
+            annotations: visible.iter().map(|item| encode_annotation(cp, item)).collect(),
+        });
+    }
+    if !invisible.is_empty() {
+        attributes.push(AttributeInfo::RuntimeInvisibleAnnotations {
+            annotations: invisible
+                .iter()
+                .map(|item| encode_annotation(cp, item))
+                .collect(),
+        });
+    }
+    attributes
+}
+
+fn method_annotation_attributes(cp: &mut Vec<CpInfo>, method: &MethodNode) -> Vec<AttributeInfo> {
+    let mut attributes = annotation_attributes(
+        cp,
+        &method.visible_annotations,
+        &method.invisible_annotations,
+    );
+    if !method.visible_parameter_annotations.is_empty() {
+        attributes.push(AttributeInfo::RuntimeVisibleParameterAnnotations {
+            parameters: encode_parameter_annotations(cp, &method.visible_parameter_annotations),
+        });
+    }
+    if !method.invisible_parameter_annotations.is_empty() {
+        attributes.push(AttributeInfo::RuntimeInvisibleParameterAnnotations {
+            parameters: encode_parameter_annotations(cp, &method.invisible_parameter_annotations),
+        });
+    }
+    if let Some(value) = &method.annotation_default {
+        attributes.push(AttributeInfo::AnnotationDefault {
+            default_value: encode_element_value(cp, value),
+        });
+    }
+    attributes.extend(type_annotation_attributes(
+        cp,
+        &method.visible_type_annotations,
+        &method.invisible_type_annotations,
+    ));
+    attributes
+}
+
+fn type_annotation_attributes(
+    cp: &mut Vec<CpInfo>,
+    visible: &[TypeAnnotationNode],
+    invisible: &[TypeAnnotationNode],
+) -> Vec<AttributeInfo> {
+    let mut attributes = Vec::new();
+    if !visible.is_empty() {
+        attributes.push(AttributeInfo::RuntimeVisibleTypeAnnotations {
+            annotations: visible
+                .iter()
+                .map(|item| encode_type_annotation(cp, item, declaration_target(item.type_ref)))
+                .collect(),
+        });
+    }
+    if !invisible.is_empty() {
+        attributes.push(AttributeInfo::RuntimeInvisibleTypeAnnotations {
+            annotations: invisible
+                .iter()
+                .map(|item| encode_type_annotation(cp, item, declaration_target(item.type_ref)))
+                .collect(),
+        });
+    }
+    attributes
+}
+
+/// The bytecode offsets of the instructions and labels of a method's code.
+struct CodeOffsets {
+    /// The offset of each instruction, by handle.
+    insns: HashMap<NodeId, u16>,
+    /// The offset of each label placed in the code, by label id.
+    labels: HashMap<usize, u16>,
+}
+
+impl CodeOffsets {
+    fn insn_offset(&self, id: NodeId) -> Result<u16, ClassWriteError> {
+        self.insns.get(&id).copied().ok_or_else(|| {
+            ClassWriteError::FrameComputation(format!(
+                "type annotation on {id:?}, which is not an instruction of the code"
+            ))
+        })
+    }
+
+    fn label_offset(&self, label: &LabelNode) -> Result<u16, ClassWriteError> {
+        self.labels
+            .get(&label.id)
+            .copied()
+            .ok_or(ClassWriteError::MissingLabel(label.id))
+    }
+}
+
+/// Encodes the instruction, exception handler and local variable type annotations of `method`
+/// into attributes of its `Code` attribute, at the `offsets` of its laid out code.
+fn code_type_annotation_attributes(
+    cp: &mut Vec<CpInfo>,
+    method: &MethodNode,
+    offsets: &CodeOffsets,
+) -> Result<Vec<AttributeInfo>, ClassWriteError> {
+    let has_visible = !method.visible_insn_annotations.is_empty()
+        || !method.visible_try_catch_annotations.is_empty()
+        || !method.visible_local_variable_annotations.is_empty();
+    let has_invisible = !method.invisible_insn_annotations.is_empty()
+        || !method.invisible_try_catch_annotations.is_empty()
+        || !method.invisible_local_variable_annotations.is_empty();
+    if !has_visible && !has_invisible {
+        return Ok(Vec::new());
+    }
+
+    let mut encode = |insns: &[InsnAnnotationNode],
+                      try_catches: &[TypeAnnotationNode],
+                      local_variables: &[LocalVariableAnnotationNode]|
+     -> Result<Vec<TypeAnnotation>, ClassWriteError> {
+        let mut annotations = Vec::new();
+        for node in insns {
+            let offset = offsets.insn_offset(node.insn)?;
+            let type_ref = node.type_annotation.type_ref;
+            let target_info = if type_ref.sort() >= TypeReference::CAST {
+                TypeAnnotationTarget::TypeArgument {
+                    offset,
+                    type_argument_index: type_ref.type_argument_index(),
+                }
+            } else {
+                TypeAnnotationTarget::Offset { offset }
+            };
+            annotations.push(encode_type_annotation(cp, &node.type_annotation, target_info));
+        }
+        for node in try_catches {
+            annotations.push(encode_type_annotation(cp, node, declaration_target(node.type_ref)));
+        }
+        for node in local_variables {
+            let mut table = Vec::with_capacity(node.index.len());
+            for ((start, end), index) in node.start.iter().zip(&node.end).zip(&node.index) {
+                let start_pc = offsets.label_offset(start)?;
+                table.push(LocalVarTarget {
+                    start_pc,
+                    length: offsets.label_offset(end)?.saturating_sub(start_pc),
+                    index: *index,
+                });
+            }
+            annotations.push(encode_type_annotation(
+                cp,
+                &node.type_annotation,
+                TypeAnnotationTarget::LocalVar { table },
+            ));
+        }
+        Ok(annotations)
+    };
+
+    let mut attributes = Vec::new();
+    if has_visible {
+        attributes.push(AttributeInfo::RuntimeVisibleTypeAnnotations {
+            annotations: encode(
+                &method.visible_insn_annotations,
+                &method.visible_try_catch_annotations,
+                &method.visible_local_variable_annotations,
+            )?,
+        });
+    }
+    if has_invisible {
+        attributes.push(AttributeInfo::RuntimeInvisibleTypeAnnotations {
+            annotations: encode(
+                &method.invisible_insn_annotations,
+                &method.invisible_try_catch_annotations,
+                &method.invisible_local_variable_annotations,
+            )?,
+        });
+    }
+    Ok(attributes)
+}
+
+/// Returns the `target_info` of a type annotation that does not refer to bytecode offsets.
+fn declaration_target(type_ref: TypeReference) -> TypeAnnotationTarget {
+    match type_ref.sort() {
+        TypeReference::CLASS_TYPE_PARAMETER | TypeReference::METHOD_TYPE_PARAMETER => {
+            TypeAnnotationTarget::TypeParameter {
+                type_parameter_index: type_ref.type_parameter_index(),
+            }
+        }
+        TypeReference::CLASS_EXTENDS => TypeAnnotationTarget::SuperType {
+            supertype_index: type_ref.super_type_index() as u16,
+        },
+        TypeReference::CLASS_TYPE_PARAMETER_BOUND | TypeReference::METHOD_TYPE_PARAMETER_BOUND => {
+            TypeAnnotationTarget::TypeParameterBound {
+                type_parameter_index: type_ref.type_parameter_index(),
+                bound_index: type_ref.type_parameter_bound_index(),
+            }
+        }
+        TypeReference::METHOD_FORMAL_PARAMETER => TypeAnnotationTarget::FormalParameter {
+            formal_parameter_index: type_ref.formal_parameter_index(),
+        },
+        TypeReference::THROWS => TypeAnnotationTarget::Throws {
+            throws_type_index: type_ref.exception_index(),
+        },
+        TypeReference::EXCEPTION_PARAMETER => TypeAnnotationTarget::Catch {
+            exception_table_index: type_ref.try_catch_block_index(),
+        },
+        _ => TypeAnnotationTarget::Empty,
+    }
+}
+
+fn encode_type_annotation(
+    cp: &mut Vec<CpInfo>,
+    node: &TypeAnnotationNode,
+    target_info: TypeAnnotationTarget,
+) -> TypeAnnotation {
+    TypeAnnotation {
+        target_type: node.type_ref.sort(),
+        target_info,
+        target_path: node.type_path.clone().unwrap_or_default(),
+        annotation: encode_annotation(cp, &node.annotation),
+    }
+}
+
+fn encode_parameter_annotations(
+    cp: &mut Vec<CpInfo>,
+    parameters: &[Vec<AnnotationNode>],
+) -> Vec<Vec<Annotation>> {
+    parameters
+        .iter()
+        .map(|annotations| {
+            annotations
+                .iter()
+                .map(|item| encode_annotation(cp, item))
+                .collect()
+        })
+        .collect()
+}
+
+fn encode_annotation(cp: &mut Vec<CpInfo>, annotation: &AnnotationNode) -> Annotation {
+    let type_index = ensure_utf8(cp, &annotation.descriptor);
+    let mut element_value_pairs = Vec::with_capacity(annotation.values.len());
+    for (name, value) in &annotation.values {
+        element_value_pairs.push(ElementValuePair {
+            element_name_index: ensure_utf8(cp, name),
+            value: encode_element_value(cp, value),
+        });
+    }
+    Annotation {
+        type_index,
+        element_value_pairs,
+    }
+}
+
+fn encode_element_value(cp: &mut Vec<CpInfo>, value: &AnnotationValue) -> ElementValue {
+    let (tag, const_value_index) = match value {
+        AnnotationValue::Byte(value) => (b'B', ensure_int(cp, *value as i32)),
+        AnnotationValue::Char(value) => (b'C', ensure_int(cp, *value as i32)),
+        AnnotationValue::Double(value) => (b'D', ensure_double(cp, *value)),
+        AnnotationValue::Float(value) => (b'F', ensure_float(cp, *value)),
+        AnnotationValue::Int(value) => (b'I', ensure_int(cp, *value)),
+        AnnotationValue::Long(value) => (b'J', ensure_long(cp, *value)),
+        AnnotationValue::Short(value) => (b'S', ensure_int(cp, *value as i32)),
+        AnnotationValue::Boolean(value) => (b'Z', ensure_int(cp, *value as i32)),
+        AnnotationValue::String(value) => (b's', ensure_utf8(cp, value)),
+        AnnotationValue::Enum { descriptor, value } => {
+            return ElementValue::Enum {
+                type_name_index: ensure_utf8(cp, descriptor),
+                const_name_index: ensure_utf8(cp, value),
+            };
+        }
+        AnnotationValue::Class(descriptor) => {
+            return ElementValue::Class {
+                class_info_index: ensure_utf8(cp, descriptor),
+            };
+        }
+        AnnotationValue::Annotation(annotation) => {
+            return ElementValue::Annotation(encode_annotation(cp, annotation));
+        }
+        AnnotationValue::Array(values) => {
+            return ElementValue::Array(
+                values
+                    .iter()
+                    .map(|item| encode_element_value(cp, item))
+                    .collect(),
+            );
+        }
+    };
+    ElementValue::Const {
+        tag,
+        const_value_index,
+    }
+}
+
 fn write_constant_pool(out: &mut Vec<u8>, cp: &[CpInfo]) -> Result<(), ClassWriteError> {
     write_u2(out, cp.len() as u16);
     for entry in cp.iter().skip(1) {
@@ -1942,6 +4336,11 @@ fn write_constant_pool(out: &mut Vec<u8>, cp: &[CpInfo]) -> Result<(), ClassWrit
             CpInfo::Unusable => {}
             CpInfo::Utf8(value) => {
                 let bytes = encode_modified_utf8(value);
+                if bytes.len() > u16::MAX as usize {
+                    return Err(ClassWriteError::Utf8TooLong {
+                        length: bytes.len(),
+                    });
+                }
                 write_u1(out, 1);
                 write_u2(out, bytes.len() as u16);
                 out.extend_from_slice(&bytes);
@@ -2095,6 +4494,34 @@ fn ensure_class(cp: &mut Vec<CpInfo>, name: &str) -> u16 {
     (cp.len() - 1) as u16
 }
 
+fn ensure_module(cp: &mut Vec<CpInfo>, name: &str) -> u16 {
+    for (index, entry) in cp.iter().enumerate() {
+        if let CpInfo::Module { name_index } = entry
+            && let Some(CpInfo::Utf8(value)) = cp.get(*name_index as usize)
+            && value == name
+        {
+            return index as u16;
+        }
+    }
+    let name_index = ensure_utf8(cp, name);
+    cp.push(CpInfo::Module { name_index });
+    (cp.len() - 1) as u16
+}
+
+fn ensure_package(cp: &mut Vec<CpInfo>, name: &str) -> u16 {
+    for (index, entry) in cp.iter().enumerate() {
+        if let CpInfo::Package { name_index } = entry
+            && let Some(CpInfo::Utf8(value)) = cp.get(*name_index as usize)
+            && value == name
+        {
+            return index as u16;
+        }
+    }
+    let name_index = ensure_utf8(cp, name);
+    cp.push(CpInfo::Package { name_index });
+    (cp.len() - 1) as u16
+}
+
 fn ensure_name_and_type(cp: &mut Vec<CpInfo>, name: &str, descriptor: &str) -> u16 {
     let name_index = ensure_utf8(cp, name);
     let descriptor_index = ensure_utf8(cp, descriptor);
@@ -2288,8 +4715,12 @@ fn ensure_double(cp: &mut Vec<CpInfo>, value: f64) -> u16 {
     (cp.len() - 2) as u16
 }
 
-fn ensure_bootstrap_arg(cp: &mut Vec<CpInfo>, arg: &BootstrapArgument) -> u16 {
-    match arg {
+fn ensure_bootstrap_arg(
+    class_attributes: &mut Vec<AttributeInfo>,
+    cp: &mut Vec<CpInfo>,
+    arg: &BootstrapArgument,
+) -> Result<u16, ClassWriteError> {
+    Ok(match arg {
         BootstrapArgument::Integer(value) => ensure_int(cp, *value),
         BootstrapArgument::Float(value) => ensure_float(cp, *value),
         BootstrapArgument::Long(value) => ensure_long(cp, *value),
@@ -2298,19 +4729,24 @@ fn ensure_bootstrap_arg(cp: &mut Vec<CpInfo>, arg: &BootstrapArgument) -> u16 {
         BootstrapArgument::Class(value) => ensure_class(cp, value),
         BootstrapArgument::MethodType(value) => ensure_method_type(cp, value),
         BootstrapArgument::Handle(value) => ensure_method_handle(cp, value),
-    }
+        BootstrapArgument::ConstantDynamic(value) => {
+            ensure_constant_dynamic(class_attributes, cp, value)?
+        }
+    })
 }
 
+/// Returns the index of the entry of the `BootstrapMethods` attribute with the given method and
+/// arguments, adding the entry and the attribute if needed.
 fn ensure_bootstrap_method(
     class_attributes: &mut Vec<AttributeInfo>,
     cp: &mut Vec<CpInfo>,
     bootstrap_method: &Handle,
     bootstrap_args: &[BootstrapArgument],
-) -> u16 {
+) -> Result<u16, ClassWriteError> {
     let bootstrap_method_ref = ensure_method_handle(cp, bootstrap_method);
     let mut bootstrap_arguments = Vec::with_capacity(bootstrap_args.len());
     for arg in bootstrap_args {
-        bootstrap_arguments.push(ensure_bootstrap_arg(cp, arg));
+        bootstrap_arguments.push(ensure_bootstrap_arg(class_attributes, cp, arg)?);
     }
 
     let attr_pos = if let Some(index) = class_attributes
@@ -2330,15 +4766,21 @@ fn ensure_bootstrap_method(
             entry.bootstrap_method_ref == bootstrap_method_ref
                 && entry.bootstrap_arguments == bootstrap_arguments
         }) {
-            return index as u16;
+            return Ok(index as u16);
         }
+        let count = methods.len() + 1;
+        let index = u16::try_from(methods.len()).map_err(|_| ClassWriteError::TooManyEntries {
+            table: "BootstrapMethods",
+            count,
+            max: u16::MAX as usize,
+        })?;
         methods.push(BootstrapMethod {
             bootstrap_method_ref,
             bootstrap_arguments,
         });
-        (methods.len() - 1) as u16
+        Ok(index)
     } else {
-        0
+        Ok(0)
     }
 }
 
@@ -2367,50 +4809,281 @@ fn ensure_invoke_dynamic(
     (cp.len() - 1) as u16
 }
 
-fn resolve_invokedynamic_methods(
-    methods: &mut [MethodNode],
-    cp: &mut Vec<CpInfo>,
+fn ensure_constant_dynamic(
     class_attributes: &mut Vec<AttributeInfo>,
-) {
-    for method in methods {
-        let mut resolved = InsnList::new();
-        for insn in method.instructions.insns().iter().cloned() {
-            let insn = match insn {
-                Insn::InvokeDynamic(mut node) => {
-                    if node.method_index == 0
-                        && let (Some(name), Some(descriptor), Some(bootstrap_method)) = (
-                            node.name.as_ref(),
-                            node.descriptor.as_ref(),
-                            node.bootstrap_method.as_ref(),
-                        )
-                    {
-                        let bsm_index = ensure_bootstrap_method(
-                            class_attributes,
-                            cp,
-                            bootstrap_method,
-                            &node.bootstrap_args,
-                        );
-                        node.method_index = ensure_invoke_dynamic(cp, bsm_index, name, descriptor);
-                    }
-                    Insn::InvokeDynamic(node)
-                }
-                other => other,
-            };
-            resolved.add(insn);
-        }
-        method.instructions = resolved;
-    }
-}
-
-fn cp_find_utf8(cp: &[CpInfo], value: &str) -> Option<u16> {
+    cp: &mut Vec<CpInfo>,
+    value: &ConstantDynamic,
+) -> Result<u16, ClassWriteError> {
+    let bootstrap_method_attr_index = ensure_bootstrap_method(
+        class_attributes,
+        cp,
+        &value.bootstrap_method,
+        &value.bootstrap_args,
+    )?;
+    let name_and_type_index = ensure_name_and_type(cp, &value.name, &value.descriptor);
     for (index, entry) in cp.iter().enumerate() {
-        if let CpInfo::Utf8(existing) = entry
-            && existing == value
+        if let CpInfo::Dynamic {
+            bootstrap_method_attr_index: existing_bsm,
+            name_and_type_index: existing_nt,
+        } = entry
+            && *existing_bsm == bootstrap_method_attr_index
+            && *existing_nt == name_and_type_index
         {
-            return Some(index as u16);
+            return Ok(index as u16);
         }
     }
-    None
+    cp.push(CpInfo::Dynamic {
+        bootstrap_method_attr_index,
+        name_and_type_index,
+    });
+    Ok((cp.len() - 1) as u16)
+}
+
+/// Returns the `LDC` opcode that loads the constant pool entry at `index`.
+fn ldc_opcode(index: u16, is_wide: bool) -> u8 {
+    if is_wide {
+        opcodes::LDC2_W
+    } else if index <= 0xFF {
+        opcodes::LDC
+    } else {
+        opcodes::LDC_W
+    }
+}
+
+/// Adds the constant loaded by a symbolic `LDC` value to the pool.
+///
+/// Returns its index and whether it is a `long` or a `double`, or `None` if the value is
+/// already an index.
+fn ensure_ldc_constant(
+    class_attributes: &mut Vec<AttributeInfo>,
+    cp: &mut Vec<CpInfo>,
+    value: &LdcValue,
+) -> Result<Option<(u16, bool)>, ClassWriteError> {
+    Ok(Some(match value {
+        LdcValue::Index(_) => return Ok(None),
+        LdcValue::String(value) => (ensure_string(cp, value), false),
+        LdcValue::Type(Type::Object(name)) => (ensure_class(cp, name), false),
+        LdcValue::Type(value @ Type::Method { .. }) => {
+            (ensure_method_type(cp, &value.get_descriptor()), false)
+        }
+        LdcValue::Type(value) => (ensure_class(cp, &value.get_descriptor()), false),
+        LdcValue::Int(value) => (ensure_int(cp, *value), false),
+        LdcValue::Float(value) => (ensure_float(cp, *value), false),
+        LdcValue::Long(value) => (ensure_long(cp, *value), true),
+        LdcValue::Double(value) => (ensure_double(cp, *value), true),
+        LdcValue::Handle(value) => (ensure_method_handle(cp, value), false),
+        LdcValue::ConstantDynamic(value) => (
+            ensure_constant_dynamic(class_attributes, cp, value)?,
+            value.is_wide(),
+        ),
+    }))
+}
+
+fn resolve_symbolic_insns(
+    methods: &mut [MethodNode],
+    cp: &mut Vec<CpInfo>,
+    class_attributes: &mut Vec<AttributeInfo>,
+) -> Result<(), ClassWriteError> {
+    // The instructions are replaced in place, so that they keep their handles.
+    for method in methods {
+        for id in method.insn_nodes.ids() {
+            if let Some(AbstractInsnNode::Insn(insn)) = method.insn_nodes.get_mut(id) {
+                *insn = resolve_symbolic_insn(insn.clone(), cp, class_attributes)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn resolve_symbolic_insn(
+    insn: Insn,
+    cp: &mut Vec<CpInfo>,
+    class_attributes: &mut Vec<AttributeInfo>,
+) -> Result<Insn, ClassWriteError> {
+    Ok(match insn {
+        Insn::InvokeDynamic(mut node) => {
+            if node.method_index == 0
+                && let (Some(name), Some(descriptor), Some(bootstrap_method)) = (
+                    node.name.as_ref(),
+                    node.descriptor.as_ref(),
+                    node.bootstrap_method.as_ref(),
+                )
+            {
+                let bsm_index = ensure_bootstrap_method(
+                    class_attributes,
+                    cp,
+                    bootstrap_method,
+                    &node.bootstrap_args,
+                )?;
+                node.method_index = ensure_invoke_dynamic(cp, bsm_index, name, descriptor);
+            }
+            Insn::InvokeDynamic(node)
+        }
+        Insn::Ldc(node) => match ensure_ldc_constant(class_attributes, cp, &node.value)? {
+            Some((index, is_wide)) => {
+                Insn::Ldc(LdcInsnNode::from_index(ldc_opcode(index, is_wide), index))
+            }
+            None => Insn::Ldc(node),
+        },
+        other => other,
+    })
+}
+
+/// Lays out the bytecode of a method from its label-based `insn_nodes`, and regenerates its
+/// debug tables and, unless `compute_frames` is set, its `StackMapTable` from the new offsets.
+/// Returns the laid out code, without attributes, and the offsets of its instructions and labels.
+fn layout_method_code(
+    class_name: &str,
+    method: &mut MethodNode,
+    cp: &mut Vec<CpInfo>,
+    compute_frames: bool,
+) -> Result<(CodeAttribute, CodeOffsets), ClassWriteError> {
+    let layout = layout_code(method.insn_nodes.nodes().cloned().collect(), |code, insn| {
+        emit_insn(code, insn, None)
+    })?;
+    // Offsets past 65535 wrap around, so an oversized method also has labels that seem missing.
+    if layout.code.len() > u16::MAX as usize {
+        return Err(ClassWriteError::MethodTooLarge {
+            class: class_name.to_string(),
+            method: method.name.clone(),
+            descriptor: method.descriptor.clone(),
+            size: layout.code.len(),
+        });
+    }
+    if let Some(label) = layout.missing_labels.first() {
+        return Err(ClassWriteError::MissingLabel(label.id));
+    }
+    let has_stack_map = method
+        .code_attributes
+        .iter()
+        .any(|attr| matches!(attr, AttributeInfo::StackMapTable { .. }));
+    method
+        .code_attributes
+        .retain(|attr| !matches!(attr, AttributeInfo::StackMapTable { .. }));
+    if !compute_frames {
+        // An offset-based StackMapTable cannot follow the new offsets, and the target added by
+        // an inverted jump has no frame.
+        if has_stack_map || (layout.inverted_jumps && !layout.frames.is_empty()) {
+            return Err(ClassWriteError::FramesRequired {
+                class: class_name.to_string(),
+                method: method.name.clone(),
+            });
+        }
+        if !layout.frames.is_empty() {
+            let entries = stack_map_entries(&layout, |name| ensure_class(cp, name))?;
+            method
+                .code_attributes
+                .push(AttributeInfo::StackMapTable { entries });
+        }
+    }
+    let insn_ids = method.insn_nodes.iter().filter(|(_, node)| {
+        !matches!(
+            node,
+            AbstractInsnNode::Label(_)
+                | AbstractInsnNode::LineNumber(_)
+                | AbstractInsnNode::Frame(_)
+        )
+    });
+    let mut offsets = CodeOffsets {
+        insns: HashMap::new(),
+        labels: layout.label_offsets.clone(),
+    };
+    for ((id, _), offset) in insn_ids.zip(&layout.insn_offsets) {
+        if let Ok(offset) = u16::try_from(*offset) {
+            offsets.insns.insert(id, offset);
+        }
+    }
+
+    let mut exception_table = Vec::with_capacity(method.try_catch_blocks.len());
+    for block in &method.try_catch_blocks {
+        exception_table.push(ExceptionTableEntry {
+            start_pc: layout.label_offset(&block.start)?,
+            end_pc: layout.label_offset(&block.end)?,
+            handler_pc: layout.label_offset(&block.handler)?,
+            catch_type: match &block.catch_type {
+                Some(name) => ensure_class(cp, name),
+                None => 0,
+            },
+        });
+    }
+
+    method.code_attributes.retain(|attr| {
+        !matches!(
+            attr,
+            AttributeInfo::LineNumberTable { .. }
+                | AttributeInfo::LocalVariableTable { .. }
+                | AttributeInfo::LocalVariableTypeTable { .. }
+        )
+    });
+    let mut line_numbers = Vec::with_capacity(layout.line_numbers.len());
+    for line in &layout.line_numbers {
+        line_numbers.push(LineNumber {
+            start_pc: layout.label_offset(&line.start)?,
+            line_number: line.line,
+        });
+    }
+    if !line_numbers.is_empty() {
+        method.code_attributes.push(AttributeInfo::LineNumberTable {
+            entries: line_numbers,
+        });
+    }
+    let mut local_variables = Vec::new();
+    let mut local_variable_types = Vec::new();
+    for variable in &method.local_variables {
+        let start_pc = layout.label_offset(&variable.start)?;
+        let length = layout.label_offset(&variable.end)?.saturating_sub(start_pc);
+        let name_index = ensure_utf8(cp, &variable.name);
+        local_variables.push(LocalVariable {
+            start_pc,
+            length,
+            name_index,
+            descriptor_index: ensure_utf8(cp, &variable.descriptor),
+            index: variable.index,
+        });
+        if let Some(signature) = &variable.signature {
+            local_variable_types.push(LocalVariableType {
+                start_pc,
+                length,
+                name_index,
+                signature_index: ensure_utf8(cp, signature),
+                index: variable.index,
+            });
+        }
+    }
+    if !local_variables.is_empty() {
+        method.code_attributes.push(AttributeInfo::LocalVariableTable {
+            entries: local_variables,
+        });
+    }
+    if !local_variable_types.is_empty() {
+        method.code_attributes.push(AttributeInfo::LocalVariableTypeTable {
+            entries: local_variable_types,
+        });
+    }
+    let code = CodeAttribute {
+        max_stack: method.max_stack,
+        max_locals: method.max_locals,
+        code: layout.code,
+        instructions: layout.instructions,
+        instruction_offsets: layout.insn_offsets.iter().map(|&offset| offset as u16).collect(),
+        insn_nodes: Vec::new(),
+        exception_table,
+        try_catch_blocks: Vec::new(),
+        local_variables: Vec::new(),
+        attributes: Vec::new(),
+    };
+    Ok((code, offsets))
+}
+
+fn cp_find_utf8(cp: &[CpInfo], value: &str) -> Option<u16> {
+    for (index, entry) in cp.iter().enumerate() {
+        if let CpInfo::Utf8(existing) = entry
+            && existing == value
+        {
+            return Some(index as u16);
+        }
+    }
+    None
 }
 
 fn write_u1(out: &mut Vec<u8>, value: u8) {
@@ -2451,6 +5124,40 @@ fn write_i4_at(out: &mut [u8], pos: usize, value: i32) {
     out[pos + 3] = bytes[3];
 }
 
+/// Writes a local variable instruction (including `RET`), with a `WIDE` prefix if `var_index`
+/// does not fit in an unsigned byte.
+fn write_var_insn(out: &mut Vec<u8>, opcode: u8, var_index: u16) {
+    match u8::try_from(var_index) {
+        Ok(var_index) => {
+            write_u1(out, opcode);
+            write_u1(out, var_index);
+        }
+        Err(_) => {
+            write_u1(out, opcodes::WIDE);
+            write_u1(out, opcode);
+            write_u2(out, var_index);
+        }
+    }
+}
+
+/// Writes an `IINC` instruction, with a `WIDE` prefix if `var_index` does not fit in an
+/// unsigned byte or `increment` in a signed byte.
+fn write_iinc_insn(out: &mut Vec<u8>, var_index: u16, increment: i16) {
+    match (u8::try_from(var_index), i8::try_from(increment)) {
+        (Ok(var_index), Ok(increment)) => {
+            write_u1(out, opcodes::IINC);
+            write_u1(out, var_index);
+            write_i1(out, increment);
+        }
+        _ => {
+            write_u1(out, opcodes::WIDE);
+            write_u1(out, opcodes::IINC);
+            write_u2(out, var_index);
+            write_i2(out, increment);
+        }
+    }
+}
+
 fn write_switch_padding(out: &mut Vec<u8>, opcode_offset: usize) {
     let mut padding = (4 - ((opcode_offset + 1) % 4)) % 4;
     while padding > 0 {
@@ -2478,11 +5185,12 @@ enum FrameType {
 
 fn compute_stack_map_table(
     method: &MethodNode,
-    class_node: &ClassNode,
+    hierarchy: &FrameHierarchy<'_>,
     code: &CodeAttribute,
     cp: &mut Vec<CpInfo>,
     max_locals: u16,
 ) -> Result<Vec<StackMapFrame>, ClassWriteError> {
+    let class_node = hierarchy.class_node;
     let insns = parse_instructions(&code.code)?;
     if insns.is_empty() {
         return Ok(Vec::new());
@@ -2494,7 +5202,7 @@ fn compute_stack_map_table(
     }
 
     let handlers = build_exception_handlers(code, cp)?;
-    let handler_common = handler_common_types(&handlers);
+    let handler_common = handler_common_types(&handlers, hierarchy)?;
     let mut frames: std::collections::HashMap<u16, FrameState> = std::collections::HashMap::new();
     let mut worklist = std::collections::VecDeque::new();
     let mut in_worklist = std::collections::HashSet::new();
@@ -2526,7 +5234,7 @@ fn compute_stack_map_table(
         let out_frame = execute_instruction(insn1, &frame, class_node, cp)?;
 
         for succ in instruction_successors(insn) {
-            if let Some(next_frame) = merge_frame(&out_frame, frames.get(&succ)) {
+            if let Some(next_frame) = merge_frame(&out_frame, frames.get(&succ), hierarchy)? {
                 let changed = match frames.get(&succ) {
                     Some(existing) => existing != &next_frame,
                     None => true,
@@ -2550,7 +5258,9 @@ fn compute_stack_map_table(
                 .cloned()
                 .unwrap_or_else(|| handler.exception_type.clone());
             handler_frame.stack.push(exception_type);
-            if let Some(next_frame) = merge_frame(&handler_frame, frames.get(&handler.handler_pc)) {
+            if let Some(next_frame) =
+                merge_frame(&handler_frame, frames.get(&handler.handler_pc), hierarchy)?
+            {
                 let changed = match frames.get(&handler.handler_pc) {
                     Some(existing) => existing != &next_frame,
                     None => true,
@@ -2605,42 +5315,54 @@ struct FrameState {
     stack: Vec<FrameType>,
 }
 
-fn merge_frame(frame: &FrameState, existing: Option<&FrameState>) -> Option<FrameState> {
+fn merge_frame(
+    frame: &FrameState,
+    existing: Option<&FrameState>,
+    hierarchy: &FrameHierarchy<'_>,
+) -> Result<Option<FrameState>, ClassWriteError> {
     match existing {
-        None => Some(frame.clone()),
+        None => Ok(Some(frame.clone())),
         Some(other) => {
             let merged = FrameState {
-                locals: merge_vec(&frame.locals, &other.locals),
-                stack: merge_vec(&frame.stack, &other.stack),
+                locals: merge_vec(&frame.locals, &other.locals, hierarchy)?,
+                stack: merge_vec(&frame.stack, &other.stack, hierarchy)?,
             };
-            if merged == *other { None } else { Some(merged) }
+            Ok(if merged == *other { None } else { Some(merged) })
         }
     }
 }
 
-fn merge_vec(a: &[FrameType], b: &[FrameType]) -> Vec<FrameType> {
+fn merge_vec(
+    a: &[FrameType],
+    b: &[FrameType],
+    hierarchy: &FrameHierarchy<'_>,
+) -> Result<Vec<FrameType>, ClassWriteError> {
     let len = a.len().max(b.len());
     let mut merged = Vec::with_capacity(len);
     for i in 0..len {
         let left = a.get(i).cloned().unwrap_or(FrameType::Top);
         let right = b.get(i).cloned().unwrap_or(FrameType::Top);
-        merged.push(merge_type(&left, &right));
+        merged.push(merge_type(&left, &right, hierarchy)?);
     }
-    merged
+    Ok(merged)
 }
 
-fn merge_type(a: &FrameType, b: &FrameType) -> FrameType {
+fn merge_type(
+    a: &FrameType,
+    b: &FrameType,
+    hierarchy: &FrameHierarchy<'_>,
+) -> Result<FrameType, ClassWriteError> {
     if a == b {
-        return a.clone();
+        return Ok(a.clone());
     }
-    match (a, b) {
+    Ok(match (a, b) {
         (FrameType::Top, _) => FrameType::Top,
         (_, FrameType::Top) => FrameType::Top,
         (FrameType::Null, FrameType::Object(name)) | (FrameType::Object(name), FrameType::Null) => {
             FrameType::Object(name.clone())
         }
         (FrameType::Object(left), FrameType::Object(right)) => {
-            FrameType::Object(common_superclass(left, right))
+            FrameType::Object(hierarchy.merge_classes(left, right)?)
         }
         (FrameType::Object(_), FrameType::Uninitialized(_))
         | (FrameType::Uninitialized(_), FrameType::Object(_))
@@ -2649,42 +5371,104 @@ fn merge_type(a: &FrameType, b: &FrameType) -> FrameType {
             FrameType::Object("java/lang/Object".to_string())
         }
         _ => FrameType::Top,
-    }
+    })
 }
 
-fn common_superclass(left: &str, right: &str) -> String {
-    if left == right {
-        return left.to_string();
-    }
-    if left.starts_with('[') || right.starts_with('[') {
-        return "java/lang/Object".to_string();
+/// The class hierarchy used to merge frame types: the class being written, then the writer's
+/// hierarchy, then the JDK classes known by default (all of `java.base` with the
+/// `jdk-hierarchy` feature).
+struct FrameHierarchy<'a> {
+    class_node: &'a ClassNode,
+    hierarchy: Option<&'a dyn ClassHierarchy>,
+    /// Whether unknown classes are an error rather than merged into `java/lang/Object`.
+    strict: bool,
+}
+
+impl FrameHierarchy<'_> {
+    fn merge_classes(&self, left: &str, right: &str) -> Result<String, ClassWriteError> {
+        if left == right {
+            return Ok(left.to_string());
+        }
+        if left.starts_with('[') || right.starts_with('[') {
+            return self.merge_arrays(left, right);
+        }
+        match self.common_super_class(left, right) {
+            Err(ClassWriteError::UnknownClass(_)) if !self.strict => {
+                Ok("java/lang/Object".to_string())
+            }
+            result => result,
+        }
     }
 
-    let mut ancestors = std::collections::HashSet::new();
-    let mut current = left;
-    ancestors.insert(current.to_string());
-    while let Some(parent) = known_superclass(current) {
-        if ancestors.insert(parent.to_string()) {
-            current = parent;
-        } else {
-            break;
+    /// Merges two reference types of which at least one is an array type. Arrays of references
+    /// with the same dimensions merge by element type, as in the verifier; any other pair
+    /// merges into the deepest array of `java/lang/Object` that both types are assignable to.
+    fn merge_arrays(&self, left: &str, right: &str) -> Result<String, ClassWriteError> {
+        let (left_dimensions, left_element) = split_array_type(left);
+        let (right_dimensions, right_element) = split_array_type(right);
+        if left_dimensions == right_dimensions
+            && let (Some(left_element), Some(right_element)) = (left_element, right_element)
+        {
+            let element = self.merge_classes(left_element, right_element)?;
+            return Ok(format!("{}L{element};", "[".repeat(left_dimensions)));
         }
+        // An array of primitives is an object, so its last dimension does not count.
+        let reference_dimensions = |dimensions: usize, element: Option<&str>| match element {
+            Some(_) => dimensions,
+            None => dimensions.saturating_sub(1),
+        };
+        let dimensions = reference_dimensions(left_dimensions, left_element)
+            .min(reference_dimensions(right_dimensions, right_element));
+        Ok(match dimensions {
+            0 => "java/lang/Object".to_string(),
+            dimensions => format!("{}Ljava/lang/Object;", "[".repeat(dimensions)),
+        })
     }
-    ancestors.insert("java/lang/Object".to_string());
+}
 
-    current = right;
-    if ancestors.contains(current) {
-        return current.to_string();
+/// Splits the internal name of a class or array type into its number of array dimensions and
+/// the internal name of its element class, which is `None` for arrays of primitives.
+fn split_array_type(name: &str) -> (usize, Option<&str>) {
+    let element = name.trim_start_matches('[');
+    let dimensions = name.len() - element.len();
+    if dimensions == 0 {
+        return (0, Some(name));
     }
-    while let Some(parent) = known_superclass(current) {
-        if ancestors.contains(parent) {
-            return parent.to_string();
+    let class = element.strip_prefix('L').and_then(|class| class.strip_suffix(';'));
+    (dimensions, class)
+}
+
+impl ClassHierarchy for FrameHierarchy<'_> {
+    fn class_info(&self, name: &str) -> Option<ClassInfo> {
+        if name == self.class_node.name {
+            return Some(ClassInfo::from_class_node(self.class_node));
+        }
+        if let Some(info) = self.hierarchy.and_then(|hierarchy| hierarchy.class_info(name)) {
+            return Some(info);
         }
-        current = parent;
+        known_class_info(name)
     }
-    "java/lang/Object".to_string()
 }
 
+#[cfg(feature = "jdk-hierarchy")]
+fn known_class_info(name: &str) -> Option<ClassInfo> {
+    crate::class_hierarchy::JdkClassHierarchy.class_info(name)
+}
+
+#[cfg(not(feature = "jdk-hierarchy"))]
+fn known_class_info(name: &str) -> Option<ClassInfo> {
+    let super_name = match name {
+        "java/lang/Object" => None,
+        name => Some(known_superclass(name)?.to_string()),
+    };
+    Some(ClassInfo {
+        super_name,
+        interfaces: Vec::new(),
+        is_interface: false,
+    })
+}
+
+#[cfg(not(feature = "jdk-hierarchy"))]
 fn known_superclass(name: &str) -> Option<&'static str> {
     match name {
         "java/lang/Throwable" => Some("java/lang/Object"),
@@ -2710,10 +5494,11 @@ fn pad_locals(locals: &mut Vec<FrameType>, max_locals: u16) {
 
 fn compute_maxs(
     method: &MethodNode,
-    class_node: &ClassNode,
+    hierarchy: &FrameHierarchy<'_>,
     code: &CodeAttribute,
     cp: &[CpInfo],
 ) -> Result<(u16, u16), ClassWriteError> {
+    let class_node = hierarchy.class_node;
     let insns = parse_instructions(&code.code)?;
     if insns.is_empty() {
         let initial = initial_frame(method, class_node)?;
@@ -2763,7 +5548,7 @@ fn compute_maxs(
         max_locals = max_locals.max(out_frame.locals.len());
 
         for succ in instruction_successors(insn) {
-            if let Some(next_frame) = merge_frame(&out_frame, frames.get(&succ)) {
+            if let Some(next_frame) = merge_frame(&out_frame, frames.get(&succ), hierarchy)? {
                 let changed = match frames.get(&succ) {
                     Some(existing) => existing != &next_frame,
                     None => true,
@@ -2785,7 +5570,9 @@ fn compute_maxs(
             handler_frame.stack.push(handler.exception_type.clone());
             max_stack = max_stack.max(stack_slots(&handler_frame.stack));
             max_locals = max_locals.max(handler_frame.locals.len());
-            if let Some(next_frame) = merge_frame(&handler_frame, frames.get(&handler.handler_pc)) {
+            if let Some(next_frame) =
+                merge_frame(&handler_frame, frames.get(&handler.handler_pc), hierarchy)?
+            {
                 let changed = match frames.get(&handler.handler_pc) {
                     Some(existing) => existing != &next_frame,
                     None => true,
@@ -2800,6 +5587,22 @@ fn compute_maxs(
         }
     }
 
+    if max_locals > u16::MAX as usize {
+        return Err(ClassWriteError::TooManyLocals {
+            class: class_node.name.clone(),
+            method: method.name.clone(),
+            descriptor: method.descriptor.clone(),
+            max_locals,
+        });
+    }
+    if max_stack > u16::MAX as usize {
+        return Err(ClassWriteError::StackTooLarge {
+            class: class_node.name.clone(),
+            method: method.name.clone(),
+            descriptor: method.descriptor.clone(),
+            max_stack,
+        });
+    }
     Ok((max_stack as u16, max_locals as u16))
 }
 
@@ -2870,8 +5673,8 @@ fn initial_frame(
             locals.push(FrameType::Object(class_node.name.clone()));
         }
     }
-    let (params, _) = parse_method_descriptor(&method.descriptor)?;
-    for param in params {
+    let method_type = Type::try_get_method_type(&method.descriptor)?;
+    for param in method_type.get_argument_types().unwrap_or_default() {
         push_local_type(&mut locals, param);
     }
     Ok(FrameState {
@@ -2880,25 +5683,18 @@ fn initial_frame(
     })
 }
 
-fn push_local_type(locals: &mut Vec<FrameType>, ty: FieldType) {
+fn push_local_type(locals: &mut Vec<FrameType>, ty: &Type) {
     match ty {
-        FieldType::Long => {
+        Type::Long => {
             locals.push(FrameType::Long);
             locals.push(FrameType::Top);
         }
-        FieldType::Double => {
+        Type::Double => {
             locals.push(FrameType::Double);
             locals.push(FrameType::Top);
         }
-        FieldType::Float => locals.push(FrameType::Float),
-        FieldType::Boolean
-        | FieldType::Byte
-        | FieldType::Char
-        | FieldType::Short
-        | FieldType::Int => locals.push(FrameType::Integer),
-        FieldType::Object(name) => locals.push(FrameType::Object(name)),
-        FieldType::Array(desc) => locals.push(FrameType::Object(desc)),
-        FieldType::Void => {}
+        Type::Void | Type::Method { .. } => {}
+        _ => locals.push(type_to_frame(ty)),
     }
 }
 
@@ -2940,39 +5736,31 @@ fn build_exception_handlers(
 
 fn handler_common_types(
     handlers: &[ExceptionHandlerInfo],
-) -> std::collections::HashMap<u16, FrameType> {
+    hierarchy: &FrameHierarchy<'_>,
+) -> Result<std::collections::HashMap<u16, FrameType>, ClassWriteError> {
     let mut map: std::collections::HashMap<u16, FrameType> = std::collections::HashMap::new();
     for handler in handlers {
-        map.entry(handler.handler_pc)
-            .and_modify(|existing| {
-                *existing = merge_exception_type(existing, &handler.exception_type);
-            })
-            .or_insert_with(|| handler.exception_type.clone());
+        let merged = match map.get(&handler.handler_pc) {
+            Some(existing) => merge_exception_type(existing, &handler.exception_type, hierarchy)?,
+            None => handler.exception_type.clone(),
+        };
+        map.insert(handler.handler_pc, merged);
     }
-    map
+    Ok(map)
 }
 
-fn merge_exception_type(left: &FrameType, right: &FrameType) -> FrameType {
-    match (left, right) {
-        (FrameType::Object(l), FrameType::Object(r)) => FrameType::Object(common_superclass(l, r)),
+fn merge_exception_type(
+    left: &FrameType,
+    right: &FrameType,
+    hierarchy: &FrameHierarchy<'_>,
+) -> Result<FrameType, ClassWriteError> {
+    Ok(match (left, right) {
+        (FrameType::Object(l), FrameType::Object(r)) => {
+            FrameType::Object(hierarchy.merge_classes(l, r)?)
+        }
         _ if left == right => left.clone(),
         _ => FrameType::Object("java/lang/Object".to_string()),
-    }
-}
-
-fn dump_frame_debug(
-    method: &MethodNode,
-    label: &str,
-    iterations: usize,
-    hits: &std::collections::HashMap<u16, u32>,
-) {
-    let mut entries: Vec<(u16, u32)> = hits.iter().map(|(k, v)| (*k, *v)).collect();
-    entries.sort_by(|a, b| b.1.cmp(&a.1));
-    let top = entries.into_iter().take(10).collect::<Vec<_>>();
-    eprintln!(
-        "[frame-debug] method={}{} label={} iterations={} top_offsets={:?}",
-        method.name, method.descriptor, label, iterations, top
-    );
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -2985,31 +5773,23 @@ struct ParsedInstruction {
 #[derive(Debug, Clone)]
 enum Operand {
     None,
-    I1(i8),
-    I2(i16),
-    I4(i32),
+    I1,
+    I2,
     U1(u8),
     U2(u16),
-    U4(u32),
     Jump(i16),
     JumpWide(i32),
     TableSwitch {
         default_offset: i32,
-        low: i32,
-        high: i32,
         offsets: Vec<i32>,
     },
     LookupSwitch {
         default_offset: i32,
         pairs: Vec<(i32, i32)>,
     },
-    Iinc {
-        index: u16,
-        increment: i16,
-    },
+    Iinc,
     InvokeInterface {
         index: u16,
-        count: u8,
     },
     InvokeDynamic {
         index: u16,
@@ -3034,22 +5814,22 @@ fn parse_instructions(code: &[u8]) -> Result<Vec<ParsedInstruction>, ClassWriteE
         pos += 1;
         let operand = match opcode {
             opcodes::BIPUSH => {
-                let value = read_i1(code, &mut pos)?;
-                Operand::I1(value)
+                read_i1(code, &mut pos)?;
+                Operand::I1
+            }
+            opcodes::SIPUSH => {
+                read_i2(code, &mut pos)?;
+                Operand::I2
             }
-            opcodes::SIPUSH => Operand::I2(read_i2(code, &mut pos)?),
             opcodes::LDC => Operand::U1(read_u1(code, &mut pos)?),
             opcodes::LDC_W | opcodes::LDC2_W => Operand::U2(read_u2(code, &mut pos)?),
             opcodes::ILOAD..=opcodes::ALOAD | opcodes::ISTORE..=opcodes::ASTORE | opcodes::RET => {
                 Operand::U1(read_u1(code, &mut pos)?)
             }
             opcodes::IINC => {
-                let index = read_u1(code, &mut pos)? as u16;
-                let inc = read_i1(code, &mut pos)? as i16;
-                Operand::Iinc {
-                    index,
-                    increment: inc,
-                }
+                read_u1(code, &mut pos)?;
+                read_i1(code, &mut pos)?;
+                Operand::Iinc
             }
             opcodes::IFEQ..=opcodes::JSR | opcodes::IFNULL | opcodes::IFNONNULL => {
                 Operand::Jump(read_i2(code, &mut pos)?)
@@ -3072,8 +5852,6 @@ fn parse_instructions(code: &[u8]) -> Result<Vec<ParsedInstruction>, ClassWriteE
                 }
                 Operand::TableSwitch {
                     default_offset,
-                    low,
-                    high,
                     offsets,
                 }
             }
@@ -3100,9 +5878,9 @@ fn parse_instructions(code: &[u8]) -> Result<Vec<ParsedInstruction>, ClassWriteE
             | opcodes::INSTANCEOF => Operand::U2(read_u2(code, &mut pos)?),
             opcodes::INVOKEINTERFACE => {
                 let index = read_u2(code, &mut pos)?;
-                let count = read_u1(code, &mut pos)?;
                 let _ = read_u1(code, &mut pos)?;
-                Operand::InvokeInterface { index, count }
+                let _ = read_u1(code, &mut pos)?;
+                Operand::InvokeInterface { index }
             }
             opcodes::INVOKEDYNAMIC => {
                 let index = read_u2(code, &mut pos)?;
@@ -3226,10 +6004,10 @@ fn jump_target(insn: &ParsedInstruction) -> Option<u16> {
 fn instruction_length(insn: &ParsedInstruction) -> usize {
     match &insn.operand {
         Operand::None => 1,
-        Operand::I1(_) | Operand::U1(_) => 2,
-        Operand::I2(_) | Operand::U2(_) | Operand::Jump(_) => 3,
-        Operand::I4(_) | Operand::U4(_) | Operand::JumpWide(_) => 5,
-        Operand::Iinc { .. } => 3,
+        Operand::I1 | Operand::U1(_) => 2,
+        Operand::I2 | Operand::U2(_) | Operand::Jump(_) => 3,
+        Operand::JumpWide(_) => 5,
+        Operand::Iinc => 3,
         Operand::InvokeInterface { .. } => 5,
         Operand::InvokeDynamic { .. } => 5,
         Operand::MultiANewArray { .. } => 4,
@@ -3866,14 +6644,13 @@ fn field_type(insn: &ParsedInstruction, cp: &[CpInfo]) -> Result<FrameType, Clas
         }
     };
     let descriptor = cp_field_descriptor(cp, index)?;
-    let field_type = parse_field_descriptor(descriptor)?;
-    Ok(field_type_to_frame(field_type))
+    Ok(type_to_frame(&Type::try_get_type(descriptor)?))
 }
 
 fn method_type(
     insn: &ParsedInstruction,
     cp: &[CpInfo],
-) -> Result<(Vec<FieldType>, Option<FrameType>, String, bool), ClassWriteError> {
+) -> Result<(Vec<Type>, Option<FrameType>, String, bool), ClassWriteError> {
     let index = match insn.operand {
         Operand::U2(value) => value,
         Operand::InvokeInterface { index, .. } => index,
@@ -3886,27 +6663,24 @@ fn method_type(
         }
     };
     let (owner, descriptor, name) = cp_method_descriptor(cp, index, insn.opcode)?;
-    let (args, ret) = parse_method_descriptor(descriptor)?;
-    let ret_frame = match ret {
-        FieldType::Void => None,
-        other => Some(field_type_to_frame(other)),
+    let method_type = Type::try_get_method_type(descriptor)?;
+    let args = method_type.get_argument_types().unwrap_or_default().to_vec();
+    let ret_frame = match method_type.get_return_type() {
+        Some(Type::Void) | None => None,
+        Some(other) => Some(type_to_frame(other)),
     };
     Ok((args, ret_frame, owner.to_string(), name == "<init>"))
 }
 
-fn field_type_to_frame(field_type: FieldType) -> FrameType {
-    match field_type {
-        FieldType::Boolean
-        | FieldType::Byte
-        | FieldType::Char
-        | FieldType::Short
-        | FieldType::Int => FrameType::Integer,
-        FieldType::Float => FrameType::Float,
-        FieldType::Long => FrameType::Long,
-        FieldType::Double => FrameType::Double,
-        FieldType::Object(name) => FrameType::Object(name),
-        FieldType::Array(desc) => FrameType::Object(desc),
-        FieldType::Void => FrameType::Top,
+fn type_to_frame(ty: &Type) -> FrameType {
+    match ty {
+        Type::Boolean | Type::Byte | Type::Char | Type::Short | Type::Int => FrameType::Integer,
+        Type::Float => FrameType::Float,
+        Type::Long => FrameType::Long,
+        Type::Double => FrameType::Double,
+        Type::Object(name) => FrameType::Object(name.clone()),
+        Type::Array(_) => FrameType::Object(ty.get_descriptor()),
+        Type::Void | Type::Method { .. } => FrameType::Top,
     }
 }
 
@@ -4009,109 +6783,6 @@ fn cp_utf8(cp: &[CpInfo], index: u16) -> Result<&str, ClassWriteError> {
     }
 }
 
-#[derive(Debug, Clone)]
-enum FieldType {
-    Boolean,
-    Byte,
-    Char,
-    Short,
-    Int,
-    Float,
-    Long,
-    Double,
-    Object(String),
-    Array(String),
-    Void,
-}
-
-fn parse_field_descriptor(desc: &str) -> Result<FieldType, ClassWriteError> {
-    let mut chars = desc.chars().peekable();
-    parse_field_type(&mut chars)
-}
-
-fn parse_method_descriptor(desc: &str) -> Result<(Vec<FieldType>, FieldType), ClassWriteError> {
-    let mut chars = desc.chars().peekable();
-    if chars.next() != Some('(') {
-        return Err(ClassWriteError::FrameComputation(
-            "bad method descriptor".to_string(),
-        ));
-    }
-    let mut params = Vec::new();
-    while let Some(&ch) = chars.peek() {
-        if ch == ')' {
-            chars.next();
-            break;
-        }
-        params.push(parse_field_type(&mut chars)?);
-    }
-    let ret = parse_return_type(&mut chars)?;
-    Ok((params, ret))
-}
-
-fn parse_field_type<I>(chars: &mut std::iter::Peekable<I>) -> Result<FieldType, ClassWriteError>
-where
-    I: Iterator<Item = char>,
-{
-    match chars.next() {
-        Some('Z') => Ok(FieldType::Boolean),
-        Some('B') => Ok(FieldType::Byte),
-        Some('C') => Ok(FieldType::Char),
-        Some('S') => Ok(FieldType::Short),
-        Some('I') => Ok(FieldType::Int),
-        Some('F') => Ok(FieldType::Float),
-        Some('J') => Ok(FieldType::Long),
-        Some('D') => Ok(FieldType::Double),
-        Some('L') => {
-            let mut name = String::new();
-            for ch in chars.by_ref() {
-                if ch == ';' {
-                    break;
-                }
-                name.push(ch);
-            }
-            Ok(FieldType::Object(name))
-        }
-        Some('[') => {
-            let mut desc = String::from("[");
-            let inner = parse_field_type(chars)?;
-            match inner {
-                FieldType::Object(name) => {
-                    desc.push('L');
-                    desc.push_str(&name);
-                    desc.push(';');
-                }
-                FieldType::Boolean => desc.push('Z'),
-                FieldType::Byte => desc.push('B'),
-                FieldType::Char => desc.push('C'),
-                FieldType::Short => desc.push('S'),
-                FieldType::Int => desc.push('I'),
-                FieldType::Float => desc.push('F'),
-                FieldType::Long => desc.push('J'),
-                FieldType::Double => desc.push('D'),
-                FieldType::Void => {}
-                FieldType::Array(inner_desc) => desc.push_str(&inner_desc),
-            }
-            Ok(FieldType::Array(desc))
-        }
-        _ => Err(ClassWriteError::FrameComputation(
-            "bad field descriptor".to_string(),
-        )),
-    }
-}
-
-fn parse_return_type<I>(chars: &mut std::iter::Peekable<I>) -> Result<FieldType, ClassWriteError>
-where
-    I: Iterator<Item = char>,
-{
-    match chars.peek() {
-        Some('V') => {
-            chars.next();
-            Ok(FieldType::Void)
-        }
-        _ => parse_field_type(chars),
-    }
-}
-
 fn read_u1(code: &[u8], pos: &mut usize) -> Result<u8, ClassWriteError> {
     if *pos >= code.len() {
         return Err(ClassWriteError::FrameComputation(
@@ -4156,8 +6827,9 @@ fn read_i4(code: &[u8], pos: &mut usize) -> Result<i32, ClassWriteError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::class_hierarchy::{ClassNodeHierarchy, DirectoryClassHierarchy};
     use crate::class_reader::ClassReader;
-    use crate::insn::InvokeDynamicInsnNode;
+    use crate::error::ClassReadError;
     use crate::opcodes;
 
     #[test]
@@ -4179,15 +6851,15 @@ mod tests {
     #[test]
     fn test_basic_class_generation() {
         let mut cw = ClassWriter::new(0);
-        cw.visit(52, 0, 0x0001, "TestClass", Some("java/lang/Object"), &[]);
+        cw.visit(52, 0, 0x0001, "TestClass", None, Some("java/lang/Object"), &[]);
         cw.visit_source_file("TestClass.java");
 
         // Add a field
-        let fv = cw.visit_field(0x0002, "myField", "I");
+        let fv = cw.visit_field(0x0002, "myField", "I", None, None);
         fv.visit_end(&mut cw);
 
         // Add a default constructor
-        let mut mv = cw.visit_method(0x0001, "<init>", "()V");
+        let mut mv = cw.visit_method(0x0001, "<init>", "()V", None, &[]);
         mv.visit_code();
         mv.visit_var_insn(opcodes::ALOAD, 0);
         mv.visit_method_insn(
@@ -4213,9 +6885,9 @@ mod tests {
     fn test_compute_frames_flag() {
         // Simple linear code, but checking if logic runs without panic
         let mut cw = ClassWriter::new(COMPUTE_FRAMES);
-        cw.visit(52, 0, 0x0001, "FrameTest", Some("java/lang/Object"), &[]);
+        cw.visit(52, 0, 0x0001, "FrameTest", None, Some("java/lang/Object"), &[]);
 
-        let mut mv = cw.visit_method(0x0009, "main", "([Ljava/lang/String;)V");
+        let mut mv = cw.visit_method(0x0009, "main", "([Ljava/lang/String;)V", None, &[]);
         mv.visit_code();
         mv.visit_field_insn(
             opcodes::GETSTATIC,
@@ -4243,10 +6915,2122 @@ mod tests {
     #[test]
     fn test_class_node_structure() {
         let mut cw = ClassWriter::new(0);
-        cw.visit(52, 0, 0, "MyNode", None, &[]);
+        cw.visit(52, 0, 0, "MyNode", None, None, &[]);
 
         let node = cw.to_class_node().expect("Should create class node");
         assert_eq!(node.name, "MyNode");
         assert_eq!(node.major_version, 52);
     }
+
+    /// Writes a class with a raw class attribute `name` holding `info`, and reads it back.
+    ///
+    /// `info` cannot refer to the constant pool, whose layout is up to the writer.
+    fn read_class_attribute(name: &str, info: &[u8]) -> Result<ClassNode, ClassReadError> {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0001, "Raw", None, Some("java/lang/Object"), &[]);
+        cw.add_attribute(AttributeInfo::Unknown {
+            name: name.to_string(),
+            info: info.to_vec(),
+        });
+        let bytes = cw.to_bytes().expect("Should write raw attribute");
+        ClassReader::new(&bytes).to_class_node()
+    }
+
+    #[test]
+    fn test_annotations_round_trip() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0001, "Annotated", None, Some("java/lang/Object"), &[]);
+        cw.visit_annotation(
+            &AnnotationNode::new("Ljava/lang/Deprecated;")
+                .with_value("since", AnnotationValue::String("1.0".to_string())),
+            true,
+        );
+
+        let mut fv = cw.visit_field(0x0002, "value", "I", None, None);
+        fv.visit_annotation(AnnotationNode::new("Ljavax/annotation/Nullable;"), false);
+        fv.visit_end(&mut cw);
+
+        let mut mv = cw.visit_method(0x0401, "level", "(II)I", None, &[]);
+        mv.visit_parameter_annotation(
+            1,
+            AnnotationNode::new("Lcom/example/Range;").with_value(
+                "values",
+                AnnotationValue::Array(vec![AnnotationValue::Int(1), AnnotationValue::Long(2)]),
+            ),
+            true,
+        );
+        mv.visit_annotation_default(AnnotationValue::Enum {
+            descriptor: "Lcom/example/Level;".to_string(),
+            value: "HIGH".to_string(),
+        });
+        mv.visit_end(&mut cw);
+        let mut mv = cw.visit_method(0x0401, "values", "()[I", None, &[]);
+        mv.visit_annotation_default(AnnotationValue::Array(Vec::new()));
+        mv.visit_end(&mut cw);
+
+        let bytes = cw.to_bytes().expect("Should write annotations");
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read annotations back");
+
+        assert_eq!(node.visible_annotations.len(), 1);
+        assert_eq!(
+            node.visible_annotations[0].value("since"),
+            Some(&AnnotationValue::String("1.0".to_string()))
+        );
+        assert_eq!(
+            node.fields[0].invisible_annotations[0].descriptor,
+            "Ljavax/annotation/Nullable;"
+        );
+
+        let method = &node.methods[0];
+        // The parameters before the annotated one have no annotations.
+        assert_eq!(method.visible_parameter_annotations.len(), 2);
+        assert!(method.visible_parameter_annotations[0].is_empty());
+        assert_eq!(
+            method.visible_parameter_annotations[1][0].value("values"),
+            Some(&AnnotationValue::Array(vec![
+                AnnotationValue::Int(1),
+                AnnotationValue::Long(2)
+            ]))
+        );
+        assert!(matches!(
+            &method.annotation_default,
+            Some(AnnotationValue::Enum { value, .. }) if value == "HIGH"
+        ));
+        assert_eq!(
+            node.methods[1].annotation_default,
+            Some(AnnotationValue::Array(Vec::new()))
+        );
+        assert!(node.methods.iter().all(|method| method.attributes.is_empty()));
+        assert!(node.attributes.is_empty());
+
+        // One annotation with one element value pair, but no element value.
+        let truncated = [0, 1, 0, 1, 0, 1, 0, 1];
+        assert!(read_class_attribute("RuntimeVisibleAnnotations", &truncated).is_err());
+        // An element value with an unknown tag.
+        let bad_tag = [0, 1, 0, 1, 0, 1, 0, 1, b'x'];
+        assert!(read_class_attribute("RuntimeVisibleAnnotations", &bad_tag).is_err());
+    }
+
+
+    #[test]
+    fn test_copies_keep_annotable_parameter_count() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0001, "Annotated", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0401, "run", "(ILjava/lang/String;J)V", None, &[]);
+        mv.visit_annotable_parameter_count(3, true);
+        mv.visit_parameter_annotation(0, AnnotationNode::new("Lcom/example/Positive;"), true);
+        mv.visit_annotable_parameter_count(2, false);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write annotations");
+        let counts = |bytes: &[u8]| {
+            let node = ClassReader::new(bytes)
+                .to_class_node()
+                .expect("Should read annotations back");
+            let method = &node.methods[0];
+            (
+                method.visible_parameter_annotations.len(),
+                method.invisible_parameter_annotations.len(),
+            )
+        };
+        assert_eq!(counts(&bytes), (3, 2));
+
+        let mut writer = ClassWriter::new(0);
+        ClassReader::new(&bytes)
+            .accept(&mut writer, 0)
+            .expect("Should visit class");
+        assert_eq!(counts(&writer.to_bytes().expect("Should write copy")), (3, 2));
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class node");
+        let mut writer = ClassWriter::new(0);
+        node.accept(&mut writer).expect("Should replay class node");
+        assert_eq!(counts(&writer.to_bytes().expect("Should write replay")), (3, 2));
+    }
+
+    #[test]
+    fn test_type_annotations_round_trip() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0001, "TypeAnnotated", None, Some("java/lang/Object"), &[]);
+
+        let mut fv = cw.visit_field(0x0002, "names", "Ljava/util/List;", None, None);
+        fv.visit_type_annotation(
+            TypeReference::new_type_reference(TypeReference::FIELD),
+            TypePath::from_string("0;"),
+            AnnotationNode::new("Lcom/example/NonNull;"),
+            true,
+        );
+        fv.visit_end(&mut cw);
+
+        let start = Label::new();
+        let end = Label::new();
+        let mut mv = cw.visit_method(
+            0x0009,
+            "cast",
+            "(Ljava/lang/Object;)Ljava/lang/String;",
+            None,
+            &[],
+        );
+        mv.visit_type_annotation(
+            TypeReference::new_type_reference(TypeReference::METHOD_RETURN),
+            None,
+            AnnotationNode::new("Lcom/example/Nullable;"),
+            false,
+        );
+        mv.visit_code();
+        mv.visit_var_insn(opcodes::ALOAD, 0);
+        mv.visit_type_insn(opcodes::CHECKCAST, "java/lang/String");
+        mv.visit_insn_annotation(
+            TypeReference::new_type_argument_reference(TypeReference::CAST, 0),
+            None,
+            AnnotationNode::new("Lcom/example/NonNull;"),
+            true,
+        );
+        mv.visit_label(start);
+        mv.visit_var_insn(opcodes::ASTORE, 1);
+        mv.visit_var_insn(opcodes::ALOAD, 1);
+        mv.visit_insn(opcodes::ARETURN);
+        mv.visit_label(end);
+        mv.visit_local_variable_annotation(
+            TypeReference::new_type_reference(TypeReference::LOCAL_VARIABLE),
+            None,
+            &[start],
+            &[end],
+            &[1],
+            AnnotationNode::new("Lcom/example/NonNull;"),
+            true,
+        );
+        mv.visit_maxs(1, 2);
+        mv.visit_end(&mut cw);
+
+        let bytes = cw.to_bytes().expect("Should write type annotations");
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read type annotations back");
+
+        let field_annotation = &node.fields[0].visible_type_annotations[0];
+        assert_eq!(field_annotation.type_ref.sort(), TypeReference::FIELD);
+        assert_eq!(
+            field_annotation.type_path.as_ref().map(|path| path.to_string()),
+            Some("0;".to_string())
+        );
+
+        let method = &node.methods[0];
+        assert_eq!(
+            method.invisible_type_annotations[0].type_ref.sort(),
+            TypeReference::METHOD_RETURN
+        );
+        assert_eq!(method.visible_insn_annotations.len(), 1);
+        assert!(matches!(
+            method.insn_nodes.get(method.visible_insn_annotations[0].insn),
+            Some(AbstractInsnNode::Insn(Insn::Type(insn))) if insn.insn.opcode == opcodes::CHECKCAST
+        ));
+        assert_eq!(
+            method.visible_insn_annotations[0].type_annotation.type_ref.sort(),
+            TypeReference::CAST
+        );
+        let local = &method.visible_local_variable_annotations[0];
+        let after = |label: &LabelNode| {
+            let (id, _) = method
+                .insn_nodes
+                .iter()
+                .find(|(_, node)| matches!(node, AbstractInsnNode::Label(l) if l.id == label.id))
+                .expect("Local variable range labels should be placed");
+            method.insn_nodes.next(id).and_then(|id| method.insn_nodes.get(id))
+        };
+        assert!(matches!(
+            after(&local.start[0]),
+            Some(AbstractInsnNode::Insn(Insn::Var(VarInsnNode { var_index: 1, .. })))
+        ));
+        assert!(after(&local.end[0]).is_none());
+        assert_eq!(local.index, vec![1]);
+        assert!(method.code_attributes.is_empty());
+
+        // A class type annotation whose type path runs past the end of the attribute.
+        let long_path = [0, 1, 0x10, 0xFF, 0xFF, 5];
+        assert!(read_class_attribute("RuntimeVisibleTypeAnnotations", &long_path).is_err());
+        let bad_target = [0, 1, 0x99];
+        assert!(read_class_attribute("RuntimeVisibleTypeAnnotations", &bad_target).is_err());
+    }
+
+    #[test]
+    fn test_module_round_trip() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(53, 0, constants::ACC_MODULE, "module-info", None, None, &[]);
+        let mut module = cw.visit_module("com.example.app", constants::ACC_OPEN, Some("1.0"));
+        module
+            .visit_main_class("com/example/app/Main")
+            .visit_package("com/example/app")
+            .visit_package("com/example/app/spi")
+            .visit_require("java.base", constants::ACC_MANDATED, None)
+            .visit_require("java.logging", constants::ACC_TRANSITIVE, Some("17"))
+            .visit_export("com/example/app/spi", 0, &[])
+            .visit_open("com/example/app", 0, &["com.example.test"])
+            .visit_use("com/example/app/spi/Plugin")
+            .visit_provide(
+                "com/example/app/spi/Plugin",
+                &["com/example/app/DefaultPlugin"],
+            );
+        module.visit_end(&mut cw);
+
+        let bytes = cw.to_bytes().expect("Should write module-info");
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read module-info back");
+
+        let module = node.module.as_ref().expect("Module should be decoded");
+        assert_eq!(module.name, "com.example.app");
+        assert_eq!(module.access_flags, constants::ACC_OPEN);
+        assert_eq!(module.version.as_deref(), Some("1.0"));
+        assert_eq!(module.main_class.as_deref(), Some("com/example/app/Main"));
+        assert_eq!(module.packages, vec!["com/example/app", "com/example/app/spi"]);
+        assert_eq!(module.requires.len(), 2);
+        assert_eq!(module.requires[1].module, "java.logging");
+        assert_eq!(module.requires[1].version.as_deref(), Some("17"));
+        assert!(module.exports[0].modules.is_empty());
+        assert_eq!(module.opens[0].modules, vec!["com.example.test"]);
+        assert_eq!(module.uses, vec!["com/example/app/spi/Plugin"]);
+        assert_eq!(
+            module.provides[0].providers,
+            vec!["com/example/app/DefaultPlugin"]
+        );
+        assert!(node.attributes.is_empty());
+
+        // A requires entry swallows the remaining counts.
+        let truncated = [0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert!(read_class_attribute("Module", &truncated).is_err());
+        assert!(read_class_attribute("ModulePackages", &[0, 2, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn test_record_components_round_trip() {
+        use crate::class_reader::SKIP_DEBUG;
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(60, 0, 0x0011, "Point", None, Some("java/lang/Record"), &[]);
+        let mut rv = cw.visit_record_component(
+            "tags",
+            "Ljava/util/List;",
+            Some("Ljava/util/List<Ljava/lang/String;>;"),
+        );
+        rv.visit_annotation(AnnotationNode::new("Lcom/example/NonNull;"), true)
+            .visit_type_annotation(
+                TypeReference::new_type_reference(TypeReference::FIELD),
+                TypePath::from_string("0;"),
+                AnnotationNode::new("Lcom/example/NonEmpty;"),
+                false,
+            );
+        rv.visit_end(&mut cw);
+        cw.visit_record_component("x", "I", None).visit_end(&mut cw);
+
+        let bytes = cw.to_bytes().expect("Should write record");
+        // The signature of a component is not debug information.
+        let node = ClassReader::new(&bytes)
+            .to_class_node_with_options(SKIP_DEBUG)
+            .expect("Should read record back");
+
+        let components = node.record_components.as_deref().expect("Should be a record");
+        assert_eq!(components.len(), 2);
+        let tags = &components[0];
+        assert_eq!(tags.name, "tags");
+        assert_eq!(tags.descriptor, "Ljava/util/List;");
+        assert_eq!(
+            tags.signature.as_deref(),
+            Some("Ljava/util/List<Ljava/lang/String;>;")
+        );
+        assert_eq!(tags.visible_annotations[0].descriptor, "Lcom/example/NonNull;");
+        assert_eq!(
+            tags.invisible_type_annotations[0].annotation.descriptor,
+            "Lcom/example/NonEmpty;"
+        );
+        assert!(tags.attributes.is_empty());
+        assert_eq!(components[1].name, "x");
+        assert!(components[1].signature.is_none());
+        assert!(node.attributes.is_empty());
+
+        assert!(read_class_attribute("Record", &[0, 1]).is_err());
+    }
+
+    #[test]
+    fn test_empty_record_round_trip() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(60, 0, 0x0011, "Empty", None, Some("java/lang/Record"), &[]);
+        cw.visit_record();
+        let bytes = cw.to_bytes().expect("Should write empty record");
+        let mut node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read empty record");
+        assert_eq!(node.record_components.as_ref().map(Vec::len), Some(0));
+        assert!(node.attributes.is_empty());
+
+        let bytes = ClassWriter::write_class_node(&node, 0).expect("Should rewrite empty record");
+        let rewritten = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read rewritten record");
+        assert_eq!(rewritten.record_components.map(|components| components.len()), Some(0));
+
+        node.record_components = Some(vec![RecordComponentNode::new("x", "I", None)]);
+        let bytes = ClassWriter::write_class_node(&node, 0).expect("Should add a component");
+        let grown = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read grown record");
+        assert_eq!(grown.record_components.map(|components| components.len()), Some(1));
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0001, "Plain", None, Some("java/lang/Object"), &[]);
+        let bytes = cw.to_bytes().expect("Should write class");
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class");
+        assert!(node.record_components.is_none());
+    }
+
+    #[test]
+    fn test_nest_and_permitted_subclasses_round_trip() {
+        use crate::class_reader::{SKIP_CODE, SKIP_FRAMES};
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(61, 0, 0x0401, "Shape", None, Some("java/lang/Object"), &[]);
+        cw.visit_nest_member("Shape$Circle")
+            .visit_nest_member("Shape$Square")
+            .visit_permitted_subclass("Shape$Circle");
+        let bytes = cw.to_bytes().expect("Should write nest host");
+        let node = ClassReader::new(&bytes)
+            .to_class_node_with_options(SKIP_CODE | SKIP_FRAMES)
+            .expect("Should read nest host back");
+        assert!(node.nest_host.is_none());
+        assert_eq!(node.nest_members, vec!["Shape$Circle", "Shape$Square"]);
+        assert_eq!(node.permitted_subclasses, vec!["Shape$Circle"]);
+        assert!(node.attributes.is_empty());
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(61, 0, 0x0011, "Shape$Circle", None, Some("Shape"), &[]);
+        cw.visit_nest_host("Shape");
+        let bytes = cw.to_bytes().expect("Should write nest member");
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read nest member back");
+        assert_eq!(node.nest_host.as_deref(), Some("Shape"));
+        assert!(node.nest_members.is_empty());
+
+        // A member past the end of the attribute, and a byte after the last member.
+        let error = read_class_attribute("NestMembers", &[0, 3, 0, 1])
+            .expect_err("Should reject a truncated member table");
+        assert!(matches!(error.root(), ClassReadError::UnexpectedEof));
+        assert_eq!(
+            error.location().and_then(|location| location.attribute.as_deref()),
+            Some("NestMembers")
+        );
+        let error = read_class_attribute("NestMembers", &[0, 0, 0])
+            .expect_err("Should reject bytes after the last member");
+        assert!(matches!(error.root(), ClassReadError::InvalidAttribute(_)));
+        assert!(read_class_attribute("NestHost", &[0xFF, 0xFF]).is_err());
+    }
+
+    #[test]
+    fn test_debug_attributes_round_trip() {
+        use crate::class_reader::{SKIP_CODE, SKIP_DEBUG};
+
+        // The extension is kept as raw bytes, which need not be valid UTF-8.
+        let smap = b"SMAP\nInline.kt\n\xff\x00*E\n".to_vec();
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0001, "Inline", None, Some("java/lang/Object"), &[]);
+        cw.add_attribute(AttributeInfo::SourceDebugExtension {
+            debug_extension: smap.clone(),
+        });
+        let mut mv = cw.visit_method(0x0009, "first", "(Ljava/util/List;)V", None, &[]);
+        let (start, end) = (Label::new(), Label::new());
+        mv.visit_code();
+        mv.visit_label(start);
+        mv.visit_insn(opcodes::RETURN);
+        mv.visit_label(end);
+        mv.visit_local_variable(
+            "list",
+            "Ljava/util/List;",
+            Some("Ljava/util/List<Ljava/lang/String;>;"),
+            start,
+            end,
+            0,
+        );
+        mv.visit_maxs(0, 1);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class back");
+        assert!(node.attributes.iter().any(|attr| matches!(
+            attr,
+            AttributeInfo::SourceDebugExtension { debug_extension } if *debug_extension == smap
+        )));
+        let local = &node.methods[0].local_variables[0];
+        assert_eq!(local.name, "list");
+        assert_eq!(
+            local.signature.as_deref(),
+            Some("Ljava/util/List<Ljava/lang/String;>;")
+        );
+
+        let skipped = ClassReader::new(&bytes)
+            .to_class_node_with_options(SKIP_DEBUG)
+            .expect("Should read class without debug information");
+        assert!(skipped.attributes.is_empty());
+        assert!(skipped.methods[0].local_variables.is_empty());
+        assert!(skipped.methods[0].code_attributes.is_empty());
+        let skipped = ClassReader::new(&bytes)
+            .to_class_node_with_options(SKIP_CODE)
+            .expect("Should read class without code");
+        assert_eq!(skipped.attributes.len(), 1);
+        assert!(skipped.methods[0].local_variables.is_empty());
+
+        // A truncated table fails, unless debug information is skipped without being parsed.
+        let mut node = node;
+        node.methods[0].local_variables.clear();
+        node.methods[0].code_attributes.push(AttributeInfo::Unknown {
+            name: "LocalVariableTypeTable".to_string(),
+            info: vec![0, 2],
+        });
+        let truncated = ClassWriter::write_class_node(&node, 0).expect("Should write raw table");
+        let error = ClassReader::new(&truncated)
+            .to_class_node()
+            .expect_err("Should reject an entry past the end of the attribute");
+        assert!(matches!(error.root(), ClassReadError::UnexpectedEof));
+        assert_eq!(
+            error.location().and_then(|location| location.attribute.as_deref()),
+            Some("LocalVariableTypeTable")
+        );
+        ClassReader::new(&truncated)
+            .to_class_node_with_options(SKIP_DEBUG)
+            .expect("Should skip the truncated table");
+    }
+
+    struct RemoveMethodAdapter {
+        next: ClassWriter,
+        removed: &'static str,
+    }
+
+    impl class_reader::ClassVisitor for RemoveMethodAdapter {
+        fn delegate(&mut self) -> Option<&mut dyn class_reader::ClassVisitor> {
+            Some(&mut self.next)
+        }
+
+        fn visit_method(
+            &mut self,
+            access_flags: u16,
+            name: &str,
+            descriptor: &str,
+            signature: Option<&str>,
+            exceptions: &[&str],
+        ) -> Option<Box<dyn class_reader::MethodVisitor>> {
+            if name == self.removed {
+                return None;
+            }
+            let next =
+                self.next.visit_method(access_flags, name, descriptor, signature, exceptions);
+            Some(Box::new(ReplaceConstantAdapter {
+                next: Box::new(next),
+            }))
+        }
+    }
+
+    struct ReplaceConstantAdapter {
+        next: Box<dyn class_reader::MethodVisitor>,
+    }
+
+    impl class_reader::MethodVisitor for ReplaceConstantAdapter {
+        fn delegate(&mut self) -> Option<&mut dyn class_reader::MethodVisitor> {
+            Some(self.next.as_mut())
+        }
+
+        fn visit_insn(&mut self, opcode: u8) {
+            let opcode = if opcode == opcodes::ICONST_1 {
+                opcodes::ICONST_2
+            } else {
+                opcode
+            };
+            self.next.visit_insn(opcode);
+        }
+    }
+
+    #[test]
+    fn test_visitor_invalid_ldc_constant() {
+        for constant in [
+            class_reader::LdcConstant::Class(String::new()),
+            class_reader::LdcConstant::MethodType("I".to_string()),
+        ] {
+            let mut cw = ClassWriter::new(COMPUTE_MAXS);
+            cw.visit(52, 0, 0x0021, "Invalid", None, Some("java/lang/Object"), &[]);
+            let mut mv = class_reader::ClassVisitor::visit_method(
+                &mut cw, 0x0009, "load", "()V", None, &[],
+            )
+            .expect("Should visit method");
+            mv.visit_code();
+            mv.visit_ldc_insn(constant);
+            mv.visit_insn(opcodes::POP);
+            mv.visit_insn(opcodes::RETURN);
+            mv.visit_maxs(0, 0);
+            mv.visit_end();
+            let error = cw.to_bytes().expect_err("Should reject invalid constant");
+            assert!(matches!(error, ClassWriteError::InvalidDescriptor(_)), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_visitor_chaining() {
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES);
+        cw.visit(52, 0, 0x0001, "Chained", None, Some("java/lang/Object"), &[]);
+        cw.visit_source_file("Chained.java");
+        let mut fv = cw.visit_field(0x0002, "value", "I", None, None);
+        fv.visit_annotation(AnnotationNode::new("Ljava/lang/Deprecated;"), true);
+        fv.visit_end(&mut cw);
+
+        let mut mv = cw.visit_method(0x0009, "one", "()I", None, &[]);
+        mv.visit_code();
+        mv.visit_insn(opcodes::ICONST_1);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+
+        let mut mv = cw.visit_method(0x0009, "abs", "(I)I", None, &[]);
+        let positive = Label::new();
+        let start = Label::new();
+        mv.visit_code();
+        mv.visit_label(start);
+        mv.visit_line_number(3, LabelNode::from_label(start));
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_jump_insn(opcodes::IFGE, positive);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::INEG);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_label(positive);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+
+        let mut mv = cw.visit_method(0x0009, "removed", "()V", None, &[]);
+        mv.visit_code();
+        mv.visit_insn(opcodes::RETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write source class");
+
+        let mut adapter = RemoveMethodAdapter {
+            next: ClassWriter::new(COMPUTE_FRAMES),
+            removed: "removed",
+        };
+        ClassReader::new(&bytes)
+            .accept(&mut adapter, 0)
+            .expect("Should visit source class");
+        let bytes = adapter.next.to_bytes().expect("Should write chained class");
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read chained class back");
+        assert_eq!(node.name, "Chained");
+        assert_eq!(node.source_file.as_deref(), Some("Chained.java"));
+        assert_eq!(node.fields.len(), 1);
+        assert_eq!(node.fields[0].visible_annotations.len(), 1);
+        let names: Vec<&str> = node.methods.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["one", "abs"]);
+        assert!(matches!(
+            method_code(&bytes, 0).instructions[0],
+            Insn::Simple(InsnNode { opcode: opcodes::ICONST_2 })
+        ));
+        let abs = method_code(&bytes, 1);
+        assert_eq!(abs.instructions.len(), 7);
+        assert!(matches!(abs.instructions[1], Insn::Jump(_)));
+        assert!(abs.attributes.iter().any(|attr| matches!(
+            attr,
+            AttributeInfo::LineNumberTable { entries } if entries.len() == 1
+        )));
+
+        // Without COMPUTE_FRAMES, the frames read are written back.
+        let mut writer = ClassWriter::new(0);
+        ClassReader::new(&bytes)
+            .accept(&mut writer, 0)
+            .expect("Should visit chained class");
+        let copied = writer.to_bytes().expect("Should write copied class");
+        let stack_map = |bytes: &[u8]| {
+            method_code(bytes, 1).attributes.into_iter().find_map(|attr| match attr {
+                AttributeInfo::StackMapTable { entries } => Some(format!("{entries:?}")),
+                _ => None,
+            })
+        };
+        assert!(stack_map(&bytes).is_some());
+        assert_eq!(stack_map(&copied), stack_map(&bytes));
+    }
+
+    #[test]
+    fn test_visitors_outlive_moved_writer() {
+        use class_reader::ClassVisitor as _;
+
+        let mut writer = ClassWriter::new(COMPUTE_MAXS);
+        writer.visit(52, 0, 0x0001, "Moved", None, Some("java/lang/Object"), &[]);
+        let mut mv: Box<dyn class_reader::MethodVisitor> =
+            Box::new(writer.visit_method(0x0009, "run", "()V", None, &[]));
+        let fv = writer.visit_field(0x0002, "value", "I", None, None);
+        // The visitors hold no reference to the writer, which can be moved meanwhile.
+        let mut writer = Box::new(writer);
+        mv.visit_code();
+        mv.visit_insn(opcodes::RETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end();
+        drop(fv);
+        writer.visit_end();
+        let bytes = writer.to_bytes().expect("Should write class");
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class back");
+        assert_eq!(node.methods.len(), 1);
+        assert_eq!(node.methods[0].name, "run");
+        assert_eq!(node.fields.len(), 1);
+        assert_eq!(node.fields[0].name, "value");
+    }
+
+    /// Records the declarations and attributes a class is visited with.
+    #[derive(Default)]
+    struct AttributeRecorder {
+        events: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+    }
+
+    impl AttributeRecorder {
+        fn record(&self, event: String) {
+            self.events.borrow_mut().push(event);
+        }
+
+        fn member(&self) -> Box<AttributeRecorder> {
+            Box::new(AttributeRecorder {
+                events: self.events.clone(),
+            })
+        }
+    }
+
+    impl class_reader::ClassVisitor for AttributeRecorder {
+        fn visit(
+            &mut self,
+            _major: u16,
+            _minor: u16,
+            access_flags: u16,
+            name: &str,
+            signature: Option<&str>,
+            super_name: Option<&str>,
+            interfaces: &[&str],
+        ) {
+            self.record(format!(
+                "class {access_flags:#x} {name} {signature:?} {super_name:?} {interfaces:?}"
+            ));
+        }
+
+        fn visit_source(&mut self, source: &str) {
+            self.record(format!("source {source}"));
+        }
+
+        fn visit_outer_class(&mut self, owner: &str, name: Option<&str>, descriptor: Option<&str>) {
+            self.record(format!("outer class {owner} {name:?} {descriptor:?}"));
+        }
+
+        fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+            self.record(format!("attribute {name} {content:?}"));
+        }
+
+        fn visit_inner_class(
+            &mut self,
+            name: &str,
+            outer_name: Option<&str>,
+            inner_name: Option<&str>,
+            access_flags: u16,
+        ) {
+            self.record(format!(
+                "inner class {name} {outer_name:?} {inner_name:?} {access_flags:#x}"
+            ));
+        }
+
+        fn visit_record_component(
+            &mut self,
+            name: &str,
+            descriptor: &str,
+            signature: Option<&str>,
+        ) -> Option<Box<dyn class_reader::RecordComponentVisitor>> {
+            self.record(format!("component {name} {descriptor} {signature:?}"));
+            Some(self.member())
+        }
+
+        fn visit_field(
+            &mut self,
+            access_flags: u16,
+            name: &str,
+            descriptor: &str,
+            signature: Option<&str>,
+            value: Option<&ConstantValue>,
+        ) -> Option<Box<dyn class_reader::FieldVisitor>> {
+            self.record(format!(
+                "field {access_flags:#x} {name} {descriptor} {signature:?} {value:?}"
+            ));
+            Some(self.member())
+        }
+
+        fn visit_method(
+            &mut self,
+            access_flags: u16,
+            name: &str,
+            descriptor: &str,
+            signature: Option<&str>,
+            exceptions: &[&str],
+        ) -> Option<Box<dyn class_reader::MethodVisitor>> {
+            self.record(format!(
+                "method {access_flags:#x} {name} {descriptor} {signature:?} {exceptions:?}"
+            ));
+            Some(self.member())
+        }
+    }
+
+    impl class_reader::RecordComponentVisitor for AttributeRecorder {
+        fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+            self.record(format!("attribute {name} {content:?}"));
+        }
+    }
+
+    impl class_reader::FieldVisitor for AttributeRecorder {
+        fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+            self.record(format!("attribute {name} {content:?}"));
+        }
+    }
+
+    impl class_reader::MethodVisitor for AttributeRecorder {
+        fn visit_attribute(&mut self, name: &str, content: &[u8]) {
+            self.record(format!("attribute {name} {content:?}"));
+        }
+    }
+
+    /// Returns the declaration and attribute events of the class file `bytes`.
+    fn attribute_events(bytes: &[u8]) -> Vec<String> {
+        let mut recorder = AttributeRecorder::default();
+        ClassReader::new(bytes)
+            .accept(&mut recorder, 0)
+            .expect("Should visit class");
+        recorder.events.take()
+    }
+
+    #[test]
+    fn test_copies_keep_attributes() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(
+            60,
+            0,
+            0x0030,
+            "Outer$1Local",
+            Some("<T:Ljava/lang/Object;>Ljava/lang/Record;"),
+            Some("java/lang/Record"),
+            &["java/lang/Runnable"],
+        );
+        cw.visit_source_file("Outer.java");
+        cw.visit_outer_class("Outer", Some("run"), Some("()V"))
+            .visit_attribute("SourceDebugExtension", b"SMAP\n*E\n")
+            .visit_attribute("Deprecated", &[])
+            .visit_attribute("com.example.Custom", &[1, 2, 3])
+            .visit_inner_class("Outer$1Local", None, Some("Local"), 0x0010)
+            .visit_inner_class("java/util/Map$Entry", Some("java/util/Map"), Some("Entry"), 0x0609);
+        let signature = Some("Ljava/util/List<TT;>;");
+        let mut rv = cw.visit_record_component("items", "Ljava/util/List;", signature);
+        rv.visit_attribute("com.example.Custom", &[4]);
+        rv.visit_end(&mut cw);
+
+        let mut fv = cw.visit_field(0x0019, "LIMIT", "J", None, Some(&ConstantValue::Long(7)));
+        fv.visit_attribute("Synthetic", &[]);
+        fv.visit_end(&mut cw);
+        let value = ConstantValue::String("items".to_string());
+        cw.visit_field(0x0019, "NAME", "Ljava/lang/String;", None, Some(&value))
+            .visit_end(&mut cw);
+        cw.visit_field(0x0012, "items", "Ljava/util/List;", signature, None)
+            .visit_end(&mut cw);
+
+        let mut mv = cw.visit_method(
+            0x0001,
+            "run",
+            "()V",
+            Some("<E:Ljava/lang/Exception;>()V^TE;"),
+            &["java/io/IOException", "java/lang/InterruptedException"],
+        );
+        mv.visit_attribute("Deprecated", &[]);
+        mv.visit_code();
+        mv.visit_insn(opcodes::RETURN);
+        mv.visit_maxs(0, 1);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+
+        let events = attribute_events(&bytes);
+        for event in [
+            "class 0x30 Outer$1Local Some(\"<T:Ljava/lang/Object;>Ljava/lang/Record;\") \
+             Some(\"java/lang/Record\") [\"java/lang/Runnable\"]",
+            "outer class Outer Some(\"run\") Some(\"()V\")",
+            "attribute com.example.Custom [1, 2, 3]",
+            "inner class java/util/Map$Entry Some(\"java/util/Map\") Some(\"Entry\") 0x609",
+            "attribute com.example.Custom [4]",
+            "field 0x19 LIMIT J None Some(Long(7))",
+            "attribute Synthetic []",
+            "method 0x1 run ()V Some(\"<E:Ljava/lang/Exception;>()V^TE;\") \
+             [\"java/io/IOException\", \"java/lang/InterruptedException\"]",
+        ] {
+            assert!(events.iter().any(|recorded| recorded == event), "{event} in {events:#?}");
+        }
+
+        let mut writer = ClassWriter::new(0);
+        ClassReader::new(&bytes)
+            .accept(&mut writer, 0)
+            .expect("Should visit class");
+        let copied = writer.to_bytes().expect("Should write copied class");
+        assert_eq!(attribute_events(&copied), events);
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class node");
+        let mut recorder = AttributeRecorder::default();
+        node.accept(&mut recorder).expect("Should replay class node");
+        assert_eq!(recorder.events.take(), events);
+        let mut writer = ClassWriter::new(0);
+        node.accept(&mut writer).expect("Should replay class node");
+        let replayed = writer.to_bytes().expect("Should write replayed class");
+        assert_eq!(attribute_events(&replayed), events);
+    }
+
+    /// Returns the tag name of the constant referenced by the first method instruction of the
+    /// first method of `bytes`.
+    fn first_method_ref(bytes: &[u8]) -> &'static str {
+        let class_file = class_reader::read_class_file(bytes).expect("Should read class file");
+        let code = method_code(bytes, 0);
+        let index = code
+            .instructions
+            .iter()
+            .find_map(|insn| match insn {
+                Insn::Method(MethodInsnNode {
+                    method_ref: MemberRef::Index(index),
+                    ..
+                }) => Some(*index),
+                _ => None,
+            })
+            .expect("Method should call a method");
+        match class_file.constant_pool[index as usize] {
+            CpInfo::Methodref { .. } => "Methodref",
+            CpInfo::InterfaceMethodref { .. } => "InterfaceMethodref",
+            _ => panic!("Should reference a method"),
+        }
+    }
+
+    #[test]
+    fn test_copies_keep_interface_method_refs() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0021, "Lists", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "empty", "()Ljava/util/List;", None, &[]);
+        mv.visit_code();
+        let descriptor = "()Ljava/util/List;";
+        mv.visit_method_insn(opcodes::INVOKESTATIC, "java/util/List", "of", descriptor, true);
+        mv.visit_insn(opcodes::ARETURN);
+        mv.visit_maxs(1, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+        assert_eq!(first_method_ref(&bytes), "InterfaceMethodref");
+
+        let mut writer = ClassWriter::new(0);
+        ClassReader::new(&bytes)
+            .accept(&mut writer, 0)
+            .expect("Should visit class");
+        let copied = writer.to_bytes().expect("Should write copied class");
+        assert_eq!(first_method_ref(&copied), "InterfaceMethodref");
+    }
+
+    #[test]
+    fn test_replayed_class_node_keeps_interface_method_refs() {
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0021, "Lists", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "empty", "()Ljava/util/List;", None, &[]);
+        mv.visit_code();
+        let descriptor = "()Ljava/util/List;";
+        mv.visit_method_insn(opcodes::INVOKESTATIC, "java/util/List", "of", descriptor, true);
+        mv.visit_insn(opcodes::ARETURN);
+        mv.visit_maxs(1, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class node");
+        let mut writer = ClassWriter::new(0);
+        node.accept(&mut writer).expect("Should replay class node");
+        let replayed = writer.to_bytes().expect("Should write replayed class");
+        assert_eq!(first_method_ref(&replayed), "InterfaceMethodref");
+    }
+
+    struct LdcCollector {
+        constants: std::rc::Rc<std::cell::RefCell<Vec<class_reader::LdcConstant>>>,
+    }
+
+    impl class_reader::ClassVisitor for LdcCollector {
+        fn visit_method(
+            &mut self,
+            _access_flags: u16,
+            _name: &str,
+            _descriptor: &str,
+            _signature: Option<&str>,
+            _exceptions: &[&str],
+        ) -> Option<Box<dyn class_reader::MethodVisitor>> {
+            Some(Box::new(LdcCollector {
+                constants: self.constants.clone(),
+            }))
+        }
+    }
+
+    impl class_reader::MethodVisitor for LdcCollector {
+        fn visit_ldc_insn(&mut self, value: class_reader::LdcConstant) {
+            self.constants.borrow_mut().push(value);
+        }
+    }
+
+    fn collect_ldc_constants(bytes: &[u8]) -> Vec<class_reader::LdcConstant> {
+        let mut collector = LdcCollector {
+            constants: Default::default(),
+        };
+        ClassReader::new(bytes)
+            .accept(&mut collector, 0)
+            .expect("Should visit class");
+        collector.constants.take()
+    }
+
+    fn constant_bootstrap(name: &str, descriptor: &str) -> Handle {
+        Handle {
+            reference_kind: constants::REF_INVOKE_STATIC,
+            owner: "java/lang/invoke/ConstantBootstraps".to_string(),
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+            is_interface: false,
+        }
+    }
+
+    #[test]
+    fn test_constant_dynamic_round_trip() {
+        let zero = ConstantDynamic::new(
+            "zero",
+            "J",
+            constant_bootstrap(
+                "primitiveClass",
+                "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/Class;)\
+                 Ljava/lang/Class;",
+            ),
+            &[],
+        );
+        let answer = ConstantDynamic::new(
+            "answer",
+            "Ljava/lang/Object;",
+            constant_bootstrap(
+                "invoke",
+                "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/Class;\
+                 Ljava/lang/invoke/MethodHandle;[Ljava/lang/Object;)Ljava/lang/Object;",
+            ),
+            &[
+                BootstrapArgument::Integer(42),
+                BootstrapArgument::ConstantDynamic(zero.clone()),
+            ],
+        );
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(55, 0, 0x0021, "Lazy", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "answer", "()Ljava/lang/Object;", None, &[]);
+        mv.visit_code();
+        mv.visit_ldc_insn(LdcInsnNode::constant_dynamic(answer));
+        mv.visit_insn(opcodes::ARETURN);
+        mv.visit_maxs(1, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write condy class");
+
+        let values = collect_ldc_constants(&bytes);
+        let [class_reader::LdcConstant::Dynamic(value)] = values.as_slice() else {
+            panic!("Expected a dynamic constant, got {values:?}");
+        };
+        assert_eq!(value.name, "answer");
+        assert_eq!(value.bootstrap_method.name, "invoke");
+        assert!(matches!(value.bootstrap_args[0], BootstrapArgument::Integer(42)));
+        let BootstrapArgument::ConstantDynamic(nested) = &value.bootstrap_args[1] else {
+            panic!("Expected a nested dynamic constant");
+        };
+        assert_eq!(nested.name, "zero");
+        assert_eq!(nested.bootstrap_method.name, "primitiveClass");
+
+        let mut node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read condy class");
+        let mut insns = NodeList::new();
+        insns.add(Insn::from(LdcInsnNode::constant_dynamic(zero)));
+        insns.add(Insn::from(InsnNode::from(opcodes::LRETURN)));
+        node.methods[0].descriptor = "()J".to_string();
+        node.methods[0].max_stack = 2;
+        node.methods[0].insn_nodes = insns;
+        let bytes = ClassWriter::write_class_node(&node, 0).expect("Should write condy tree");
+        let values = collect_ldc_constants(&bytes);
+        let [class_reader::LdcConstant::Dynamic(value)] = values.as_slice() else {
+            panic!("Expected a dynamic constant, got {values:?}");
+        };
+        assert_eq!(value.name, "zero");
+        assert_eq!(value.descriptor, "J");
+        assert!(matches!(
+            method_code(&bytes, 0).instructions[0],
+            Insn::Ldc(LdcInsnNode {
+                insn: InsnNode {
+                    opcode: opcodes::LDC2_W
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bootstrap_methods_are_shared() {
+        let object_methods = Handle {
+            reference_kind: constants::REF_INVOKE_STATIC,
+            owner: "java/lang/runtime/ObjectMethods".to_string(),
+            name: "bootstrap".to_string(),
+            descriptor: "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;\
+                Ljava/lang/invoke/TypeDescriptor;Ljava/lang/Class;Ljava/lang/String;\
+                [Ljava/lang/invoke/MethodHandle;)Ljava/lang/Object;"
+                .to_string(),
+            is_interface: false,
+        };
+        let arguments = [
+            BootstrapArgument::Class("Point".to_string()),
+            BootstrapArgument::String("x".to_string()),
+            BootstrapArgument::Handle(Handle {
+                reference_kind: constants::REF_GET_FIELD,
+                owner: "Point".to_string(),
+                name: "x".to_string(),
+                descriptor: "I".to_string(),
+                is_interface: false,
+            }),
+        ];
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES);
+        cw.visit(60, 0, 0x0031, "Point", None, Some("java/lang/Record"), &[]);
+        for (name, descriptor, load, ret) in [
+            ("toString", "(LPoint;)Ljava/lang/String;", 1, opcodes::ARETURN),
+            ("hashCode", "(LPoint;)I", 1, opcodes::IRETURN),
+            ("equals", "(LPoint;Ljava/lang/Object;)Z", 2, opcodes::IRETURN),
+        ] {
+            let method_descriptor = descriptor.replace("LPoint;", "");
+            let mut mv = cw.visit_method(0x0011, name, &method_descriptor, None, &[]);
+            mv.visit_code();
+            for index in 0..load {
+                mv.visit_var_insn(opcodes::ALOAD, index);
+            }
+            mv.visit_invoke_dynamic_insn(name, descriptor, object_methods.clone(), &arguments);
+            mv.visit_insn(ret);
+            mv.visit_maxs(0, 0);
+            mv.visit_end(&mut cw);
+        }
+        let bytes = cw.to_bytes().expect("Should write record methods");
+        let class_file = class_reader::read_class_file(&bytes).expect("Should read record");
+        let methods = class_file
+            .attributes
+            .iter()
+            .find_map(|attr| match attr {
+                AttributeInfo::BootstrapMethods { methods } => Some(methods),
+                _ => None,
+            })
+            .expect("Should write bootstrap methods");
+        assert_eq!(methods.len(), 1);
+
+        // A table that is full cannot take one more bootstrap method.
+        let mut cw = ClassWriter::new(0);
+        cw.visit(60, 0, 0x0021, "Full", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "run", "()V", None, &[]);
+        mv.visit_code();
+        mv.visit_invoke_dynamic_insn("run", "()V", object_methods, &[]);
+        mv.visit_insn(opcodes::RETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let mut node = cw.to_class_node().expect("Should build class node");
+        let full = BootstrapMethod {
+            bootstrap_method_ref: 0,
+            bootstrap_arguments: Vec::new(),
+        };
+        node.attributes.push(AttributeInfo::BootstrapMethods {
+            methods: vec![full; u16::MAX as usize + 1],
+        });
+        let error = ClassWriter::write_class_node(&node, 0).expect_err("Should overflow");
+        assert!(matches!(
+            error,
+            ClassWriteError::TooManyEntries { table: "BootstrapMethods", count: 65537, .. }
+        ));
+    }
+
+    #[test]
+    fn test_method_handle_and_method_type_constants() {
+        let parse_int = Handle {
+            reference_kind: constants::REF_INVOKE_STATIC,
+            owner: "java/lang/Integer".to_string(),
+            name: "parseInt".to_string(),
+            descriptor: "(Ljava/lang/String;)I".to_string(),
+            is_interface: false,
+        };
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0021, "Handles", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "run", "()V", None, &[]);
+        mv.visit_code();
+        mv.visit_ldc_insn(LdcInsnNode::handle(parse_int.clone()));
+        mv.visit_insn(opcodes::POP);
+        let method_type = LdcInsnNode::method_type("(Ljava/lang/String;)I");
+        mv.visit_ldc_insn(method_type.expect("Should be a method descriptor"));
+        assert!(LdcInsnNode::method_type("Ljava/lang/String;").is_err());
+        assert!(LdcInsnNode::method_type("(Ljava/lang/String;").is_err());
+        mv.visit_insn(opcodes::POP);
+        mv.visit_insn(opcodes::RETURN);
+        mv.visit_maxs(1, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write handle constants");
+
+        let values = collect_ldc_constants(&bytes);
+        let [
+            class_reader::LdcConstant::MethodHandle(handle),
+            class_reader::LdcConstant::MethodType(descriptor),
+        ] = values.as_slice()
+        else {
+            panic!("Expected a method handle and a method type, got {values:?}");
+        };
+        assert_eq!(handle.reference_kind, constants::REF_INVOKE_STATIC);
+        assert_eq!(handle.owner, "java/lang/Integer");
+        assert_eq!(handle.name, "parseInt");
+        assert_eq!(handle.descriptor, "(Ljava/lang/String;)I");
+        assert!(!handle.is_interface);
+        assert_eq!(descriptor, "(Ljava/lang/String;)I");
+
+        let mut node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read handle constants");
+        let mut insns = NodeList::new();
+        insns.add(Insn::from(LdcInsnNode::handle(parse_int)));
+        insns.add(Insn::from(InsnNode::from(opcodes::POP)));
+        insns.add(Insn::from(LdcInsnNode::string("42")));
+        insns.add(Insn::from(InsnNode::from(opcodes::POP)));
+        insns.add(Insn::from(InsnNode::from(opcodes::RETURN)));
+        node.methods[0].insn_nodes = insns;
+        let bytes = ClassWriter::write_class_node(&node, 0).expect("Should write handle tree");
+        let values = collect_ldc_constants(&bytes);
+        assert!(matches!(
+            values.as_slice(),
+            [
+                class_reader::LdcConstant::MethodHandle(handle),
+                class_reader::LdcConstant::String(value),
+            ] if handle.name == "parseInt" && value == "42"
+        ));
+    }
+
+    #[test]
+    fn test_insert_into_read_method() {
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES);
+        cw.visit(52, 0, 0x0021, "Edited", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "abs", "(I)I", None, &[]);
+        let positive = Label::new();
+        mv.visit_code();
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_jump_insn(opcodes::IFGE, positive);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::INEG);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_label(positive);
+        mv.visit_line_number(7, LabelNode::from_label(positive));
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write source class");
+
+        let mut node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read source class");
+        let method = &mut node.methods[0];
+        let (start, end, handler) = (LabelNode::new(), LabelNode::new(), LabelNode::new());
+        let mut nodes = std::mem::take(&mut method.insn_nodes).into_nodes();
+        nodes.insert(0, AbstractInsnNode::Insn(InsnNode::from(opcodes::NOP).into()));
+        nodes.insert(0, AbstractInsnNode::Label(start));
+        nodes.push(AbstractInsnNode::Label(end));
+        nodes.push(AbstractInsnNode::Label(handler));
+        nodes.push(AbstractInsnNode::Insn(InsnNode::from(opcodes::ATHROW).into()));
+        for node in nodes {
+            method.insn_nodes.add_node(node);
+        }
+        method.try_catch_blocks.push(TryCatchBlockNode {
+            start,
+            end,
+            handler,
+            catch_type: Some("java/lang/RuntimeException".to_string()),
+        });
+        method.local_variables.push(LocalVariableNode {
+            name: "value".to_string(),
+            descriptor: "I".to_string(),
+            signature: None,
+            start,
+            end,
+            index: 0,
+        });
+        let bytes = ClassWriter::write_class_node(&node, COMPUTE_FRAMES)
+            .expect("Should write edited class");
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read edited class");
+        let method = &node.methods[0];
+        let code = method_code(&bytes, 0);
+        assert!(matches!(code.instructions[2], Insn::Jump(JumpInsnNode { offset: 7, .. })));
+        assert!(code.attributes.iter().any(|attr| matches!(
+            attr,
+            AttributeInfo::LineNumberTable { entries }
+                if entries.len() == 1 && entries[0].start_pc == 10
+        )));
+        let [entry] = code.exception_table.as_slice() else {
+            panic!("Expected one exception handler");
+        };
+        assert_eq!((entry.start_pc, entry.end_pc, entry.handler_pc), (0, 13, 13));
+        let [block] = method.try_catch_blocks.as_slice() else {
+            panic!("Expected one try-catch block");
+        };
+        assert_eq!(block.catch_type.as_deref(), Some("java/lang/RuntimeException"));
+        let [variable] = method.local_variables.as_slice() else {
+            panic!("Expected one local variable");
+        };
+        assert_eq!((variable.name.as_str(), variable.index), ("value", 0));
+        assert!(matches!(
+            method.insn_nodes.nodes().last(),
+            Some(AbstractInsnNode::Insn(Insn::Simple(InsnNode {
+                opcode: opcodes::ATHROW
+            })))
+        ));
+    }
+
+    #[test]
+    fn test_class_node_accept_round_trip() {
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES);
+        cw.visit(52, 0, 0x0021, "Replayed", None, Some("java/lang/Object"), &[]);
+        cw.visit_source_file("Replayed.java");
+        let mut fv = cw.visit_field(0x0002, "value", "I", None, None);
+        fv.visit_annotation(AnnotationNode::new("Ljava/lang/Deprecated;"), true);
+        fv.visit_end(&mut cw);
+        let mut mv = cw.visit_method(0x0009, "abs", "(I)I", None, &[]);
+        let positive = Label::new();
+        mv.visit_code();
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_jump_insn(opcodes::IFGE, positive);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::INEG);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_label(positive);
+        mv.visit_line_number(7, LabelNode::from_label(positive));
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let source_bytes = cw.to_bytes().expect("Should write source class");
+        let source = ClassReader::new(&source_bytes)
+            .to_class_node()
+            .expect("Should read source class");
+
+        let mut writer = ClassWriter::new(COMPUTE_FRAMES);
+        source.accept(&mut writer).expect("Should replay class node");
+        let bytes = writer.to_bytes().expect("Should write replayed class");
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read replayed class");
+        assert_eq!(node.name, "Replayed");
+        assert_eq!(node.source_file.as_deref(), Some("Replayed.java"));
+        assert_eq!(node.fields[0].visible_annotations.len(), 1);
+        assert_eq!(
+            format!("{:?}", method_code(&bytes, 0).instructions),
+            format!("{:?}", method_code(&source_bytes, 0).instructions)
+        );
+        assert_eq!(node.methods[0].insn_nodes.len(), source.methods[0].insn_nodes.len());
+    }
+
+    #[test]
+    fn test_method_visitor_full_instruction_set() {
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES);
+        cw.visit(52, 0, 0x0021, "Switches", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "pick", "(I)I", None, &[]);
+        let (start, end, handler) = (Label::new(), Label::new(), Label::new());
+        let (one, two, other, five) = (Label::new(), Label::new(), Label::new(), Label::new());
+        mv.visit_parameter(Some("key"), 0x0010);
+        mv.visit_code();
+        mv.visit_try_catch_block(start, end, handler, Some("java/lang/RuntimeException"));
+        mv.visit_label(start);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_table_switch_insn(0, 1, other, &[one, two]);
+        mv.visit_label(one);
+        mv.visit_int_insn(opcodes::BIPUSH, 10);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_label(two);
+        mv.visit_int_insn(opcodes::SIPUSH, 1000);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_label(other);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_lookup_switch_insn(end, &[5], &[five]);
+        mv.visit_label(five);
+        mv.visit_iinc_insn(0, 1);
+        mv.visit_insn(opcodes::ICONST_2);
+        mv.visit_insn(opcodes::ICONST_3);
+        mv.visit_multi_anewarray_insn("[[I", 2);
+        mv.visit_insn(opcodes::POP);
+        mv.visit_insn(opcodes::ICONST_1);
+        mv.visit_int_insn(opcodes::NEWARRAY, 10);
+        mv.visit_insn(opcodes::POP);
+        mv.visit_label(end);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_label(handler);
+        mv.visit_insn(opcodes::POP);
+        mv.visit_insn(opcodes::ICONST_M1);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_local_variable("key", "I", None, start, end, 0);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class back");
+        let method = &node.methods[0];
+        let nodes: Vec<_> = method.insn_nodes.nodes().collect();
+        let position = |label: &LabelNode| {
+            nodes
+                .iter()
+                .position(|node| matches!(node, AbstractInsnNode::Label(l) if l.id == label.id))
+                .expect("Label should be placed")
+        };
+        // The node of the instruction at a label, after its frame.
+        let insn_at = |label: &LabelNode| {
+            nodes[position(label) + 1..]
+                .iter()
+                .find(|node| !matches!(node, AbstractInsnNode::Frame(_)))
+                .expect("Label should be followed by an instruction")
+        };
+        let Some(AbstractInsnNode::TableSwitchLabel(table)) =
+            nodes.iter().find(|node| matches!(node, AbstractInsnNode::TableSwitchLabel(_)))
+        else {
+            panic!("TABLESWITCH should be read back");
+        };
+        assert_eq!((table.low, table.high, table.labels.len()), (0, 1, 2));
+        assert!(matches!(
+            insn_at(&table.labels[1]),
+            AbstractInsnNode::Insn(Insn::Int(IntInsnNode { operand: 1000, .. }))
+        ));
+        let Some(AbstractInsnNode::LookupSwitchLabel(lookup)) =
+            nodes.iter().find(|node| matches!(node, AbstractInsnNode::LookupSwitchLabel(_)))
+        else {
+            panic!("LOOKUPSWITCH should be read back");
+        };
+        assert_eq!(lookup.keys, vec![5]);
+        assert!(matches!(
+            insn_at(&lookup.labels[0]),
+            AbstractInsnNode::Insn(Insn::Iinc(IincInsnNode { var_index: 0, increment: 1, .. }))
+        ));
+        assert!(nodes.iter().any(|node| matches!(
+            node,
+            AbstractInsnNode::Insn(Insn::MultiANewArray(MultiANewArrayInsnNode {
+                dimensions: 2,
+                ..
+            }))
+        )));
+
+        let block = &method.try_catch_blocks[0];
+        assert_eq!(block.catch_type.as_deref(), Some("java/lang/RuntimeException"));
+        assert_eq!(position(&block.start), 0);
+        assert!(matches!(
+            insn_at(&block.handler),
+            AbstractInsnNode::Insn(Insn::Simple(InsnNode { opcode: opcodes::POP }))
+        ));
+        let variable = &method.local_variables[0];
+        assert_eq!((variable.name.as_str(), variable.index), ("key", 0));
+        assert_eq!(variable.end.id, lookup.default.id);
+        let parameters = method
+            .attributes
+            .iter()
+            .find_map(|attr| match attr {
+                AttributeInfo::MethodParameters { parameters } => Some(parameters),
+                _ => None,
+            })
+            .expect("MethodParameters should be written");
+        assert_eq!(parameters[0].access_flags, 0x0010);
+        assert!(matches!(
+            &node.constant_pool[parameters[0].name_index as usize],
+            CpInfo::Utf8(name) if name == "key"
+        ));
+    }
+
+    #[test]
+    fn test_long_jump_widening() {
+        let generate = |nops: usize| {
+            let mut cw = ClassWriter::new(COMPUTE_FRAMES);
+            cw.visit(52, 0, 0x0021, "Far", None, Some("java/lang/Object"), &[]);
+            let mut mv = cw.visit_method(0x0009, "loop", "(I)V", None, &[]);
+            let (start, end) = (Label::new(), Label::new());
+            mv.visit_code();
+            mv.visit_label(start);
+            mv.visit_var_insn(opcodes::ILOAD, 0);
+            mv.visit_jump_insn(opcodes::IFEQ, end);
+            for _ in 0..nops {
+                mv.visit_insn(opcodes::NOP);
+            }
+            mv.visit_jump_insn(opcodes::GOTO, start);
+            mv.visit_label(end);
+            mv.visit_line_number(9, LabelNode::from_label(end));
+            mv.visit_insn(opcodes::ACONST_NULL);
+            mv.visit_type_insn(opcodes::CHECKCAST, "java/lang/String");
+            mv.visit_insn_annotation(
+                TypeReference::new_type_argument_reference(TypeReference::CAST, 0),
+                None,
+                AnnotationNode::new("LNonNull;"),
+                true,
+            );
+            mv.visit_insn(opcodes::POP);
+            mv.visit_insn(opcodes::RETURN);
+            mv.visit_maxs(0, 0);
+            mv.visit_end(&mut cw);
+            cw.to_bytes().expect("Should write class")
+        };
+
+        let check = |bytes: &[u8]| {
+            let node = ClassReader::new(bytes)
+                .to_class_node()
+                .expect("Should read class with long jumps");
+            let method = &node.methods[0];
+            let code = method_code(bytes, 0);
+            let insns = &code.instructions;
+            assert!(matches!(
+                &insns[1],
+                Insn::Jump(JumpInsnNode { insn, offset: 8 }) if insn.opcode == opcodes::IFNE
+            ));
+            let Insn::Jump(forward) = &insns[2] else {
+                panic!("the inverted jump should skip over a GOTO_W");
+            };
+            assert_eq!(forward.insn.opcode, opcodes::GOTO_W);
+            assert!(insns.iter().any(|insn| matches!(
+                insn,
+                Insn::Jump(JumpInsnNode { insn, offset }) if insn.opcode == opcodes::GOTO_W
+                    && *offset < i16::MIN as i32
+            )));
+
+            let nodes: Vec<_> = method.insn_nodes.nodes().collect();
+            let target = nodes
+                .iter()
+                .find_map(|node| match node {
+                    AbstractInsnNode::JumpLabel(jump) if jump.insn.opcode == opcodes::GOTO_W => {
+                        Some(jump.target)
+                    }
+                    _ => None,
+                })
+                .expect("GOTO_W should be read back with its label");
+            let position = nodes
+                .iter()
+                .position(|node| matches!(node, AbstractInsnNode::Label(l) if l.id == target.id))
+                .expect("GOTO_W target should be placed");
+            assert!(matches!(
+                nodes[position + 1],
+                AbstractInsnNode::LineNumber(LineNumberInsnNode { line: 9, .. })
+            ));
+            let annotated = method.visible_insn_annotations[0].insn;
+            assert!(matches!(
+                method.insn_nodes.get(annotated),
+                Some(AbstractInsnNode::Insn(Insn::Type(_)))
+            ));
+            let offset = code
+                .attributes
+                .iter()
+                .find_map(|attr| match attr {
+                    AttributeInfo::RuntimeVisibleTypeAnnotations { annotations } => {
+                        match annotations[0].target_info {
+                            TypeAnnotationTarget::TypeArgument { offset, .. } => Some(offset),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .expect("The cast annotation should be written");
+            assert_eq!(code.code[offset as usize], opcodes::CHECKCAST);
+        };
+        check(&generate(33_000));
+
+        // The same overflow, introduced by editing the code of a read method.
+        let mut node = ClassReader::new(&generate(0))
+            .to_class_node()
+            .expect("Should read class");
+        let method = &mut node.methods[0];
+        let ifeq = method
+            .insn_nodes
+            .nodes()
+            .position(|node| matches!(node, AbstractInsnNode::JumpLabel(_)))
+            .and_then(|index| method.insn_nodes.id_at(index))
+            .expect("IFEQ should be read back");
+        let mut padding = NodeList::new();
+        for _ in 0..33_000 {
+            padding.add(Insn::from(InsnNode::from(opcodes::NOP)));
+        }
+        // The annotation keeps the handle of the CHECKCAST, which the padding moves.
+        method.insn_nodes.insert_list_after(ifeq, padding);
+        let bytes = ClassWriter::write_class_node(&node, COMPUTE_FRAMES)
+            .expect("Should write edited class");
+        check(&bytes);
+    }
+
+    #[test]
+    fn test_wide_local_variable_insns() {
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS);
+        cw.visit(52, 0, 0x0021, "Locals", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "count", "()I", None, &[]);
+        mv.visit_code();
+        mv.visit_insn(opcodes::ICONST_0);
+        mv.visit_var_insn(opcodes::ISTORE, 300);
+        mv.visit_iinc_insn(300, 1000);
+        mv.visit_iinc_insn(300, -1);
+        mv.visit_insn(opcodes::ICONST_0);
+        mv.visit_var_insn(opcodes::ISTORE, 1);
+        mv.visit_iinc_insn(1, -200);
+        mv.visit_var_insn(opcodes::ILOAD, 300);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class with wide locals");
+
+        let [hi, lo] = 300u16.to_be_bytes();
+        assert!(bytes.windows(4).any(|w| w == [opcodes::WIDE, opcodes::ISTORE, hi, lo]));
+        let [inc_hi, inc_lo] = 1000i16.to_be_bytes();
+        assert!(
+            bytes
+                .windows(6)
+                .any(|w| w == [opcodes::WIDE, opcodes::IINC, hi, lo, inc_hi, inc_lo])
+        );
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node()
+            .expect("Should read class with wide locals");
+        let method = &node.methods[0];
+        assert_eq!(method.max_locals, 301);
+        let insns = method_code(&bytes, 0).instructions;
+        assert!(matches!(
+            &insns[1],
+            Insn::Var(VarInsnNode { insn, var_index: 300 }) if insn.opcode == opcodes::ISTORE
+        ));
+        assert!(matches!(
+            insns[2],
+            Insn::Iinc(IincInsnNode { var_index: 300, increment: 1000, .. })
+        ));
+        assert!(matches!(
+            insns[3],
+            Insn::Iinc(IincInsnNode { var_index: 300, increment: -1, .. })
+        ));
+        assert!(matches!(
+            insns[6],
+            Insn::Iinc(IincInsnNode { var_index: 1, increment: -200, .. })
+        ));
+
+        let rewritten = ClassWriter::write_class_node(&node, COMPUTE_FRAMES | COMPUTE_MAXS)
+            .expect("Should write read class");
+        assert_eq!(rewritten, bytes);
+    }
+
+    fn write_shape_merge(cw: ClassWriter) -> Result<Vec<u8>, ClassWriteError> {
+        write_merge(cw, "shapes/Circle", "shapes/Square")
+    }
+
+    fn write_merge(cw: ClassWriter, left: &str, right: &str) -> Result<Vec<u8>, ClassWriteError> {
+        let mut cw = cw;
+        cw.visit(52, 0, 0x0021, "Shapes", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "pick", "(Z)Ljava/lang/Object;", None, &[]);
+        let square = Label::new();
+        let join = Label::new();
+        mv.visit_code();
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_jump_insn(opcodes::IFEQ, square);
+        mv.visit_insn(opcodes::ACONST_NULL);
+        mv.visit_type_insn(opcodes::CHECKCAST, left);
+        mv.visit_jump_insn(opcodes::GOTO, join);
+        mv.visit_label(square);
+        mv.visit_insn(opcodes::ACONST_NULL);
+        mv.visit_type_insn(opcodes::CHECKCAST, right);
+        mv.visit_label(join);
+        mv.visit_insn(opcodes::ARETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        cw.to_bytes()
+    }
+
+    /// Returns the `Code` attribute of a method of a class file, with offset-based instructions.
+    fn method_code(bytes: &[u8], method: usize) -> CodeAttribute {
+        let class_file = class_reader::read_class_file(bytes).expect("Should read class file");
+        class_file.methods[method]
+            .attributes
+            .iter()
+            .find_map(|attr| match attr {
+                AttributeInfo::Code(code) => Some(code.clone()),
+                _ => None,
+            })
+            .expect("Method should have code")
+    }
+
+    fn shape_classes() -> Vec<ClassNode> {
+        [
+            ("shapes/Shape", "java/lang/Object"),
+            ("shapes/Circle", "shapes/Shape"),
+            ("shapes/Square", "shapes/Shape"),
+        ]
+        .into_iter()
+        .map(|(name, super_name)| {
+            let mut cw = ClassWriter::new(0);
+            cw.visit(52, 0, 0x0021, name, None, Some(super_name), &[]);
+            cw.to_class_node().expect("Should build shape class")
+        })
+        .collect()
+    }
+
+    fn joined_stack_type(bytes: &[u8]) -> String {
+        let node = ClassReader::new(bytes)
+            .to_class_node()
+            .expect("Should read merged class");
+        let frame = node.methods[0]
+            .insn_nodes
+            .nodes()
+            .rev()
+            .find_map(|node| match node {
+                AbstractInsnNode::Frame(frame) => Some(frame),
+                _ => None,
+            })
+            .expect("Should compute frames");
+        let Frame::Full { stack, .. } = frame else {
+            panic!("unexpected join frame {frame:?}");
+        };
+        let [FrameValue::Object(name)] = stack.as_slice() else {
+            panic!("unexpected join frame {frame:?}");
+        };
+        name.clone()
+    }
+
+    #[test]
+    fn test_class_hierarchy_common_super_class() {
+        let classes = shape_classes();
+        let hierarchy = Arc::new(ClassNodeHierarchy::from_class_nodes(&classes));
+        assert_eq!(
+            hierarchy
+                .common_super_class("shapes/Circle", "shapes/Square")
+                .unwrap(),
+            "shapes/Shape"
+        );
+        assert_eq!(
+            hierarchy
+                .common_super_class("shapes/Shape", "shapes/Square")
+                .unwrap(),
+            "shapes/Shape"
+        );
+        assert!(matches!(
+            hierarchy.common_super_class("shapes/Circle", "shapes/Triangle"),
+            Err(ClassWriteError::UnknownClass(name)) if name == "shapes/Triangle"
+        ));
+
+        let bytes = write_shape_merge(ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS))
+            .expect("Should merge unknown classes");
+        assert_eq!(joined_stack_type(&bytes), "java/lang/Object");
+
+        let bytes = write_shape_merge(
+            ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS).with_class_hierarchy(hierarchy),
+        )
+        .expect("Should merge known classes");
+        assert_eq!(joined_stack_type(&bytes), "shapes/Shape");
+
+        let error = write_shape_merge(
+            ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS).with_strict_class_hierarchy(true),
+        )
+        .expect_err("Should reject unknown classes");
+        assert!(matches!(error, ClassWriteError::UnknownClass(_)));
+    }
+
+    #[test]
+    fn test_compute_frames_merges_arrays() {
+        let merged = |cw: ClassWriter, left: &str, right: &str| {
+            joined_stack_type(&write_merge(cw, left, right).expect("Should merge arrays"))
+        };
+        let cw = || ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS);
+        for (left, right, expected) in [
+            ("[Ljava/lang/String;", "[Ljava/lang/Integer;", "[Ljava/lang/Object;"),
+            ("[[Ljava/lang/String;", "[Ljava/lang/String;", "[Ljava/lang/Object;"),
+            ("[Ljava/lang/String;", "[[I", "[Ljava/lang/Object;"),
+            ("[I", "[J", "java/lang/Object"),
+            ("[I", "java/lang/String", "java/lang/Object"),
+        ] {
+            assert_eq!(merged(cw(), left, right), expected, "{left} + {right}");
+        }
+        let hierarchy = Arc::new(ClassNodeHierarchy::from_class_nodes(&shape_classes()));
+        assert_eq!(
+            merged(
+                cw().with_class_hierarchy(hierarchy),
+                "[[Lshapes/Circle;",
+                "[[Lshapes/Square;"
+            ),
+            "[[Lshapes/Shape;"
+        );
+
+        // Loading from the merged array gives an array, which can be loaded from in turn.
+        let mut cw = cw();
+        cw.visit(52, 0, 0x0021, "Arrays", None, Some("java/lang/Object"), &[]);
+        let mut mv = cw.visit_method(0x0009, "first", "(Z)Ljava/lang/Object;", None, &[]);
+        let (integers, join, end) = (Label::new(), Label::new(), Label::new());
+        mv.visit_code();
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_jump_insn(opcodes::IFEQ, integers);
+        mv.visit_insn(opcodes::ACONST_NULL);
+        mv.visit_type_insn(opcodes::CHECKCAST, "[[Ljava/lang/String;");
+        mv.visit_jump_insn(opcodes::GOTO, join);
+        mv.visit_label(integers);
+        mv.visit_insn(opcodes::ACONST_NULL);
+        mv.visit_type_insn(opcodes::CHECKCAST, "[[Ljava/lang/Integer;");
+        mv.visit_label(join);
+        mv.visit_insn(opcodes::ICONST_0);
+        mv.visit_insn(opcodes::AALOAD);
+        mv.visit_var_insn(opcodes::ASTORE, 1);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_jump_insn(opcodes::IFEQ, end);
+        mv.visit_var_insn(opcodes::ALOAD, 1);
+        mv.visit_insn(opcodes::ICONST_0);
+        mv.visit_insn(opcodes::AALOAD);
+        mv.visit_insn(opcodes::POP);
+        mv.visit_label(end);
+        mv.visit_var_insn(opcodes::ALOAD, 1);
+        mv.visit_insn(opcodes::ARETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should compute frames");
+
+        let node = ClassReader::new(&bytes)
+            .to_class_node_with_options(crate::class_reader::EXPAND_FRAMES)
+            .expect("Should read class");
+        let nodes: Vec<_> = node.methods[0].insn_nodes.nodes().collect();
+        // Returns the frame of the first instruction with `opcode` after `start`.
+        let frame_of = |start: usize, opcode: u8| {
+            let index = start
+                + nodes[start..]
+                    .iter()
+                    .position(|node| match node {
+                        AbstractInsnNode::Insn(Insn::Simple(insn)) => insn.opcode == opcode,
+                        AbstractInsnNode::Insn(Insn::Var(insn)) => insn.insn.opcode == opcode,
+                        _ => false,
+                    })
+                    .expect("Should find instruction");
+            let frame = nodes[..index]
+                .iter()
+                .rev()
+                .find_map(|node| match node {
+                    AbstractInsnNode::Frame(frame) => Some(frame),
+                    _ => None,
+                })
+                .expect("Should compute frame");
+            (index, frame)
+        };
+        let (join, join_frame) = frame_of(0, opcodes::ICONST_0);
+        assert!(matches!(
+            join_frame,
+            Frame::Full { stack, .. }
+                if stack == &[FrameValue::Object("[[Ljava/lang/Object;".to_string())]
+        ));
+        let (load, _) = frame_of(join, opcodes::ALOAD);
+        let (_, end_frame) = frame_of(load + 1, opcodes::ALOAD);
+        assert!(matches!(
+            end_frame,
+            Frame::Full { locals, .. }
+                if locals[1] == FrameValue::Object("[Ljava/lang/Object;".to_string())
+        ));
+    }
+
+    #[test]
+    fn test_directory_class_hierarchy() {
+        let root = std::env::temp_dir().join(format!("rust-asm-hierarchy-{}", std::process::id()));
+        for class_node in shape_classes() {
+            let path = root.join(format!("{}.class", class_node.name));
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let bytes = ClassWriter::write_class_node(&class_node, 0).unwrap();
+            std::fs::write(path, bytes).unwrap();
+        }
+        let hierarchy = DirectoryClassHierarchy::new(&root);
+        assert_eq!(
+            hierarchy.super_name("shapes/Circle").unwrap().as_deref(),
+            Some("shapes/Shape")
+        );
+        assert!(hierarchy.class_info("shapes/Triangle").is_none());
+
+        let bytes = write_shape_merge(
+            ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS)
+                .with_class_hierarchy(Arc::new(hierarchy))
+                .with_strict_class_hierarchy(true),
+        )
+        .expect("Should merge classes read from the directory");
+        std::fs::remove_dir_all(&root).unwrap();
+        assert_eq!(joined_stack_type(&bytes), "shapes/Shape");
+    }
+
+    #[test]
+    fn test_class_file_limits() {
+        let method_with = |flags, name: &str, build: &dyn Fn(&mut MethodVisitor)| {
+            let mut cw = ClassWriter::new(flags);
+            cw.visit(52, 0, 0x0021, "Limits", None, Some("java/lang/Object"), &[]);
+            let mut mv = cw.visit_method(0x0009, name, "()V", None, &[]);
+            mv.visit_code();
+            build(&mut mv);
+            mv.visit_insn(opcodes::RETURN);
+            mv.visit_maxs(0, 0);
+            mv.visit_end(&mut cw);
+            cw.to_bytes()
+        };
+        let method = |name: &str, build: &dyn Fn(&mut MethodVisitor)| {
+            method_with(COMPUTE_MAXS, name, build)
+        };
+
+        let error = method("big", &|mv| {
+            for _ in 0..70_000 {
+                mv.visit_insn(opcodes::NOP);
+            }
+        })
+        .expect_err("Should reject large method");
+        assert_eq!(
+            error.to_string(),
+            "code of method Limits.big()V is 70001 bytes long, more than 65535"
+        );
+        assert!(matches!(
+            error,
+            ClassWriteError::MethodTooLarge { class, method, descriptor, size: 70_001 }
+                if class == "Limits" && method == "big" && descriptor == "()V"
+        ));
+
+        let error = method("text", &|mv| {
+            mv.visit_ldc_insn(LdcInsnNode::string(&"\u{e9}".repeat(40_000)));
+            mv.visit_insn(opcodes::POP);
+        })
+        .expect_err("Should reject long string");
+        assert!(matches!(error, ClassWriteError::Utf8TooLong { length: 80_000 }));
+
+        let error = method("locals", &|mv| {
+            mv.visit_insn(opcodes::LCONST_0);
+            mv.visit_var_insn(opcodes::LSTORE, u16::MAX);
+        })
+        .expect_err("Should reject too many locals");
+        assert_eq!(
+            error.to_string(),
+            "method Limits.locals()V needs 65537 local variable slots, more than 65535"
+        );
+        assert!(matches!(
+            error,
+            ClassWriteError::TooManyLocals { max_locals: 65_537, .. }
+        ));
+
+        let error = method_with(0, "wide", &|mv| {
+            mv.visit_insn(opcodes::DCONST_0);
+            mv.visit_var_insn(opcodes::DSTORE, u16::MAX);
+        })
+        .expect_err("Should reject too many locals without COMPUTE_MAXS");
+        assert!(matches!(
+            error,
+            ClassWriteError::TooManyLocals { max_locals: 65_537, .. }
+        ));
+
+        let error = method("branch", &|mv| {
+            let end = Label::new();
+            mv.visit_jump_insn(opcodes::GOTO, end);
+            for _ in 0..70_000 {
+                mv.visit_insn(opcodes::NOP);
+            }
+            mv.visit_label(end);
+        })
+        .expect_err("Should reject large method with a branch");
+        assert!(matches!(
+            error,
+            ClassWriteError::MethodTooLarge { method, size, .. }
+                if method == "branch" && size > 70_000
+        ));
+
+        let error = method("parameters", &|mv| {
+            for _ in 0..256 {
+                mv.visit_parameter(None, 0);
+            }
+        })
+        .expect_err("Should reject too many parameters");
+        assert!(matches!(
+            error,
+            ClassWriteError::TooManyEntries { table: "MethodParameters", count: 256, max: 255 }
+        ));
+
+        method("push", &|mv| {
+            mv.visit_int_insn(opcodes::BIPUSH, -128);
+            mv.visit_int_insn(opcodes::SIPUSH, 32_767);
+            mv.visit_int_insn(opcodes::NEWARRAY, 11);
+            mv.visit_insn(opcodes::POP2);
+        })
+        .expect("Should accept operands in range");
+        for (opcode, operand) in [
+            (opcodes::BIPUSH, 128),
+            (opcodes::SIPUSH, -32_769),
+            (opcodes::NEWARRAY, 3),
+        ] {
+            let error = method("push", &|mv| {
+                mv.visit_int_insn(opcode, operand);
+                mv.visit_insn(opcodes::POP);
+            })
+            .expect_err("Should reject operand out of range");
+            assert!(matches!(
+                error,
+                ClassWriteError::InvalidOperand { opcode: o, operand: v }
+                    if o == opcode && v == operand
+            ));
+        }
+
+        let mut cw = ClassWriter::new(0);
+        cw.visit(52, 0, 0x0021, "Limits", None, Some("java/lang/Object"), &[]);
+        let mut node = cw.to_class_node().expect("Should build class node");
+        node.constant_pool.extend((0..70_000).map(CpInfo::Integer));
+        let error = ClassWriter::write_class_node(&node, 0).expect_err("Should reject large pool");
+        assert!(matches!(
+            error,
+            ClassWriteError::ClassTooLarge { constant_pool_count, .. }
+                if constant_pool_count > 70_000
+        ));
+    }
+
+    #[test]
+    fn test_frames_after_relayout() {
+        let mut cw = ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS);
+        cw.visit(52, 0, 0x0021, "Relaid", None, Some("java/lang/Object"), &[]);
+        let positive = Label::new();
+        let mut mv = cw.visit_method(0x0009, "abs", "(I)I", None, &[]);
+        mv.visit_code();
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_jump_insn(opcodes::IFGE, positive);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::INEG);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_label(positive);
+        mv.visit_var_insn(opcodes::ILOAD, 0);
+        mv.visit_insn(opcodes::IRETURN);
+        mv.visit_maxs(0, 0);
+        mv.visit_end(&mut cw);
+        let bytes = cw.to_bytes().expect("Should write class");
+
+        let frame_offsets = |bytes: &[u8]| {
+            let code = method_code(bytes, 0);
+            let Some(AttributeInfo::StackMapTable { entries }) = code
+                .attributes
+                .into_iter()
+                .find(|attr| matches!(attr, AttributeInfo::StackMapTable { .. }))
+            else {
+                panic!("Method should have frames");
+            };
+            let mut offsets: Vec<usize> = Vec::new();
+            for frame in entries {
+                let delta = frame.offset_delta() as usize;
+                offsets.push(offsets.last().map_or(delta, |previous| previous + delta + 1));
+            }
+            offsets
+        };
+        // Inserts 40000 NOPs at the start of the method, or between the IFGE and its target.
+        let with_nops = |in_jump: bool| {
+            let mut node = ClassReader::new(&bytes)
+                .to_class_node()
+                .expect("Should read class");
+            let code = &mut node.methods[0].insn_nodes;
+            let mut nops = NodeList::new();
+            for _ in 0..40_000 {
+                nops.add(Insn::from(InsnNode::from(opcodes::NOP)));
+            }
+            if in_jump {
+                let jump = code
+                    .iter()
+                    .find(|(_, node)| matches!(node, AbstractInsnNode::JumpLabel(_)))
+                    .map(|(id, _)| id)
+                    .expect("Method should have a jump");
+                code.insert_list_after(jump, nops);
+            } else {
+                code.insert_list_first(nops);
+            }
+            node
+        };
+
+        let relaid = ClassWriter::write_class_node(&with_nops(false), 0)
+            .expect("Should move the frames with their instructions");
+        let shifted: Vec<usize> =
+            frame_offsets(&bytes).iter().map(|offset| offset + 40_000).collect();
+        assert_eq!(frame_offsets(&relaid), shifted);
+
+        // Widening the IFGE adds a branch target without a frame.
+        let node = with_nops(true);
+        assert!(matches!(
+            ClassWriter::write_class_node(&node, 0),
+            Err(ClassWriteError::FramesRequired { .. })
+        ));
+        ClassWriter::write_class_node(&node, COMPUTE_FRAMES).expect("Should compute frames");
+
+        let mut node = with_nops(false);
+        let code = method_code(&bytes, 0);
+        node.methods[0].code_attributes.extend(
+            code.attributes
+                .iter()
+                .filter(|attr| matches!(attr, AttributeInfo::StackMapTable { .. }))
+                .cloned(),
+        );
+        assert!(matches!(
+            ClassWriter::write_class_node(&node, 0),
+            Err(ClassWriteError::FramesRequired { .. })
+        ));
+    }
+
+    #[cfg(feature = "jdk-hierarchy")]
+    #[test]
+    fn test_jdk_class_hierarchy() {
+        use crate::class_hierarchy::JdkClassHierarchy;
+
+        assert!(JdkClassHierarchy.is_interface("java/lang/CharSequence").unwrap());
+        assert!(
+            JdkClassHierarchy
+                .is_assignable("java/lang/CharSequence", "java/lang/String")
+                .unwrap()
+        );
+        assert!(
+            JdkClassHierarchy
+                .is_assignable("java/util/List", "java/util/ArrayList")
+                .unwrap()
+        );
+        // Through the interfaces of a super class and of a super interface.
+        assert!(
+            JdkClassHierarchy
+                .is_assignable("java/lang/Iterable", "java/util/ArrayList")
+                .unwrap()
+        );
+        assert!(
+            !JdkClassHierarchy
+                .is_assignable("java/util/Set", "java/util/ArrayList")
+                .unwrap()
+        );
+        assert_eq!(
+            JdkClassHierarchy
+                .common_super_class("java/lang/String", "java/lang/CharSequence")
+                .unwrap(),
+            "java/lang/CharSequence"
+        );
+        assert_eq!(
+            JdkClassHierarchy
+                .common_super_class("java/lang/String", "java/lang/Runnable")
+                .unwrap(),
+            "java/lang/Object"
+        );
+
+        let strict =
+            || ClassWriter::new(COMPUTE_FRAMES | COMPUTE_MAXS).with_strict_class_hierarchy(true);
+        let merges = [
+            ("java/util/ArrayList", "java/util/LinkedList", "java/util/AbstractList"),
+            ("java/lang/String", "java/lang/StringBuilder", "java/lang/Object"),
+            ("java/lang/Integer", "java/lang/Long", "java/lang/Number"),
+            ("java/io/FileNotFoundException", "java/io/EOFException", "java/io/IOException"),
+        ];
+        for (left, right, expected) in merges {
+            let bytes = write_merge(strict(), left, right).expect("Should merge JDK classes");
+            assert_eq!(joined_stack_type(&bytes), expected);
+        }
+    }
 }
@@ -17,7 +17,10 @@ pub struct ConstantPoolBuilder {
     interface_method_ref: HashMap<(String, String, String), u16>,
     method_type: HashMap<String, u16>,
     method_handle: HashMap<(u8, String, String, String, bool), u16>,
+    dynamic: HashMap<(u16, String, String), u16>,
     invoke_dynamic: HashMap<(u16, String, String), u16>,
+    module: HashMap<String, u16>,
+    package: HashMap<String, u16>,
 }
 
 impl ConstantPoolBuilder {
@@ -212,6 +215,19 @@ impl ConstantPoolBuilder {
                             .or_insert(index);
                     }
                 }
+                CpInfo::Dynamic {
+                    bootstrap_method_attr_index,
+                    name_and_type_index,
+                } => {
+                    if let Some((name, desc)) =
+                        cp_name_and_type(&builder.cp, *name_and_type_index)
+                    {
+                        builder
+                            .dynamic
+                            .entry((*bootstrap_method_attr_index, name.to_string(), desc.to_string()))
+                            .or_insert(index);
+                    }
+                }
                 CpInfo::InvokeDynamic {
                     bootstrap_method_attr_index,
                     name_and_type_index,
@@ -225,6 +241,16 @@ impl ConstantPoolBuilder {
                             .or_insert(index);
                     }
                 }
+                CpInfo::Module { name_index } => {
+                    if let Some(name) = cp_utf8(&builder.cp, *name_index) {
+                        builder.module.entry(name.to_string()).or_insert(index);
+                    }
+                }
+                CpInfo::Package { name_index } => {
+                    if let Some(name) = cp_utf8(&builder.cp, *name_index) {
+                        builder.package.entry(name.to_string()).or_insert(index);
+                    }
+                }
                 _ => {}
             }
         }
@@ -385,7 +411,7 @@ impl ConstantPoolBuilder {
             return *index;
         }
         let reference_index = match handle.reference_kind {
-            1 | 2 | 3 | 4 => self.field_ref(&handle.owner, &handle.name, &handle.descriptor),
+            1..=4 => self.field_ref(&handle.owner, &handle.name, &handle.descriptor),
             9 => self.interface_method_ref(&handle.owner, &handle.name, &handle.descriptor),
             _ => self.method_ref(&handle.owner, &handle.name, &handle.descriptor),
         };
@@ -397,6 +423,23 @@ impl ConstantPoolBuilder {
         index
     }
 
+    /// Adds a Dynamic constant (`CONSTANT_Dynamic`) to the pool.
+    ///
+    /// `bsm_index` is the index of the bootstrap method in the `BootstrapMethods` attribute.
+    pub fn dynamic(&mut self, bsm_index: u16, name: &str, descriptor: &str) -> u16 {
+        let key = (bsm_index, name.to_string(), descriptor.to_string());
+        if let Some(index) = self.dynamic.get(&key) {
+            return *index;
+        }
+        let name_and_type_index = self.name_and_type(name, descriptor);
+        let index = self.push(CpInfo::Dynamic {
+            bootstrap_method_attr_index: bsm_index,
+            name_and_type_index,
+        });
+        self.dynamic.insert(key, index);
+        index
+    }
+
     pub fn invoke_dynamic(&mut self, bsm_index: u16, name: &str, descriptor: &str) -> u16 {
         let key = (bsm_index, name.to_string(), descriptor.to_string());
         if let Some(index) = self.invoke_dynamic.get(&key) {
@@ -411,6 +454,32 @@ impl ConstantPoolBuilder {
         index
     }
 
+    /// Adds a Module constant to the pool.
+    ///
+    /// Used by the `Module` attribute of `module-info` classes.
+    pub fn module(&mut self, name: &str) -> u16 {
+        if let Some(index) = self.module.get(name) {
+            return *index;
+        }
+        let name_index = self.utf8(name);
+        let index = self.push(CpInfo::Module { name_index });
+        self.module.insert(name.to_string(), index);
+        index
+    }
+
+    /// Adds a Package constant to the pool.
+    ///
+    /// The name is the internal form of the package name (e.g., `java/lang`).
+    pub fn package(&mut self, name: &str) -> u16 {
+        if let Some(index) = self.package.get(name) {
+            return *index;
+        }
+        let name_index = self.utf8(name);
+        let index = self.push(CpInfo::Package { name_index });
+        self.package.insert(name.to_string(), index);
+        index
+    }
+
     fn push(&mut self, entry: CpInfo) -> u16 {
         self.cp.push(entry);
         (self.cp.len() - 1) as u16
@@ -49,6 +49,22 @@ pub const V24: u16 = 68;
 /// Java 25 ClassFile version 69
 pub const V25: u16 = 69;
 
+/// The latest ClassFile major version known to this library.
+///
+/// Class files with a newer version are rejected by default when reading,
+/// see [`VersionPolicy`](crate::class_reader::VersionPolicy).
+pub const LATEST_VERSION: u16 = V25;
+
+/// The minor version of class files that depend on the preview features of their Java SE release.
+pub const PREVIEW_MINOR_VERSION: u16 = 0xFFFF;
+
+/// Returns whether a class file with the given version depends on preview features.
+///
+/// Preview features exist since Java 12; older class files may use any minor version.
+pub fn is_preview_version(major_version: u16, minor_version: u16) -> bool {
+    major_version >= V12 && minor_version == PREVIEW_MINOR_VERSION
+}
+
 pub const ACC_PUBLIC: u16 = 0x0001;
 pub const ACC_PRIVATE: u16 = 0x0002;
 pub const ACC_PROTECTED: u16 = 0x0004;
@@ -68,6 +84,10 @@ pub const ACC_SYNTHETIC: u16 = 0x1000;
 pub const ACC_ANNOTATION: u16 = 0x2000;
 pub const ACC_ENUM: u16 = 0x4000;
 pub const ACC_MODULE: u16 = 0x8000;
+pub const ACC_OPEN: u16 = 0x0020;
+pub const ACC_TRANSITIVE: u16 = 0x0020;
+pub const ACC_STATIC_PHASE: u16 = 0x0040;
+pub const ACC_MANDATED: u16 = 0x8000;
 
 
 //method handle info
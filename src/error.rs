@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("class read error")]
@@ -6,6 +8,64 @@ pub enum Error {
     ClassWriter(#[source] ClassWriteError),
 }
 
+/// Where in a class file a [`ClassReadError`] occurred.
+///
+/// Every field is optional: the reader fills in whatever context was known at the point of
+/// failure, innermost first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorLocation {
+    /// The byte offset in the class file of the innermost structure being read (a constant pool
+    /// entry, field, method or attribute).
+    pub offset: Option<usize>,
+    /// The internal name of the class being read.
+    pub class_name: Option<String>,
+    /// The name of the field or method being read.
+    pub member_name: Option<String>,
+    /// The descriptor of the field or method being read.
+    pub member_descriptor: Option<String>,
+    /// The name of the innermost attribute being read.
+    pub attribute: Option<String>,
+    /// The bytecode offset of the instruction being read, for errors in a `Code` attribute.
+    pub code_offset: Option<usize>,
+}
+
+impl fmt::Display for ErrorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(class_name) = &self.class_name {
+            parts.push(format!("class {class_name}"));
+        }
+        if let Some(member_name) = &self.member_name {
+            let descriptor = self.member_descriptor.as_deref().unwrap_or_default();
+            parts.push(format!("member {member_name}{descriptor}"));
+        }
+        if let Some(attribute) = &self.attribute {
+            parts.push(format!("attribute {attribute}"));
+        }
+        if let Some(code_offset) = self.code_offset {
+            parts.push(format!("bytecode offset {code_offset}"));
+        }
+        if let Some(offset) = self.offset {
+            parts.push(format!("byte offset {offset}"));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// An error reading a class file.
+///
+/// Errors raised past the class file header are wrapped in [`ClassReadError::Located`] to record
+/// where they occurred, so match on [`ClassReadError::root`] rather than on the error itself:
+///
+/// ```
+/// use rust_asm::class_reader::ClassReader;
+/// use rust_asm::error::ClassReadError;
+///
+/// let bytes = [0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 52, 0, 2, 1];
+/// let error = ClassReader::new(&bytes).to_class_node().unwrap_err();
+/// assert!(matches!(error, ClassReadError::Located { .. }));
+/// assert!(matches!(error.root(), ClassReadError::UnexpectedEof));
+/// ```
 #[derive(thiserror::Error, Debug)]
 pub enum ClassReadError {
     #[error("unexpected end of input")]
@@ -18,7 +78,9 @@ pub enum ClassReadError {
     InvalidConstantPoolTag(u8),
     #[error("invalid constant pool index {0}")]
     InvalidIndex(u16),
-    #[error("invalid attribute {0}")]
+    /// A malformed attribute. The message describes the problem; the attribute name is recorded in
+    /// the error's [`ErrorLocation`].
+    #[error("invalid attribute: {0}")]
     InvalidAttribute(String),
     #[error("invalid opcode 0x{opcode:02x} at {offset}")]
     InvalidOpcode {
@@ -29,6 +91,127 @@ pub enum ClassReadError {
     },
     #[error("modified utf8 error: {0}")]
     Utf8Error(String),
+    #[error("invalid descriptor: {0}")]
+    InvalidDescriptor(#[from] DescriptorError),
+    /// Another error, annotated with where in the class file it occurred. Use
+    /// [`ClassReadError::root`] to get at the underlying error.
+    #[error("{source} (in {location})")]
+    Located {
+        location: Box<ErrorLocation>,
+        source: Box<ClassReadError>,
+    },
+}
+
+impl ClassReadError {
+    /// Returns the innermost error, without any location context.
+    pub fn root(&self) -> &ClassReadError {
+        match self {
+            ClassReadError::Located { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Returns where in the class file this error occurred, if known.
+    pub fn location(&self) -> Option<&ErrorLocation> {
+        match self {
+            ClassReadError::Located { location, .. } => Some(location),
+            _ => None,
+        }
+    }
+
+    /// Adds context to this error with `update`, which should only fill in fields that are still
+    /// unset so that the innermost context wins.
+    pub(crate) fn located(self, update: impl FnOnce(&mut ErrorLocation)) -> Self {
+        let (mut location, source) = match self {
+            ClassReadError::Located { location, source } => (location, source),
+            error => (Box::default(), Box::new(error)),
+        };
+        update(&mut location);
+        ClassReadError::Located { location, source }
+    }
+
+    /// Records the byte offset of the innermost structure being read, if not already known.
+    pub(crate) fn at_offset(self, offset: usize) -> Self {
+        self.located(|location| {
+            location.offset.get_or_insert(offset);
+        })
+    }
+
+    /// Records the bytecode offset of the instruction being read, if not already known.
+    pub(crate) fn at_code_offset(self, code_offset: usize) -> Self {
+        self.located(|location| {
+            location.code_offset.get_or_insert(code_offset);
+        })
+    }
+
+    /// Records the attribute being read, if not already known.
+    pub(crate) fn in_attribute(self, name: &str) -> Self {
+        self.located(|location| {
+            location.attribute.get_or_insert_with(|| name.to_string());
+        })
+    }
+
+    /// Records the field or method being read, if not already known.
+    pub(crate) fn in_member(self, name: Option<&str>, descriptor: Option<&str>) -> Self {
+        self.located(|location| {
+            if location.member_name.is_none() {
+                location.member_name = name.map(str::to_string);
+                location.member_descriptor = descriptor.map(str::to_string);
+            }
+        })
+    }
+
+    /// Records the class being read, if not already known.
+    pub(crate) fn in_class(self, name: Option<&str>) -> Self {
+        self.located(|location| {
+            if location.class_name.is_none() {
+                location.class_name = name.map(str::to_string);
+            }
+        })
+    }
+}
+
+/// An error from parsing a field or method descriptor, or from using a [`Type`] of the wrong
+/// sort.
+///
+/// [`Type`]: crate::types::Type
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum DescriptorError {
+    #[error("unexpected end of descriptor {0:?}")]
+    UnexpectedEnd(String),
+    #[error("invalid character {character:?} at {position} in descriptor {descriptor:?}")]
+    InvalidCharacter {
+        descriptor: String,
+        /// Byte offset of the character in the descriptor.
+        position: usize,
+        character: char,
+    },
+    #[error("unexpected characters at {position} in descriptor {descriptor:?}")]
+    TrailingCharacters { descriptor: String, position: usize },
+    #[error("array type with more than 255 dimensions at {position} in descriptor {descriptor:?}")]
+    TooManyDimensions { descriptor: String, position: usize },
+    #[error("expected a method type, found {0:?}")]
+    NotMethodType(String),
+    #[error("expected a field type, found method type {0:?}")]
+    UnexpectedMethodType(String),
+}
+
+/// An error from parsing a generic signature.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("unexpected end of signature {0:?}")]
+    UnexpectedEnd(String),
+    #[error("invalid character {character:?} at {position} in signature {signature:?}")]
+    InvalidCharacter {
+        signature: String,
+        /// Byte offset of the character in the signature.
+        position: usize,
+        character: char,
+    },
+    #[error("array type with more than 255 dimensions at {position} in signature {signature:?}")]
+    TooManyDimensions { signature: String, position: usize },
+    #[error("type nested too deeply at {position} in signature {signature:?}")]
+    TooDeeplyNested { signature: String, position: usize },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -41,4 +224,60 @@ pub enum ClassWriteError {
     InvalidOpcode { opcode: u8, offset: usize },
     #[error("frame computation error: {0}")]
     FrameComputation(String),
+    #[error("operand {operand} of opcode 0x{opcode:02X} is out of range")]
+    InvalidOperand { opcode: u8, operand: i32 },
+    #[error("label {0} is not placed in the method's code")]
+    MissingLabel(usize),
+    #[error("stack map frames of method {class}.{method} must be recomputed, use COMPUTE_FRAMES")]
+    FramesRequired { class: String, method: String },
+    #[error("class {0} is not in the class hierarchy")]
+    UnknownClass(String),
+    #[error("code of method {class}.{method}{descriptor} is {size} bytes long, more than 65535")]
+    MethodTooLarge {
+        class: String,
+        method: String,
+        descriptor: String,
+        size: usize,
+    },
+    #[error("constant pool of class {class} has {constant_pool_count} entries, more than 65535")]
+    ClassTooLarge {
+        class: String,
+        constant_pool_count: usize,
+    },
+    #[error("constant pool string is {length} bytes long in modified UTF-8, more than 65535")]
+    Utf8TooLong { length: usize },
+    #[error(
+        "method {class}.{method}{descriptor} needs {max_locals} local variable slots, \
+         more than 65535"
+    )]
+    TooManyLocals {
+        class: String,
+        method: String,
+        descriptor: String,
+        max_locals: usize,
+    },
+    #[error(
+        "method {class}.{method}{descriptor} needs {max_stack} operand stack slots, \
+         more than 65535"
+    )]
+    StackTooLarge {
+        class: String,
+        method: String,
+        descriptor: String,
+        max_stack: usize,
+    },
+    #[error("class {class} has {count} interfaces, more than 65535")]
+    TooManyInterfaces { class: String, count: usize },
+    #[error("class {class} has {count} fields, more than 65535")]
+    TooManyFields { class: String, count: usize },
+    #[error("class {class} has {count} methods, more than 65535")]
+    TooManyMethods { class: String, count: usize },
+    #[error("{table} has {count} entries, more than {max}")]
+    TooManyEntries {
+        table: &'static str,
+        count: usize,
+        max: usize,
+    },
+    #[error("invalid descriptor: {0}")]
+    InvalidDescriptor(#[from] DescriptorError),
 }
@@ -1,4 +1,7 @@
+use crate::class_reader::Frame;
+use crate::error::DescriptorError;
 use crate::opcodes;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::types::Type;
 
@@ -19,6 +22,10 @@ pub struct IntInsnNode {
     pub operand: i32,
 }
 
+/// A local variable instruction (e.g., ILOAD, ASTORE, RET).
+///
+/// There is no node for the `WIDE` prefix: the reader folds it into this node, and the writer
+/// adds it when `var_index` does not fit in a byte.
 #[derive(Debug, Clone)]
 pub struct VarInsnNode {
     pub insn: InsnNode,
@@ -72,12 +79,33 @@ pub struct JumpLabelInsnNode {
     pub target: LabelNode,
 }
 
+#[derive(Debug, Clone)]
+pub struct TableSwitchLabelInsnNode {
+    pub insn: InsnNode,
+    pub default: LabelNode,
+    pub low: i32,
+    pub high: i32,
+    /// The targets of the keys `low` to `high`, in order.
+    pub labels: Vec<LabelNode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LookupSwitchLabelInsnNode {
+    pub insn: InsnNode,
+    pub default: LabelNode,
+    pub keys: Vec<i32>,
+    /// The target of each key, in the same order as `keys`.
+    pub labels: Vec<LabelNode>,
+}
+
 #[derive(Debug, Clone)]
 pub struct LdcInsnNode {
     pub insn: InsnNode,
     pub value: LdcValue,
 }
 
+/// An `IINC` instruction, written with a `WIDE` prefix when `var_index` does not fit in an
+/// unsigned byte or `increment` in a signed byte.
 #[derive(Debug, Clone)]
 pub struct IincInsnNode {
     pub insn: InsnNode,
@@ -175,12 +203,30 @@ pub struct TryCatchBlockNode {
     pub catch_type: Option<String>,
 }
 
+/// A local variable declaration, from the `LocalVariableTable` attribute and, for variables of a
+/// generic type, the `LocalVariableTypeTable` attribute.
+#[derive(Debug, Clone)]
+pub struct LocalVariableNode {
+    pub name: String,
+    pub descriptor: String,
+    pub signature: Option<String>,
+    /// The start of the variable's scope (inclusive).
+    pub start: LabelNode,
+    /// The end of the variable's scope (exclusive).
+    pub end: LabelNode,
+    pub index: u16,
+}
+
 #[derive(Debug, Clone)]
 pub enum AbstractInsnNode {
     Label(LabelNode),
     LineNumber(LineNumberInsnNode),
     Insn(Insn),
     JumpLabel(JumpLabelInsnNode),
+    TableSwitchLabel(TableSwitchLabelInsnNode),
+    LookupSwitchLabel(LookupSwitchLabelInsnNode),
+    /// The stack map frame of the instruction that follows.
+    Frame(Frame),
 }
 
 #[derive(Debug, Clone)]
@@ -208,6 +254,9 @@ pub enum MemberRef {
         owner: String,
         name: String,
         descriptor: String,
+        /// Whether `owner` is an interface, so that a method reference is written as an
+        /// `InterfaceMethodref`. Always `false` for field references.
+        is_interface: bool,
     },
 }
 
@@ -220,6 +269,8 @@ pub enum LdcValue {
     Float(f32),
     Long(i64),
     Double(f64),
+    Handle(Handle),
+    ConstantDynamic(ConstantDynamic),
 }
 
 #[derive(Debug, Clone)]
@@ -241,61 +292,593 @@ pub enum BootstrapArgument {
     Class(String),
     MethodType(String),
     Handle(Handle),
+    ConstantDynamic(ConstantDynamic),
+}
+
+/// A dynamically-computed constant (`CONSTANT_Dynamic`), whose value is produced by calling
+/// its bootstrap method the first time it is loaded.
+#[derive(Debug, Clone)]
+pub struct ConstantDynamic {
+    pub name: String,
+    /// The field descriptor of the constant's type.
+    pub descriptor: String,
+    pub bootstrap_method: Handle,
+    pub bootstrap_args: Vec<BootstrapArgument>,
+}
+
+impl ConstantDynamic {
+    pub fn new(
+        name: &str,
+        descriptor: &str,
+        bootstrap_method: Handle,
+        bootstrap_args: &[BootstrapArgument],
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+            bootstrap_method,
+            bootstrap_args: bootstrap_args.to_vec(),
+        }
+    }
+
+    /// Returns whether the constant is a `long` or a `double`, which takes two stack slots
+    /// and is loaded with `LDC2_W`.
+    pub fn is_wide(&self) -> bool {
+        matches!(self.descriptor.as_str(), "J" | "D")
+    }
+}
+
+static NEXT_NODE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A handle to an element of an [`InsnList`] or a [`NodeList`].
+///
+/// A handle stays valid while its element is in a list, even when other elements are inserted,
+/// removed or moved around it, when the element is replaced, and when it is moved to another
+/// list with [`NodeList::extract`] and one of the `insert_list_*` methods. Cloning a list gives
+/// the elements of the copy new handles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// Returns a handle that was never returned before.
+    pub(crate) fn next() -> Self {
+        Self(NEXT_NODE_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// An element of a [`Links`] list, with the slots of its neighbours.
+struct Link<T> {
+    id: NodeId,
+    item: T,
+    previous: Option<usize>,
+    next: Option<usize>,
+}
+
+/// A doubly linked list stored in a vector of slots, which backs `InsnList` and `NodeList`.
+///
+/// `slots` maps each handle to the slot of its element, so handle lookups and edits take
+/// constant time. The slots of removed elements are reused by the next insertions.
+struct Links<T> {
+    links: Vec<Option<Link<T>>>,
+    free: Vec<usize>,
+    slots: HashMap<NodeId, usize>,
+    first: Option<usize>,
+    last: Option<usize>,
+}
+
+impl<T> Default for Links<T> {
+    fn default() -> Self {
+        Self {
+            links: Vec::new(),
+            free: Vec::new(),
+            slots: HashMap::new(),
+            first: None,
+            last: None,
+        }
+    }
+}
+
+impl<T: Clone> Clone for Links<T> {
+    /// Copies the elements, giving them new handles so that the copy can be inserted into the
+    /// original list.
+    fn clone(&self) -> Self {
+        let mut links = Self::default();
+        for link in self.iter() {
+            links.insert(None, NodeId::next(), link.item.clone());
+        }
+        links
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Links<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(|link| (link.id, &link.item)))
+            .finish()
+    }
+}
+
+impl<T> Links<T> {
+    fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn link(&self, slot: usize) -> &Link<T> {
+        self.links[slot].as_ref().expect("linked slot should be occupied")
+    }
+
+    fn link_mut(&mut self, slot: usize) -> &mut Link<T> {
+        self.links[slot].as_mut().expect("linked slot should be occupied")
+    }
+
+    fn slot(&self, id: NodeId) -> Option<usize> {
+        self.slots.get(&id).copied()
+    }
+
+    fn expect_slot(&self, id: NodeId) -> usize {
+        self.slot(id).unwrap_or_else(|| panic!("{id:?} is not in this list"))
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            links: self,
+            front: self.first,
+            back: self.last,
+            len: self.len(),
+        }
+    }
+
+    /// Links `item` before the element in slot `before`, or at the end when it is `None`.
+    fn insert(&mut self, before: Option<usize>, id: NodeId, item: T) {
+        let previous = match before {
+            Some(slot) => self.link(slot).previous,
+            None => self.last,
+        };
+        let link = Link {
+            id,
+            item,
+            previous,
+            next: before,
+        };
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.links[slot] = Some(link);
+                slot
+            }
+            None => {
+                self.links.push(Some(link));
+                self.links.len() - 1
+            }
+        };
+        match previous {
+            Some(previous) => self.link_mut(previous).next = Some(slot),
+            None => self.first = Some(slot),
+        }
+        match before {
+            Some(next) => self.link_mut(next).previous = Some(slot),
+            None => self.last = Some(slot),
+        }
+        self.slots.insert(id, slot);
+    }
+
+    /// Moves the elements of `list` before the element in slot `before`, or at the end when it
+    /// is `None`, keeping their handles.
+    fn insert_list(&mut self, before: Option<usize>, mut list: Links<T>) {
+        let mut current = list.first;
+        while let Some(slot) = current {
+            let link = list.links[slot].take().expect("linked slot should be occupied");
+            current = link.next;
+            self.insert(before, link.id, link.item);
+        }
+    }
+
+    fn unlink(&mut self, slot: usize) -> Link<T> {
+        let link = self.links[slot].take().expect("linked slot should be occupied");
+        match link.previous {
+            Some(previous) => self.link_mut(previous).next = link.next,
+            None => self.first = link.next,
+        }
+        match link.next {
+            Some(next) => self.link_mut(next).previous = link.previous,
+            None => self.last = link.previous,
+        }
+        self.slots.remove(&link.id);
+        self.free.push(slot);
+        link
+    }
+
+    /// Returns the slots from `first` to `last` (inclusive).
+    fn expect_range(&self, first: NodeId, last: NodeId) -> Vec<usize> {
+        let end = self.expect_slot(last);
+        let mut range = Vec::new();
+        let mut current = Some(self.expect_slot(first));
+        while let Some(slot) = current {
+            range.push(slot);
+            if slot == end {
+                return range;
+            }
+            current = self.link(slot).next;
+        }
+        panic!("{last:?} is before {first:?}");
+    }
+
+    /// Unlinks the elements in the slots of `range` and returns them as a new list, keeping
+    /// their handles.
+    fn extract(&mut self, range: Vec<usize>) -> Links<T> {
+        let mut list = Links::default();
+        for slot in range {
+            let link = self.unlink(slot);
+            list.insert(None, link.id, link.item);
+        }
+        list
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn into_items(mut self) -> Vec<T> {
+        let mut items = Vec::with_capacity(self.len());
+        let mut current = self.first;
+        while let Some(slot) = current {
+            let link = self.links[slot].take().expect("linked slot should be occupied");
+            current = link.next;
+            items.push(link.item);
+        }
+        items
+    }
 }
 
+/// Iterates over the elements of a [`Links`] list, from both ends.
+struct Iter<'a, T> {
+    links: &'a Links<T>,
+    front: Option<usize>,
+    back: Option<usize>,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a Link<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let link = self.links.link(self.front?);
+        self.front = link.next;
+        self.len -= 1;
+        Some(link)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let link = self.links.link(self.back?);
+        self.back = link.previous;
+        self.len -= 1;
+        Some(link)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Implements the editing API shared by `InsnList` and `NodeList`, whose elements are stored in
+/// the [`Links`] list `$field`.
+macro_rules! editable_list {
+    ($list:ident, $item:ty, $field:ident) => {
+        impl $list {
+            /// Returns the number of elements in the list.
+            pub fn len(&self) -> usize {
+                self.$field.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            /// Returns the handle of the first element.
+            pub fn first(&self) -> Option<NodeId> {
+                self.$field.first.map(|slot| self.$field.link(slot).id)
+            }
+
+            /// Returns the handle of the last element.
+            pub fn last(&self) -> Option<NodeId> {
+                self.$field.last.map(|slot| self.$field.link(slot).id)
+            }
+
+            /// Returns the handle of the element following `id`, or `None` if `id` is the last
+            /// element or is not in the list.
+            pub fn next(&self, id: NodeId) -> Option<NodeId> {
+                let next = self.$field.link(self.$field.slot(id)?).next?;
+                Some(self.$field.link(next).id)
+            }
+
+            /// Returns the handle of the element preceding `id`, or `None` if `id` is the first
+            /// element or is not in the list.
+            pub fn previous(&self, id: NodeId) -> Option<NodeId> {
+                let previous = self.$field.link(self.$field.slot(id)?).previous?;
+                Some(self.$field.link(previous).id)
+            }
+
+            /// Returns the position of `id` in the list. This walks the list, so it takes
+            /// linear time.
+            pub fn index_of(&self, id: NodeId) -> Option<usize> {
+                self.$field.slot(id)?;
+                self.$field.iter().position(|link| link.id == id)
+            }
+
+            /// Returns the handle of the element at `index`. This walks the list, so it takes
+            /// linear time.
+            pub fn id_at(&self, index: usize) -> Option<NodeId> {
+                self.$field.iter().nth(index).map(|link| link.id)
+            }
+
+            /// Returns whether `id` is in the list.
+            pub fn contains(&self, id: NodeId) -> bool {
+                self.$field.slot(id).is_some()
+            }
+
+            pub fn get(&self, id: NodeId) -> Option<&$item> {
+                let slot = self.$field.slot(id)?;
+                Some(&self.$field.link(slot).item)
+            }
+
+            pub fn get_mut(&mut self, id: NodeId) -> Option<&mut $item> {
+                let slot = self.$field.slot(id)?;
+                Some(&mut self.$field.link_mut(slot).item)
+            }
+
+            /// Returns the handles of the elements, in order.
+            ///
+            /// The returned vector is a snapshot: the list can be edited while iterating over it,
+            /// and `get` returns `None` for the elements removed meanwhile.
+            pub fn ids(&self) -> Vec<NodeId> {
+                self.$field.iter().map(|link| link.id).collect()
+            }
+
+            /// Iterates over the elements and their handles.
+            pub fn iter(
+                &self,
+            ) -> impl DoubleEndedIterator<Item = (NodeId, &$item)> + ExactSizeIterator {
+                self.$field.iter().map(|link| (link.id, &link.item))
+            }
+
+            /// Inserts an element at the start of the list and returns its handle.
+            pub fn insert_first<T: Into<$item>>(&mut self, item: T) -> NodeId {
+                let id = NodeId::next();
+                self.$field.insert(self.$field.first, id, item.into());
+                id
+            }
+
+            /// Appends an element to the list and returns its handle.
+            pub fn push<T: Into<$item>>(&mut self, item: T) -> NodeId {
+                let id = NodeId::next();
+                self.$field.insert(None, id, item.into());
+                id
+            }
+
+            /// Inserts an element before `at` and returns its handle.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `at` is not in the list.
+            pub fn insert_before<T: Into<$item>>(&mut self, at: NodeId, item: T) -> NodeId {
+                let slot = self.$field.expect_slot(at);
+                let id = NodeId::next();
+                self.$field.insert(Some(slot), id, item.into());
+                id
+            }
+
+            /// Inserts an element after `at` and returns its handle.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `at` is not in the list.
+            pub fn insert_after<T: Into<$item>>(&mut self, at: NodeId, item: T) -> NodeId {
+                let next = self.$field.link(self.$field.expect_slot(at)).next;
+                let id = NodeId::next();
+                self.$field.insert(next, id, item.into());
+                id
+            }
+
+            /// Inserts all the elements of `list` at the start of this list, keeping their
+            /// handles.
+            pub fn insert_list_first(&mut self, list: $list) {
+                self.$field.insert_list(self.$field.first, list.$field);
+            }
+
+            /// Appends all the elements of `list` to this list, keeping their handles.
+            pub fn add_list(&mut self, list: $list) {
+                self.$field.insert_list(None, list.$field);
+            }
+
+            /// Inserts all the elements of `list` before `at`, keeping their handles.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `at` is not in the list.
+            pub fn insert_list_before(&mut self, at: NodeId, list: $list) {
+                let slot = self.$field.expect_slot(at);
+                self.$field.insert_list(Some(slot), list.$field);
+            }
+
+            /// Inserts all the elements of `list` after `at`, keeping their handles.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `at` is not in the list.
+            pub fn insert_list_after(&mut self, at: NodeId, list: $list) {
+                let next = self.$field.link(self.$field.expect_slot(at)).next;
+                self.$field.insert_list(next, list.$field);
+            }
+
+            /// Removes the element `id` and returns it, or returns `None` if it is not in the
+            /// list.
+            pub fn remove(&mut self, id: NodeId) -> Option<$item> {
+                let slot = self.$field.slot(id)?;
+                Some(self.$field.unlink(slot).item)
+            }
+
+            /// Replaces the element `id`, which keeps its handle, and returns the previous one.
+            /// Returns `None` and leaves the list unchanged if `id` is not in the list.
+            pub fn replace<T: Into<$item>>(&mut self, id: NodeId, item: T) -> Option<$item> {
+                let slot = self.$field.slot(id)?;
+                Some(std::mem::replace(&mut self.$field.link_mut(slot).item, item.into()))
+            }
+
+            /// Removes the elements from `first` to `last` (inclusive) and returns them as a new
+            /// list, keeping their handles.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `first` or `last` is not in the list, or if `last` is before `first`.
+            pub fn extract(&mut self, first: NodeId, last: NodeId) -> $list {
+                let range = self.$field.expect_range(first, last);
+                $list {
+                    $field: self.$field.extract(range),
+                }
+            }
+
+            /// Moves the elements from `first` to `last` (inclusive) before `at`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `first`, `last` or `at` is not in the list, if `last` is before
+            /// `first`, or if `at` is in the moved range.
+            pub fn move_before(&mut self, first: NodeId, last: NodeId, at: NodeId) {
+                let slot = self.expect_outside(first, last, at);
+                let range = self.extract(first, last);
+                self.$field.insert_list(Some(slot), range.$field);
+            }
+
+            /// Moves the elements from `first` to `last` (inclusive) after `at`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `first`, `last` or `at` is not in the list, if `last` is before
+            /// `first`, or if `at` is in the moved range.
+            pub fn move_after(&mut self, first: NodeId, last: NodeId, at: NodeId) {
+                self.expect_outside(first, last, at);
+                let range = self.extract(first, last);
+                self.insert_list_after(at, range);
+            }
+
+            /// Removes all the elements.
+            pub fn clear(&mut self) {
+                self.$field.clear();
+            }
+
+            /// Returns the slot of `at`, checking that it is outside the range from `first` to
+            /// `last`.
+            fn expect_outside(&self, first: NodeId, last: NodeId, at: NodeId) -> usize {
+                let range = self.$field.expect_range(first, last);
+                let slot = self.$field.expect_slot(at);
+                assert!(!range.contains(&slot), "{at:?} is in the moved range");
+                slot
+            }
+        }
+    };
+}
+
+/// A list of instructions, with jump and switch targets given as bytecode offsets.
+///
+/// Elements are addressed by [`NodeId`] handles; see [`NodeList`] for the editing API.
 #[derive(Debug, Clone, Default)]
 pub struct InsnList {
-    insns: Vec<Insn>,
+    insns: Links<Insn>,
 }
 
 impl InsnList {
     pub fn new() -> Self {
-        Self { insns: Vec::new() }
+        Self::default()
     }
 
     pub fn add<T: Into<Insn>>(&mut self, insn: T) -> &mut Self {
-        self.insns.push(insn.into());
+        self.push(insn);
         self
     }
 
-    pub fn insns(&self) -> &[Insn] {
-        &self.insns
+    /// Iterates over the instructions, in order.
+    pub fn insns(&self) -> impl DoubleEndedIterator<Item = &Insn> + ExactSizeIterator {
+        self.insns.iter().map(|link| &link.item)
     }
 
     pub fn into_insns(self) -> Vec<Insn> {
-        self.insns
+        self.insns.into_items()
     }
 }
 
+editable_list!(InsnList, Insn, insns);
+
+/// A list of instructions, labels and line numbers, with jump and switch targets given as
+/// labels.
+///
+/// Each element has a [`NodeId`] handle that survives the edits of the list, so a
+/// transformation can remember positions while inserting, removing, replacing or moving
+/// elements. To edit the list while walking it, iterate over a snapshot of the handles or
+/// step with [`next`](Self::next):
+///
+/// ```rust
+/// use rust_asm::insn::{AbstractInsnNode, Insn, InsnNode, NodeList};
+/// use rust_asm::opcodes;
+///
+/// let mut list = NodeList::new();
+/// list.add(Insn::from(InsnNode::from(opcodes::NOP)));
+/// let ret = list.push(Insn::from(InsnNode::from(opcodes::RETURN)));
+///
+/// let mut current = list.first();
+/// while let Some(id) = current {
+///     current = list.next(id);
+///     if let Some(AbstractInsnNode::Insn(Insn::Simple(node))) = list.get(id)
+///         && node.opcode == opcodes::NOP
+///     {
+///         list.remove(id);
+///     }
+/// }
+/// list.insert_before(ret, Insn::from(InsnNode::from(opcodes::ICONST_0)));
+/// list.insert_before(ret, Insn::from(InsnNode::from(opcodes::POP)));
+/// assert_eq!(list.len(), 3);
+/// assert_eq!(list.index_of(ret), Some(2));
+/// ```
 #[derive(Debug, Clone, Default)]
 pub struct NodeList {
-    nodes: Vec<AbstractInsnNode>,
+    nodes: Links<AbstractInsnNode>,
 }
 
 impl NodeList {
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self::default()
     }
 
     pub fn add<T: Into<AbstractInsnNode>>(&mut self, node: T) -> &mut Self {
-        self.nodes.push(node.into());
+        self.push(node);
         self
     }
 
     pub fn add_node(&mut self, node: AbstractInsnNode) -> &mut Self {
-        self.nodes.push(node);
+        self.push(node);
         self
     }
 
-    pub fn nodes(&self) -> &[AbstractInsnNode] {
-        &self.nodes
+    /// Iterates over the nodes, in order.
+    pub fn nodes(&self) -> impl DoubleEndedIterator<Item = &AbstractInsnNode> + ExactSizeIterator {
+        self.nodes.iter().map(|link| &link.item)
     }
 
     pub fn into_nodes(self) -> Vec<AbstractInsnNode> {
-        self.nodes
+        self.nodes.into_items()
     }
 }
 
+editable_list!(NodeList, AbstractInsnNode, nodes);
+
 impl From<LabelNode> for AbstractInsnNode {
     fn from(value: LabelNode) -> Self {
         AbstractInsnNode::Label(value)
@@ -308,6 +891,12 @@ impl From<LineNumberInsnNode> for AbstractInsnNode {
     }
 }
 
+impl From<Frame> for AbstractInsnNode {
+    fn from(value: Frame) -> Self {
+        AbstractInsnNode::Frame(value)
+    }
+}
+
 impl From<Insn> for AbstractInsnNode {
     fn from(value: Insn) -> Self {
         AbstractInsnNode::Insn(value)
@@ -320,6 +909,18 @@ impl From<JumpLabelInsnNode> for AbstractInsnNode {
     }
 }
 
+impl From<TableSwitchLabelInsnNode> for AbstractInsnNode {
+    fn from(value: TableSwitchLabelInsnNode) -> Self {
+        AbstractInsnNode::TableSwitchLabel(value)
+    }
+}
+
+impl From<LookupSwitchLabelInsnNode> for AbstractInsnNode {
+    fn from(value: LookupSwitchLabelInsnNode) -> Self {
+        AbstractInsnNode::LookupSwitchLabel(value)
+    }
+}
+
 impl FieldInsnNode {
     pub fn new(opcode: u8, owner: &str, name: &str, descriptor: &str) -> Self {
         Self {
@@ -328,6 +929,7 @@ impl FieldInsnNode {
                 owner: owner.to_string(),
                 name: name.to_string(),
                 descriptor: descriptor.to_string(),
+                is_interface: false,
             },
         }
     }
@@ -341,13 +943,27 @@ impl FieldInsnNode {
 }
 
 impl MethodInsnNode {
+    /// Creates a method instruction whose owner is an interface only for `INVOKEINTERFACE`.
     pub fn new(opcode: u8, owner: &str, name: &str, descriptor: &str) -> Self {
+        Self::with_interface(opcode, owner, name, descriptor, opcode == opcodes::INVOKEINTERFACE)
+    }
+
+    /// Creates a method instruction, with `is_interface` telling whether `owner` is an
+    /// interface (e.g., for an `INVOKESTATIC` of a static interface method).
+    pub fn with_interface(
+        opcode: u8,
+        owner: &str,
+        name: &str,
+        descriptor: &str,
+        is_interface: bool,
+    ) -> Self {
         Self {
             insn: opcode.into(),
             method_ref: MemberRef::Symbolic {
                 owner: owner.to_string(),
                 name: name.to_string(),
                 descriptor: descriptor.to_string(),
+                is_interface,
             },
         }
     }
@@ -454,6 +1070,32 @@ impl LdcInsnNode {
             value: LdcValue::Type(value),
         }
     }
+
+    /// Creates an `LDC` of a method type, given its method descriptor, or returns an error if
+    /// the descriptor is malformed or not a method descriptor.
+    pub fn method_type(descriptor: &str) -> Result<Self, DescriptorError> {
+        Type::try_get_method_type(descriptor).map(Self::typed)
+    }
+
+    /// Creates an `LDC` of a method handle.
+    pub fn handle(value: Handle) -> Self {
+        Self {
+            insn: opcodes::LDC.into(),
+            value: LdcValue::Handle(value),
+        }
+    }
+
+    pub fn constant_dynamic(value: ConstantDynamic) -> Self {
+        let opcode = if value.is_wide() {
+            opcodes::LDC2_W
+        } else {
+            opcodes::LDC
+        };
+        Self {
+            insn: opcode.into(),
+            value: LdcValue::ConstantDynamic(value),
+        }
+    }
 }
 
 impl From<InsnNode> for Insn {
@@ -539,3 +1181,95 @@ impl From<MultiANewArrayInsnNode> for Insn {
         Insn::MultiANewArray(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opcodes_of(list: &NodeList) -> Vec<u8> {
+        list.nodes()
+            .filter_map(|node| match node {
+                AbstractInsnNode::Insn(Insn::Simple(node)) => Some(node.opcode),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn simple(opcode: u8) -> Insn {
+        Insn::from(InsnNode::from(opcode))
+    }
+
+    #[test]
+    fn test_node_list_editing() {
+        let mut list = NodeList::new();
+        let a = list.push(simple(opcodes::ICONST_0));
+        let b = list.push(simple(opcodes::ICONST_1));
+        let c = list.push(simple(opcodes::ICONST_2));
+        let ret = list.push(simple(opcodes::RETURN));
+
+        let nop = list.insert_after(a, simple(opcodes::NOP));
+        list.insert_first(LabelNode::new());
+        assert_eq!(list.next(a), Some(nop));
+        assert_eq!(list.previous(a), list.first());
+
+        list.move_before(b, c, a);
+        assert_eq!(
+            opcodes_of(&list),
+            [opcodes::ICONST_1, opcodes::ICONST_2, opcodes::ICONST_0, opcodes::NOP, opcodes::RETURN]
+        );
+
+        let moved = list.extract(b, c);
+        assert_eq!(moved.len(), 2);
+        assert_eq!(moved.index_of(c), Some(1));
+        assert!(!list.contains(b));
+        assert_eq!(list.previous(a), list.first());
+        list.insert_list_after(ret, moved);
+        assert_eq!(list.index_of(c), Some(list.len() - 1));
+
+        let old = list.replace(nop, simple(opcodes::POP));
+        assert!(matches!(
+            old,
+            Some(AbstractInsnNode::Insn(Insn::Simple(InsnNode {
+                opcode: opcodes::NOP
+            })))
+        ));
+        for id in list.ids() {
+            if list.next(id).is_some() {
+                list.remove(id);
+            }
+        }
+        assert_eq!(list.ids(), [c]);
+        assert!(!list.contains(nop));
+        assert_eq!(list.remove(nop).map(|_| ()), None);
+    }
+
+    #[test]
+    fn test_insert_clone_into_source_list() {
+        let mut list = NodeList::new();
+        let first = list.push(simple(opcodes::ICONST_0));
+        let last = list.push(simple(opcodes::RETURN));
+        let copy = list.clone();
+        let copied = copy.ids();
+        assert!(copied.iter().all(|id| !list.contains(*id)));
+
+        list.insert_list_after(first, copy);
+        assert_eq!(
+            opcodes_of(&list),
+            [opcodes::ICONST_0, opcodes::ICONST_0, opcodes::RETURN, opcodes::RETURN]
+        );
+        assert_eq!(list.next(first), Some(copied[0]));
+        assert_eq!(list.index_of(last), Some(3));
+        assert!(list.remove(copied[1]).is_some());
+        assert_eq!(list.next(copied[0]), Some(last));
+        assert_eq!(list.previous(last), Some(copied[0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "is in the moved range")]
+    fn test_move_into_own_range() {
+        let mut list = InsnList::new();
+        let first = list.push(simple(opcodes::NOP));
+        let last = list.push(simple(opcodes::NOP));
+        list.move_after(first, last, last);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod class_hierarchy;
 pub mod class_reader;
 pub mod class_writer;
 pub mod constants;
@@ -7,5 +8,7 @@ pub mod insn;
 pub mod macros;
 pub mod nodes;
 pub mod opcodes;
+pub mod signature;
+pub mod type_reference;
 pub mod types;
 pub mod constant_pool;
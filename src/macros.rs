@@ -91,6 +91,7 @@
 ///         owner,
 ///         name,
 ///         descriptor,
+///         ..
 ///     } = &node.field_ref
 ///     {
 ///         assert_eq!(owner, "java/lang/System");
@@ -1,6 +1,9 @@
-use crate::class_reader::{AttributeInfo, ExceptionTableEntry};
+use crate::class_reader::{self, AttributeInfo, ClassVisitor, MethodVisitor};
+use crate::error::ClassReadError;
 use crate::constant_pool::CpInfo;
-use crate::insn::InsnList;
+use crate::insn::{LabelNode, LocalVariableNode, NodeId, NodeList, TryCatchBlockNode};
+use crate::type_reference::{TypePath, TypeReference};
+use std::collections::HashMap;
 
 /// Represents a parsed Java Class File.
 ///
@@ -62,6 +65,41 @@ pub struct ClassNode {
     ///
     /// This value is empty when no enclosing class information is available.
     pub outer_class: String,
+
+    /// The runtime visible annotations of this class (`RuntimeVisibleAnnotations`).
+    pub visible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime invisible annotations of this class (`RuntimeInvisibleAnnotations`).
+    pub invisible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime visible type annotations of this class (`RuntimeVisibleTypeAnnotations`).
+    pub visible_type_annotations: Vec<TypeAnnotationNode>,
+
+    /// The runtime invisible type annotations of this class (`RuntimeInvisibleTypeAnnotations`).
+    pub invisible_type_annotations: Vec<TypeAnnotationNode>,
+
+    /// The module declared by this class, for `module-info` classes
+    /// (`Module`, `ModulePackages` and `ModuleMainClass` attributes).
+    pub module: Option<ModuleNode>,
+
+    /// The components of a record class (`Record` attribute), or `None` if this class is not a
+    /// record. A record without components has an empty list.
+    pub record_components: Option<Vec<RecordComponentNode>>,
+
+    /// The internal name of the nest host of this class (`NestHost`).
+    pub nest_host: Option<String>,
+
+    /// The internal names of the nest members, if this class is a nest host (`NestMembers`).
+    pub nest_members: Vec<String>,
+
+    /// The internal names of the permitted subclasses of a sealed class (`PermittedSubclasses`).
+    pub permitted_subclasses: Vec<String>,
+}
+
+impl Default for ClassNode {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ClassNode {
@@ -82,7 +120,194 @@ impl ClassNode {
             attributes: Vec::new(),
             inner_classes: Vec::new(),
             outer_class: String::new(),
+            visible_annotations: Vec::new(),
+            invisible_annotations: Vec::new(),
+            visible_type_annotations: Vec::new(),
+            invisible_type_annotations: Vec::new(),
+            module: None,
+            record_components: None,
+            nest_host: None,
+            nest_members: Vec::new(),
+            permitted_subclasses: Vec::new(),
+        }
+    }
+
+    /// Returns whether this class depends on the preview features of its Java SE release
+    /// (its minor version is [`PREVIEW_MINOR_VERSION`](crate::constants::PREVIEW_MINOR_VERSION)).
+    pub fn uses_preview_features(&self) -> bool {
+        crate::constants::is_preview_version(self.major_version, self.minor_version)
+    }
+
+    /// Makes `visitor` visit this class with the events
+    /// [`ClassReader::accept`](crate::class_reader::ClassReader::accept) emits for the class
+    /// file it is written to.
+    ///
+    /// Generic signatures, thrown exceptions, constant values, the enclosing method, inner
+    /// classes and non-standard attributes are decoded from the `attributes` of the class and its
+    /// members, against [`constant_pool`](Self::constant_pool). Method code is replayed from
+    /// [`MethodNode::insn_nodes`], with its stack map frames.
+    pub fn accept(&self, visitor: &mut dyn ClassVisitor) -> Result<(), ClassReadError> {
+        self.accept_class(visitor).map_err(|error| error.in_class(Some(&self.name)))
+    }
+
+    fn accept_class(&self, visitor: &mut dyn ClassVisitor) -> Result<(), ClassReadError> {
+        let cp = &self.constant_pool;
+        let interfaces: Vec<&str> = self.interfaces.iter().map(String::as_str).collect();
+        visitor.visit(
+            self.major_version,
+            self.minor_version,
+            self.access_flags,
+            &self.name,
+            class_reader::attribute_signature(cp, &self.attributes)?,
+            self.super_name.as_deref(),
+            &interfaces,
+        );
+        if let Some(source) = &self.source_file {
+            visitor.visit_source(source);
+        }
+
+        if let Some(module) = &self.module
+            && let Some(mut mv) =
+                visitor.visit_module(&module.name, module.access_flags, module.version.as_deref())
+        {
+            if let Some(main_class) = &module.main_class {
+                mv.visit_main_class(main_class);
+            }
+            for package in &module.packages {
+                mv.visit_package(package);
+            }
+            for require in &module.requires {
+                mv.visit_require(&require.module, require.access_flags, require.version.as_deref());
+            }
+            for export in &module.exports {
+                mv.visit_export(&export.package, export.access_flags, &export.modules);
+            }
+            for open in &module.opens {
+                mv.visit_open(&open.package, open.access_flags, &open.modules);
+            }
+            for service in &module.uses {
+                mv.visit_use(service);
+            }
+            for provide in &module.provides {
+                mv.visit_provide(&provide.service, &provide.providers);
+            }
+            mv.visit_end();
+        }
+
+        if let Some(nest_host) = &self.nest_host {
+            visitor.visit_nest_host(nest_host);
+        }
+        class_reader::accept_outer_class(cp, &self.attributes, visitor)?;
+        for annotation in &self.visible_annotations {
+            visitor.visit_annotation(annotation, true);
+        }
+        for annotation in &self.invisible_annotations {
+            visitor.visit_annotation(annotation, false);
+        }
+        for annotation in &self.visible_type_annotations {
+            visitor.visit_type_annotation(annotation, true);
         }
+        for annotation in &self.invisible_type_annotations {
+            visitor.visit_type_annotation(annotation, false);
+        }
+        for (name, content) in self.attributes.iter().filter_map(class_reader::raw_attribute) {
+            visitor.visit_attribute(name, content);
+        }
+        for nest_member in &self.nest_members {
+            visitor.visit_nest_member(nest_member);
+        }
+        for permitted_subclass in &self.permitted_subclasses {
+            visitor.visit_permitted_subclass(permitted_subclass);
+        }
+        class_reader::accept_inner_classes(cp, &self.attributes, visitor)?;
+
+        if self.record_components.is_some() {
+            visitor.visit_record();
+        }
+        for component in self.record_components.iter().flatten() {
+            if let Some(mut rv) = visitor.visit_record_component(
+                &component.name,
+                &component.descriptor,
+                component.signature.as_deref(),
+            ) {
+                for annotation in &component.visible_annotations {
+                    rv.visit_annotation(annotation, true);
+                }
+                for annotation in &component.invisible_annotations {
+                    rv.visit_annotation(annotation, false);
+                }
+                for annotation in &component.visible_type_annotations {
+                    rv.visit_type_annotation(annotation, true);
+                }
+                for annotation in &component.invisible_type_annotations {
+                    rv.visit_type_annotation(annotation, false);
+                }
+                for (name, content) in
+                    component.attributes.iter().filter_map(class_reader::raw_attribute)
+                {
+                    rv.visit_attribute(name, content);
+                }
+                rv.visit_end();
+            }
+        }
+
+        for field in &self.fields {
+            let located = |error: ClassReadError| {
+                error.in_member(Some(&field.name), Some(&field.descriptor))
+            };
+            let signature =
+                class_reader::attribute_signature(cp, &field.attributes).map_err(located)?;
+            let value =
+                class_reader::attribute_constant_value(cp, &field.attributes).map_err(located)?;
+            if let Some(mut fv) = visitor.visit_field(
+                field.access_flags,
+                &field.name,
+                &field.descriptor,
+                signature,
+                value.as_ref(),
+            ) {
+                for annotation in &field.visible_annotations {
+                    fv.visit_annotation(annotation, true);
+                }
+                for annotation in &field.invisible_annotations {
+                    fv.visit_annotation(annotation, false);
+                }
+                for annotation in &field.visible_type_annotations {
+                    fv.visit_type_annotation(annotation, true);
+                }
+                for annotation in &field.invisible_type_annotations {
+                    fv.visit_type_annotation(annotation, false);
+                }
+                for (name, content) in
+                    field.attributes.iter().filter_map(class_reader::raw_attribute)
+                {
+                    fv.visit_attribute(name, content);
+                }
+                fv.visit_end();
+            }
+        }
+
+        for method in &self.methods {
+            let located = |error: ClassReadError| {
+                error.in_member(Some(&method.name), Some(&method.descriptor))
+            };
+            let signature =
+                class_reader::attribute_signature(cp, &method.attributes).map_err(located)?;
+            let exceptions =
+                class_reader::attribute_exceptions(cp, &method.attributes).map_err(located)?;
+            if let Some(mut mv) = visitor.visit_method(
+                method.access_flags,
+                &method.name,
+                &method.descriptor,
+                signature,
+                &exceptions,
+            ) {
+                method.accept(self, &mut *mv).map_err(located)?;
+            }
+        }
+
+        visitor.visit_end();
+        Ok(())
     }
 }
 
@@ -119,13 +344,25 @@ pub struct FieldNode {
 
     /// Attributes associated with this field (e.g., `ConstantValue`, `Synthetic`, `Deprecated`, `Signature`).
     pub attributes: Vec<AttributeInfo>,
+
+    /// The runtime visible annotations of this field.
+    pub visible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime invisible annotations of this field.
+    pub invisible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime visible type annotations of this field.
+    pub visible_type_annotations: Vec<TypeAnnotationNode>,
+
+    /// The runtime invisible type annotations of this field.
+    pub invisible_type_annotations: Vec<TypeAnnotationNode>,
 }
 
 /// Represents a method within a class.
 ///
 /// # See Also
 /// * [JVM Specification: method_info](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.6)
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct MethodNode {
     /// A bitmask of access flags (e.g., `ACC_PUBLIC`, `ACC_STATIC`, `ACC_SYNCHRONIZED`).
     pub access_flags: u16,
@@ -146,15 +383,422 @@ pub struct MethodNode {
     /// The maximum number of local variables required by the method's bytecode.
     pub max_locals: u16,
 
-    /// Decoded JVM instructions in an `InsnList`.
-    pub instructions: InsnList,
+    /// The method's code as a list of instructions, labels, line numbers and stack map frames.
+    ///
+    /// Branch targets, exception handlers, local variable scopes and code type annotations refer
+    /// to labels and handles of this list, so instructions can be inserted or removed without
+    /// breaking them. The writer lays out the bytecode from it and regenerates the exception
+    /// table and the `LineNumberTable`, `LocalVariableTable` and `LocalVariableTypeTable`
+    /// attributes. The offset-based instructions of the class file are in
+    /// [`CodeAttribute::instructions`](crate::class_reader::CodeAttribute::instructions).
+    ///
+    /// Without `COMPUTE_FRAMES`, the `StackMapTable` is written from the frames of this list at
+    /// the new offsets of the instructions they precede. Writing fails with
+    /// [`FramesRequired`](crate::error::ClassWriteError::FramesRequired) when `code_attributes`
+    /// has an offset-based `StackMapTable`, or when a conditional jump had to be widened, which
+    /// adds a branch target without a frame.
+    pub insn_nodes: NodeList,
+
+    /// The exception handlers of `insn_nodes`, in exception table order.
+    pub try_catch_blocks: Vec<TryCatchBlockNode>,
 
-    /// Exception handlers (raw entries in the code attribute).
-    pub exception_table: Vec<ExceptionTableEntry>,
+    /// The local variables of `insn_nodes` (the `LocalVariableTable` attribute).
+    pub local_variables: Vec<LocalVariableNode>,
 
     /// Attributes associated with the `Code` attribute (e.g., `LineNumberTable`, `LocalVariableTable`).
     pub code_attributes: Vec<AttributeInfo>,
 
     /// Other attributes associated with this method (e.g., `Exceptions`, `Synthetic`, `Deprecated`, `Signature`).
     pub attributes: Vec<AttributeInfo>,
+
+    /// The runtime visible annotations of this method.
+    pub visible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime invisible annotations of this method.
+    pub invisible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime visible parameter annotations, one list per parameter.
+    ///
+    /// This is empty when the method has no `RuntimeVisibleParameterAnnotations` attribute.
+    /// Note that the number of entries may be smaller than the number of parameters in the
+    /// descriptor (javac omits synthetic parameters).
+    pub visible_parameter_annotations: Vec<Vec<AnnotationNode>>,
+
+    /// The runtime invisible parameter annotations, one list per parameter.
+    pub invisible_parameter_annotations: Vec<Vec<AnnotationNode>>,
+
+    /// The default value of an annotation interface element (`AnnotationDefault`).
+    pub annotation_default: Option<AnnotationValue>,
+
+    /// The runtime visible type annotations on the method declaration
+    /// (type parameters and bounds, return type, receiver, formal parameters, `throws`).
+    pub visible_type_annotations: Vec<TypeAnnotationNode>,
+
+    /// The runtime invisible type annotations on the method declaration.
+    pub invisible_type_annotations: Vec<TypeAnnotationNode>,
+
+    /// The runtime visible type annotations on instructions of the method's code.
+    pub visible_insn_annotations: Vec<InsnAnnotationNode>,
+
+    /// The runtime invisible type annotations on instructions of the method's code.
+    pub invisible_insn_annotations: Vec<InsnAnnotationNode>,
+
+    /// The runtime visible type annotations on exception handlers.
+    ///
+    /// The type reference of each annotation holds the index of its handler in
+    /// `try_catch_blocks`.
+    pub visible_try_catch_annotations: Vec<TypeAnnotationNode>,
+
+    /// The runtime invisible type annotations on exception handlers.
+    pub invisible_try_catch_annotations: Vec<TypeAnnotationNode>,
+
+    /// The runtime visible type annotations on local variables.
+    pub visible_local_variable_annotations: Vec<LocalVariableAnnotationNode>,
+
+    /// The runtime invisible type annotations on local variables.
+    pub invisible_local_variable_annotations: Vec<LocalVariableAnnotationNode>,
+}
+
+impl Clone for MethodNode {
+    /// Copies the method. The instructions of the copy have new handles, which its instruction
+    /// annotations refer to.
+    fn clone(&self) -> Self {
+        let insn_nodes = self.insn_nodes.clone();
+        let handles: HashMap<NodeId, NodeId> =
+            self.insn_nodes.ids().into_iter().zip(insn_nodes.ids()).collect();
+        let insn_annotations = |annotations: &[InsnAnnotationNode]| {
+            annotations
+                .iter()
+                .map(|annotation| InsnAnnotationNode {
+                    insn: handles.get(&annotation.insn).copied().unwrap_or(annotation.insn),
+                    type_annotation: annotation.type_annotation.clone(),
+                })
+                .collect()
+        };
+        Self {
+            access_flags: self.access_flags,
+            name: self.name.clone(),
+            descriptor: self.descriptor.clone(),
+            has_code: self.has_code,
+            max_stack: self.max_stack,
+            max_locals: self.max_locals,
+            visible_insn_annotations: insn_annotations(&self.visible_insn_annotations),
+            invisible_insn_annotations: insn_annotations(&self.invisible_insn_annotations),
+            insn_nodes,
+            try_catch_blocks: self.try_catch_blocks.clone(),
+            local_variables: self.local_variables.clone(),
+            code_attributes: self.code_attributes.clone(),
+            attributes: self.attributes.clone(),
+            visible_annotations: self.visible_annotations.clone(),
+            invisible_annotations: self.invisible_annotations.clone(),
+            visible_parameter_annotations: self.visible_parameter_annotations.clone(),
+            invisible_parameter_annotations: self.invisible_parameter_annotations.clone(),
+            annotation_default: self.annotation_default.clone(),
+            visible_type_annotations: self.visible_type_annotations.clone(),
+            invisible_type_annotations: self.invisible_type_annotations.clone(),
+            visible_try_catch_annotations: self.visible_try_catch_annotations.clone(),
+            invisible_try_catch_annotations: self.invisible_try_catch_annotations.clone(),
+            visible_local_variable_annotations: self.visible_local_variable_annotations.clone(),
+            invisible_local_variable_annotations: self
+                .invisible_local_variable_annotations
+                .clone(),
+        }
+    }
+}
+
+impl MethodNode {
+    /// Makes `visitor` visit this method, ending with `visit_end`.
+    ///
+    /// `class_node` is the class declaring the method; its constant pool and bootstrap methods
+    /// resolve the symbolic references of [`insn_nodes`](Self::insn_nodes). See
+    /// [`ClassNode::accept`] for how the code is replayed.
+    pub fn accept(
+        &self,
+        class_node: &ClassNode,
+        visitor: &mut dyn MethodVisitor,
+    ) -> Result<(), ClassReadError> {
+        crate::class_reader::accept_method_parameters(
+            &class_node.constant_pool,
+            &self.attributes,
+            visitor,
+        )?;
+        if let Some(value) = &self.annotation_default {
+            visitor.visit_annotation_default(value);
+        }
+        for annotation in &self.visible_annotations {
+            visitor.visit_annotation(annotation, true);
+        }
+        for annotation in &self.invisible_annotations {
+            visitor.visit_annotation(annotation, false);
+        }
+        for annotation in &self.visible_type_annotations {
+            visitor.visit_type_annotation(annotation, true);
+        }
+        for annotation in &self.invisible_type_annotations {
+            visitor.visit_type_annotation(annotation, false);
+        }
+        class_reader::accept_parameter_annotations(
+            &self.visible_parameter_annotations,
+            true,
+            visitor,
+        );
+        class_reader::accept_parameter_annotations(
+            &self.invisible_parameter_annotations,
+            false,
+            visitor,
+        );
+        for (name, content) in self.attributes.iter().filter_map(class_reader::raw_attribute) {
+            visitor.visit_attribute(name, content);
+        }
+        if self.has_code {
+            visitor.visit_code();
+            class_reader::accept_method_code(class_node, self, visitor)?;
+            visitor.visit_maxs(self.max_stack, self.max_locals);
+        }
+        visitor.visit_end();
+        Ok(())
+    }
+}
+
+/// An annotation, decoded from one of the `Runtime*Annotations` attributes.
+///
+/// # See Also
+/// * [JVM Specification: The RuntimeVisibleAnnotations Attribute](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.16)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationNode {
+    /// The descriptor of the annotation type (e.g., `Ljavax/annotation/Nullable;`).
+    pub descriptor: String,
+
+    /// The element name / value pairs of this annotation, in class file order.
+    pub values: Vec<(String, AnnotationValue)>,
+}
+
+impl AnnotationNode {
+    pub fn new(descriptor: &str) -> Self {
+        Self {
+            descriptor: descriptor.to_string(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Appends an element value pair and returns the annotation.
+    pub fn with_value(mut self, name: &str, value: AnnotationValue) -> Self {
+        self.values.push((name.to_string(), value));
+        self
+    }
+
+    /// Returns the value of the element with the given name, if present.
+    pub fn value(&self, name: &str) -> Option<&AnnotationValue> {
+        self.values
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// The value of an annotation element (an `element_value` structure).
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotationValue {
+    Byte(i8),
+    Char(u16),
+    Double(f64),
+    Float(f32),
+    Int(i32),
+    Long(i64),
+    Short(i16),
+    Boolean(bool),
+    String(String),
+    /// An enum constant, given by the enum type descriptor and the constant name.
+    Enum { descriptor: String, value: String },
+    /// A class literal, given by its return descriptor (e.g., `Ljava/lang/String;` or `V`).
+    Class(String),
+    Annotation(AnnotationNode),
+    Array(Vec<AnnotationValue>),
+}
+
+/// A type annotation (JSR 308), decoded from one of the `Runtime*TypeAnnotations` attributes.
+///
+/// # See Also
+/// * [JVM Specification: The RuntimeVisibleTypeAnnotations Attribute](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.20)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAnnotationNode {
+    /// The reference to the annotated type.
+    pub type_ref: TypeReference,
+
+    /// The path to the annotated type argument, wildcard bound, array element type, or
+    /// nested type, or `None` if the annotation targets `type_ref` as a whole.
+    pub type_path: Option<TypePath>,
+
+    pub annotation: AnnotationNode,
+}
+
+impl TypeAnnotationNode {
+    pub fn new(
+        type_ref: TypeReference,
+        type_path: Option<TypePath>,
+        annotation: AnnotationNode,
+    ) -> Self {
+        Self {
+            type_ref,
+            type_path,
+            annotation,
+        }
+    }
+}
+
+/// A type annotation on an instruction (`instanceof`, `new`, a cast, a method reference, or a
+/// type argument of an invocation).
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsnAnnotationNode {
+    /// The handle of the annotated instruction in [`MethodNode::insn_nodes`].
+    pub insn: NodeId,
+
+    pub type_annotation: TypeAnnotationNode,
+}
+
+/// A type annotation on a local variable or resource variable.
+///
+/// The variable may live in several ranges; `start`, `end` and `index` have one entry per range.
+/// Like the scopes of [`MethodNode::local_variables`], ranges are delimited by labels of
+/// [`MethodNode::insn_nodes`], `end` being exclusive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVariableAnnotationNode {
+    pub start: Vec<LabelNode>,
+    pub end: Vec<LabelNode>,
+
+    /// The local variable slot of each range.
+    pub index: Vec<u16>,
+
+    pub type_annotation: TypeAnnotationNode,
+}
+
+/// A component of a record class.
+///
+/// # See Also
+/// * [JVM Specification: The Record Attribute](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.30)
+#[derive(Debug, Clone)]
+pub struct RecordComponentNode {
+    /// The name of the component.
+    pub name: String,
+
+    /// The field descriptor of the component (e.g., `Ljava/lang/String;` or `I`).
+    pub descriptor: String,
+
+    /// The generic signature of the component, if any (`Signature`).
+    pub signature: Option<String>,
+
+    /// Other attributes of the component.
+    pub attributes: Vec<AttributeInfo>,
+
+    /// The runtime visible annotations of this component.
+    pub visible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime invisible annotations of this component.
+    pub invisible_annotations: Vec<AnnotationNode>,
+
+    /// The runtime visible type annotations of this component.
+    pub visible_type_annotations: Vec<TypeAnnotationNode>,
+
+    /// The runtime invisible type annotations of this component.
+    pub invisible_type_annotations: Vec<TypeAnnotationNode>,
+}
+
+impl RecordComponentNode {
+    pub fn new(name: &str, descriptor: &str, signature: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+            signature: signature.map(|value| value.to_string()),
+            attributes: Vec::new(),
+            visible_annotations: Vec::new(),
+            invisible_annotations: Vec::new(),
+            visible_type_annotations: Vec::new(),
+            invisible_type_annotations: Vec::new(),
+        }
+    }
+}
+
+/// A module declaration, decoded from the `Module`, `ModulePackages` and `ModuleMainClass`
+/// attributes of a `module-info` class.
+///
+/// Package names are in internal form (e.g., `java/lang`) and class names are internal names.
+///
+/// # See Also
+/// * [JVM Specification: The Module Attribute](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.25)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModuleNode {
+    /// The fully qualified name of the module (e.g., `java.base`).
+    pub name: String,
+
+    /// The module flags (`ACC_OPEN`, `ACC_SYNTHETIC`, `ACC_MANDATED`).
+    pub access_flags: u16,
+
+    pub version: Option<String>,
+
+    /// The main class of the module (`ModuleMainClass`).
+    pub main_class: Option<String>,
+
+    /// All packages of the module (`ModulePackages`).
+    pub packages: Vec<String>,
+
+    pub requires: Vec<ModuleRequireNode>,
+    pub exports: Vec<ModuleExportNode>,
+    pub opens: Vec<ModuleOpenNode>,
+
+    /// The services used by the module.
+    pub uses: Vec<String>,
+
+    pub provides: Vec<ModuleProvideNode>,
+}
+
+impl ModuleNode {
+    pub fn new(name: &str, access_flags: u16, version: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            access_flags,
+            version: version.map(|value| value.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// A module dependency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleRequireNode {
+    pub module: String,
+
+    /// The dependency flags (`ACC_TRANSITIVE`, `ACC_STATIC_PHASE`, `ACC_SYNTHETIC`, `ACC_MANDATED`).
+    pub access_flags: u16,
+
+    pub version: Option<String>,
+}
+
+/// An exported package, optionally qualified to a set of modules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleExportNode {
+    pub package: String,
+    pub access_flags: u16,
+
+    /// The modules the package is exported to, or empty for an unqualified export.
+    pub modules: Vec<String>,
+}
+
+/// A package opened for deep reflection, optionally qualified to a set of modules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleOpenNode {
+    pub package: String,
+    pub access_flags: u16,
+
+    /// The modules the package is opened to, or empty for an unqualified open.
+    pub modules: Vec<String>,
+}
+
+/// A service implementation provided by the module.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleProvideNode {
+    /// The internal name of the service interface.
+    pub service: String,
+
+    /// The internal names of the implementation classes.
+    pub providers: Vec<String>,
 }
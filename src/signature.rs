@@ -0,0 +1,934 @@
+use std::fmt;
+
+use crate::error::SignatureError;
+use crate::types::MAX_ARRAY_DIMENSIONS;
+
+/// Wildcard indicator of a type argument with an upper bound (`? extends T`).
+pub const EXTENDS: char = '+';
+/// Wildcard indicator of a type argument with a lower bound (`? super T`).
+pub const SUPER: char = '-';
+/// Wildcard indicator of a type argument without wildcard (`T`).
+pub const INSTANCEOF: char = '=';
+
+/// A visitor to visit a generic signature.
+///
+/// The methods of this trait must be called in the following order, where each
+/// `visit_class_bound`, `visit_interface_bound`, `visit_super_class`, `visit_interface`,
+/// `visit_parameter_type`, `visit_return_type`, `visit_exception_type`, `visit_array_type` and
+/// `visit_type_argument_wildcard` is followed by the events of the type it introduces:
+/// * class signatures: (`visit_formal_type_parameter` -> `visit_class_bound`? ->
+///   `visit_interface_bound`*)* -> `visit_super_class` -> `visit_interface`*
+/// * method signatures: (`visit_formal_type_parameter` -> `visit_class_bound`? ->
+///   `visit_interface_bound`*)* -> `visit_parameter_type`* -> `visit_return_type` ->
+///   `visit_exception_type`*
+/// * type signatures: `visit_base_type` | `visit_type_variable` | `visit_array_type` |
+///   (`visit_class_type` -> `visit_type_argument`* ->
+///   (`visit_inner_class_type` -> `visit_type_argument`*)* -> `visit_end`)
+///
+/// Unlike ASM, the methods that introduce a type don't return a new visitor: the events of the
+/// type are sent to the same visitor. Every method forwards to [`SignatureVisitor::delegate`] by
+/// default, so an adapter that wraps a [`SignatureWriter`] only implements the events it changes
+/// (for instance `visit_class_type` and `visit_inner_class_type` to rename classes).
+///
+/// # See Also
+/// * [JVM Specification: Signatures](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1)
+pub trait SignatureVisitor {
+    /// Returns the signature visitor to which events are forwarded by default, if any.
+    fn delegate(&mut self) -> Option<&mut dyn SignatureVisitor> {
+        None
+    }
+
+    /// Visits a formal type parameter, by name.
+    fn visit_formal_type_parameter(&mut self, name: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_formal_type_parameter(name);
+        }
+    }
+
+    /// Visits the class bound of the last visited formal type parameter.
+    fn visit_class_bound(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_class_bound();
+        }
+    }
+
+    /// Visits an interface bound of the last visited formal type parameter.
+    fn visit_interface_bound(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_interface_bound();
+        }
+    }
+
+    /// Visits the super class of a class signature.
+    fn visit_super_class(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_super_class();
+        }
+    }
+
+    /// Visits an interface implemented by a class signature.
+    fn visit_interface(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_interface();
+        }
+    }
+
+    /// Visits the type of a parameter of a method signature.
+    fn visit_parameter_type(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_parameter_type();
+        }
+    }
+
+    /// Visits the return type of a method signature.
+    fn visit_return_type(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_return_type();
+        }
+    }
+
+    /// Visits the type of an exception thrown by a method signature.
+    fn visit_exception_type(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_exception_type();
+        }
+    }
+
+    /// Visits a primitive type or `void`, as its descriptor character (e.g., `'I'`, `'V'`).
+    fn visit_base_type(&mut self, descriptor: char) {
+        if let Some(next) = self.delegate() {
+            next.visit_base_type(descriptor);
+        }
+    }
+
+    /// Visits a type variable, by name.
+    fn visit_type_variable(&mut self, name: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_variable(name);
+        }
+    }
+
+    /// Visits an array type. The events of its element type follow.
+    fn visit_array_type(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_array_type();
+        }
+    }
+
+    /// Starts the visit of a class or interface type, given its internal name.
+    fn visit_class_type(&mut self, name: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_class_type(name);
+        }
+    }
+
+    /// Visits a nested class type of the last visited class type, given its simple name.
+    fn visit_inner_class_type(&mut self, name: &str) {
+        if let Some(next) = self.delegate() {
+            next.visit_inner_class_type(name);
+        }
+    }
+
+    /// Visits an unbounded wildcard type argument (`*`) of the last visited class type.
+    fn visit_type_argument(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_argument();
+        }
+    }
+
+    /// Visits a type argument of the last visited class type. `wildcard` is [`EXTENDS`],
+    /// [`SUPER`] or [`INSTANCEOF`], and the events of the argument's type follow.
+    fn visit_type_argument_wildcard(&mut self, wildcard: char) {
+        if let Some(next) = self.delegate() {
+            next.visit_type_argument_wildcard(wildcard);
+        }
+    }
+
+    /// Ends the visit of a class type.
+    fn visit_end(&mut self) {
+        if let Some(next) = self.delegate() {
+            next.visit_end();
+        }
+    }
+}
+
+/// Parses a generic signature and makes a [`SignatureVisitor`] visit it.
+pub struct SignatureReader<'a> {
+    signature: &'a str,
+}
+
+impl<'a> SignatureReader<'a> {
+    pub fn new(signature: &'a str) -> Self {
+        Self { signature }
+    }
+
+    /// Makes `visitor` visit this class or method signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SignatureError`] if the signature is malformed, before any event is sent.
+    pub fn accept(&self, visitor: &mut dyn SignatureVisitor) -> Result<(), SignatureError> {
+        let mut parser = Parser::new(self.signature);
+        let type_parameters = parser.type_parameters()?;
+        if parser.peek() == Some(b'(') {
+            let signature = parser.method_signature(type_parameters)?;
+            parser.finish()?;
+            signature.accept(visitor);
+        } else {
+            let signature = parser.class_signature(type_parameters)?;
+            parser.finish()?;
+            signature.accept(visitor);
+        }
+        Ok(())
+    }
+
+    /// Makes `visitor` visit this type signature, as found in the `Signature` attribute of a
+    /// field or in a `LocalVariableTypeTable`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SignatureError`] if the signature is malformed, before any event is sent.
+    pub fn accept_type(&self, visitor: &mut dyn SignatureVisitor) -> Result<(), SignatureError> {
+        TypeSignature::parse(self.signature)?.accept(visitor);
+        Ok(())
+    }
+}
+
+/// A [`SignatureVisitor`] that builds the string form of the visited signature.
+#[derive(Debug, Default)]
+pub struct SignatureWriter {
+    signature: String,
+    has_formals: bool,
+    has_parameters: bool,
+    /// Whether each class type being visited, innermost last, has its `<` written.
+    argument_stack: Vec<bool>,
+}
+
+impl SignatureWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the signature visited so far.
+    pub fn as_str(&self) -> &str {
+        &self.signature
+    }
+
+    fn end_formals(&mut self) {
+        if self.has_formals {
+            self.has_formals = false;
+            self.signature.push('>');
+        }
+    }
+
+    fn start_argument(&mut self) {
+        if let Some(has_arguments) = self.argument_stack.last_mut()
+            && !*has_arguments
+        {
+            *has_arguments = true;
+            self.signature.push('<');
+        }
+    }
+
+    fn end_arguments(&mut self) {
+        if self.argument_stack.pop() == Some(true) {
+            self.signature.push('>');
+        }
+    }
+}
+
+impl SignatureVisitor for SignatureWriter {
+    fn visit_formal_type_parameter(&mut self, name: &str) {
+        if !self.has_formals {
+            self.has_formals = true;
+            self.signature.push('<');
+        }
+        self.signature.push_str(name);
+        self.signature.push(':');
+    }
+
+    fn visit_class_bound(&mut self) {}
+
+    fn visit_interface_bound(&mut self) {
+        self.signature.push(':');
+    }
+
+    fn visit_super_class(&mut self) {
+        self.end_formals();
+    }
+
+    fn visit_interface(&mut self) {}
+
+    fn visit_parameter_type(&mut self) {
+        self.end_formals();
+        if !self.has_parameters {
+            self.has_parameters = true;
+            self.signature.push('(');
+        }
+    }
+
+    fn visit_return_type(&mut self) {
+        self.end_formals();
+        if !self.has_parameters {
+            self.signature.push('(');
+        }
+        self.signature.push(')');
+    }
+
+    fn visit_exception_type(&mut self) {
+        self.signature.push('^');
+    }
+
+    fn visit_base_type(&mut self, descriptor: char) {
+        self.signature.push(descriptor);
+    }
+
+    fn visit_type_variable(&mut self, name: &str) {
+        self.signature.push('T');
+        self.signature.push_str(name);
+        self.signature.push(';');
+    }
+
+    fn visit_array_type(&mut self) {
+        self.signature.push('[');
+    }
+
+    fn visit_class_type(&mut self, name: &str) {
+        self.signature.push('L');
+        self.signature.push_str(name);
+        self.argument_stack.push(false);
+    }
+
+    fn visit_inner_class_type(&mut self, name: &str) {
+        self.end_arguments();
+        self.signature.push('.');
+        self.signature.push_str(name);
+        self.argument_stack.push(false);
+    }
+
+    fn visit_type_argument(&mut self) {
+        self.start_argument();
+        self.signature.push('*');
+    }
+
+    fn visit_type_argument_wildcard(&mut self, wildcard: char) {
+        self.start_argument();
+        if wildcard != INSTANCEOF {
+            self.signature.push(wildcard);
+        }
+    }
+
+    fn visit_end(&mut self) {
+        self.end_arguments();
+        self.signature.push(';');
+    }
+}
+
+impl fmt::Display for SignatureWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.signature)
+    }
+}
+
+/// A Java type in a generic signature.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeSignature {
+    /// A primitive type or `void`, as its descriptor character (e.g., `'I'`, `'V'`).
+    Base(char),
+    /// A type variable, by name (e.g., `T`).
+    TypeVariable(String),
+    /// An array type, with its element type.
+    Array(Box<TypeSignature>),
+    /// A class or interface type.
+    Class(ClassTypeSignature),
+}
+
+/// A class or interface type in a generic signature (e.g., `Ljava/util/Map<TK;TV;>.Entry;`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassTypeSignature {
+    /// The internal name of the outermost class (e.g., `java/util/Map`).
+    pub name: String,
+    pub type_arguments: Vec<TypeArgument>,
+    /// The nested classes selected from the outermost class, outermost first.
+    pub inner_classes: Vec<InnerClassTypeSignature>,
+}
+
+/// A nested class type selected from an enclosing class type, with its own type arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InnerClassTypeSignature {
+    /// The simple name of the nested class (e.g., `Entry`).
+    pub name: String,
+    pub type_arguments: Vec<TypeArgument>,
+}
+
+/// A type argument of a class type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypeArgument {
+    /// `?`
+    Unbounded,
+    /// `? extends T`
+    Extends(TypeSignature),
+    /// `? super T`
+    Super(TypeSignature),
+    /// `T`
+    Exact(TypeSignature),
+}
+
+/// A formal type parameter of a generic class or method (e.g., `T:Ljava/lang/Object;`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormalTypeParameter {
+    pub name: String,
+    /// The class bound, if any. Parameters bounded only by interfaces have none.
+    pub class_bound: Option<TypeSignature>,
+    pub interface_bounds: Vec<TypeSignature>,
+}
+
+/// The generic signature of a class (the `Signature` attribute of a `ClassFile`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassSignature {
+    pub type_parameters: Vec<FormalTypeParameter>,
+    pub super_class: ClassTypeSignature,
+    pub interfaces: Vec<ClassTypeSignature>,
+}
+
+/// The generic signature of a method (the `Signature` attribute of a `method_info`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodSignature {
+    pub type_parameters: Vec<FormalTypeParameter>,
+    pub parameter_types: Vec<TypeSignature>,
+    /// The return type, `TypeSignature::Base('V')` for `void`.
+    pub return_type: TypeSignature,
+    /// The thrown class types and type variables.
+    pub exception_types: Vec<TypeSignature>,
+}
+
+impl TypeSignature {
+    /// Parses a type signature, such as the `Signature` of a field.
+    pub fn parse(signature: &str) -> Result<Self, SignatureError> {
+        let mut parser = Parser::new(signature);
+        let ty = parser.type_signature(false)?;
+        parser.finish()?;
+        Ok(ty)
+    }
+
+    /// Makes `visitor` visit this type.
+    pub fn accept(&self, visitor: &mut dyn SignatureVisitor) {
+        match self {
+            TypeSignature::Base(descriptor) => visitor.visit_base_type(*descriptor),
+            TypeSignature::TypeVariable(name) => visitor.visit_type_variable(name),
+            TypeSignature::Array(element) => {
+                visitor.visit_array_type();
+                element.accept(visitor);
+            }
+            TypeSignature::Class(class_type) => class_type.accept(visitor),
+        }
+    }
+}
+
+impl ClassTypeSignature {
+    /// Makes `visitor` visit this class type.
+    pub fn accept(&self, visitor: &mut dyn SignatureVisitor) {
+        visitor.visit_class_type(&self.name);
+        accept_type_arguments(&self.type_arguments, visitor);
+        for inner_class in &self.inner_classes {
+            visitor.visit_inner_class_type(&inner_class.name);
+            accept_type_arguments(&inner_class.type_arguments, visitor);
+        }
+        visitor.visit_end();
+    }
+}
+
+impl ClassSignature {
+    /// Parses the generic signature of a class.
+    pub fn parse(signature: &str) -> Result<Self, SignatureError> {
+        let mut parser = Parser::new(signature);
+        let type_parameters = parser.type_parameters()?;
+        let class_signature = parser.class_signature(type_parameters)?;
+        parser.finish()?;
+        Ok(class_signature)
+    }
+
+    /// Makes `visitor` visit this class signature.
+    pub fn accept(&self, visitor: &mut dyn SignatureVisitor) {
+        accept_type_parameters(&self.type_parameters, visitor);
+        visitor.visit_super_class();
+        self.super_class.accept(visitor);
+        for interface in &self.interfaces {
+            visitor.visit_interface();
+            interface.accept(visitor);
+        }
+    }
+}
+
+impl MethodSignature {
+    /// Parses the generic signature of a method.
+    pub fn parse(signature: &str) -> Result<Self, SignatureError> {
+        let mut parser = Parser::new(signature);
+        let type_parameters = parser.type_parameters()?;
+        let method_signature = parser.method_signature(type_parameters)?;
+        parser.finish()?;
+        Ok(method_signature)
+    }
+
+    /// Makes `visitor` visit this method signature.
+    pub fn accept(&self, visitor: &mut dyn SignatureVisitor) {
+        accept_type_parameters(&self.type_parameters, visitor);
+        for parameter_type in &self.parameter_types {
+            visitor.visit_parameter_type();
+            parameter_type.accept(visitor);
+        }
+        visitor.visit_return_type();
+        self.return_type.accept(visitor);
+        for exception_type in &self.exception_types {
+            visitor.visit_exception_type();
+            exception_type.accept(visitor);
+        }
+    }
+}
+
+fn accept_type_parameters(
+    type_parameters: &[FormalTypeParameter],
+    visitor: &mut dyn SignatureVisitor,
+) {
+    for type_parameter in type_parameters {
+        visitor.visit_formal_type_parameter(&type_parameter.name);
+        if let Some(class_bound) = &type_parameter.class_bound {
+            visitor.visit_class_bound();
+            class_bound.accept(visitor);
+        }
+        for interface_bound in &type_parameter.interface_bounds {
+            visitor.visit_interface_bound();
+            interface_bound.accept(visitor);
+        }
+    }
+}
+
+fn accept_type_arguments(type_arguments: &[TypeArgument], visitor: &mut dyn SignatureVisitor) {
+    for type_argument in type_arguments {
+        let (wildcard, ty) = match type_argument {
+            TypeArgument::Unbounded => {
+                visitor.visit_type_argument();
+                continue;
+            }
+            TypeArgument::Extends(ty) => (EXTENDS, ty),
+            TypeArgument::Super(ty) => (SUPER, ty),
+            TypeArgument::Exact(ty) => (INSTANCEOF, ty),
+        };
+        visitor.visit_type_argument_wildcard(wildcard);
+        ty.accept(visitor);
+    }
+}
+
+macro_rules! impl_display_with_writer {
+    ($($ty:ty),*) => {
+        $(
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let mut writer = SignatureWriter::new();
+                    self.accept(&mut writer);
+                    f.write_str(writer.as_str())
+                }
+            }
+        )*
+    };
+}
+
+impl_display_with_writer!(TypeSignature, ClassTypeSignature, ClassSignature, MethodSignature);
+
+/// The maximum nesting depth of a type signature, counting both array dimensions and type
+/// argument lists. This bounds the recursion of the parser and of everything that walks the
+/// parsed tree.
+const MAX_NESTING_DEPTH: usize = 256;
+
+/// A recursive descent parser over the grammar of JVMS 4.7.9.1.
+struct Parser<'a> {
+    signature: &'a str,
+    pos: usize,
+    /// The number of enclosing array dimensions and type argument lists.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(signature: &'a str) -> Self {
+        Self {
+            signature,
+            pos: 0,
+            depth: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.signature.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), SignatureError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(_) => Err(self.invalid_character()),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    fn finish(&self) -> Result<(), SignatureError> {
+        match self.peek() {
+            Some(_) => Err(self.invalid_character()),
+            None => Ok(()),
+        }
+    }
+
+    fn unexpected_end(&self) -> SignatureError {
+        SignatureError::UnexpectedEnd(self.signature.to_string())
+    }
+
+    fn invalid_character(&self) -> SignatureError {
+        SignatureError::InvalidCharacter {
+            signature: self.signature.to_string(),
+            position: self.pos,
+            character: self.signature[self.pos..].chars().next().unwrap_or_default(),
+        }
+    }
+
+    fn too_deeply_nested(&self, position: usize) -> SignatureError {
+        SignatureError::TooDeeplyNested {
+            signature: self.signature.to_string(),
+            position,
+        }
+    }
+
+    /// Reads a non-empty name, up to (but excluding) one of the `terminators`.
+    fn name(&mut self, terminators: &[u8]) -> Result<&'a str, SignatureError> {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                None => return Err(self.unexpected_end()),
+                Some(c) if terminators.contains(&c) => break,
+                Some(b'.' | b';' | b'[' | b'<' | b'>' | b':') => {
+                    return Err(self.invalid_character());
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+        if self.pos == start {
+            return Err(self.invalid_character());
+        }
+        Ok(&self.signature[start..self.pos])
+    }
+
+    fn type_parameters(&mut self) -> Result<Vec<FormalTypeParameter>, SignatureError> {
+        let mut type_parameters = Vec::new();
+        if self.peek() != Some(b'<') {
+            return Ok(type_parameters);
+        }
+        self.pos += 1;
+        loop {
+            let name = self.name(b":")?.to_string();
+            self.pos += 1; // skip ':'
+            let class_bound = match self.peek() {
+                Some(b'L' | b'T' | b'[') => Some(self.reference_type()?),
+                _ => None,
+            };
+            let mut interface_bounds = Vec::new();
+            while self.peek() == Some(b':') {
+                self.pos += 1;
+                interface_bounds.push(self.reference_type()?);
+            }
+            type_parameters.push(FormalTypeParameter {
+                name,
+                class_bound,
+                interface_bounds,
+            });
+            if self.peek() == Some(b'>') {
+                self.pos += 1;
+                return Ok(type_parameters);
+            }
+        }
+    }
+
+    fn class_signature(
+        &mut self,
+        type_parameters: Vec<FormalTypeParameter>,
+    ) -> Result<ClassSignature, SignatureError> {
+        let super_class = self.class_type()?;
+        let mut interfaces = Vec::new();
+        while self.peek().is_some() {
+            interfaces.push(self.class_type()?);
+        }
+        Ok(ClassSignature {
+            type_parameters,
+            super_class,
+            interfaces,
+        })
+    }
+
+    fn method_signature(
+        &mut self,
+        type_parameters: Vec<FormalTypeParameter>,
+    ) -> Result<MethodSignature, SignatureError> {
+        self.expect(b'(')?;
+        let mut parameter_types = Vec::new();
+        while self.peek() != Some(b')') {
+            parameter_types.push(self.type_signature(false)?);
+        }
+        self.pos += 1; // skip ')'
+        let return_type = self.type_signature(true)?;
+        let mut exception_types = Vec::new();
+        while self.peek() == Some(b'^') {
+            self.pos += 1;
+            exception_types.push(match self.peek() {
+                Some(b'L' | b'T') => self.reference_type()?,
+                Some(_) => return Err(self.invalid_character()),
+                None => return Err(self.unexpected_end()),
+            });
+        }
+        Ok(MethodSignature {
+            type_parameters,
+            parameter_types,
+            return_type,
+            exception_types,
+        })
+    }
+
+    fn reference_type(&mut self) -> Result<TypeSignature, SignatureError> {
+        match self.peek() {
+            Some(b'L' | b'T' | b'[') => self.type_signature(false),
+            Some(_) => Err(self.invalid_character()),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    fn type_signature(&mut self, allow_void: bool) -> Result<TypeSignature, SignatureError> {
+        let Some(c) = self.peek() else {
+            return Err(self.unexpected_end());
+        };
+        match c {
+            b'Z' | b'C' | b'B' | b'S' | b'I' | b'F' | b'J' | b'D' => {
+                self.pos += 1;
+                Ok(TypeSignature::Base(c as char))
+            }
+            b'V' if allow_void => {
+                self.pos += 1;
+                Ok(TypeSignature::Base('V'))
+            }
+            b'T' => {
+                self.pos += 1;
+                let name = self.name(b";")?.to_string();
+                self.pos += 1; // skip ';'
+                Ok(TypeSignature::TypeVariable(name))
+            }
+            b'[' => {
+                // The dimensions are counted rather than parsed recursively, so that a long run
+                // of '[' cannot overflow the stack.
+                let start = self.pos;
+                while self.peek() == Some(b'[') {
+                    self.pos += 1;
+                }
+                let dimensions = self.pos - start;
+                if dimensions > MAX_ARRAY_DIMENSIONS {
+                    return Err(SignatureError::TooManyDimensions {
+                        signature: self.signature.to_string(),
+                        position: start,
+                    });
+                }
+                if self.depth + dimensions > MAX_NESTING_DEPTH {
+                    return Err(self.too_deeply_nested(start));
+                }
+                self.depth += dimensions;
+                let mut ty = self.type_signature(false)?;
+                self.depth -= dimensions;
+                for _ in 0..dimensions {
+                    ty = TypeSignature::Array(Box::new(ty));
+                }
+                Ok(ty)
+            }
+            b'L' => Ok(TypeSignature::Class(self.class_type()?)),
+            _ => Err(self.invalid_character()),
+        }
+    }
+
+    fn class_type(&mut self) -> Result<ClassTypeSignature, SignatureError> {
+        self.expect(b'L')?;
+        let name = self.name(b"<.;")?.to_string();
+        let type_arguments = self.type_arguments()?;
+        let mut inner_classes = Vec::new();
+        while self.peek() == Some(b'.') {
+            self.pos += 1;
+            let name = self.name(b"<.;")?.to_string();
+            let type_arguments = self.type_arguments()?;
+            inner_classes.push(InnerClassTypeSignature {
+                name,
+                type_arguments,
+            });
+        }
+        self.expect(b';')?;
+        Ok(ClassTypeSignature {
+            name,
+            type_arguments,
+            inner_classes,
+        })
+    }
+
+    fn type_arguments(&mut self) -> Result<Vec<TypeArgument>, SignatureError> {
+        let mut type_arguments = Vec::new();
+        if self.peek() != Some(b'<') {
+            return Ok(type_arguments);
+        }
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(self.too_deeply_nested(self.pos));
+        }
+        self.pos += 1;
+        self.depth += 1;
+        loop {
+            let type_argument = match self.peek() {
+                Some(b'*') => {
+                    self.pos += 1;
+                    TypeArgument::Unbounded
+                }
+                Some(b'+') => {
+                    self.pos += 1;
+                    TypeArgument::Extends(self.reference_type()?)
+                }
+                Some(b'-') => {
+                    self.pos += 1;
+                    TypeArgument::Super(self.reference_type()?)
+                }
+                _ => TypeArgument::Exact(self.reference_type()?),
+            };
+            type_arguments.push(type_argument);
+            if self.peek() == Some(b'>') {
+                self.pos += 1;
+                self.depth -= 1;
+                return Ok(type_arguments);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURES: &[&str] = &[
+        "<T:Ljava/lang/Object;>(Ljava/util/List<TT;>;)TT;",
+        "<K::Ljava/lang/Comparable<-TK;>;V:Ljava/lang/Object;>Ljava/util/AbstractMap<TK;TV;>;\
+         Ljava/io/Serializable;",
+        "<E:Ljava/lang/Exception;>([[ILjava/util/Map<*+Ljava/lang/Number;>.Entry<TE;>;)V^TE;\
+         ^Ljava/io/IOException;",
+        "Lpkg/Outer.Inner<Ljava/lang/String;>;",
+    ];
+
+    /// Renames every class type, leaving nested class names as they are.
+    struct Renamer {
+        writer: SignatureWriter,
+    }
+
+    impl SignatureVisitor for Renamer {
+        fn delegate(&mut self) -> Option<&mut dyn SignatureVisitor> {
+            Some(&mut self.writer)
+        }
+
+        fn visit_class_type(&mut self, name: &str) {
+            self.writer.visit_class_type(&name.replace("java/util/", "shaded/util/"));
+        }
+    }
+
+    #[test]
+    fn test_signature_round_trip() {
+        for signature in SIGNATURES {
+            let mut writer = SignatureWriter::new();
+            let reader = SignatureReader::new(signature);
+            if signature.starts_with('L') {
+                reader.accept_type(&mut writer).expect("Should read type signature");
+                assert_eq!(TypeSignature::parse(signature).unwrap().to_string(), *signature);
+            } else {
+                reader.accept(&mut writer).expect("Should read signature");
+            }
+            assert_eq!(writer.as_str(), *signature);
+        }
+
+        let method = MethodSignature::parse(SIGNATURES[0]).expect("Should parse method");
+        assert_eq!(method.type_parameters[0].name, "T");
+        assert_eq!(
+            method.return_type,
+            TypeSignature::TypeVariable("T".to_string())
+        );
+        assert_eq!(method.to_string(), SIGNATURES[0]);
+
+        let class = ClassSignature::parse(SIGNATURES[1]).expect("Should parse class");
+        assert!(class.type_parameters[0].class_bound.is_none());
+        assert_eq!(class.type_parameters[0].interface_bounds.len(), 1);
+        assert_eq!(class.super_class.name, "java/util/AbstractMap");
+        assert_eq!(class.interfaces.len(), 1);
+        assert_eq!(class.to_string(), SIGNATURES[1]);
+
+        let method = MethodSignature::parse(SIGNATURES[2]).expect("Should parse method");
+        let TypeSignature::Class(map_entry) = &method.parameter_types[1] else {
+            panic!("Should be a class type");
+        };
+        assert_eq!(map_entry.type_arguments[0], TypeArgument::Unbounded);
+        assert!(matches!(map_entry.type_arguments[1], TypeArgument::Extends(_)));
+        assert_eq!(map_entry.inner_classes[0].name, "Entry");
+        assert_eq!(method.exception_types.len(), 2);
+    }
+
+    #[test]
+    fn test_signature_remapping() {
+        let mut renamer = Renamer {
+            writer: SignatureWriter::new(),
+        };
+        SignatureReader::new(SIGNATURES[2])
+            .accept(&mut renamer)
+            .expect("Should read signature");
+        assert_eq!(
+            renamer.writer.as_str(),
+            "<E:Ljava/lang/Exception;>([[ILshaded/util/Map<*+Ljava/lang/Number;>.Entry<TE;>;)V\
+             ^TE;^Ljava/io/IOException;"
+        );
+    }
+
+    #[test]
+    fn test_malformed_signatures() {
+        assert_eq!(
+            TypeSignature::parse("Ljava/util/List<TT;>"),
+            Err(SignatureError::UnexpectedEnd(
+                "Ljava/util/List<TT;>".to_string()
+            ))
+        );
+        assert!(matches!(
+            MethodSignature::parse("(V)V"),
+            Err(SignatureError::InvalidCharacter {
+                position: 1,
+                character: 'V',
+                ..
+            })
+        ));
+        assert!(matches!(
+            ClassSignature::parse("<>Ljava/lang/Object;"),
+            Err(SignatureError::InvalidCharacter {
+                position: 1,
+                character: '>',
+                ..
+            })
+        ));
+        assert!(matches!(
+            TypeSignature::parse("TT;I"),
+            Err(SignatureError::InvalidCharacter { position: 3, .. })
+        ));
+        assert!(matches!(
+            TypeSignature::parse(&format!("{}I", "[".repeat(60000))),
+            Err(SignatureError::TooManyDimensions { position: 0, .. })
+        ));
+        assert!(matches!(
+            TypeSignature::parse(&"LA<".repeat(15000)),
+            Err(SignatureError::TooDeeplyNested { .. })
+        ));
+        let nested = format!(
+            "{}TT;{}",
+            "LA<".repeat(MAX_NESTING_DEPTH),
+            ">;".repeat(MAX_NESTING_DEPTH)
+        );
+        assert!(TypeSignature::parse(&nested).is_ok());
+    }
+}
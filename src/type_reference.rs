@@ -0,0 +1,232 @@
+use std::fmt;
+
+/// A reference to a type appearing in a class, field or method declaration, or on an instruction.
+///
+/// The value packs the target type (the "sort") into the most significant byte, followed by the
+/// target info, as in ASM. Type annotations use it to locate the annotated type.
+///
+/// # See Also
+/// * [JVM Specification: The target_type and target_info items](https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.20.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeReference {
+    value: u32,
+}
+
+impl TypeReference {
+    pub const CLASS_TYPE_PARAMETER: u8 = 0x00;
+    pub const METHOD_TYPE_PARAMETER: u8 = 0x01;
+    pub const CLASS_EXTENDS: u8 = 0x10;
+    pub const CLASS_TYPE_PARAMETER_BOUND: u8 = 0x11;
+    pub const METHOD_TYPE_PARAMETER_BOUND: u8 = 0x12;
+    pub const FIELD: u8 = 0x13;
+    pub const METHOD_RETURN: u8 = 0x14;
+    pub const METHOD_RECEIVER: u8 = 0x15;
+    pub const METHOD_FORMAL_PARAMETER: u8 = 0x16;
+    pub const THROWS: u8 = 0x17;
+    pub const LOCAL_VARIABLE: u8 = 0x40;
+    pub const RESOURCE_VARIABLE: u8 = 0x41;
+    pub const EXCEPTION_PARAMETER: u8 = 0x42;
+    pub const INSTANCEOF: u8 = 0x43;
+    pub const NEW: u8 = 0x44;
+    pub const CONSTRUCTOR_REFERENCE: u8 = 0x45;
+    pub const METHOD_REFERENCE: u8 = 0x46;
+    pub const CAST: u8 = 0x47;
+    pub const CONSTRUCTOR_INVOCATION_TYPE_ARGUMENT: u8 = 0x48;
+    pub const METHOD_INVOCATION_TYPE_ARGUMENT: u8 = 0x49;
+    pub const CONSTRUCTOR_REFERENCE_TYPE_ARGUMENT: u8 = 0x4A;
+    pub const METHOD_REFERENCE_TYPE_ARGUMENT: u8 = 0x4B;
+
+    /// Creates a `TypeReference` from its packed value.
+    pub fn new(value: u32) -> Self {
+        Self { value }
+    }
+
+    /// Returns a reference with the given sort and no target info
+    /// (`FIELD`, `METHOD_RETURN`, `METHOD_RECEIVER`, `LOCAL_VARIABLE`, `RESOURCE_VARIABLE`,
+    /// `INSTANCEOF`, `NEW`, `CONSTRUCTOR_REFERENCE` or `METHOD_REFERENCE`).
+    pub fn new_type_reference(sort: u8) -> Self {
+        Self::new((sort as u32) << 24)
+    }
+
+    /// Returns a reference to a type parameter of a generic class or method.
+    pub fn new_type_parameter_reference(sort: u8, param_index: u8) -> Self {
+        Self::new(((sort as u32) << 24) | ((param_index as u32) << 16))
+    }
+
+    /// Returns a reference to a bound of a type parameter of a generic class or method.
+    pub fn new_type_parameter_bound_reference(sort: u8, param_index: u8, bound_index: u8) -> Self {
+        Self::new(
+            ((sort as u32) << 24) | ((param_index as u32) << 16) | ((bound_index as u32) << 8),
+        )
+    }
+
+    /// Returns a reference to the super class (`-1`) or to an interface of a class.
+    pub fn new_super_type_reference(itf_index: i32) -> Self {
+        Self::new(((Self::CLASS_EXTENDS as u32) << 24) | (((itf_index as u32) & 0xFFFF) << 8))
+    }
+
+    /// Returns a reference to the type of a formal parameter of a method.
+    pub fn new_formal_parameter_reference(param_index: u8) -> Self {
+        Self::new(((Self::METHOD_FORMAL_PARAMETER as u32) << 24) | ((param_index as u32) << 16))
+    }
+
+    /// Returns a reference to the type of an exception in the `throws` clause of a method.
+    pub fn new_exception_reference(exception_index: u16) -> Self {
+        Self::new(((Self::THROWS as u32) << 24) | ((exception_index as u32) << 8))
+    }
+
+    /// Returns a reference to the type of the exception declared in a `catch` clause.
+    ///
+    /// `try_catch_block_index` is an index into the method's exception table.
+    pub fn new_try_catch_reference(try_catch_block_index: u16) -> Self {
+        Self::new(
+            ((Self::EXCEPTION_PARAMETER as u32) << 24) | ((try_catch_block_index as u32) << 8),
+        )
+    }
+
+    /// Returns a reference to the type of a type argument in a cast, constructor or method
+    /// invocation, or method reference.
+    pub fn new_type_argument_reference(sort: u8, arg_index: u8) -> Self {
+        Self::new(((sort as u32) << 24) | arg_index as u32)
+    }
+
+    /// Returns the sort of this reference (one of the constants of this type).
+    pub fn sort(&self) -> u8 {
+        (self.value >> 24) as u8
+    }
+
+    /// Returns the index of the referenced type parameter.
+    ///
+    /// Only meaningful for `CLASS_TYPE_PARAMETER`, `METHOD_TYPE_PARAMETER`,
+    /// `CLASS_TYPE_PARAMETER_BOUND` and `METHOD_TYPE_PARAMETER_BOUND` references.
+    pub fn type_parameter_index(&self) -> u8 {
+        (self.value >> 16) as u8
+    }
+
+    /// Returns the index of the referenced type parameter bound.
+    pub fn type_parameter_bound_index(&self) -> u8 {
+        (self.value >> 8) as u8
+    }
+
+    /// Returns the index of the referenced interface, or `-1` for the super class.
+    pub fn super_type_index(&self) -> i32 {
+        (self.value >> 8) as u16 as i16 as i32
+    }
+
+    /// Returns the index of the referenced formal parameter.
+    pub fn formal_parameter_index(&self) -> u8 {
+        (self.value >> 16) as u8
+    }
+
+    /// Returns the index of the referenced exception in the `throws` clause.
+    pub fn exception_index(&self) -> u16 {
+        (self.value >> 8) as u16
+    }
+
+    /// Returns the index of the referenced entry in the exception table.
+    pub fn try_catch_block_index(&self) -> u16 {
+        (self.value >> 8) as u16
+    }
+
+    /// Returns the index of the referenced type argument.
+    pub fn type_argument_index(&self) -> u8 {
+        self.value as u8
+    }
+
+    /// Returns the packed value of this reference.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// The path to a type argument, wildcard bound, array element type, or nested type within
+/// an annotated type.
+///
+/// Each step is a `(kind, argument)` pair, where `argument` is only meaningful for
+/// [`TypePath::TYPE_ARGUMENT`] steps.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TypePath {
+    steps: Vec<(u8, u8)>,
+}
+
+impl TypePath {
+    /// A step into the element type of an array type.
+    pub const ARRAY_ELEMENT: u8 = 0;
+    /// A step into the nested type of a class type.
+    pub const INNER_TYPE: u8 = 1;
+    /// A step into the bound of a wildcard type.
+    pub const WILDCARD_BOUND: u8 = 2;
+    /// A step into a type argument of a generic type.
+    pub const TYPE_ARGUMENT: u8 = 3;
+
+    pub fn new(steps: Vec<(u8, u8)>) -> Self {
+        Self { steps }
+    }
+
+    /// Returns the number of steps in this path.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Returns the kind of the step at `index`.
+    pub fn step(&self, index: usize) -> u8 {
+        self.steps[index].0
+    }
+
+    /// Returns the type argument index of the step at `index`.
+    pub fn step_argument(&self, index: usize) -> u8 {
+        self.steps[index].1
+    }
+
+    pub fn steps(&self) -> &[(u8, u8)] {
+        &self.steps
+    }
+
+    /// Parses a type path in the string form produced by `Display`
+    /// (e.g., `"[.*0;"`), returning `None` if it is malformed.
+    pub fn from_string(path: &str) -> Option<Self> {
+        let bytes = path.as_bytes();
+        let mut steps = Vec::new();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            match bytes[pos] {
+                b'[' => steps.push((Self::ARRAY_ELEMENT, 0)),
+                b'.' => steps.push((Self::INNER_TYPE, 0)),
+                b'*' => steps.push((Self::WILDCARD_BOUND, 0)),
+                b'0'..=b'9' => {
+                    let start = pos;
+                    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                        pos += 1;
+                    }
+                    if pos >= bytes.len() || bytes[pos] != b';' {
+                        return None;
+                    }
+                    let argument = path[start..pos].parse::<u8>().ok()?;
+                    steps.push((Self::TYPE_ARGUMENT, argument));
+                }
+                _ => return None,
+            }
+            pos += 1;
+        }
+        Some(Self { steps })
+    }
+}
+
+impl fmt::Display for TypePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (kind, argument) in &self.steps {
+            match *kind {
+                Self::ARRAY_ELEMENT => write!(f, "[")?,
+                Self::INNER_TYPE => write!(f, ".")?,
+                Self::WILDCARD_BOUND => write!(f, "*")?,
+                Self::TYPE_ARGUMENT => write!(f, "{};", argument)?,
+                _ => write!(f, "_")?,
+            }
+        }
+        Ok(())
+    }
+}
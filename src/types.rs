@@ -1,5 +1,10 @@
 use std::fmt;
-use std::hash::{Hash, Hasher};
+use std::hash::Hash;
+
+use crate::error::DescriptorError;
+
+/// The maximum number of dimensions of an array type (JVMS §4.3.2).
+pub(crate) const MAX_ARRAY_DIMENSIONS: usize = 255;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Type {
@@ -37,20 +42,46 @@ impl Type {
     /// Returns the `Type` corresponding to the given field or method descriptor.
     ///
     /// # Panics
-    /// Panics if the descriptor is invalid.
+    /// Panics if the descriptor is invalid. See [`Type::try_get_type`].
     pub fn get_type(descriptor: &str) -> Self {
-        let bytes = descriptor.as_bytes();
+        Self::try_get_type(descriptor).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Returns the `Type` corresponding to the given field or method descriptor, or an error if
+    /// the descriptor is malformed or followed by extra characters.
+    pub fn try_get_type(descriptor: &str) -> Result<Self, DescriptorError> {
         let mut pos = 0;
-        Self::parse(bytes, &mut pos)
+        let ty = if descriptor.starts_with('(') {
+            Self::parse_method(descriptor, &mut pos)?
+        } else {
+            Self::parse(descriptor, &mut pos, true)?
+        };
+        if pos < descriptor.len() {
+            return Err(DescriptorError::TrailingCharacters {
+                descriptor: descriptor.to_string(),
+                position: pos,
+            });
+        }
+        Ok(ty)
     }
 
     /// Returns the `Type` corresponding to the given internal name.
     /// If the name starts with `'['`, it is treated as an array descriptor.
+    ///
+    /// # Panics
+    /// Panics if the name is an invalid array descriptor. See [`Type::try_get_object_type`].
     pub fn get_object_type(internal_name: &str) -> Self {
+        Self::try_get_object_type(internal_name).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Returns the `Type` corresponding to the given internal name, or an error if it is not a
+    /// valid internal name or array descriptor.
+    pub fn try_get_object_type(internal_name: &str) -> Result<Self, DescriptorError> {
         if internal_name.starts_with('[') {
-            Self::get_type(internal_name)
+            Self::try_get_type(internal_name)
         } else {
-            Type::Object(internal_name.to_string())
+            Self::check_internal_name(internal_name, 0, internal_name.len())?;
+            Ok(Type::Object(internal_name.to_string()))
         }
     }
 
@@ -58,11 +89,17 @@ impl Type {
     ///
     /// # Panics
     /// Panics if the descriptor is not a valid method descriptor.
+    /// See [`Type::try_get_method_type`].
     pub fn get_method_type(descriptor: &str) -> Self {
-        let ty = Self::get_type(descriptor);
-        match ty {
-            Type::Method { .. } => ty,
-            _ => panic!("Not a method descriptor: {}", descriptor),
+        Self::try_get_method_type(descriptor).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Returns the method `Type` corresponding to the given method descriptor, or an error if
+    /// it is malformed or not a method descriptor.
+    pub fn try_get_method_type(descriptor: &str) -> Result<Self, DescriptorError> {
+        match Self::try_get_type(descriptor)? {
+            ty @ Type::Method { .. } => Ok(ty),
+            _ => Err(DescriptorError::NotMethodType(descriptor.to_string())),
         }
     }
 
@@ -122,8 +159,16 @@ impl Type {
     }
 
     /// Returns the Java class name corresponding to this type (e.g. "int", "java.lang.Object[]").
+    ///
+    /// # Panics
+    /// Panics if called on a method type. See [`Type::try_get_class_name`].
     pub fn get_class_name(&self) -> String {
-        match self {
+        self.try_get_class_name().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Returns the Java class name corresponding to this type, or an error for method types.
+    pub fn try_get_class_name(&self) -> Result<String, DescriptorError> {
+        Ok(match self {
             Type::Void => "void".to_string(),
             Type::Boolean => "boolean".to_string(),
             Type::Char => "char".to_string(),
@@ -133,10 +178,12 @@ impl Type {
             Type::Float => "float".to_string(),
             Type::Long => "long".to_string(),
             Type::Double => "double".to_string(),
-            Type::Array(elem) => format!("{}[]", elem.get_class_name()),
+            Type::Array(elem) => format!("{}[]", elem.try_get_class_name()?),
             Type::Object(name) => name.replace('/', "."),
-            Type::Method { .. } => panic!("get_class_name() called on a method type"),
-        }
+            Type::Method { .. } => {
+                return Err(DescriptorError::UnexpectedMethodType(self.get_descriptor()));
+            }
+        })
     }
 
     /// Returns the internal name of this type.
@@ -195,86 +242,125 @@ impl Type {
     }
 
     /// Returns the number of arguments of this method type.
-    /// Panics if called on a non-method type.
+    ///
+    /// # Panics
+    /// Panics if called on a non-method type. See [`Type::try_get_argument_count`].
     pub fn get_argument_count(&self) -> usize {
+        self.try_get_argument_count().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Returns the number of arguments of this method type, or an error for other types.
+    pub fn try_get_argument_count(&self) -> Result<usize, DescriptorError> {
         match self {
-            Type::Method { argument_types, .. } => argument_types.len(),
-            _ => panic!("get_argument_count() called on a non-method type"),
+            Type::Method { argument_types, .. } => Ok(argument_types.len()),
+            _ => Err(DescriptorError::NotMethodType(self.get_descriptor())),
         }
     }
 
-    /// Parses a type from a byte slice starting at position `pos`.
-    /// Returns the type and advances `pos` to the next position after the type.
-    fn parse(bytes: &[u8], pos: &mut usize) -> Self {
-        let c = bytes[*pos] as char;
-        match c {
-            'V' => {
-                *pos += 1;
-                Type::Void
-            }
-            'Z' => {
-                *pos += 1;
-                Type::Boolean
-            }
-            'C' => {
-                *pos += 1;
-                Type::Char
-            }
-            'B' => {
-                *pos += 1;
-                Type::Byte
-            }
-            'S' => {
-                *pos += 1;
-                Type::Short
-            }
-            'I' => {
-                *pos += 1;
-                Type::Int
-            }
-            'F' => {
-                *pos += 1;
-                Type::Float
-            }
-            'J' => {
-                *pos += 1;
-                Type::Long
-            }
-            'D' => {
-                *pos += 1;
-                Type::Double
+    /// Parses a method descriptor starting with the `'('` at position `pos`, and advances `pos`
+    /// past its return type.
+    fn parse_method(descriptor: &str, pos: &mut usize) -> Result<Self, DescriptorError> {
+        *pos += 1; // skip '('
+        let mut argument_types = Vec::new();
+        while descriptor.as_bytes().get(*pos) != Some(&b')') {
+            argument_types.push(Self::parse(descriptor, pos, false)?);
+        }
+        *pos += 1; // skip ')'
+        let return_type = Box::new(Self::parse(descriptor, pos, true)?);
+        Ok(Type::Method {
+            argument_types,
+            return_type,
+        })
+    }
+
+    /// Parses a field type (or `V` if `allow_void` is set) starting at position `pos`, and
+    /// advances `pos` to the next position after the type.
+    fn parse(
+        descriptor: &str,
+        pos: &mut usize,
+        allow_void: bool,
+    ) -> Result<Self, DescriptorError> {
+        let start = *pos;
+        let Some(&c) = descriptor.as_bytes().get(start) else {
+            return Err(DescriptorError::UnexpectedEnd(descriptor.to_string()));
+        };
+        *pos += 1;
+        let ty = match c {
+            b'V' if allow_void => Type::Void,
+            b'Z' => Type::Boolean,
+            b'C' => Type::Char,
+            b'B' => Type::Byte,
+            b'S' => Type::Short,
+            b'I' => Type::Int,
+            b'F' => Type::Float,
+            b'J' => Type::Long,
+            b'D' => Type::Double,
+            b'L' => {
+                let Some(end) = descriptor[*pos..].find(';').map(|len| *pos + len) else {
+                    return Err(DescriptorError::UnexpectedEnd(descriptor.to_string()));
+                };
+                Self::check_internal_name(descriptor, *pos, end)?;
+                let name = descriptor[*pos..end].to_string();
+                *pos = end + 1; // skip ';'
+                Type::Object(name)
             }
-            'L' => {
-                *pos += 1; // skip 'L'
-                let start = *pos;
-                while bytes[*pos] != b';' {
+            b'[' => {
+                // The dimensions are counted rather than parsed recursively, so that a long run
+                // of '[' cannot overflow the stack.
+                let mut dimensions = 1;
+                while descriptor.as_bytes().get(*pos) == Some(&b'[') {
+                    dimensions += 1;
                     *pos += 1;
                 }
-                let name = std::str::from_utf8(&bytes[start..*pos])
-                    .expect("Invalid UTF-8 in internal name")
-                    .to_string();
-                *pos += 1; // skip ';'
-                Type::Object(name)
-            }
-            '[' => {
-                *pos += 1; // skip '['
-                let elem = Box::new(Self::parse(bytes, pos));
-                Type::Array(elem)
-            }
-            '(' => {
-                *pos += 1; // skip '('
-                let mut args = Vec::new();
-                while bytes[*pos] != b')' {
-                    args.push(Self::parse(bytes, pos));
+                if dimensions > MAX_ARRAY_DIMENSIONS {
+                    return Err(DescriptorError::TooManyDimensions {
+                        descriptor: descriptor.to_string(),
+                        position: start,
+                    });
                 }
-                *pos += 1; // skip ')'
-                let ret = Box::new(Self::parse(bytes, pos));
-                Type::Method {
-                    argument_types: args,
-                    return_type: ret,
+                let mut ty = Self::parse(descriptor, pos, false)?;
+                for _ in 0..dimensions {
+                    ty = Type::Array(Box::new(ty));
                 }
+                ty
+            }
+            _ => return Err(Self::invalid_character(descriptor, start)),
+        };
+        Ok(ty)
+    }
+
+    /// Checks that `descriptor[start..end]` is an internal name: non-empty segments separated by
+    /// `/`, without `.`, `;` or `[` (JVMS §4.2.1).
+    fn check_internal_name(
+        descriptor: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<(), DescriptorError> {
+        let mut segment_start = start;
+        for (position, c) in descriptor.bytes().enumerate().take(end).skip(start) {
+            match c {
+                b'.' | b';' | b'[' => return Err(Self::invalid_character(descriptor, position)),
+                b'/' if position == segment_start => {
+                    return Err(Self::invalid_character(descriptor, position));
+                }
+                b'/' => segment_start = position + 1,
+                _ => {}
             }
-            _ => panic!("Invalid descriptor character: {}", c),
+        }
+        if segment_start < end {
+            Ok(())
+        } else if end == descriptor.len() {
+            Err(DescriptorError::UnexpectedEnd(descriptor.to_string()))
+        } else {
+            Err(Self::invalid_character(descriptor, end))
+        }
+    }
+
+    fn invalid_character(descriptor: &str, position: usize) -> DescriptorError {
+        DescriptorError::InvalidCharacter {
+            descriptor: descriptor.to_string(),
+            position,
+            character: descriptor[position..].chars().next().unwrap_or_default(),
         }
     }
 }
@@ -283,4 +369,108 @@ impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.get_descriptor())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descriptor_errors() {
+        assert_eq!(Type::try_get_type("[I"), Ok(Type::Array(Box::new(Type::Int))));
+        assert!(matches!(
+            Type::try_get_type("Ljava/lang/String"),
+            Err(DescriptorError::UnexpectedEnd(_))
+        ));
+        assert!(matches!(Type::try_get_type("(I"), Err(DescriptorError::UnexpectedEnd(_))));
+        assert!(matches!(
+            Type::try_get_type("(V)V"),
+            Err(DescriptorError::InvalidCharacter {
+                position: 1,
+                character: 'V',
+                ..
+            })
+        ));
+        assert!(matches!(
+            Type::try_get_type("II"),
+            Err(DescriptorError::TrailingCharacters { position: 1, .. })
+        ));
+        assert!(matches!(
+            Type::try_get_method_type("I"),
+            Err(DescriptorError::NotMethodType(_))
+        ));
+        assert!(matches!(
+            Type::get_method_type("()V").try_get_class_name(),
+            Err(DescriptorError::UnexpectedMethodType(_))
+        ));
+    }
+
+    #[test]
+    fn test_internal_name_errors() {
+        let invalid = |descriptor: &str, position: usize, character: char| {
+            assert_eq!(
+                Type::try_get_type(descriptor),
+                Err(DescriptorError::InvalidCharacter {
+                    descriptor: descriptor.to_string(),
+                    position,
+                    character,
+                }),
+                "{descriptor}"
+            );
+        };
+        invalid("L;", 1, ';');
+        invalid("La.b;", 2, '.');
+        invalid("La[b;", 2, '[');
+        invalid("L/a;", 1, '/');
+        invalid("La//b;", 3, '/');
+        invalid("La/;", 3, ';');
+        invalid("(La.b;)V", 3, '.');
+        assert_eq!(
+            Type::try_get_type("[La/b;"),
+            Ok(Type::Array(Box::new(Type::Object("a/b".to_string()))))
+        );
+
+        assert_eq!(Type::try_get_object_type("a/b"), Ok(Type::Object("a/b".to_string())));
+        assert!(matches!(
+            Type::try_get_object_type("java.lang.String"),
+            Err(DescriptorError::InvalidCharacter { position: 4, character: '.', .. })
+        ));
+        assert!(matches!(
+            Type::try_get_object_type("a;"),
+            Err(DescriptorError::InvalidCharacter { position: 1, character: ';', .. })
+        ));
+        assert!(matches!(
+            Type::try_get_object_type("a/"),
+            Err(DescriptorError::UnexpectedEnd(_))
+        ));
+        assert!(matches!(
+            Type::try_get_object_type(""),
+            Err(DescriptorError::UnexpectedEnd(_))
+        ));
+    }
+
+    #[test]
+    fn test_array_dimensions_limit() {
+        let array = |dimensions: usize| format!("{}I", "[".repeat(dimensions));
+
+        let ty = Type::try_get_type(&array(MAX_ARRAY_DIMENSIONS))
+            .expect("Should parse 255 dimensions");
+        assert_eq!(ty.get_descriptor(), array(MAX_ARRAY_DIMENSIONS));
+        assert!(matches!(
+            Type::try_get_type(&array(MAX_ARRAY_DIMENSIONS + 1)),
+            Err(DescriptorError::TooManyDimensions { position: 0, .. })
+        ));
+        // Longer than a descriptor can be in a class file, and deeper than the stack allows.
+        assert!(matches!(
+            Type::try_get_type(&array(65_000)),
+            Err(DescriptorError::TooManyDimensions { .. })
+        ));
+        assert!(matches!(
+            Type::try_get_method_type(&format!("(I{})V", array(300))),
+            Err(DescriptorError::TooManyDimensions { position: 2, .. })
+        ));
+        assert!(matches!(
+            Type::try_get_type("[[["),
+            Err(DescriptorError::UnexpectedEnd(_))
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+import java.lang.module.ModuleDescriptor;
+import java.lang.reflect.Modifier;
+import java.net.URI;
+import java.nio.file.FileSystem;
+import java.nio.file.FileSystems;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.util.ArrayDeque;
+import java.util.Arrays;
+import java.util.Deque;
+import java.util.Set;
+import java.util.TreeMap;
+import java.util.stream.Collectors;
+import java.util.stream.Stream;
+
+/**
+ * Prints the java.base class hierarchy table used by the "jdk-hierarchy" feature:
+ *
+ * <pre>java tools/JdkHierarchy.java &gt; src/class_hierarchy/jdk_classes.rs</pre>
+ *
+ * The table holds the public classes of the exported java.base packages, their super classes
+ * and the interfaces they implement.
+ */
+public class JdkHierarchy {
+    public static void main(String[] args) throws Exception {
+        Module base = Object.class.getModule();
+        Set<String> exported = base.getDescriptor().exports().stream()
+                .filter(export -> !export.isQualified())
+                .map(ModuleDescriptor.Exports::source)
+                .collect(Collectors.toSet());
+        TreeMap<String, Class<?>> classes = new TreeMap<>();
+        FileSystem jrt = FileSystems.getFileSystem(URI.create("jrt:/"));
+        Path root = jrt.getPath("modules", "java.base");
+        try (Stream<Path> paths = Files.walk(root)) {
+            for (Path path : (Iterable<Path>) paths::iterator) {
+                String file = root.relativize(path).toString();
+                if (!file.endsWith(".class") || file.equals("module-info.class")) {
+                    continue;
+                }
+                String name = file.substring(0, file.length() - ".class".length());
+                int slash = name.lastIndexOf('/');
+                if (slash < 0 || !exported.contains(name.substring(0, slash).replace('/', '.'))) {
+                    continue;
+                }
+                Class<?> type = Class.forName(name.replace('/', '.'), false, null);
+                if (!Modifier.isPublic(type.getModifiers())) {
+                    continue;
+                }
+                Deque<Class<?>> pending = new ArrayDeque<>();
+                pending.push(type);
+                while (!pending.isEmpty()) {
+                    Class<?> c = pending.pop();
+                    if (classes.putIfAbsent(c.getName().replace('.', '/'), c) != null) {
+                        continue;
+                    }
+                    if (c.getSuperclass() != null) {
+                        pending.push(c.getSuperclass());
+                    }
+                    pending.addAll(Arrays.asList(c.getInterfaces()));
+                }
+            }
+        }
+
+        System.out.println("// @generated by `java tools/JdkHierarchy.java` from java.base "
+                + Runtime.version().feature() + ". Do not edit.");
+        System.out.println();
+        System.out.println("/// `(name, super_name, interfaces, is_interface)` of the public java.base classes, their");
+        System.out.println("/// super classes and their interfaces, sorted by name.");
+        System.out.println("#[rustfmt::skip]");
+        System.out.println("pub(super) static JDK_CLASSES: &[(&str, Option<&str>, &[&str], bool)] = &[");
+        for (var entry : classes.entrySet()) {
+            Class<?> type = entry.getValue();
+            String superName = type.isInterface()
+                    ? "Some(\"java/lang/Object\")"
+                    : type.getSuperclass() == null
+                    ? "None"
+                    : "Some(\"" + type.getSuperclass().getName().replace('.', '/') + "\")";
+            String interfaces = Arrays.stream(type.getInterfaces())
+                    .map(i -> "\"" + i.getName().replace('.', '/') + "\"")
+                    .collect(Collectors.joining(", ", "&[", "]"));
+            System.out.println("    (\"" + entry.getKey() + "\", " + superName + ", " + interfaces
+                    + ", " + type.isInterface() + "),");
+        }
+        System.out.println("];");
+    }
+}